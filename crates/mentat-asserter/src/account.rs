@@ -40,20 +40,34 @@ pub fn contains_currency(currencies: &[Currency], currency: &Currency) -> bool {
 pub fn assert_unique_amounts(amounts: &[Option<UncheckedAmount>]) -> AssertResult<()> {
     let mut seen = HashSet::new();
 
-    for amt in amounts.iter().filter_map(|a| a.as_ref()) {
+    for (i, amt) in amounts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, a)| Some((i, a.as_ref()?)))
+    {
         let key = hash(amt.currency.as_ref());
 
         if seen.contains(&key) {
-            Err(format!(
-                "amount currency {:?} of amount {amt:?} is invalid: {}",
-                amt.currency,
-                AccountBalanceError::CurrencyUsedMultipleTimes
-            ))?;
+            Err(
+                AsserterError::from(AccountBalanceError::CurrencyUsedMultipleTimes)
+                    .at("currency")
+                    .at(format!("[{i}]"))
+                    .with_value(&amt.currency)
+                    .context(|e| {
+                        format!(
+                            "amount currency {:?} of amount {amt:?} is invalid: {e}",
+                            amt.currency
+                        )
+                    }),
+            )?;
         }
 
         seen.insert(key);
 
-        amount(Some(amt)).map_err(|e| format!("amount {amt:?} is invalid: {e}"))?;
+        amount(Some(amt)).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .context(|e| format!("amount {amt:?} is invalid: {e}"))
+        })?;
     }
 
     Ok(())
@@ -68,13 +82,17 @@ pub fn account_balance_response(
     response: &UncheckedAccountBalanceResponse,
 ) -> AssertResult<()> {
     block_identifier(response.block_identifier.as_ref()).map_err(|e| {
-        format!(
-            "block identifier {:?} is invalid: {e}",
-            response.block_identifier
-        )
+        e.at("block_identifier").context(|e| {
+            format!(
+                "block identifier {:?} is invalid: {e}",
+                response.block_identifier
+            )
+        })
+    })?;
+    assert_unique_amounts(&response.balances).map_err(|e| {
+        e.at("balances")
+            .context(|e| format!("balance amounts {:?} are invalid: {e}", response.balances))
     })?;
-    assert_unique_amounts(&response.balances)
-        .map_err(|e| format!("balance amounts {:?} are invalid: {e}", response.balances))?;
 
     if request_block.is_none() {
         return Ok(());
@@ -83,19 +101,33 @@ pub fn account_balance_response(
     let block_identifier = response.block_identifier.as_ref().unwrap();
 
     if matches!(request_block.hash.as_ref(), Some(i) if i != &block_identifier.hash) {
-        Err(format!(
-            "requested block hash {}, but got {}: {}",
-            request_block.hash.as_ref().unwrap(),
-            block_identifier.hash,
-            AccountBalanceError::ReturnedBlockHashMismatch,
-        ))?
+        Err(
+            AsserterError::from(AccountBalanceError::ReturnedBlockHashMismatch)
+                .at("hash")
+                .at("block_identifier")
+                .with_value(&block_identifier.hash)
+                .context(|e| {
+                    format!(
+                        "requested block hash {}, but got {}: {e}",
+                        request_block.hash.as_ref().unwrap(),
+                        block_identifier.hash,
+                    )
+                }),
+        )?
     } else if matches!(request_block.index, Some(i) if i != block_identifier.index) {
-        Err(format!(
-            "requested block index {} but got {}: {}",
-            request_block.index.unwrap(),
-            block_identifier.index,
-            AccountBalanceError::ReturnedBlockIndexMismatch,
-        ))?
+        Err(
+            AsserterError::from(AccountBalanceError::ReturnedBlockIndexMismatch)
+                .at("index")
+                .at("block_identifier")
+                .with_value(&block_identifier.index)
+                .context(|e| {
+                    format!(
+                        "requested block index {} but got {}: {e}",
+                        request_block.index.unwrap(),
+                        block_identifier.index,
+                    )
+                }),
+        )?
     } else {
         Ok(())
     }
@@ -105,11 +137,16 @@ pub fn account_balance_response(
 /// [`AccountCoinsResponse`] is invalid.
 pub fn account_coins(response: &UncheckedAccountCoinsResponse) -> AssertResult<()> {
     block_identifier(response.block_identifier.as_ref()).map_err(|e| {
-        format!(
-            "block identifier {:?} is invalid: {e}",
-            response.block_identifier
-        )
+        e.at("block_identifier").context(|e| {
+            format!(
+                "block identifier {:?} is invalid: {e}",
+                response.block_identifier
+            )
+        })
+    })?;
+    coins(&response.coins).map_err(|e| {
+        e.at("coins")
+            .context(|e| format!("coins {:?} are invalid: {e}", response.coins))
     })?;
-    coins(&response.coins).map_err(|e| format!("coins {:?} are invalid: {e}", response.coins))?;
     Ok(())
 }
@@ -2,6 +2,7 @@
 use std::{
     fs::File,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use serde::{Deserialize, Serialize};
@@ -43,25 +44,39 @@ pub struct Validations {
 }
 
 impl Validations {
-    /// Creates a new `Validations` struct given a config file.
+    /// `from_file` reads a validation config from a JSON file on the
+    /// filesystem. The path is parsed relative to the current directory.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content =
+            File::open(path).map_err(|e| format!("failed to read file {}: {e}", path.display()))?;
+        serde_json::from_reader(content).map_err(|e| {
+            format!(
+                "failed to deserialize contents of file {}: {e}",
+                path.display()
+            )
+        })
+    }
+
+    /// Creates a new `Validations` struct given a config file. The files
+    /// bundled with the asserter take precedence, otherwise the file is read
+    /// from the filesystem with [`Validations::from_file`].
     pub(crate) fn get_validation_config(
         validation_file_path: Option<&PathBuf>,
     ) -> Result<Self, String> {
-        if let Some(path) = validation_file_path {
-            let content = DATA_DIR
-                .get_file(path)
-                .ok_or_else(|| format!("failed to read file {}", path.display()))?;
-            let config: Self =
-                serde_json::from_str(content.contents_utf8().unwrap()).map_err(|e| {
-                    format!(
-                        "failed to deserialize contents of file {}: {e}",
-                        path.display()
-                    )
-                })?;
-            return Ok(config);
-        }
+        let path = match validation_file_path {
+            Some(path) => path,
+            None => return Ok(Self::default()),
+        };
 
-        Ok(Self::default())
+        match DATA_DIR.get_file(path) {
+            Some(content) => serde_json::from_str(content.contents_utf8().unwrap()).map_err(|e| {
+                format!(
+                    "failed to deserialize contents of file {}: {e}",
+                    path.display()
+                )
+            }),
+            None => Self::from_file(path),
+        }
     }
 }
 
@@ -95,6 +110,9 @@ pub struct Asserter {
     pub(crate) request: Option<RequestAsserter>,
     pub(crate) response: Option<ResponseAsserter>,
     pub(crate) validations: Validations,
+    pub(crate) signature_verifier: Option<Arc<dyn SignatureVerifier>>,
+    pub(crate) rules: Vec<Arc<dyn AssertionRule>>,
+    pub(crate) spec_version: Option<SpecVersion>,
 }
 
 impl Asserter {
@@ -143,6 +161,9 @@ impl Asserter {
             }),
             response: None,
             validations,
+            signature_verifier: None,
+            rules: Vec::new(),
+            spec_version: None,
         })
     }
 
@@ -209,13 +230,19 @@ impl Asserter {
                 error_type_map,
             }),
             validations,
+            signature_verifier: None,
+            rules: Vec::new(),
+            spec_version: None,
         })
     }
 
     /// NewClientWithResponses constructs a new Asserter
     /// from a NetworkStatusResponse and
-    /// NetworkOptionsResponse.
-    pub(crate) fn new_client_with_responses(
+    /// NetworkOptionsResponse. The [`SpecVersion`] in
+    /// `Version.rosetta_version` only decides the default timestamp start
+    /// index, features are checked once [`Asserter::with_spec_version`] is
+    /// set.
+    pub fn new_client_with_responses(
         network: Option<NetworkIdentifier>,
         status: Option<UncheckedNetworkStatusResponse>,
         options: Option<UncheckedNetworkOptionsResponse>,
@@ -228,23 +255,66 @@ impl Asserter {
         network_options_response(options.as_ref())
             .map_err(|e| format!("network options response {options:?} is invalid: {e}"))?;
         // safe to unwrap.
-        let allow = options.unwrap().allow.unwrap();
+        let options = options.unwrap();
+        let spec_version = rosetta_version(&options)?;
+        let allow = options.allow.unwrap();
+        // this is safe to unwrap.
+        let genesis_block = status.unwrap().genesis_block_identifier;
 
         let validations = Validations::get_validation_config(validation_file_path)
             .map_err(|e| format!("config {validation_file_path:?} is invalid: {e}"))?;
 
+        // Servers implementing timestamp_start_index return valid timestamps
+        // from the genesis block on, unless they set it.
+        let supports =
+            |feature: SpecFeature| spec_version.map_or(false, |v| v >= feature.introduced_in());
+        let timestamp_start_index = allow.timestamp_start_index.or_else(|| {
+            supports(SpecFeature::TimestampStartIndex)
+                .then(|| genesis_block.as_ref().map(|b| b.index))
+                .flatten()
+        });
+
         Self::new_client_with_options(
             network,
-            // this is safe to unwrap.
-            status.unwrap().genesis_block_identifier,
+            genesis_block,
             allow.operation_types,
             allow.operation_statuses,
             allow.errors,
-            allow.timestamp_start_index,
+            timestamp_start_index,
             validations,
         )
     }
 
+    /// `with_signature_verifier` makes
+    /// [`Asserter::construction_combine_request`] verify every signature
+    /// against its embedded public key.
+    pub fn with_signature_verifier<V: SignatureVerifier + 'static>(mut self, verifier: V) -> Self {
+        self.signature_verifier = Some(Arc::new(verifier));
+        self
+    }
+
+    /// `with_rule` adds a user-defined [`AssertionRule`] that
+    /// [`Asserter::block`] and [`Asserter::transaction`] run after their own
+    /// assertions. Rules run in the order they are added.
+    pub fn with_rule<R: AssertionRule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
+    /// `validates_responses` returns whether the asserter can check
+    /// responses, i.e. it was built with `new_client_with_options` or
+    /// `new_client_with_responses`. Response checks of other asserters fail
+    /// with [`AsserterError::NotInitialized`].
+    pub fn validates_responses(&self) -> bool {
+        self.response.is_some()
+    }
+
+    /// `validations` returns the stricter [`Validations`] the asserter
+    /// applies to transactions.
+    pub fn validations(&self) -> &Validations {
+        &self.validations
+    }
+
     /// ClientConfiguration returns all variables currently set in an Asserter.
     /// This function will error if it is called on an uninitialized asserter.
     pub(crate) fn client_configuration(&self) -> AssertResult<Configuration> {
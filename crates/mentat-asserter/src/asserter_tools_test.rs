@@ -517,6 +517,7 @@ fn test_new() {
     )
     .unwrap();
     assert!(!asserter.validations.enabled);
+    assert!(asserter.validates_responses());
 
     println!("non existent validation file: ");
     Asserter::new_client_with_responses(
@@ -536,3 +537,54 @@ fn test_new() {
     )
     .unwrap_err();
 }
+
+#[test]
+fn test_validations_from_file() {
+    let custom_path = temp_dir().join("custom_validation.json");
+    std::fs::write(
+        &custom_path,
+        json!({
+            "enabled": true,
+            "chain_type": "account",
+            "payment": { "name": "TRANSFER", "operation": { "count": 2, "should_balance": true } },
+            "fee": { "name": "GAS", "operation": { "count": 1, "should_balance": false } },
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let invalid_path = temp_dir().join("invalid_validation.json");
+    std::fs::write(&invalid_path, "blah").unwrap();
+
+    let tests = vec![
+        TestCase {
+            name: "custom file",
+            payload: custom_path.clone(),
+            criteria: Some(("TRANSFER".to_string(), "GAS".to_string())),
+        },
+        TestCase {
+            name: "bundled file",
+            payload: PathBuf::from("validation_fee_and_payment_balanced.json"),
+            criteria: Some(("PAYMENT".to_string(), "FEE".to_string())),
+        },
+        TestCase {
+            name: "missing file",
+            payload: temp_dir().join("missing_validation.json"),
+            criteria: None,
+        },
+        TestCase {
+            name: "invalid file",
+            payload: invalid_path,
+            criteria: None,
+        },
+    ];
+
+    TestCase::run_output_match(tests, |path| {
+        Validations::get_validation_config(Some(&path))
+            .ok()
+            .map(|validations| (validations.payment.name, validations.fee.name))
+    });
+
+    let validations = Validations::from_file(&custom_path).unwrap();
+    assert!(validations.enabled);
+    assert_eq!(validations.fee.operation.count, 1);
+}
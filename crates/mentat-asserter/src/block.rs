@@ -10,9 +10,15 @@ use super::*;
 pub fn currency(currency: Option<&UncheckedCurrency>) -> AssertResult<()> {
     let currency = currency.ok_or(BlockError::AmountCurrencyIsNil)?;
     if currency.symbol.is_empty() {
-        Err(BlockError::AmountCurrencySymbolEmpty)?
+        Err(AsserterError::from(BlockError::AmountCurrencySymbolEmpty)
+            .at("symbol")
+            .with_value(&currency.symbol))
     } else if currency.decimals < 0 {
-        Err(BlockError::AmountCurrencyHasNegDecimals)?
+        Err(
+            AsserterError::from(BlockError::AmountCurrencyHasNegDecimals)
+                .at("decimals")
+                .with_value(&currency.decimals),
+        )
     } else {
         Ok(())
     }
@@ -24,11 +30,15 @@ pub fn amount(amount: Option<&UncheckedAmount>) -> AssertResult<()> {
     let amount = amount.ok_or(BlockError::AmountValueMissing)?;
 
     if amount.value.is_empty() {
-        Err(BlockError::AmountValueMissing)?
+        Err(AsserterError::from(BlockError::AmountValueMissing)
+            .at("value")
+            .with_value(&amount.value))
     } else if BigInt::from_str(&amount.value).is_err() {
-        Err(BlockError::AmountIsNotInt)?
+        Err(AsserterError::from(BlockError::AmountIsNotInt)
+            .at("value")
+            .with_value(&amount.value))
     } else {
-        currency(amount.currency.as_ref())
+        currency(amount.currency.as_ref()).map_err(|e| e.at("currency"))
     }
 }
 
@@ -42,13 +52,23 @@ pub fn operation_identifier(
     let ident = ident.ok_or(BlockError::OperationIdentifierIndexIsNil)?;
 
     if ident.index != index {
-        Err(format!(
-            "expected identifier index {index} but got {}: {}",
-            ident.index,
-            BlockError::OperationIdentifierIndexOutOfOrder,
-        ))?
+        Err(
+            AsserterError::from(BlockError::OperationIdentifierIndexOutOfOrder)
+                .at("index")
+                .with_value(&ident.index)
+                .context(|e| {
+                    format!(
+                        "expected identifier index {index} but got {}: {e}",
+                        ident.index
+                    )
+                }),
+        )
     } else if matches!(ident.network_index, Some(i) if i < 0) {
-        Err(BlockError::OperationIdentifierNetworkIndexInvalid)?
+        Err(
+            AsserterError::from(BlockError::OperationIdentifierNetworkIndexInvalid)
+                .at("network_index")
+                .with_value(&ident.network_index),
+        )
     } else {
         Ok(())
     }
@@ -60,11 +80,18 @@ pub fn account_identifier(account: Option<&AccountIdentifier>) -> AssertResult<(
     let account = account.ok_or(BlockError::AccountIsNil)?;
 
     if account.address.is_empty() {
-        Err(BlockError::AccountAddrMissing)?
+        Err(AsserterError::from(BlockError::AccountAddrMissing)
+            .at("address")
+            .with_value(&account.address))
     } else if account.sub_account.is_none() {
         Ok(())
     } else if matches!(&account.sub_account, Some(acct) if acct.address.is_empty()) {
-        Err(BlockError::AccountSubAccountAddrMissing)?
+        Err(
+            AsserterError::from(BlockError::AccountSubAccountAddrMissing)
+                .at("address")
+                .at("sub_account")
+                .with_value(""),
+        )
     } else {
         Ok(())
     }
@@ -93,7 +120,10 @@ impl Asserter {
         let status = status.unwrap();
 
         if construction {
-            Err(BlockError::OperationStatusNotEmptyForConstruction)?
+            Err(
+                AsserterError::from(BlockError::OperationStatusNotEmptyForConstruction)
+                    .with_value(status),
+            )?
         }
 
         if self
@@ -102,10 +132,9 @@ impl Asserter {
             .and_then(|r| r.operation_status_map.get(status))
             .is_none()
         {
-            Err(format!(
-                "operation status {status} is invalid: {}",
-                BlockError::OperationStatusInvalid,
-            ))?
+            Err(AsserterError::from(BlockError::OperationStatusInvalid)
+                .with_value(status)
+                .context(|e| format!("operation status {status} is invalid: {e}")))
         } else {
             Ok(())
         }
@@ -119,10 +148,9 @@ impl Asserter {
         }
 
         if t.is_empty() || !self.operation_types.contains(&t) {
-            Err(format!(
-                "operation type {t} is invalid: {}",
-                BlockError::OperationTypeInvalid
-            ))?
+            Err(AsserterError::from(BlockError::OperationTypeInvalid)
+                .with_value(&t)
+                .context(|e| format!("operation type {t} is invalid: {e}")))
         } else {
             Ok(())
         }
@@ -143,25 +171,31 @@ impl Asserter {
         let operation = operation.ok_or(BlockError::OperationIsNil)?;
 
         operation_identifier(operation.operation_identifier.as_ref(), index).map_err(|e| {
-            format!(
-                "operation identifier {:?} is invalid in operation {index}: {e}",
-                operation.operation_identifier
-            )
+            e.at("operation_identifier").context(|e| {
+                format!(
+                    "operation identifier {:?} is invalid in operation {index}: {e}",
+                    operation.operation_identifier
+                )
+            })
         })?;
 
         self.operation_type(operation.type_.clone()).map_err(|e| {
-            format!(
-                "operation type {:?} is invalid in operation {index}: {e}",
-                operation.type_
-            )
+            e.at("type").context(|e| {
+                format!(
+                    "operation type {:?} is invalid in operation {index}: {e}",
+                    operation.type_
+                )
+            })
         })?;
 
         self.operation_status(operation.status.as_ref(), construction)
             .map_err(|e| {
-                format!(
-                    "operation status {:?} is invalid in operation {index}: {e}",
-                    operation.status
-                )
+                e.at("status").context(|e| {
+                    format!(
+                        "operation status {:?} is invalid in operation {index}: {e}",
+                        operation.status
+                    )
+                })
             })?;
 
         if operation.amount.is_none() {
@@ -169,28 +203,36 @@ impl Asserter {
         }
 
         account_identifier(operation.account.as_ref()).map_err(|e| {
-            format!(
-                "operation account identifier {:?} is invalid in operation {index}: {e}",
-                operation.account
-            )
+            e.at("account").context(|e| {
+                format!(
+                    "operation account identifier {:?} is invalid in operation {index}: {e}",
+                    operation.account
+                )
+            })
         })?;
 
         amount(operation.amount.as_ref()).map_err(|e| {
-            format!(
-                "operation amount {:?} is invalid in operation {index}: {e}",
-                operation.amount
-            )
+            e.at("amount").context(|e| {
+                format!(
+                    "operation amount {:?} is invalid in operation {index}: {e}",
+                    operation.amount
+                )
+            })
         })?;
 
         if operation.coin_change.is_none() {
             return Ok(());
         }
 
+        self.require_feature(SpecFeature::Coins)
+            .map_err(|e| e.at("coin_change"))?;
         coin_change(operation.coin_change.as_ref()).map_err(|e| {
-            format!(
-                "operation coin change {:?} is invalid in operation {index}: {e}",
-                operation.coin_change
-            )
+            e.at("coin_change").context(|e| {
+                format!(
+                    "operation coin change {:?} is invalid in operation {index}: {e}",
+                    operation.coin_change
+                )
+            })
         })?;
 
         Ok(())
@@ -215,7 +257,10 @@ impl Asserter {
 
         for (index, op) in operations.iter().enumerate() {
             self.operation(op.as_ref(), index as isize, construction)
-                .map_err(|e| format!("operation {op:?} is invalid: {e}"))?;
+                .map_err(|e| {
+                    e.at(format!("[{index}]"))
+                        .context(|e| format!("operation {op:?} is invalid: {e}"))
+                })?;
             let op = op.as_ref().unwrap();
             if self.validations.enabled {
                 if op.type_ == self.validations.payment.name {
@@ -226,20 +271,33 @@ impl Asserter {
 
                 if op.type_ == self.validations.fee.name {
                     if !op.related_operations.is_empty() {
-                        Err(format!(
-                            "operation {op:?} is invalid with operation index {index}: {}",
-                            BlockError::RelatedOperationInFeeNotAllowed
-                        ))?;
+                        Err(
+                            AsserterError::from(BlockError::RelatedOperationInFeeNotAllowed)
+                                .at("related_operations")
+                                .at(format!("[{index}]"))
+                                .with_value(&op.related_operations)
+                                .context(|e| {
+                                    format!(
+                                        "operation {op:?} is invalid with operation index {index}: {e}"
+                                    )
+                                }),
+                        )?;
                     }
 
                     let val = BigInt::from_str(&op.amount.as_ref().unwrap().value)
                         .map_err(|err| err.to_string())?;
 
                     if !matches!(val.sign(), Sign::Minus) {
-                        Err(format!(
-                            "operation {op:?} is invalid with operation index {index}: {}",
-                            BlockError::FeeAmountNotNegative
-                        ))?;
+                        Err(AsserterError::from(BlockError::FeeAmountNotNegative)
+                            .at("value")
+                            .at("amount")
+                            .at(format!("[{index}]"))
+                            .with_value(&op.amount.as_ref().unwrap().value)
+                            .context(|e| {
+                                format!(
+                                    "operation {op:?} is invalid with operation index {index}: {e}"
+                                )
+                            }))?;
                     }
 
                     fee_total += val;
@@ -253,25 +311,42 @@ impl Asserter {
             let operation_identifier_index = op.operation_identifier.as_ref().unwrap().index;
             let mut related_indexes = IndexSet::new();
 
-            for related_op in op.related_operations.iter().flat_map(|i| i.as_ref()) {
+            for (related_index, related_op) in op
+                .related_operations
+                .iter()
+                .enumerate()
+                .filter_map(|(i, related_op)| Some((i, related_op.as_ref()?)))
+            {
                 related_ops_exist = true;
+                let located = |err: BlockError| {
+                    AsserterError::from(err)
+                        .at("index")
+                        .at(format!("[{related_index}]"))
+                        .at("related_operations")
+                        .at(format!("[{index}]"))
+                        .with_value(&related_op.index)
+                };
 
                 if related_op.index >= operation_identifier_index {
-                    Err(format!(
-                        "related operation index {} >= operation index {}: {}",
-                        related_op.index,
-                        operation_identifier_index,
-                        BlockError::RelatedOperationIndexOutOfOrder,
-                    ))?;
+                    Err(
+                        located(BlockError::RelatedOperationIndexOutOfOrder).context(|e| {
+                            format!(
+                                "related operation index {} >= operation index {}: {e}",
+                                related_op.index, operation_identifier_index,
+                            )
+                        }),
+                    )?;
                 }
 
                 if related_indexes.contains(&related_op.index) {
-                    Err(format!(
-                        "related operation index {} found for operation index {}: {}",
-                        related_op.index,
-                        operation_identifier_index,
-                        BlockError::RelatedOperationIndexDuplicate,
-                    ))?;
+                    Err(
+                        located(BlockError::RelatedOperationIndexDuplicate).context(|e| {
+                            format!(
+                                "related operation index {} found for operation index {}: {e}",
+                                related_op.index, operation_identifier_index,
+                            )
+                        }),
+                    )?;
                 }
 
                 related_indexes.insert(related_op.index);
@@ -335,28 +410,47 @@ impl Asserter {
         let transaction = transaction.ok_or(BlockError::TxIsNil)?;
 
         transaction_identifier(transaction.transaction_identifier.as_ref()).map_err(|e| {
-            format!(
-                "transaction identifier {:?} is invalid: {e}",
-                transaction.transaction_identifier
-            )
+            e.at("transaction_identifier").context(|e| {
+                format!(
+                    "transaction identifier {:?} is invalid: {e}",
+                    transaction.transaction_identifier
+                )
+            })
         })?;
         let transaction_identifier = transaction.transaction_identifier.as_ref().unwrap();
 
         self.operations(&transaction.operations, false)
             .map_err(|e| {
-                format!(
-                    "invalid operation in transaction operations {}: {e}",
-                    transaction_identifier.hash
-                )
+                e.at("operations").context(|e| {
+                    format!(
+                        "invalid operation in transaction operations {}: {e}",
+                        transaction_identifier.hash
+                    )
+                })
             })?;
 
+        if !transaction.related_transactions.is_empty() {
+            self.require_feature(SpecFeature::RelatedTransactions)
+                .map_err(|e| e.at("related_transactions"))?;
+        }
         self.related_transactions(&transaction.related_transactions)
             .map_err(|e| {
+                e.at("related_transactions").context(|e| {
+                    format!(
+                        "invalid related transaction in related transactions {:?}: {e}",
+                        transaction.related_transactions
+                    )
+                })
+            })?;
+
+        self.transaction_rules(transaction).map_err(|e| {
+            e.context(|e| {
                 format!(
-                    "invalid related transaction in related transactions {:?}: {e}",
-                    transaction.related_transactions
+                    "transaction {} violates a rule: {e}",
+                    transaction_identifier.hash
                 )
-            })?;
+            })
+        })?;
 
         Ok(())
     }
@@ -371,10 +465,9 @@ impl Asserter {
         related_transactions: &[Option<UncheckedRelatedTransaction>],
     ) -> AssertResult<()> {
         if let Some(dup) = duplicate_related_transaction(related_transactions) {
-            Err(format!(
-                "{}: {dup:?}",
-                BlockError::DuplicateRelatedTransaction
-            ))?;
+            Err(AsserterError::from(BlockError::DuplicateRelatedTransaction)
+                .with_value(dup)
+                .context(|e| format!("{e}: {dup:?}")))?;
         }
 
         for (i, related) in related_transactions
@@ -383,24 +476,33 @@ impl Asserter {
             .enumerate()
         {
             network_identifier(related.network_identifier.as_ref()).map_err(|e| {
-                format!(
-                    "network identifier {:?} is invalid in related transaction at index {i}: {e}",
-                    related.network_identifier
-                )
+                e.at("network_identifier").at(format!("[{i}]")).context(|e| {
+                    format!(
+                        "network identifier {:?} is invalid in related transaction at index {i}: {e}",
+                        related.network_identifier
+                    )
+                })
             })?;
 
             transaction_identifier(related.transaction_identifier.as_ref()).map_err(|e| {
-                format!(
-                    "invalid transaction identifier {:?} in related transaction at index {i}: {e}",
-                    related.transaction_identifier
-                )
+                e.at("transaction_identifier").at(format!("[{i}]")).context(|e| {
+                    format!(
+                        "invalid transaction identifier {:?} in related transaction at index {i}: {e}",
+                        related.transaction_identifier
+                    )
+                })
             })?;
 
             self.direction(&related.direction).map_err(|e| {
-                format!(
-                    "invalid direction {:?} in related transaction at index {i}: {e}",
-                    related.direction
-                )
+                e.at("direction")
+                    .at(format!("[{i}]"))
+                    .with_value(&related.direction)
+                    .context(|e| {
+                        format!(
+                            "invalid direction {:?} in related transaction at index {i}: {e}",
+                            related.direction
+                        )
+                    })
             })?;
         }
         Ok(())
@@ -425,16 +527,20 @@ impl Asserter {
         let block = block.ok_or(BlockError::BlockIsNil)?;
 
         block_identifier(block.block_identifier.as_ref()).map_err(|e| {
-            format!(
-                "block identifier {:?} is invalid: {e}",
-                block.block_identifier,
-            )
+            e.at("block_identifier").context(|e| {
+                format!(
+                    "block identifier {:?} is invalid: {e}",
+                    block.block_identifier,
+                )
+            })
         })?;
         block_identifier(block.parent_block_identifier.as_ref()).map_err(|e| {
-            format!(
-                "parent block identifier {:?} is invalid: {e}",
-                block.parent_block_identifier,
-            )
+            e.at("parent_block_identifier").context(|e| {
+                format!(
+                    "parent block identifier {:?} is invalid: {e}",
+                    block.parent_block_identifier,
+                )
+            })
         })?;
         let block_identifier = block.block_identifier.as_ref().unwrap();
         let parent_block_identifier = block.parent_block_identifier.as_ref().unwrap();
@@ -452,15 +558,51 @@ impl Asserter {
         // Only check for timestamp validity if timestamp start index is <=
         // the current block index.
         if asserter.timestamp_start_index as isize <= block_identifier.index {
-            timestamp(block.timestamp)
-                .map_err(|e| format!("timestamp {} is invalid: {e}", block.timestamp))?;
+            timestamp(block.timestamp).map_err(|e| {
+                AsserterError::from(e)
+                    .at("timestamp")
+                    .with_value(&block.timestamp)
+                    .context(|e| format!("timestamp {} is invalid: {e}", block.timestamp))
+            })?;
         }
 
-        block.transactions.iter().try_for_each(|transaction| {
-            self.transaction(transaction.as_ref())
-                .map_err(|e| format!("transaction {transaction:?} is invalid: {e}").into())
+        block
+            .transactions
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, transaction)| {
+                self.transaction(transaction.as_ref()).map_err(|e| {
+                    e.at(format!("[{i}]"))
+                        .at("transactions")
+                        .context(|e| format!("transaction {transaction:?} is invalid: {e}"))
+                })
+            })?;
+
+        self.block_rules(block).map_err(|e| {
+            e.context(|e| format!("block {} violates a rule: {e}", block_identifier.hash))
         })
     }
+
+    /// `block_response` runs [`Asserter::block`] on the block of a
+    /// [`BlockResponse`], if it has one, and ensures its other transaction
+    /// identifiers are well-formatted.
+    pub fn block_response(&self, response: &UncheckedBlockResponse) -> AssertResult<()> {
+        if let Some(block) = &response.block {
+            self.block(Some(block)).map_err(|e| e.at("block"))?;
+        }
+
+        response
+            .other_transactions
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, ident)| {
+                transaction_identifier(ident.as_ref()).map_err(|e| {
+                    e.at(format!("[{i}]"))
+                        .at("other_transactions")
+                        .context(|e| format!("other transaction {ident:?} is invalid: {e}"))
+                })
+            })
+    }
 }
 
 /// `block_identifier` ensures a [`UncheckedBlockIdentifier`]
@@ -468,9 +610,13 @@ impl Asserter {
 pub fn block_identifier(block: Option<&UncheckedBlockIdentifier>) -> AssertResult<()> {
     let block = block.ok_or(BlockError::BlockIdentifierIsNil)?;
     if block.hash.is_empty() {
-        Err(BlockError::BlockIdentifierHashMissing)?
+        Err(AsserterError::from(BlockError::BlockIdentifierHashMissing)
+            .at("hash")
+            .with_value(&block.hash))
     } else if block.index < 0 {
-        Err(BlockError::BlockIdentifierIndexIsNeg)?
+        Err(AsserterError::from(BlockError::BlockIdentifierIndexIsNeg)
+            .at("index")
+            .with_value(&block.index))
     } else {
         Ok(())
     }
@@ -516,7 +662,9 @@ pub fn duplicate_related_transaction(
 pub fn transaction_identifier(ident: Option<&TransactionIdentifier>) -> AssertResult<()> {
     let ident = ident.ok_or(BlockError::TxIdentifierIsNil)?;
     if ident.hash.is_empty() {
-        Err(BlockError::TxIdentifierHashMissing.into())
+        Err(AsserterError::from(BlockError::TxIdentifierHashMissing)
+            .at("hash")
+            .with_value(&ident.hash))
     } else {
         Ok(())
     }
@@ -1119,6 +1119,7 @@ struct BlockTestExtras {
     genesis_index: isize,
     start_index: Option<isize>,
     validation_file_path: Option<PathBuf>,
+    rosetta_version: Option<&'static str>,
 }
 
 #[test]
@@ -1497,7 +1498,7 @@ fn test_block() {
             }),
             Some(UncheckedNetworkOptionsResponse {
                 version: Some(Version {
-                    rosetta_version: "1.4.0".into(),
+                    rosetta_version: extras.rosetta_version.unwrap_or("1.4.0").into(),
                     node_version: "1.0".into(),
                     middleware_version: None,
                     metadata: Default::default(),
@@ -1570,6 +1571,40 @@ fn test_block() {
             },
             criteria: None,
         },
+        TestCase {
+            name: "genesis block (without start index before 1.4.6)",
+            payload: MethodPayload {
+                caller: asserter(BlockTestExtras {
+                    genesis_index: valid_block_ident.index,
+                    rosetta_version: Some("1.4.5"),
+                    ..Default::default()
+                }),
+                payload: Some(UncheckedBlock {
+                    block_identifier: Some(valid_block_ident.clone()),
+                    parent_block_identifier: Some(valid_parent_block_ident.clone()),
+                    transactions: vec![valid_transaction.clone()],
+                    ..Default::default()
+                }),
+            },
+            criteria: None,
+        },
+        TestCase {
+            name: "genesis block (without start index since 1.4.6)",
+            payload: MethodPayload {
+                caller: asserter(BlockTestExtras {
+                    genesis_index: valid_block_ident.index,
+                    rosetta_version: Some("1.4.6"),
+                    ..Default::default()
+                }),
+                payload: Some(UncheckedBlock {
+                    block_identifier: Some(valid_block_ident.clone()),
+                    parent_block_identifier: Some(valid_parent_block_ident.clone()),
+                    transactions: vec![valid_transaction.clone()],
+                    ..Default::default()
+                }),
+            },
+            criteria: Some(BlockError::TimestampBeforeMin.into()),
+        },
         TestCase {
             name: "genesis block (with start index)",
             payload: MethodPayload {
@@ -1836,3 +1871,138 @@ fn test_block() {
 
     TestCase::run_err_match(tests, |t| t.caller.block(t.payload.as_ref()));
 }
+
+#[test]
+fn test_block_error_location() {
+    let asserter = Asserter::new_client_with_options(
+        Some(NetworkIdentifier {
+            blockchain: "HELLO".into(),
+            network: "WORLD".into(),
+            sub_network_identifier: None,
+        }),
+        Some(UncheckedBlockIdentifier {
+            hash: "block 0".into(),
+            index: 0,
+        }),
+        vec!["PAYMENT".into()],
+        vec![Some(OperationStatus {
+            status: "SUCCESS".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Validations::default(),
+    )
+    .unwrap();
+    let operation = |index: isize, value: &str, symbol: &str| {
+        Some(UncheckedOperation {
+            operation_identifier: Some(UncheckedOperationIdentifier {
+                index,
+                network_index: None,
+            }),
+            type_: "PAYMENT".into(),
+            status: Some("SUCCESS".into()),
+            account: Some(AccountIdentifier {
+                address: "test".into(),
+                ..Default::default()
+            }),
+            amount: Some(UncheckedAmount {
+                value: value.into(),
+                currency: Some(UncheckedCurrency {
+                    symbol: symbol.into(),
+                    decimals: 8,
+                    metadata: Default::default(),
+                }),
+                metadata: Default::default(),
+            }),
+            ..Default::default()
+        })
+    };
+    let block = |operations: Vec<Option<UncheckedOperation>>| UncheckedBlock {
+        block_identifier: Some(UncheckedBlockIdentifier {
+            hash: "block 1".into(),
+            index: 1,
+        }),
+        parent_block_identifier: Some(UncheckedBlockIdentifier {
+            hash: "block 0".into(),
+            index: 0,
+        }),
+        timestamp: MIN_UNIX_EPOCH + 1,
+        transactions: vec![
+            Some(UncheckedTransaction {
+                transaction_identifier: Some(TransactionIdentifier {
+                    hash: "tx 0".into(),
+                }),
+                operations: vec![operation(0, "1000", "BTC")],
+                ..Default::default()
+            }),
+            Some(UncheckedTransaction {
+                transaction_identifier: Some(TransactionIdentifier {
+                    hash: "tx 1".into(),
+                }),
+                operations,
+                ..Default::default()
+            }),
+        ],
+        ..Default::default()
+    };
+
+    let err = asserter
+        .block(Some(&block(vec![
+            operation(0, "1000", "BTC"),
+            operation(1, "1.5", "BTC"),
+        ])))
+        .unwrap_err();
+    assert_eq!(err.code(), "block.amount_is_not_int");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("transactions[1].operations[1].amount.value")
+    );
+    assert_eq!(err.value(), Some(&json!("1.5")));
+    assert!(err.to_string().contains("Amount.Value is not an integer"));
+    assert!(err.to_string().starts_with("transaction "));
+
+    let err = asserter
+        .block(Some(&block(vec![operation(0, "1000", "")])))
+        .unwrap_err();
+    assert_eq!(err.code(), "block.amount_currency_symbol_empty");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("transactions[1].operations[0].amount.currency.symbol")
+    );
+    assert_eq!(err.value(), Some(&json!("")));
+
+    let err = asserter
+        .block(Some(&block(vec![operation(1, "1000", "BTC")])))
+        .unwrap_err();
+    assert_eq!(err.code(), "block.operation_identifier_index_out_of_order");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("transactions[1].operations[0].operation_identifier.index")
+    );
+    assert_eq!(err.value(), Some(&json!(1)));
+    let err = asserter
+        .block_response(&UncheckedBlockResponse {
+            block: Some(block(vec![operation(0, "1.5", "BTC")])),
+            other_transactions: Vec::new(),
+        })
+        .unwrap_err();
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("block.transactions[1].operations[0].amount.value")
+    );
+
+    let err = asserter
+        .block_response(&UncheckedBlockResponse {
+            block: Some(block(vec![operation(0, "1000", "BTC")])),
+            other_transactions: vec![
+                Some(TransactionIdentifier {
+                    hash: "tx 2".into(),
+                }),
+                None,
+            ],
+        })
+        .unwrap_err();
+    assert_eq!(err.code(), "block.tx_identifier_is_nil");
+    assert_eq!(err.json_path().as_deref(), Some("other_transactions[1]"));
+}
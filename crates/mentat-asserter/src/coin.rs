@@ -5,10 +5,14 @@ use super::*;
 /// `coin` returns an error if the provided [`Coin`] is invalid.
 pub fn coin(coin: Option<&UncheckedCoin>) -> AssertResult<()> {
     let coin = coin.ok_or(CoinError::IsNil)?;
-    coin_identifier(coin.coin_identifier.as_ref())
-        .map_err(|e| format!("coin identifier {:?} is invalid: {e}", coin.coin_identifier))?;
-    amount(coin.amount.as_ref())
-        .map_err(|e| format!("coin amount {:?} is invalid: {e}", coin.amount))?;
+    coin_identifier(coin.coin_identifier.as_ref()).map_err(|e| {
+        e.at("coin_identifier")
+            .context(|e| format!("coin identifier {:?} is invalid: {e}", coin.coin_identifier))
+    })?;
+    amount(coin.amount.as_ref()).map_err(|e| {
+        e.at("amount")
+            .context(|e| format!("coin amount {:?} is invalid: {e}", coin.amount))
+    })?;
     Ok(())
 }
 
@@ -18,12 +22,20 @@ pub fn coin(coin: Option<&UncheckedCoin>) -> AssertResult<()> {
 /// will also return an error.
 pub fn coins(coins: &[Option<UncheckedCoin>]) -> AssertResult<()> {
     let mut ids = IndexSet::new();
-    for c in coins {
-        coin(c.as_ref()).map_err(|e| format!("coin {c:?} is invalid: {e}"))?;
+    for (i, c) in coins.iter().enumerate() {
+        coin(c.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .context(|e| format!("coin {c:?} is invalid: {e}"))
+        })?;
         let c = c.as_ref().unwrap();
         let c_ident = c.coin_identifier.as_ref().unwrap();
         if ids.contains(&c_ident.identifier) {
-            Err(format!("coin {c:?} is invalid: {}", CoinError::Duplicate))?;
+            Err(AsserterError::from(CoinError::Duplicate)
+                .at("identifier")
+                .at("coin_identifier")
+                .at(format!("[{i}]"))
+                .with_value(&c_ident.identifier)
+                .context(|e| format!("coin {c:?} is invalid: {e}")))?;
         }
 
         ids.insert(&c_ident.identifier);
@@ -49,13 +61,17 @@ pub fn coin_change(change: Option<&UncheckedCoinChange>) -> AssertResult<()> {
     let change = change.ok_or(CoinError::ChangeIsNil)?;
 
     coin_identifier(change.coin_identifier.as_ref()).map_err(|e| {
-        format!(
-            "coin identifier {:?} is invalid: {e}",
-            change.coin_identifier
-        )
+        e.at("coin_identifier").context(|e| {
+            format!(
+                "coin identifier {:?} is invalid: {e}",
+                change.coin_identifier
+            )
+        })
+    })?;
+    coin_action(&change.coin_action).map_err(|e| {
+        e.at("coin_action")
+            .context(|e| format!("coin action {:?} is invalid: {e}", change.coin_action))
     })?;
-    coin_action(&change.coin_action)
-        .map_err(|e| format!("coin action {:?} is invalid: {e}", change.coin_action))?;
     Ok(())
 }
 
@@ -63,10 +79,9 @@ pub fn coin_change(change: Option<&UncheckedCoinChange>) -> AssertResult<()> {
 /// is invalid.
 pub fn coin_action(act: &UncheckedCoinAction) -> AssertResult<()> {
     if !act.valid() {
-        Err(AsserterError::from(format!(
-            "failed to validate coin action {act}: {}",
-            CoinError::ActionInvalid,
-        )))
+        Err(AsserterError::from(CoinError::ActionInvalid)
+            .with_value(act)
+            .context(|e| format!("failed to validate coin action {act}: {e}")))
     } else {
         Ok(())
     }
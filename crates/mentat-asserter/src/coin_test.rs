@@ -168,3 +168,39 @@ fn test_coin_change() {
 
     TestCase::run_err_match(tests, |t| coin_change(t.as_ref()));
 }
+
+#[test]
+fn test_coins_error_location() {
+    let coin = |identifier: &str, value: &str| {
+        Some(UncheckedCoin {
+            coin_identifier: Some(CoinIdentifier {
+                identifier: identifier.to_string(),
+            }),
+            amount: Some(UncheckedAmount {
+                value: value.to_string(),
+                currency: Some(UncheckedCurrency {
+                    symbol: "BTC".to_string(),
+                    decimals: 8,
+                    metadata: Default::default(),
+                }),
+                metadata: Default::default(),
+            }),
+        })
+    };
+
+    let err = coins(&[coin("coin1", "1000"), coin("coin1", "1000")]).unwrap_err();
+    assert_eq!(err.code(), "coin.duplicate");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("[1].coin_identifier.identifier")
+    );
+    assert_eq!(err.value(), Some(&json!("coin1")));
+
+    let err = coins(&[coin("coin1", "1000"), coin("coin2", "1.5")]).unwrap_err();
+    assert_eq!(err.code(), "block.amount_is_not_int");
+    assert_eq!(err.json_path().as_deref(), Some("[1].amount.value"));
+
+    let err = coin_action(&"spent".into()).unwrap_err();
+    assert_eq!(err.code(), "coin.action_invalid");
+    assert_eq!(err.value(), Some(&json!("spent")));
+}
@@ -8,10 +8,16 @@ pub fn construction_preprocess_response(
 ) -> AssertResult<()> {
     let resp = resp.ok_or(ConstructionError::ConstructionPreprocessResponseIsNil)?;
 
-    resp.required_public_keys.iter().try_for_each(|pub_key| {
-        account_identifier(pub_key.as_ref())
-            .map_err(|e| format!("account identifier {pub_key:?} is invalid: {e}"))
-    })?;
+    resp.required_public_keys
+        .iter()
+        .enumerate()
+        .try_for_each(|(i, pub_key)| {
+            account_identifier(pub_key.as_ref()).map_err(|e| {
+                e.at(format!("[{i}]"))
+                    .at("required_public_keys")
+                    .context(|e| format!("account identifier {pub_key:?} is invalid: {e}"))
+            })
+        })?;
 
     Ok(())
 }
@@ -27,8 +33,10 @@ pub fn construction_metadata_response(
         Err(ConstructionError::ConstructionMetadataResponseMetadataMissing)?;
     }
 
-    assert_unique_amounts(&resp.suggested_fee)
-        .map_err(|err| format!("suggested fee {:?} is invalid: {err}", resp.suggested_fee))?;
+    assert_unique_amounts(&resp.suggested_fee).map_err(|err| {
+        err.at("suggested_fee")
+            .context(|err| format!("suggested fee {:?} is invalid: {err}", resp.suggested_fee))
+    })?;
 
     Ok(())
 }
@@ -41,11 +49,12 @@ pub fn transaction_identifier_response(
 ) -> AssertResult<()> {
     let response = response.ok_or(ConstructionError::TxIdentifierResponseIsNil)?;
     transaction_identifier(response.transaction_identifier.as_ref()).map_err(|e| {
-        format!(
-            "transaction identifier {:?} is invalid: {e}",
-            response.transaction_identifier
-        )
-        .into()
+        e.at("transaction_identifier").context(|e| {
+            format!(
+                "transaction identifier {:?} is invalid: {e}",
+                response.transaction_identifier
+            )
+        })
     })
 }
 
@@ -72,10 +81,12 @@ pub fn construction_derive_response(
     let resp = resp.ok_or(ConstructionError::ConstructionDeriveResponseIsNil)?;
 
     account_identifier(resp.account_identifier.as_ref()).map_err(|e| {
-        format!(
-            "account identifier {:?} is invalid: {e}",
-            resp.account_identifier
-        )
+        e.at("account_identifier").context(|e| {
+            format!(
+                "account identifier {:?} is invalid: {e}",
+                resp.account_identifier
+            )
+        })
     })?;
 
     Ok(())
@@ -101,8 +112,10 @@ impl Asserter {
             Err(ConstructionError::ConstructionParseResponseOperationsEmpty)?;
         }
 
-        self.operations(&resp.operations, true)
-            .map_err(|e| format!("operations {:?} are invalid: {e}", resp.operations))?;
+        self.operations(&resp.operations, true).map_err(|e| {
+            e.at("operations")
+                .context(|e| format!("operations {:?} are invalid: {e}", resp.operations))
+        })?;
 
         if signed && resp.account_identifier_signers.is_empty() {
             Err(ConstructionError::ConstructionParseResponseSignersEmptyOnSignedTx)?;
@@ -114,17 +127,25 @@ impl Asserter {
 
         resp.account_identifier_signers
             .iter()
-            .try_for_each(|ident| {
-                account_identifier(ident.as_ref())
-                    .map_err(|e| format!("account identifier of signer {ident:?} is invalid: {e}",))
+            .enumerate()
+            .try_for_each(|(i, ident)| {
+                account_identifier(ident.as_ref()).map_err(|e| {
+                    e.at(format!("[{i}]"))
+                        .at("account_identifier_signers")
+                        .context(|e| {
+                            format!("account identifier of signer {ident:?} is invalid: {e}")
+                        })
+                })
             })?;
 
         if !resp.account_identifier_signers.is_empty() {
             account_array("signers", &resp.account_identifier_signers).map_err(|e| {
-                format!(
-                    "account identifiers of signers {:?} are invalid: {e}",
-                    resp.account_identifier_signers
-                )
+                e.at("account_identifier_signers").context(|e| {
+                    format!(
+                        "account identifiers of signers {:?} are invalid: {e}",
+                        resp.account_identifier_signers
+                    )
+                })
             })?;
         }
 
@@ -149,10 +170,16 @@ pub fn construction_payloads_response(
         Err(ConstructionError::ConstructionPayloadsResponsePayloadsEmpty)?;
     }
 
-    resp.payloads.iter().try_for_each(|payload| {
-        signing_payload(payload.as_ref())
-            .map_err(|e| format!("signing payload {payload:?} is invalid: {e}"))
-    })?;
+    resp.payloads
+        .iter()
+        .enumerate()
+        .try_for_each(|(i, payload)| {
+            signing_payload(payload.as_ref()).map_err(|e| {
+                e.at(format!("[{i}]"))
+                    .at("payloads")
+                    .context(|e| format!("signing payload {payload:?} is invalid: {e}"))
+            })
+        })?;
 
     Ok(())
 }
@@ -171,8 +198,11 @@ pub fn public_key(key: Option<&UncheckedPublicKey>) -> AssertResult<()> {
         Err(ConstructionError::PublicKeyBytesZero)?;
     }
 
-    curve_type(&key.curve_type)
-        .map_err(|e| format!("public key curve type {} is invalid: {e}", key.curve_type))?;
+    curve_type(&key.curve_type).map_err(|e| {
+        e.at("curve_type")
+            .with_value(&key.curve_type)
+            .context(|e| format!("public key curve type {} is invalid: {e}", key.curve_type))
+    })?;
 
     Ok(())
 }
@@ -195,10 +225,12 @@ pub fn signing_payload(payload: Option<&UncheckedSigningPayload>) -> AssertResul
     let payload = payload.ok_or(ConstructionError::SigningPayloadIsNil)?;
 
     account_identifier(payload.account_identifier.as_ref()).map_err(|e| {
-        format!(
-            "account identifier {:?} is invalid: {e}",
-            payload.account_identifier
-        )
+        e.at("account_identifier").context(|e| {
+            format!(
+                "account identifier {:?} is invalid: {e}",
+                payload.account_identifier
+            )
+        })
     })?;
 
     if payload.bytes.is_empty() {
@@ -215,10 +247,14 @@ pub fn signing_payload(payload: Option<&UncheckedSigningPayload>) -> AssertResul
     }
 
     signature_type(&payload.signature_type).map_err(|e| {
-        format!(
-            "signature type {:?} is invalid: {e}",
-            payload.signature_type
-        )
+        e.at("signature_type")
+            .with_value(&payload.signature_type)
+            .context(|e| {
+                format!(
+                    "signature type {:?} is invalid: {e}",
+                    payload.signature_type
+                )
+            })
     })?;
 
     Ok(())
@@ -231,33 +267,62 @@ pub fn signatures(signatures: &[Option<&UncheckedSignature>]) -> AssertResult<()
         Err(ConstructionError::SignaturesEmpty)?;
     }
 
-    for sig in signatures {
+    for (i, sig) in signatures.iter().enumerate() {
         // TODO coinbase doesn't check for nil here
         let sig = sig.unwrap();
-        signing_payload(sig.signing_payload.as_ref())
-            .map_err(|e| format!("signing payload {:?} is invalid: {e}", sig.signing_payload))?;
-
-        public_key(sig.public_key.as_ref())
-            .map_err(|e| format!("public key {:?} is invalid: {e}", sig.public_key))?;
-
-        signature_type(&sig.signature_type)
-            .map_err(|e| format!("signature type {:?} is invalid: {e}", sig.signature_type))?;
+        let index = format!("[{i}]");
+        signing_payload(sig.signing_payload.as_ref()).map_err(|e| {
+            e.at("signing_payload")
+                .at(&index)
+                .context(|e| format!("signing payload {:?} is invalid: {e}", sig.signing_payload))
+        })?;
+
+        public_key(sig.public_key.as_ref()).map_err(|e| {
+            e.at("public_key")
+                .at(&index)
+                .context(|e| format!("public key {:?} is invalid: {e}", sig.public_key))
+        })?;
+
+        signature_type(&sig.signature_type).map_err(|e| {
+            e.at("signature_type")
+                .at(&index)
+                .with_value(&sig.signature_type)
+                .context(|e| format!("signature type {:?} is invalid: {e}", sig.signature_type))
+        })?;
 
         // Return an error if the requested signature type does not match the
         // signature type in the returned signature.
         let sig_type = &sig.signing_payload.as_ref().unwrap().signature_type;
         if !sig_type.is_empty() && *sig_type != sig.signature_type {
-            Err(ConstructionError::SignaturesReturnedSigMismatch)?;
+            Err(
+                AsserterError::from(ConstructionError::SignaturesReturnedSigMismatch)
+                    .at("signature_type")
+                    .at(&index)
+                    .with_value(&sig.signature_type),
+            )?;
         } else if sig.bytes.is_empty() {
-            Err(ConstructionError::SignatureBytesEmpty)?;
+            Err(AsserterError::from(ConstructionError::SignatureBytesEmpty)
+                .at("bytes")
+                .at(&index))?;
         } else if bytes_array_zero(&sig.bytes) {
-            Err(ConstructionError::SignatureBytesZero)?;
+            Err(AsserterError::from(ConstructionError::SignatureBytesZero)
+                .at("bytes")
+                .at(&index))?;
         }
     }
 
     Ok(())
 }
 
+/// `SignatureVerifier` checks a signature against the public key embedded
+/// in it. The asserter has no cryptography of its own, so implementations
+/// are provided by crates such as `mentat-keys`.
+pub trait SignatureVerifier: std::fmt::Debug + Send + Sync {
+    /// `verify_signature` returns an error if the signature was not made by
+    /// the private key of its public key.
+    fn verify_signature(&self, signature: &UncheckedSignature) -> Result<(), String>;
+}
+
 /// signature_type returns an error if
 /// signature is not a valid [`SignatureType`].
 pub fn signature_type(st: &UncheckedSignatureType) -> AssertResult<()> {
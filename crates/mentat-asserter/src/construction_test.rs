@@ -639,6 +639,32 @@ fn test_signing_payload() {
             }),
             criteria: None,
         },
+        TestCase {
+            name: "valid signing payload with schnorr_1 signature type",
+            payload: Some(UncheckedSigningPayload {
+                account_identifier: Some(AccountIdentifier {
+                    address: "hello".into(),
+                    ..Default::default()
+                }),
+                bytes: "blah".into(),
+                signature_type: UncheckedSignatureType::SCHNORR_1.into(),
+                ..Default::default()
+            }),
+            criteria: None,
+        },
+        TestCase {
+            name: "valid signing payload with schnorr_bip340 signature type",
+            payload: Some(UncheckedSigningPayload {
+                account_identifier: Some(AccountIdentifier {
+                    address: "hello".into(),
+                    ..Default::default()
+                }),
+                bytes: "blah".into(),
+                signature_type: UncheckedSignatureType::SCHNORR_BIP340.into(),
+                ..Default::default()
+            }),
+            criteria: None,
+        },
         TestCase {
             name: "nil signing payload",
             payload: None,
@@ -1,5 +1,9 @@
 //! Errors for the validations in this module.
 
+use indexmap::IndexMap;
+use mentat_types::MentatError;
+use serde::Serialize;
+use serde_json::{json, Value};
 use thiserror::Error;
 
 /// Account Balance Errors
@@ -14,6 +18,17 @@ pub enum AccountBalanceError {
     ReturnedBlockIndexMismatch,
 }
 
+impl AccountBalanceError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::CurrencyUsedMultipleTimes => "currency_used_multiple_times",
+            Self::ReturnedBlockHashMismatch => "returned_block_hash_mismatch",
+            Self::ReturnedBlockIndexMismatch => "returned_block_index_mismatch",
+        }
+    }
+}
+
 /// Block Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -102,6 +117,59 @@ pub enum BlockError {
     FeeAmountNotNegative,
 }
 
+impl BlockError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AmountValueMissing => "amount_value_missing",
+            Self::AmountIsNotInt => "amount_is_not_int",
+            Self::AmountCurrencyIsNil => "amount_currency_is_nil",
+            Self::AmountCurrencySymbolEmpty => "amount_currency_symbol_empty",
+            Self::AmountCurrencyHasNegDecimals => "amount_currency_has_neg_decimals",
+            Self::OperationIdentifierIndexIsNil => "operation_identifier_index_is_nil",
+            Self::OperationIdentifierIndexOutOfOrder => "operation_identifier_index_out_of_order",
+            Self::OperationIdentifierNetworkIndexInvalid => {
+                "operation_identifier_network_index_invalid"
+            }
+            Self::AccountIsNil => "account_is_nil",
+            Self::AccountAddrMissing => "account_addr_missing",
+            Self::AccountSubAccountAddrMissing => "account_sub_account_addr_missing",
+            Self::OperationStatusMissing => "operation_status_missing",
+            Self::OperationStatusInvalid => "operation_status_invalid",
+            Self::OperationTypeInvalid => "operation_type_invalid",
+            Self::OperationIsNil => "operation_is_nil",
+            Self::OperationStatusNotEmptyForConstruction => {
+                "operation_status_not_empty_for_construction"
+            }
+            Self::RelatedOperationIndexOutOfOrder => "related_operation_index_out_of_order",
+            Self::RelatedOperationIndexDuplicate => "related_operation_index_duplicate",
+            Self::RelatedOperationMissing => "related_operation_missing",
+            Self::RelatedOperationInFeeNotAllowed => "related_operation_in_fee_not_allowed",
+            Self::BlockIdentifierIsNil => "block_identifier_is_nil",
+            Self::BlockIdentifierHashMissing => "block_identifier_hash_missing",
+            Self::BlockIdentifierIndexIsNeg => "block_identifier_index_is_neg",
+            Self::PartialBlockIdentifierIsNil => "partial_block_identifier_is_nil",
+            Self::PartialBlockIdentifierFieldsNotSet => "partial_block_identifier_fields_not_set",
+            Self::TxIdentifierIsNil => "tx_identifier_is_nil",
+            Self::TxIdentifierHashMissing => "tx_identifier_hash_missing",
+            Self::NoOperationsForConstruction => "no_operations_for_construction",
+            Self::TxIsNil => "tx_is_nil",
+            Self::TimestampBeforeMin => "timestamp_before_min",
+            Self::TimestampAfterMax => "timestamp_after_max",
+            Self::BlockIsNil => "block_is_nil",
+            Self::BlockHashEqualsParentBlockHash => "block_hash_equals_parent_block_hash",
+            Self::BlockIndexPrecedesParentBlockIndex => "block_index_precedes_parent_block_index",
+            Self::InvalidDirection => "invalid_direction",
+            Self::DuplicateRelatedTransaction => "duplicate_related_transaction",
+            Self::PaymentAmountNotBalancing => "payment_amount_not_balancing",
+            Self::FeeAmountNotBalancing => "fee_amount_not_balancing",
+            Self::PaymentCountMismatch => "payment_count_mismatch",
+            Self::FeeCountMismatch => "fee_count_mismatch",
+            Self::FeeAmountNotNegative => "fee_amount_not_negative",
+        }
+    }
+}
+
 // Coin Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -120,6 +188,20 @@ pub enum CoinError {
     ActionInvalid,
 }
 
+impl CoinError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::IsNil => "is_nil",
+            Self::Duplicate => "duplicate",
+            Self::IdentifierIsNil => "identifier_is_nil",
+            Self::IdentifierNotSet => "identifier_not_set",
+            Self::ChangeIsNil => "change_is_nil",
+            Self::ActionInvalid => "action_invalid",
+        }
+    }
+}
+
 /// Construction Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -176,6 +258,55 @@ pub enum ConstructionError {
     SignatureBytesZero,
     #[error("not a supported SignatureType")]
     SignatureTypeNotSupported,
+    #[error("signature is not valid for its public key")]
+    SignatureVerificationFailed,
+}
+
+impl ConstructionError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ConstructionPreprocessResponseIsNil => "construction_preprocess_response_is_nil",
+            Self::ConstructionMetadataResponseIsNil => "construction_metadata_response_is_nil",
+            Self::ConstructionMetadataResponseMetadataMissing => {
+                "construction_metadata_response_metadata_missing"
+            }
+            Self::TxIdentifierResponseIsNil => "tx_identifier_response_is_nil",
+            Self::ConstructionCombineResponseIsNil => "construction_combine_response_is_nil",
+            Self::SignedTxEmpty => "signed_tx_empty",
+            Self::ConstructionDeriveResponseIsNil => "construction_derive_response_is_nil",
+            Self::ConstructionParseResponseIsNil => "construction_parse_response_is_nil",
+            Self::ConstructionParseResponseOperationsEmpty => {
+                "construction_parse_response_operations_empty"
+            }
+            Self::ConstructionParseResponseSignersEmptyOnSignedTx => {
+                "construction_parse_response_signers_empty_on_signed_tx"
+            }
+            Self::ConstructionParseResponseSignersNonEmptyOnUnsignedTx => {
+                "construction_parse_response_signers_non_empty_on_unsigned_tx"
+            }
+            Self::ConstructionPayloadsResponseIsNil => "construction_payloads_response_is_nil",
+            Self::ConstructionPayloadsResponseUnsignedTxEmpty => {
+                "construction_payloads_response_unsigned_tx_empty"
+            }
+            Self::ConstructionPayloadsResponsePayloadsEmpty => {
+                "construction_payloads_response_payloads_empty"
+            }
+            Self::PublicKeyIsNil => "public_key_is_nil",
+            Self::PublicKeyBytesEmpty => "public_key_bytes_empty",
+            Self::PublicKeyBytesZero => "public_key_bytes_zero",
+            Self::CurveTypeNotSupported => "curve_type_not_supported",
+            Self::SigningPayloadIsNil => "signing_payload_is_nil",
+            Self::SigningPayloadBytesEmpty => "signing_payload_bytes_empty",
+            Self::SigningPayloadBytesZero => "signing_payload_bytes_zero",
+            Self::SignaturesEmpty => "signatures_empty",
+            Self::SignaturesReturnedSigMismatch => "signatures_returned_sig_mismatch",
+            Self::SignatureBytesEmpty => "signature_bytes_empty",
+            Self::SignatureBytesZero => "signature_bytes_zero",
+            Self::SignatureTypeNotSupported => "signature_type_not_supported",
+            Self::SignatureVerificationFailed => "signature_verification_failed",
+        }
+    }
 }
 
 /// Network Errors
@@ -236,6 +367,46 @@ pub enum NetworkError {
     SyncStatusStageInvalid,
 }
 
+impl NetworkError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SubNetworkIdentifierInvalid => "sub_network_identifier_invalid",
+            Self::NetworkIdentifierIsNil => "network_identifier_is_nil",
+            Self::NetworkIdentifierBlockchainMissing => "network_identifier_blockchain_missing",
+            Self::NetworkIdentifierNetworkMissing => "network_identifier_network_missing",
+            Self::PeerIDMissing => "peer_id_missing",
+            Self::VersionIsNil => "version_is_nil",
+            Self::VersionNodeVersionMissing => "version_node_version_missing",
+            Self::VersionMiddlewareVersionMissing => "version_middleware_version_missing",
+            Self::NetworkStatusResponseIsNil => "network_status_response_is_nil",
+            Self::NoAllowedOperationStatuses => "no_allowed_operation_statuses",
+            Self::NoSuccessfulAllowedOperationStatuses => {
+                "no_successful_allowed_operation_statuses"
+            }
+            Self::ErrorCodeUsedMultipleTimes => "error_code_used_multiple_times",
+            Self::ErrorDetailsPopulated => "error_details_populated",
+            Self::AllowIsNil => "allow_is_nil",
+            Self::NetworkOptionsResponseIsNil => "network_options_response_is_nil",
+            Self::NetworkListResponseIsNil => "network_list_response_is_nil",
+            Self::NetworkListResponseNetworksContainsDuplicates => {
+                "network_list_response_networks_contains_duplicates"
+            }
+            Self::BalanceExemptionIsNil => "balance_exemption_is_nil",
+            Self::BalanceExemptionTypeInvalid => "balance_exemption_type_invalid",
+            Self::BalanceExemptionMissingSubject => "balance_exemption_missing_subject",
+            Self::BalanceExemptionSubAccountAddressEmpty => {
+                "balance_exemption_sub_account_address_empty"
+            }
+            Self::BalanceExemptionNoHistoricalLookup => "balance_exemption_no_historical_lookup",
+            Self::TimestampStartIndexInvalid => "timestamp_start_index_invalid",
+            Self::SyncStatusCurrentIndexNegative => "sync_status_current_index_negative",
+            Self::SyncStatusTargetIndexNegative => "sync_status_target_index_negative",
+            Self::SyncStatusStageInvalid => "sync_status_stage_invalid",
+        }
+    }
+}
+
 /// Server Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -314,6 +485,60 @@ pub enum ServerError {
     DuplicateCurrency,
 }
 
+impl ServerError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoSupportedNetworks => "no_supported_networks",
+            Self::SupportedNetworksDuplicate => "supported_networks_duplicate",
+            Self::RequestedNetworkNotSupported => "requested_network_not_supported",
+            Self::AccountBalanceRequestIsNil => "account_balance_request_is_nil",
+            Self::AccountBalanceRequestHistoricalBalanceLookupNotSupported => {
+                "account_balance_request_historical_balance_lookup_not_supported"
+            }
+            Self::BlockRequestIsNil => "block_request_is_nil",
+            Self::BlockTransactionRequestIsNil => "block_transaction_request_is_nil",
+            Self::ConstructionMetadataRequestIsNil => "construction_metadata_request_is_nil",
+            Self::ConstructionSubmitRequestIsNil => "construction_submit_request_is_nil",
+            Self::ConstructionSubmitRequestSignedTxEmpty => {
+                "construction_submit_request_signed_tx_empty"
+            }
+            Self::MempoolTransactionRequestIsNil => "mempool_transaction_request_is_nil",
+            Self::MetadataRequestIsNil => "metadata_request_is_nil",
+            Self::NetworkRequestIsNil => "network_request_is_nil",
+            Self::ConstructionDeriveRequestIsNil => "construction_derive_request_is_nil",
+            Self::ConstructionPreprocessRequestIsNil => "construction_preprocess_request_is_nil",
+            Self::ConstructionPreprocessRequestSuggestedFeeMultiplierIsNeg => {
+                "construction_preprocess_request_suggested_fee_multiplier_is_neg"
+            }
+            Self::ConstructionPayloadsRequestIsNil => "construction_payloads_request_is_nil",
+            Self::ConstructionCombineRequestIsNil => "construction_combine_request_is_nil",
+            Self::ConstructionCombineRequestUnsignedTxEmpty => {
+                "construction_combine_request_unsigned_tx_empty"
+            }
+            Self::ConstructionHashRequestIsNil => "construction_hash_request_is_nil",
+            Self::ConstructionHashRequestSignedTxEmpty => {
+                "construction_hash_request_signed_tx_empty"
+            }
+            Self::ConstructionParseRequestIsNil => "construction_parse_request_is_nil",
+            Self::ConstructionParseRequestEmpty => "construction_parse_request_empty",
+            Self::CallRequestIsNil => "call_request_is_nil",
+            Self::CallMethodEmpty => "call_method_empty",
+            Self::CallMethodUnsupported => "call_method_unsupported",
+            Self::CallMethodDuplicate => "call_method_duplicate",
+            Self::AccountCoinsRequestIsNil => "account_coins_request_is_nil",
+            Self::MempoolCoinsNotSupported => "mempool_coins_not_supported",
+            Self::EventsBlocksRequestIsNil => "events_blocks_request_is_nil",
+            Self::OffsetIsNegative => "offset_is_negative",
+            Self::LimitIsNegative => "limit_is_negative",
+            Self::SearchTransactionsRequestIsNil => "search_transactions_request_is_nil",
+            Self::OperatorInvalid => "operator_invalid",
+            Self::MaxBlockInvalid => "max_block_invalid",
+            Self::DuplicateCurrency => "duplicate_currency",
+        }
+    }
+}
+
 /// Event Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -328,6 +553,18 @@ pub enum EventError {
     SequenceOutOfOrder,
 }
 
+impl EventError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MaxSequenceInvalid => "max_sequence_invalid",
+            Self::SequenceInvalid => "sequence_invalid",
+            Self::BlockEventTypeInvalid => "block_event_type_invalid",
+            Self::SequenceOutOfOrder => "sequence_out_of_order",
+        }
+    }
+}
+
 /// Search Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -338,6 +575,16 @@ pub enum SearchError {
     TotalCountInvalid,
 }
 
+impl SearchError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NextOffsetInvalid => "next_offset_invalid",
+            Self::TotalCountInvalid => "total_count_invalid",
+        }
+    }
+}
+
 /// Error Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -358,6 +605,58 @@ pub enum ErrorError {
     DescriptionEmpty,
 }
 
+impl ErrorError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::IsNil => "is_nil",
+            Self::CodeIsNeg => "code_is_neg",
+            Self::MessageMissing => "message_missing",
+            Self::UnexpectedCode => "unexpected_code",
+            Self::MessageMismatch => "message_mismatch",
+            Self::RetriableMismatch => "retriable_mismatch",
+            Self::DescriptionEmpty => "description_empty",
+        }
+    }
+}
+
+/// Rule errors
+#[derive(Debug, Error, Eq, PartialEq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub enum RuleError {
+    #[error("rule {rule} is violated: {reason}")]
+    Violated { rule: String, reason: String },
+}
+
+impl RuleError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Violated { .. } => "violated",
+        }
+    }
+}
+
+/// Spec errors
+#[derive(Debug, Error, Eq, PartialEq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub enum SpecError {
+    #[error("rosetta version is not a valid spec version")]
+    VersionInvalid,
+    #[error("feature is not supported by the rosetta version of the server")]
+    FeatureNotSupported,
+}
+
+impl SpecError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::VersionInvalid => "version_invalid",
+            Self::FeatureNotSupported => "feature_not_supported",
+        }
+    }
+}
+
 /// Util errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -376,6 +675,20 @@ pub enum UtilError {
     AccountArrayDuplicateAccount,
 }
 
+impl UtilError {
+    /// `code` returns the stable code of the error within its area.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::StringArrayEmpty => "string_array_empty",
+            Self::StringArrayEmptyString => "string_array_empty_string",
+            Self::StringArrayDuplicateString => "string_array_duplicate_string",
+            Self::AccountArrayEmpty => "account_array_empty",
+            Self::AccountArrayInvalidAccount => "account_array_invalid_account",
+            Self::AccountArrayDuplicateAccount => "account_array_duplicate_account",
+        }
+    }
+}
+
 /// Asserter Errors
 #[derive(Debug, Error, Eq, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -403,11 +716,163 @@ pub enum AsserterError {
     #[error(transparent)]
     Error(#[from] ErrorError),
     #[error(transparent)]
+    Rule(#[from] RuleError),
+    #[error(transparent)]
+    Spec(#[from] SpecError),
+    #[error(transparent)]
     Util(#[from] UtilError),
+    #[error(transparent)]
+    Assertion(Box<AssertionError>),
     #[error("{0}")]
     StringError(String),
 }
 
+/// `AssertionError` locates an assertion failure in the asserted object.
+#[derive(Debug, Error, Eq, PartialEq)]
+#[error("{message}")]
+pub struct AssertionError {
+    /// The message of the failure, including the context added by each
+    /// assertion it passed through.
+    pub message: String,
+    /// The fields leading from the asserted object to the offending field,
+    /// i.e. `["transactions", "[3]", "operations", "[1]", "amount", "value"]`.
+    pub path: Vec<String>,
+    /// The offending value, if known.
+    pub value: Option<Value>,
+    /// The error the failure originated from.
+    #[source]
+    pub cause: Box<AsserterError>,
+}
+
+impl AssertionError {
+    /// `json_path` joins the path of the failure, i.e.
+    /// `transactions[3].operations[1].amount.value`.
+    pub fn json_path(&self) -> String {
+        self.path.iter().fold(String::new(), |mut path, field| {
+            if !path.is_empty() && !field.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(field);
+            path
+        })
+    }
+}
+
+impl AsserterError {
+    /// `at` records that the error was found in `field` of the asserted
+    /// object, in front of the fields recorded so far. A field is either a
+    /// name or an index, i.e. `[3]`.
+    pub fn at(self, field: impl Into<String>) -> Self {
+        let mut located = self.into_located();
+        located.path.insert(0, field.into());
+        Self::Assertion(Box::new(located))
+    }
+
+    /// `with_value` records the offending value, unless one was recorded
+    /// already.
+    pub fn with_value<T: Serialize + ?Sized>(self, value: &T) -> Self {
+        let mut located = self.into_located();
+        if located.value.is_none() {
+            located.value = serde_json::to_value(value).ok();
+        }
+        Self::Assertion(Box::new(located))
+    }
+
+    /// `context` replaces the message of the error, keeping where it was
+    /// found.
+    pub fn context(self, message: impl FnOnce(&Self) -> String) -> Self {
+        let message = message(&self);
+        let mut located = self.into_located();
+        located.message = message;
+        Self::Assertion(Box::new(located))
+    }
+
+    /// Turns the error into an [`AssertionError`].
+    fn into_located(self) -> AssertionError {
+        match self {
+            Self::Assertion(located) => *located,
+            err => AssertionError {
+                message: err.to_string(),
+                path: Vec::new(),
+                value: None,
+                cause: Box::new(err),
+            },
+        }
+    }
+
+    /// `root` returns the error the failure originated from.
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Assertion(located) => located.cause.root(),
+            err => err,
+        }
+    }
+
+    /// `code` returns a stable code for the kind of failure, made of the
+    /// area and the variant of the error it originated from, i.e.
+    /// `block.amount_is_not_int`. Errors only described by a message are
+    /// `asserter.unknown`.
+    pub fn code(&self) -> String {
+        let (area, variant) = match self.root() {
+            Self::NotInitialized => ("asserter", "not_initialized"),
+            Self::AccountBalance(e) => ("account_balance", e.code()),
+            Self::Block(e) => ("block", e.code()),
+            Self::Coin(e) => ("coin", e.code()),
+            Self::Construction(e) => ("construction", e.code()),
+            Self::Network(e) => ("network", e.code()),
+            Self::Server(e) => ("server", e.code()),
+            Self::Event(e) => ("event", e.code()),
+            Self::Search(e) => ("search", e.code()),
+            Self::Error(e) => ("error", e.code()),
+            Self::Rule(e) => ("rule", e.code()),
+            Self::Spec(e) => ("spec", e.code()),
+            Self::Util(e) => ("util", e.code()),
+            Self::Assertion(_) | Self::StringError(_) => ("asserter", "unknown"),
+        };
+        format!("{area}.{variant}")
+    }
+
+    /// `json_path` returns the path to the offending field, if known.
+    pub fn json_path(&self) -> Option<String> {
+        match self {
+            Self::Assertion(located) if !located.path.is_empty() => Some(located.json_path()),
+            _ => None,
+        }
+    }
+
+    /// `value` returns the offending value, if known.
+    pub fn value(&self) -> Option<&Value> {
+        match self {
+            Self::Assertion(located) => located.value.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// `details` returns the code, path and value of the failure as the
+    /// `details` of a [`MentatError`].
+    pub fn details(&self) -> IndexMap<String, Value> {
+        let mut assertion = json!({ "code": self.code() });
+        if let Some(path) = self.json_path() {
+            assertion["path"] = path.into();
+        }
+        if let Some(value) = self.value() {
+            assertion["value"] = value.clone();
+        }
+        [("assertion".to_string(), assertion)].into()
+    }
+
+    /// `into_mentat_error` turns a failed assertion into a [`MentatError`]
+    /// with the code of [`MentatError::assertion_failed`], whose `details`
+    /// describe the failure.
+    pub fn into_mentat_error(self) -> MentatError {
+        MentatError {
+            message: self.to_string(),
+            details: self.details(),
+            ..MentatError::assertion_failed::<&str, ()>(None).unwrap_err()
+        }
+    }
+}
+
 impl From<String> for AsserterError {
     fn from(s: String) -> Self {
         Self::StringError(s)
@@ -441,10 +906,14 @@ pub fn err(err: Box<dyn std::error::Error>) -> (bool, &'static str) {
         (true, "evet error")
     } else if err.is::<NetworkError>() {
         (true, "network error")
+    } else if err.is::<RuleError>() {
+        (true, "rule error")
     } else if err.is::<SearchError>() {
         (true, "search error")
     } else if err.is::<ServerError>() {
         (true, "server error")
+    } else if err.is::<SpecError>() {
+        (true, "spec error")
     } else if err.is::<UtilError>() {
         (true, "util error")
     } else {
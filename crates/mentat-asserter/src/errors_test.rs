@@ -46,6 +46,20 @@ fn test_err() {
             payload: NetworkError::NetworkIdentifierIsNil.into(),
             criteria: (true, "network error"),
         },
+        TestCase {
+            name: "rule error",
+            payload: RuleError::Violated {
+                rule: "memo".into(),
+                reason: "payment has no memo".into(),
+            }
+            .into(),
+            criteria: (true, "rule error"),
+        },
+        TestCase {
+            name: "spec error",
+            payload: SpecError::FeatureNotSupported.into(),
+            criteria: (true, "spec error"),
+        },
         TestCase {
             name: "server error",
             payload: ServerError::NoSupportedNetworks.into(),
@@ -63,3 +77,86 @@ fn test_err() {
 
     TestCase::run_output_match(tests, crate::errors::err);
 }
+
+#[test]
+fn test_assertion_error() {
+    let err = AsserterError::from(BlockError::AmountIsNotInt)
+        .at("value")
+        .with_value("1.5")
+        .at("amount")
+        .with_value("ignored")
+        .at("[1]")
+        .at("operations")
+        .context(|e| format!("operation is invalid: {e}"));
+
+    assert_eq!(err.code(), "block.amount_is_not_int");
+    assert_eq!(err.root(), &AsserterError::from(BlockError::AmountIsNotInt));
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("operations[1].amount.value")
+    );
+    assert_eq!(err.value(), Some(&json!("1.5")));
+    assert_eq!(
+        err.to_string(),
+        "operation is invalid: Amount.Value is not an integer"
+    );
+    assert_eq!(
+        err.details()["assertion"],
+        json!({
+            "code": "block.amount_is_not_int",
+            "path": "operations[1].amount.value",
+            "value": "1.5",
+        })
+    );
+
+    // assertion failures are reported with their own code, not the code of
+    // an endpoint that is not implemented.
+    let (message, details) = (err.to_string(), err.details());
+    let mentat_err = err.into_mentat_error();
+    let assertion_failed = MentatError::assertion_failed::<&str, ()>(None).unwrap_err();
+    assert_eq!(mentat_err.code, assertion_failed.code);
+    assert_ne!(mentat_err.code, 0);
+    assert!(!mentat_err.is_not_implemented());
+    assert_eq!(mentat_err.message, message);
+    assert_eq!(mentat_err.details, details);
+
+    let codes = vec![
+        (AsserterError::NotInitialized, "asserter.not_initialized"),
+        (
+            NetworkError::NetworkIdentifierIsNil.into(),
+            "network.network_identifier_is_nil",
+        ),
+        (
+            RuleError::Violated {
+                rule: "memo".into(),
+                reason: "payment has no memo".into(),
+            }
+            .into(),
+            "rule.violated",
+        ),
+        (CoinError::Duplicate.into(), "coin.duplicate"),
+        (
+            ConstructionError::SignatureVerificationFailed.into(),
+            "construction.signature_verification_failed",
+        ),
+        ("unstructured".into(), "asserter.unknown"),
+        // acronyms are kept whole.
+        (
+            NetworkError::PeerIDMissing.into(),
+            "network.peer_id_missing",
+        ),
+        (
+            BlockError::TxIdentifierIsNil.into(),
+            "block.tx_identifier_is_nil",
+        ),
+        (
+            NetworkError::SubNetworkIdentifierInvalid.into(),
+            "network.sub_network_identifier_invalid",
+        ),
+    ];
+    for (err, code) in codes {
+        assert_eq!(err.code(), code);
+        assert_eq!(err.json_path(), None);
+        assert_eq!(err.details()["assertion"], json!({ "code": code }));
+    }
+}
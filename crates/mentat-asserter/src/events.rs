@@ -9,18 +9,24 @@ pub fn block_event(event: Option<&UncheckedBlockEvent>) -> AssertResult<()> {
     let event = event.unwrap();
 
     if event.sequence < 0 {
-        Err(EventError::SequenceInvalid)?;
+        Err(AsserterError::from(EventError::SequenceInvalid)
+            .at("sequence")
+            .with_value(&event.sequence))?;
     }
 
     block_identifier(event.block_identifier.as_ref()).map_err(|e| {
-        format!(
-            "block identifier {:?} is invalid: {e}",
-            event.block_identifier
-        )
+        e.at("block_identifier").context(|e| {
+            format!(
+                "block identifier {:?} is invalid: {e}",
+                event.block_identifier
+            )
+        })
     })?;
 
     if !event.type_.valid() {
-        Err(EventError::BlockEventTypeInvalid)?
+        Err(AsserterError::from(EventError::BlockEventTypeInvalid)
+            .at("type")
+            .with_value(&event.type_))?
     } else {
         Ok(())
     }
@@ -39,15 +45,22 @@ pub fn events_blocks_response(
     }
     let mut seq = -1;
     for (i, event) in response.events.iter().enumerate() {
-        block_event(event.as_ref())
-            .map_err(|e| format!("block event {event:?} is invalid: {e}"))?;
+        block_event(event.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .at("events")
+                .context(|e| format!("block event {event:?} is invalid: {e}"))
+        })?;
         let event = event.as_ref().unwrap();
 
         if seq == -1 {
             seq = event.sequence
         }
         if event.sequence != seq + (i as isize) {
-            Err(EventError::SequenceOutOfOrder)?;
+            Err(AsserterError::from(EventError::SequenceOutOfOrder)
+                .at("sequence")
+                .at(format!("[{i}]"))
+                .at("events")
+                .with_value(&event.sequence))?;
         }
     }
     Ok(())
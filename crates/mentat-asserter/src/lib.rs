@@ -41,12 +41,18 @@ pub use mempool::*;
 mod network;
 pub use network::*;
 
+mod rules;
+pub use rules::*;
+
 mod search;
 pub use search::*;
 
 mod server;
 pub use server::*;
 
+mod spec;
+pub use spec::*;
+
 mod util;
 pub use util::*;
 
@@ -67,7 +73,9 @@ pub mod tests {
     mod errors_test;
     mod events_test;
     mod network_test;
+    mod rules_test;
     mod search_test;
     mod server_test;
     use server_test::*;
+    mod spec_test;
 }
@@ -7,8 +7,10 @@ use super::*;
 /// The correctness of each populated [`MempoolTransaction`] is
 /// asserted by [`Transaction`].
 pub fn mempool_transactions(transactions: &[Option<TransactionIdentifier>]) -> AssertResult<()> {
-    transactions.iter().try_for_each(|t| {
-        transaction_identifier(t.as_ref())
-            .map_err(|e| format!("transaction identifier {t:?} is invalid: {e}").into())
+    transactions.iter().enumerate().try_for_each(|(i, t)| {
+        transaction_identifier(t.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .context(|e| format!("transaction identifier {t:?} is invalid: {e}"))
+        })
     })
 }
@@ -13,7 +13,11 @@ pub fn sub_network_identifier(
     };
 
     if sub_network_identifier.network.is_empty() {
-        Err(NetworkError::SubNetworkIdentifierInvalid)?;
+        Err(
+            AsserterError::from(NetworkError::SubNetworkIdentifierInvalid)
+                .at("network")
+                .with_value(&sub_network_identifier.network),
+        )?;
     }
 
     Ok(())
@@ -25,14 +29,23 @@ pub fn network_identifier(network: Option<&NetworkIdentifier>) -> AssertResult<(
     let network = network.ok_or(NetworkError::NetworkIdentifierIsNil)?;
 
     if network.blockchain.is_empty() {
-        Err(NetworkError::NetworkIdentifierBlockchainMissing)?;
+        Err(
+            AsserterError::from(NetworkError::NetworkIdentifierBlockchainMissing)
+                .at("blockchain")
+                .with_value(&network.blockchain),
+        )?;
     }
 
     if network.network.is_empty() {
-        Err(NetworkError::NetworkIdentifierNetworkMissing)?;
+        Err(
+            AsserterError::from(NetworkError::NetworkIdentifierNetworkMissing)
+                .at("network")
+                .with_value(&network.network),
+        )?;
     }
 
     sub_network_identifier(network.sub_network_identifier.as_ref())
+        .map_err(|e| e.at("sub_network_identifier"))
 }
 
 /// peer ensures a [`Peer`] has a valid peer_id.
@@ -92,29 +105,47 @@ pub fn network_status_response(resp: Option<&UncheckedNetworkStatusResponse>) ->
     let resp = resp.ok_or(NetworkError::NetworkStatusResponseIsNil)?;
 
     block_identifier(resp.current_block_identifier.as_ref()).map_err(|e| {
-        format!(
-            "current block identifier {:?} is invalid: {e}",
-            resp.current_block_identifier
-        )
+        e.at("current_block_identifier").context(|e| {
+            format!(
+                "current block identifier {:?} is invalid: {e}",
+                resp.current_block_identifier
+            )
+        })
     })?;
     timestamp(resp.current_block_timestamp).map_err(|e| {
-        format!(
-            "current block timestamp {} is invalid: {e}",
-            resp.current_block_timestamp
-        )
+        AsserterError::from(e)
+            .at("current_block_timestamp")
+            .with_value(&resp.current_block_timestamp)
+            .context(|e| {
+                format!(
+                    "current block timestamp {} is invalid: {e}",
+                    resp.current_block_timestamp
+                )
+            })
     })?;
     block_identifier(resp.genesis_block_identifier.as_ref()).map_err(|e| {
-        format!(
-            "genesis block identifier {:?} is invalid: {e}",
-            resp.genesis_block_identifier
-        )
+        e.at("genesis_block_identifier").context(|e| {
+            format!(
+                "genesis block identifier {:?} is invalid: {e}",
+                resp.genesis_block_identifier
+            )
+        })
     })?;
     resp.peers
         .iter()
-        .map(|p| peer(p.as_ref()).map_err(|e| format!("peer {p:?} is invalid: {e}").into()))
+        .enumerate()
+        .map(|(i, p)| {
+            peer(p.as_ref()).map_err(|e| {
+                e.at(format!("[{i}]"))
+                    .at("peers")
+                    .context(|e| format!("peer {p:?} is invalid: {e}"))
+            })
+        })
         .collect::<AssertResult<Vec<_>>>()?;
-    sync_status(resp.sync_status.as_ref())
-        .map_err(|e| format!("sync status {:?} is invalid: {e}", resp.sync_status).into())
+    sync_status(resp.sync_status.as_ref()).map_err(|e| {
+        e.at("sync_status")
+            .context(|e| format!("sync status {:?} is invalid: {e}", resp.sync_status))
+    })
 }
 
 /// `operation_statuses` ensures all [`OperationStatus`] in
@@ -175,8 +206,11 @@ pub fn error(err: Option<&UncheckedMentatError>) -> AssertResult<()> {
 pub fn errors(errors: &[Option<UncheckedMentatError>]) -> AssertResult<()> {
     let mut status_codes = IndexSet::new();
 
-    for err in errors {
-        error(err.as_ref()).map_err(|e| format!("error {err:?} is invalid: {e}"))?;
+    for (i, err) in errors.iter().enumerate() {
+        error(err.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .context(|e| format!("error {err:?} is invalid: {e}"))
+        })?;
         let err = err.as_ref().unwrap();
 
         if !err.details.is_empty() {
@@ -195,44 +229,63 @@ pub fn errors(errors: &[Option<UncheckedMentatError>]) -> AssertResult<()> {
 
 /// `balance_exemptions` ensures [`BalanceExemption`]] in a slice is valid.
 pub fn balance_exemptions(exemptions: &[Option<UncheckedBalanceExemption>]) -> AssertResult<()> {
-    for exemption in exemptions {
-        let exemption = exemption.as_ref().ok_or(format!(
-            "balance exemption {exemption:?} is invalid: {}",
-            NetworkError::BalanceExemptionIsNil,
-        ))?;
+    for (i, exemption) in exemptions.iter().enumerate() {
+        let index = format!("[{i}]");
+        let exemption = exemption.as_ref().ok_or_else(|| {
+            AsserterError::from(NetworkError::BalanceExemptionIsNil)
+                .at(&index)
+                .context(|e| format!("balance exemption {exemption:?} is invalid: {e}"))
+        })?;
 
         if !exemption.exemption_type.valid() {
-            Err(format!(
-                "balance exemption type {:?} is invalid: {}",
-                exemption.exemption_type,
-                NetworkError::BalanceExemptionTypeInvalid,
-            ))?;
+            Err(
+                AsserterError::from(NetworkError::BalanceExemptionTypeInvalid)
+                    .at("exemption_type")
+                    .at(&index)
+                    .with_value(&exemption.exemption_type)
+                    .context(|e| {
+                        format!(
+                            "balance exemption type {:?} is invalid: {e}",
+                            exemption.exemption_type
+                        )
+                    }),
+            )?;
         }
 
         if exemption.currency.is_none() && exemption.sub_account_address.is_none() {
-            Err(format!(
-                "balance exemption {exemption:?} is invalid: {}",
-                NetworkError::BalanceExemptionMissingSubject
-            ))?
+            Err(
+                AsserterError::from(NetworkError::BalanceExemptionMissingSubject)
+                    .at(&index)
+                    .context(|e| format!("balance exemption {exemption:?} is invalid: {e}")),
+            )?
         }
 
         if exemption.currency.is_some() {
             currency(exemption.currency.as_ref()).map_err(|e| {
-                format!(
-                    "balance exemption currency {:?} is invalid: {e}",
-                    exemption.currency
-                )
+                e.at("currency").at(&index).context(|e| {
+                    format!(
+                        "balance exemption currency {:?} is invalid: {e}",
+                        exemption.currency
+                    )
+                })
             })?;
         }
 
         if exemption.sub_account_address.is_some()
             && exemption.sub_account_address.as_ref().unwrap().is_empty()
         {
-            Err(format!(
-                "balance exemption sub account address {:?} is invalid: {}",
-                exemption.sub_account_address,
-                NetworkError::BalanceExemptionSubAccountAddressEmpty
-            ))?
+            Err(
+                AsserterError::from(NetworkError::BalanceExemptionSubAccountAddressEmpty)
+                    .at("sub_account_address")
+                    .at(&index)
+                    .with_value(&exemption.sub_account_address)
+                    .context(|e| {
+                        format!(
+                            "balance exemption sub account address {:?} is invalid: {e}",
+                            exemption.sub_account_address
+                        )
+                    }),
+            )?
         }
     }
 
@@ -246,7 +299,7 @@ pub fn call_methods(methods: &[String]) -> AssertResult<()> {
     }
 
     string_array("Allow.CallMethods", methods)
-        .map_err(|e| format!("methods {methods:?} are invalid: {e}").into())
+        .map_err(|e| e.context(|e| format!("methods {methods:?} are invalid: {e}")))
 }
 
 /// `allow` ensures a [`Allow`] object is valid.
@@ -254,37 +307,49 @@ pub fn allow(allowed: Option<&UncheckedAllow>) -> AssertResult<()> {
     let allowed = allowed.ok_or(NetworkError::AllowIsNil)?;
 
     operation_statuses(&allowed.operation_statuses).map_err(|e| {
-        format!(
-            "operation statuses {:?} are invalid: {e}",
-            allowed.operation_statuses
-        )
+        e.at("operation_statuses").context(|e| {
+            format!(
+                "operation statuses {:?} are invalid: {e}",
+                allowed.operation_statuses
+            )
+        })
     })?;
     operation_types(&allowed.operation_types).map_err(|e| {
-        format!(
-            "operation types {:?} are invalid: {e}",
-            allowed.operation_types
-        )
+        e.at("operation_types").context(|e| {
+            format!(
+                "operation types {:?} are invalid: {e}",
+                allowed.operation_types
+            )
+        })
+    })?;
+    errors(&allowed.errors).map_err(|e| {
+        e.at("errors")
+            .context(|e| format!("errors {:?} are invalid: {e}", allowed.errors))
+    })?;
+    call_methods(&allowed.call_methods).map_err(|e| {
+        e.at("call_methods")
+            .context(|e| format!("call methods {:?} are invalid: {e}", allowed.call_methods))
     })?;
-    errors(&allowed.errors).map_err(|e| format!("errors {:?} are invalid: {e}", allowed.errors))?;
-    call_methods(&allowed.call_methods)
-        .map_err(|e| format!("call methods {:?} are invalid: {e}", allowed.call_methods))?;
     balance_exemptions(&allowed.balance_exemptions).map_err(|e| {
-        format!(
-            "balance exemptions {:?} are invalid: {e}",
-            allowed.balance_exemptions
-        )
+        e.at("balance_exemptions").context(|e| {
+            format!(
+                "balance exemptions {:?} are invalid: {e}",
+                allowed.balance_exemptions
+            )
+        })
     })?;
 
     if !allowed.balance_exemptions.is_empty() && !allowed.historical_balance_lookup {
         Err(NetworkError::BalanceExemptionNoHistoricalLookup)?;
     }
 
-    if matches!(allowed.timestamp_start_index, Some(i) if i < 0) {
-        Err(format!(
-            "timestamp start index {} is invalid: {}",
-            allowed.timestamp_start_index.unwrap(),
-            NetworkError::TimestampStartIndexInvalid,
-        ))?
+    if let Some(index) = allowed.timestamp_start_index.filter(|i| *i < 0) {
+        Err(
+            AsserterError::from(NetworkError::TimestampStartIndexInvalid)
+                .at("timestamp_start_index")
+                .with_value(&index)
+                .context(|e| format!("timestamp start index {index} is invalid: {e}")),
+        )?
     }
 
     Ok(())
@@ -318,9 +383,12 @@ pub fn contains_network_identifier(
 pub fn network_list_response(resp: Option<&UncheckedNetworkListResponse>) -> AssertResult<()> {
     let resp = resp.ok_or(NetworkError::NetworkListResponseIsNil)?;
     let mut seen = Vec::new();
-    for network in &resp.network_identifiers {
-        network_identifier(network.as_ref())
-            .map_err(|e| format!("network identifier {network:?} is invalid: {e}"))?;
+    for (i, network) in resp.network_identifiers.iter().enumerate() {
+        network_identifier(network.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .at("network_identifiers")
+                .context(|e| format!("network identifier {network:?} is invalid: {e}"))
+        })?;
         if contains_network_identifier(&seen, network.as_ref()) {
             Err(NetworkError::NetworkListResponseNetworksContainsDuplicates)?;
         }
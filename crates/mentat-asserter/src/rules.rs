@@ -0,0 +1,68 @@
+//! User-defined assertion rules run alongside the built-in block assertions.
+
+use super::*;
+
+/// `AssertionRule` is a user-defined check on the blocks, transactions and
+/// operations a Rosetta server returns, such as "every transfer must carry
+/// memo metadata". Each check runs after the built-in assertions of the same
+/// object succeed, so the required fields are populated. Checks that are not
+/// overridden accept everything.
+pub trait AssertionRule: std::fmt::Debug + Send + Sync {
+    /// `name` identifies the rule in errors.
+    fn name(&self) -> &str;
+
+    /// `block` returns an error if the block violates the rule.
+    fn block(&self, _block: &UncheckedBlock) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// `transaction` returns an error if the transaction violates the rule.
+    fn transaction(&self, _transaction: &UncheckedTransaction) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// `operation` returns an error if the operation violates the rule.
+    fn operation(&self, _operation: &UncheckedOperation) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// `violated` wraps the reason an [`AssertionRule`] rejected an object.
+fn violated(rule: &dyn AssertionRule, reason: String) -> AsserterError {
+    RuleError::Violated {
+        rule: rule.name().to_string(),
+        reason,
+    }
+    .into()
+}
+
+impl Asserter {
+    /// `block_rules` returns an error if a block violates any
+    /// [`AssertionRule`] of the asserter.
+    pub(crate) fn block_rules(&self, block: &UncheckedBlock) -> AssertResult<()> {
+        self.rules.iter().try_for_each(|rule| {
+            rule.block(block)
+                .map_err(|reason| violated(rule.as_ref(), reason))
+        })
+    }
+
+    /// `transaction_rules` returns an error if a transaction or any of its
+    /// operations violates any [`AssertionRule`] of the asserter.
+    pub(crate) fn transaction_rules(&self, transaction: &UncheckedTransaction) -> AssertResult<()> {
+        for (index, operation) in transaction.operations.iter().flatten().enumerate() {
+            self.rules.iter().try_for_each(|rule| {
+                rule.operation(operation).map_err(|reason| {
+                    violated(rule.as_ref(), reason)
+                        .at(format!("[{index}]"))
+                        .at("operations")
+                        .context(|e| format!("operation {index} is invalid: {e}"))
+                })
+            })?;
+        }
+
+        self.rules.iter().try_for_each(|rule| {
+            rule.transaction(transaction)
+                .map_err(|reason| violated(rule.as_ref(), reason))
+        })
+    }
+}
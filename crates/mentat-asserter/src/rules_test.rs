@@ -0,0 +1,226 @@
+use super::*;
+
+/// Every `PAYMENT` operation must carry memo metadata.
+#[derive(Debug)]
+struct MemoRule;
+
+impl AssertionRule for MemoRule {
+    fn name(&self) -> &str {
+        "memo"
+    }
+
+    fn operation(&self, operation: &UncheckedOperation) -> Result<(), String> {
+        if operation.type_ == "PAYMENT" && !operation.metadata.contains_key("memo") {
+            Err("payment has no memo".into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A transaction must have at most a number of operations.
+#[derive(Debug)]
+struct MaxOperationsRule(usize);
+
+impl AssertionRule for MaxOperationsRule {
+    fn name(&self) -> &str {
+        "max operations"
+    }
+
+    fn transaction(&self, transaction: &UncheckedTransaction) -> Result<(), String> {
+        if transaction.operations.len() > self.0 {
+            Err(format!("more than {} operations", self.0))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A block must contain transactions.
+#[derive(Debug)]
+struct NonEmptyRule;
+
+impl AssertionRule for NonEmptyRule {
+    fn name(&self) -> &str {
+        "non empty"
+    }
+
+    fn block(&self, block: &UncheckedBlock) -> Result<(), String> {
+        if block.transactions.is_empty() {
+            Err("block has no transactions".into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn rules_asserter() -> Asserter {
+    Asserter::new_client_with_options(
+        Some(NetworkIdentifier {
+            blockchain: "HELLO".into(),
+            network: "WORLD".into(),
+            sub_network_identifier: None,
+        }),
+        Some(UncheckedBlockIdentifier {
+            hash: "block 0".into(),
+            index: 0,
+        }),
+        vec!["PAYMENT".into()],
+        vec![Some(OperationStatus {
+            status: "SUCCESS".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Validations::default(),
+    )
+    .unwrap()
+}
+
+fn payment(index: isize, memo: Option<&str>) -> Option<UncheckedOperation> {
+    Some(UncheckedOperation {
+        operation_identifier: Some(UncheckedOperationIdentifier {
+            index,
+            network_index: None,
+        }),
+        type_: "PAYMENT".into(),
+        status: Some("SUCCESS".into()),
+        account: Some(AccountIdentifier {
+            address: "test".into(),
+            ..Default::default()
+        }),
+        amount: Some(UncheckedAmount {
+            value: "1000".into(),
+            currency: Some(UncheckedCurrency {
+                symbol: "BTC".into(),
+                decimals: 8,
+                metadata: Default::default(),
+            }),
+            metadata: Default::default(),
+        }),
+        metadata: memo
+            .map(|memo| [("memo".to_string(), json!(memo))].into_iter().collect())
+            .unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+fn block(operations: Vec<Option<UncheckedOperation>>) -> UncheckedBlock {
+    UncheckedBlock {
+        block_identifier: Some(UncheckedBlockIdentifier {
+            hash: "block 1".into(),
+            index: 1,
+        }),
+        parent_block_identifier: Some(UncheckedBlockIdentifier {
+            hash: "block 0".into(),
+            index: 0,
+        }),
+        timestamp: MIN_UNIX_EPOCH + 1,
+        transactions: if operations.is_empty() {
+            Vec::new()
+        } else {
+            vec![Some(UncheckedTransaction {
+                transaction_identifier: Some(TransactionIdentifier { hash: "tx".into() }),
+                operations,
+                ..Default::default()
+            })]
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_rules() {
+    let rule_error = |rule: &str, reason: &str| {
+        Some(AsserterError::from(RuleError::Violated {
+            rule: rule.into(),
+            reason: reason.into(),
+        }))
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "no rules",
+            payload: MethodPayload {
+                caller: rules_asserter(),
+                payload: block(vec![payment(0, None)]),
+            },
+            criteria: None,
+        },
+        TestCase {
+            name: "operation rule passes",
+            payload: MethodPayload {
+                caller: rules_asserter().with_rule(MemoRule),
+                payload: block(vec![payment(0, Some("hi")), payment(1, Some("there"))]),
+            },
+            criteria: None,
+        },
+        TestCase {
+            name: "operation rule fails",
+            payload: MethodPayload {
+                caller: rules_asserter().with_rule(MemoRule),
+                payload: block(vec![payment(0, Some("hi")), payment(1, None)]),
+            },
+            criteria: rule_error("memo", "payment has no memo"),
+        },
+        TestCase {
+            name: "transaction rule fails",
+            payload: MethodPayload {
+                caller: rules_asserter()
+                    .with_rule(MemoRule)
+                    .with_rule(MaxOperationsRule(1)),
+                payload: block(vec![payment(0, Some("hi")), payment(1, Some("there"))]),
+            },
+            criteria: rule_error("max operations", "more than 1 operations"),
+        },
+        TestCase {
+            name: "block rule fails",
+            payload: MethodPayload {
+                caller: rules_asserter().with_rule(NonEmptyRule),
+                payload: block(Vec::new()),
+            },
+            criteria: rule_error("non empty", "block has no transactions"),
+        },
+        TestCase {
+            name: "rules run after built-in assertions",
+            payload: MethodPayload {
+                caller: rules_asserter().with_rule(MemoRule),
+                payload: block(vec![payment(1, None)]),
+            },
+            criteria: Some(BlockError::OperationIdentifierIndexOutOfOrder.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |t| t.caller.block(Some(&t.payload)));
+}
+
+#[test]
+fn test_transaction_rules() {
+    let asserter = rules_asserter().with_rule(MemoRule);
+    let transaction = block(vec![payment(0, None)]).transactions.remove(0);
+
+    let err = asserter.transaction(transaction.as_ref()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("transaction tx violates a rule: operation 0 is invalid"));
+}
+
+#[test]
+fn test_rules_error_location() {
+    let asserter = rules_asserter().with_rule(MemoRule).with_rule(NonEmptyRule);
+
+    let err = asserter
+        .block(Some(&block(vec![payment(0, Some("hi")), payment(1, None)])))
+        .unwrap_err();
+    assert_eq!(err.code(), "rule.violated");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("transactions[0].operations[1]")
+    );
+
+    let err = asserter.block(Some(&block(Vec::new()))).unwrap_err();
+    assert_eq!(err.code(), "rule.violated");
+    assert!(err
+        .to_string()
+        .starts_with("block block 1 violates a rule: rule non empty is violated"));
+}
@@ -12,6 +12,7 @@ impl Asserter {
         self.response
             .as_ref()
             .ok_or(AsserterError::NotInitialized)?;
+        self.require_feature(SpecFeature::Search)?;
 
         // TODO coinbase doesn't check for nil here.
         let response = response.unwrap();
@@ -22,14 +23,28 @@ impl Asserter {
             Err(SearchError::TotalCountInvalid)?;
         }
 
-        response.transactions.iter().try_for_each(|t| {
-            // TODO: coinbase never checks for nil here
-            let t = t.as_ref().unwrap();
-            block_identifier(t.block_identifier.as_ref()).map_err(|e| {
-                format!("block identifier {:?} is invalid: {e}", t.block_identifier)
-            })?;
-            self.transaction(t.transaction.as_ref())
-                .map_err(|e| format!("transaction {:?} is invalid: {e}", t.transaction).into())
-        })
+        response
+            .transactions
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, t)| {
+                // TODO: coinbase never checks for nil here
+                let t = t.as_ref().unwrap();
+                let index = format!("[{i}]");
+                block_identifier(t.block_identifier.as_ref()).map_err(|e| {
+                    e.at("block_identifier")
+                        .at(&index)
+                        .at("transactions")
+                        .context(|e| {
+                            format!("block identifier {:?} is invalid: {e}", t.block_identifier)
+                        })
+                })?;
+                self.transaction(t.transaction.as_ref()).map_err(|e| {
+                    e.at("transaction")
+                        .at(&index)
+                        .at("transactions")
+                        .context(|e| format!("transaction {:?} is invalid: {e}", t.transaction))
+                })
+            })
     }
 }
@@ -10,15 +10,17 @@ pub fn supported_networks(networks: &[Option<NetworkIdentifier>]) -> AssertResul
     }
 
     let mut parsed = Vec::new();
-    for network in networks {
-        network_identifier(network.as_ref())
-            .map_err(|e| format!("network identifier {network:?} is invalid: {e}",))?;
+    for (i, network) in networks.iter().enumerate() {
+        network_identifier(network.as_ref()).map_err(|e| {
+            e.at(format!("[{i}]"))
+                .context(|e| format!("network identifier {network:?} is invalid: {e}"))
+        })?;
 
         if contains_network_identifier(&parsed, network.as_ref()) {
-            Err(format!(
-                "network identifier {network:?} is invalid: {}",
-                ServerError::SupportedNetworksDuplicate
-            ))?;
+            Err(AsserterError::from(ServerError::SupportedNetworksDuplicate)
+                .at(format!("[{i}]"))
+                .with_value(network)
+                .context(|e| format!("network identifier {network:?} is invalid: {e}")))?;
         }
         parsed.push(network.clone().unwrap());
     }
@@ -49,10 +51,16 @@ impl Asserter {
         &self,
         request_network: Option<&NetworkIdentifier>,
     ) -> AssertResult<()> {
-        network_identifier(request_network)
-            .map_err(|e| format!("network identifier {request_network:?} is invalid: {e}"))?;
+        network_identifier(request_network).map_err(|e| {
+            e.at("network_identifier")
+                .context(|e| format!("network identifier {request_network:?} is invalid: {e}"))
+        })?;
         self.supported_network(request_network).map_err(|e| {
-            format!("network identifier {request_network:?} is not supported: {e}").into()
+            e.at("network_identifier")
+                .with_value(&request_network)
+                .context(|e| {
+                    format!("network identifier {request_network:?} is not supported: {e}")
+                })
         })
     }
 
@@ -68,10 +76,12 @@ impl Asserter {
 
         self.valid_supported_network(request.network_identifier.as_ref())?;
         account_identifier(request.account_identifier.as_ref()).map_err(|e| {
-            format!(
-                "account identifier {:?} is invalid: {e}",
-                request.account_identifier
-            )
+            e.at("account_identifier").context(|e| {
+                format!(
+                    "account identifier {:?} is invalid: {e}",
+                    request.account_identifier
+                )
+            })
         })?;
         if let Some(c) = contains_duplicate_currency(
             &request
@@ -80,16 +90,17 @@ impl Asserter {
                 .map(|i| i.as_ref())
                 .collect::<Vec<_>>(),
         ) {
-            Err(format!(
-                "currency {c:?} is invalid: {}",
-                ServerError::DuplicateCurrency
-            ))?
+            Err(AsserterError::from(ServerError::DuplicateCurrency)
+                .at("currencies")
+                .with_value(c)
+                .context(|e| format!("currency {c:?} is invalid: {e}")))?
         } else if request.block_identifier.is_none() {
             Ok(())
         } else if !asserter.historical_balance_lookup {
             Err(ServerError::AccountBalanceRequestHistoricalBalanceLookupNotSupported)?
         } else {
             partial_block_identifier(Some(request.block_identifier.as_ref().unwrap()))
+                .map_err(|e| e.at("block_identifier"))
         }
     }
 
@@ -100,6 +111,7 @@ impl Asserter {
         let request = request.ok_or(ServerError::BlockRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
         partial_block_identifier(request.block_identifier.as_ref())
+            .map_err(|e| e.at("block_identifier"))
     }
 
     /// [`block_transaction_request`] ensures that a [`BlockTransactionRequest`]
@@ -112,12 +124,15 @@ impl Asserter {
         let request = request.ok_or(ServerError::BlockTransactionRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
         block_identifier(request.block_identifier.as_ref()).map_err(|e| {
-            format!(
-                "block identifier {:?} is invalid: {e}",
-                request.block_identifier
-            )
+            e.at("block_identifier").context(|e| {
+                format!(
+                    "block identifier {:?} is invalid: {e}",
+                    request.block_identifier
+                )
+            })
         })?;
         transaction_identifier(request.transaction_identifier.as_ref())
+            .map_err(|e| e.at("transaction_identifier"))
     }
 
     /// [`construction_metadata_request`] ensures that a
@@ -131,9 +146,17 @@ impl Asserter {
 
         self.valid_supported_network(request.network_identifier.as_ref())?;
 
-        request.public_keys.iter().try_for_each(|k| {
-            public_key(k.as_ref()).map_err(|e| format!("public key {k:?} is invalid: {e}").into())
-        })
+        request
+            .public_keys
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, k)| {
+                public_key(k.as_ref()).map_err(|e| {
+                    e.at(format!("[{i}]"))
+                        .at("public_keys")
+                        .context(|e| format!("public key {k:?} is invalid: {e}"))
+                })
+            })
     }
 
     /// [`construction_submit_request`] ensures that a
@@ -162,6 +185,7 @@ impl Asserter {
         let request = request.ok_or(ServerError::MempoolTransactionRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
         transaction_identifier(request.transaction_identifier.as_ref())
+            .map_err(|e| e.at("transaction_identifier"))
     }
 
     /// [`metadata_request`] ensures that a [`MetadataRequest`]
@@ -189,8 +213,10 @@ impl Asserter {
         self.request.as_ref().ok_or(AsserterError::NotInitialized)?;
         let request = request.ok_or(ServerError::ConstructionDeriveRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
-        public_key(request.public_key.as_ref())
-            .map_err(|e| format!("public key {:?} is invalid: {e}", request.public_key).into())
+        public_key(request.public_key.as_ref()).map_err(|e| {
+            e.at("public_key")
+                .context(|e| format!("public key {:?} is invalid: {e}", request.public_key))
+        })
     }
 
     /// [`construction_preprocess_request`] ensures that a
@@ -202,16 +228,21 @@ impl Asserter {
         self.request.as_ref().ok_or(AsserterError::NotInitialized)?;
         let request = request.ok_or(ServerError::ConstructionPreprocessRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
-        self.operations(&request.operations, true)
-            .map_err(|e| format!("operations {:?} are invalid: {e}", request.operations))?;
-        assert_unique_amounts(&request.max_fee)
-            .map_err(|e| format!("max fee {:?} is invalid: {e}", request.max_fee))?;
-        if matches!(request.suggested_fee_multiplier, Some(i) if i < 0.0) {
-            Err(format!(
-                "suggested fee multiplier {} is invalid: {}",
-                request.suggested_fee_multiplier.unwrap(),
+        self.operations(&request.operations, true).map_err(|e| {
+            e.at("operations")
+                .context(|e| format!("operations {:?} are invalid: {e}", request.operations))
+        })?;
+        assert_unique_amounts(&request.max_fee).map_err(|e| {
+            e.at("max_fee")
+                .context(|e| format!("max fee {:?} is invalid: {e}", request.max_fee))
+        })?;
+        if let Some(multiplier) = request.suggested_fee_multiplier.filter(|i| *i < 0.0) {
+            Err(AsserterError::from(
                 ServerError::ConstructionPreprocessRequestSuggestedFeeMultiplierIsNeg,
-            ))?
+            )
+            .at("suggested_fee_multiplier")
+            .with_value(&multiplier)
+            .context(|e| format!("suggested fee multiplier {multiplier} is invalid: {e}")))?
         } else {
             Ok(())
         }
@@ -226,11 +257,21 @@ impl Asserter {
         self.request.as_ref().ok_or(AsserterError::NotInitialized)?;
         let request = request.ok_or(ServerError::ConstructionPayloadsRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
-        self.operations(&request.operations, true)
-            .map_err(|e| format!("operations {:?} are invalid: {e}", request.operations))?;
-        request.public_keys.iter().try_for_each(|k| {
-            public_key(k.as_ref()).map_err(|e| format!("public key {k:?} is invalid: {e}",).into())
-        })
+        self.operations(&request.operations, true).map_err(|e| {
+            e.at("operations")
+                .context(|e| format!("operations {:?} are invalid: {e}", request.operations))
+        })?;
+        request
+            .public_keys
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, k)| {
+                public_key(k.as_ref()).map_err(|e| {
+                    e.at(format!("[{i}]"))
+                        .at("public_keys")
+                        .context(|e| format!("public key {k:?} is invalid: {e}"))
+                })
+            })
     }
 
     /// [`construction_combine_request`] ensures that a
@@ -244,16 +285,38 @@ impl Asserter {
         self.valid_supported_network(request.network_identifier.as_ref())?;
         if request.unsigned_transaction.is_empty() {
             Err(ServerError::ConstructionCombineRequestUnsignedTxEmpty)?
-        } else {
-            signatures(
-                &request
-                    .signatures
-                    .iter()
-                    .map(|i| i.as_ref())
-                    .collect::<Vec<_>>(),
-            )
-            .map_err(|e| format!("signatures {:?} are invalid: {e}", request.signatures).into())
         }
+
+        signatures(
+            &request
+                .signatures
+                .iter()
+                .map(|i| i.as_ref())
+                .collect::<Vec<_>>(),
+        )
+        .map_err(|e| {
+            e.at("signatures")
+                .context(|e| format!("signatures {:?} are invalid: {e}", request.signatures))
+        })?;
+
+        if let Some(verifier) = &self.signature_verifier {
+            // safe to unwrap, the signatures were asserted above.
+            for (i, signature) in request
+                .signatures
+                .iter()
+                .map(|s| s.as_ref().unwrap())
+                .enumerate()
+            {
+                verifier.verify_signature(signature).map_err(|reason| {
+                    AsserterError::from(ConstructionError::SignatureVerificationFailed)
+                        .at(format!("[{i}]"))
+                        .at("signatures")
+                        .context(|e| format!("signature {signature:?} is invalid: {reason}: {e}"))
+                })?;
+            }
+        }
+
+        Ok(())
     }
 
     /// [`construction_hash_request`] ensures that a [`ConstructionHashRequest`]
@@ -311,7 +374,11 @@ impl Asserter {
         let request = request.ok_or(ServerError::CallRequestIsNil)?;
         self.valid_supported_network(request.network_identifier.as_ref())?;
         self.valid_call_method(request.method.as_ref())
-            .map_err(|e| format!("method {} is invalid: {e}", request.method).into())
+            .map_err(|e| {
+                e.at("method")
+                    .with_value(&request.method)
+                    .context(|e| format!("method {} is invalid: {e}", request.method))
+            })
     }
 
     /// [`account_coins_request`] ensures that a [`AccountCoinsRequest`]
@@ -327,10 +394,12 @@ impl Asserter {
         self.valid_supported_network(request.network_identifier.as_ref())?;
 
         account_identifier(request.account_identifier.as_ref()).map_err(|e| {
-            format!(
-                "account identifier {:?} is invalid: {e}",
-                request.account_identifier
-            )
+            e.at("account_identifier").context(|e| {
+                format!(
+                    "account identifier {:?} is invalid: {e}",
+                    request.account_identifier
+                )
+            })
         })?;
 
         if request.include_mempool && !asserter.mempool_coins {
@@ -344,10 +413,10 @@ impl Asserter {
                 .map(|i| i.as_ref())
                 .collect::<Vec<_>>(),
         ) {
-            Err(format!(
-                "currency {c:?} is invalid: {}",
-                ServerError::DuplicateCurrency
-            ))?
+            Err(AsserterError::from(ServerError::DuplicateCurrency)
+                .at("currencies")
+                .with_value(c)
+                .context(|e| format!("currency {c:?} is invalid: {e}")))?
         } else {
             Ok(())
         }
@@ -396,44 +465,58 @@ impl Asserter {
 
         if request.transaction_identifier.is_some() {
             transaction_identifier(request.transaction_identifier.as_ref()).map_err(|e| {
-                format!(
-                    "transaction identifier {:?} is invalid: {e}",
-                    request.transaction_identifier
-                )
+                e.at("transaction_identifier").context(|e| {
+                    format!(
+                        "transaction identifier {:?} is invalid: {e}",
+                        request.transaction_identifier
+                    )
+                })
             })?;
         }
 
         if request.account_identifier.is_some() {
             account_identifier(request.account_identifier.as_ref()).map_err(|e| {
-                format!(
-                    "account identifier {:?} is invalid: {e}",
-                    request.account_identifier
-                )
+                e.at("account_identifier").context(|e| {
+                    format!(
+                        "account identifier {:?} is invalid: {e}",
+                        request.account_identifier
+                    )
+                })
             })?;
         }
 
         if request.coin_identifier.is_some() {
             coin_identifier(request.coin_identifier.as_ref()).map_err(|e| {
-                format!(
-                    "coin identifier {:?} is invalid: {e}",
-                    request.coin_identifier
-                )
+                e.at("coin_identifier").context(|e| {
+                    format!(
+                        "coin identifier {:?} is invalid: {e}",
+                        request.coin_identifier
+                    )
+                })
             })?;
         }
 
         if request.currency.is_some() {
-            currency(request.currency.as_ref())
-                .map_err(|e| format!("currency {:?} is invalid: {e}", request.currency))?;
+            currency(request.currency.as_ref()).map_err(|e| {
+                e.at("currency")
+                    .context(|e| format!("currency {:?} is invalid: {e}", request.currency))
+            })?;
         }
 
         if request.status.is_some() {
             self.operation_status(request.status.as_ref(), false)
-                .map_err(|e| format!("operation status {:?} is invalid: {e}", request.status))?;
+                .map_err(|e| {
+                    e.at("status").context(|e| {
+                        format!("operation status {:?} is invalid: {e}", request.status)
+                    })
+                })?;
         }
 
         if let Some(t) = &request.type_ {
-            self.operation_type(t.clone())
-                .map_err(|e| format!("operation type {t:?} is invalid: {e}"))?;
+            self.operation_type(t.clone()).map_err(|e| {
+                e.at("type")
+                    .context(|e| format!("operation type {t:?} is invalid: {e}"))
+            })?;
         }
 
         if matches!(&request.address, Some(a) if a.is_empty()) {
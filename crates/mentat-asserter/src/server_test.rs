@@ -639,6 +639,86 @@ fn test_block_request() {
     TestCase::run_err_match(tests, |t| asserter.block_request(t.as_ref()));
 }
 
+#[test]
+fn test_request_error_location() {
+    let asserter = request_asserter();
+
+    let err = asserter
+        .block_request(Some(&UncheckedBlockRequest {
+            network_identifier: wrong_network_identifier(),
+            block_identifier: Some(valid_partial_block_identifier()),
+        }))
+        .unwrap_err();
+    assert_eq!(err.code(), "server.requested_network_not_supported");
+    assert_eq!(err.json_path().as_deref(), Some("network_identifier"));
+    assert_eq!(
+        err.value(),
+        Some(&serde_json::to_value(wrong_network_identifier()).unwrap())
+    );
+
+    let err = asserter
+        .construction_payload_request(Some(&UncheckedConstructionPayloadsRequest {
+            network_identifier: valid_network_identifier(),
+            operations: vec![Some(UncheckedOperation {
+                operation_identifier: Some(UncheckedOperationIdentifier {
+                    index: 0,
+                    network_index: None,
+                }),
+                type_: "PAYMENT".into(),
+                account: valid_account(),
+                amount: Some(UncheckedAmount {
+                    value: "".into(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })],
+            ..Default::default()
+        }))
+        .unwrap_err();
+    assert_eq!(err.code(), "block.amount_value_missing");
+    assert_eq!(
+        err.json_path().as_deref(),
+        Some("operations[0].amount.value")
+    );
+
+    let mentat_err = err.into_mentat_error();
+    assert_eq!(mentat_err.status_code, 500);
+    assert!(mentat_err.message.contains("Amount.Value is missing"));
+    assert_eq!(
+        mentat_err.details["assertion"],
+        json!({
+            "code": "block.amount_value_missing",
+            "path": "operations[0].amount.value",
+            "value": "",
+        })
+    );
+
+    let err = asserter
+        .construction_preprocess_request(Some(&UncheckedConstructionPreprocessRequest {
+            network_identifier: valid_network_identifier(),
+            operations: valid_ops(),
+            suggested_fee_multiplier: Some(-1.1),
+            ..Default::default()
+        }))
+        .unwrap_err();
+    assert_eq!(
+        err.code(),
+        "server.construction_preprocess_request_suggested_fee_multiplier_is_neg"
+    );
+    assert_eq!(err.json_path().as_deref(), Some("suggested_fee_multiplier"));
+    assert_eq!(err.value(), Some(&json!(-1.1)));
+
+    let err = asserter
+        .construction_derive_request(Some(&UncheckedConstructionDeriveRequest {
+            network_identifier: valid_network_identifier(),
+            public_key: missing_bytes_public_key(),
+            ..Default::default()
+        }))
+        .unwrap_err();
+    assert_eq!(err.code(), "construction.public_key_bytes_empty");
+    assert_eq!(err.json_path().as_deref(), Some("public_key"));
+}
+
 #[test]
 fn test_block_transaction_request() {
     let tests = vec![
@@ -1326,6 +1406,51 @@ fn test_construction_combine_request() {
     TestCase::run_err_match(tests, |t| asserter.construction_combine_request(t.as_ref()));
 }
 
+/// Accepts only signatures whose bytes are `hello`.
+#[derive(Debug)]
+struct HelloVerifier;
+
+impl SignatureVerifier for HelloVerifier {
+    fn verify_signature(&self, signature: &UncheckedSignature) -> Result<(), String> {
+        if signature.bytes == b"hello" {
+            Ok(())
+        } else {
+            Err("signature is not hello".into())
+        }
+    }
+}
+
+#[test]
+fn test_construction_combine_request_signature_verifier() {
+    let mut bad_signatures = valid_signatures();
+    bad_signatures[0].as_mut().unwrap().bytes = "blah".into();
+
+    let tests = vec![
+        TestCase {
+            name: "verified signatures",
+            payload: Some(UncheckedConstructionCombineRequest {
+                network_identifier: valid_network_identifier(),
+                unsigned_transaction: "blah".into(),
+                signatures: valid_signatures(),
+            }),
+            criteria: None,
+        },
+        TestCase {
+            name: "unverified signatures",
+            payload: Some(UncheckedConstructionCombineRequest {
+                network_identifier: valid_network_identifier(),
+                unsigned_transaction: "blah".into(),
+                signatures: bad_signatures,
+            }),
+            criteria: Some(ConstructionError::SignatureVerificationFailed.into()),
+        },
+    ];
+
+    let asserter = request_asserter().with_signature_verifier(HelloVerifier);
+
+    TestCase::run_err_match(tests, |t| asserter.construction_combine_request(t.as_ref()));
+}
+
 #[test]
 fn test_construction_hash_request() {
     let tests = vec![
@@ -0,0 +1,279 @@
+//! Tracks the Rosetta spec version a server implements and the features it
+//! introduced.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// `SpecVersion` is a version of the Rosetta specification, as reported in
+/// `Version.rosetta_version`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct SpecVersion {
+    /// The major version.
+    pub major: u64,
+    /// The minor version.
+    pub minor: u64,
+    /// The patch version.
+    pub patch: u64,
+}
+
+impl SpecVersion {
+    /// The latest spec version the asserter knows about.
+    pub const LATEST: Self = Self::new(1, 4, 12);
+
+    /// Creates a spec version.
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl FromStr for SpecVersion {
+    type Err = AsserterError;
+
+    /// Parses `major.minor[.patch]`, with an optional `v` prefix and ignoring
+    /// any pre-release or build suffix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            AsserterError::from(SpecError::VersionInvalid)
+                .with_value(s)
+                .context(|e| format!("rosetta version {s} is invalid: {e}"))
+        };
+        let core = s
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default();
+        let parts = core
+            .split('.')
+            .map(u64::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+
+        match parts[..] {
+            [major, minor] => Ok(Self::new(major, minor, 0)),
+            [major, minor, patch] => Ok(Self::new(major, minor, patch)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for SpecVersion {
+    type Error = AsserterError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<SpecVersion> for String {
+    fn from(version: SpecVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl fmt::Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// `SpecFeature` is a part of the Rosetta specification that was added after
+/// its first release. The asserter only accepts a feature from servers
+/// implementing the spec version that introduced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecFeature {
+    /// `NetworkIdentifier.sub_network_identifier`.
+    SubNetworkIdentifiers,
+    /// `Operation.coin_change` and the coins of an account.
+    Coins,
+    /// The `/events/blocks` endpoint.
+    Events,
+    /// The `/search/transactions` endpoint.
+    Search,
+    /// `Allow.timestamp_start_index`. Servers implementing it must return
+    /// valid timestamps from the genesis block on unless they set an index.
+    TimestampStartIndex,
+    /// `Transaction.related_transactions`.
+    RelatedTransactions,
+}
+
+impl SpecFeature {
+    /// Every feature, in the order they were introduced.
+    pub const ALL: [Self; 6] = [
+        Self::SubNetworkIdentifiers,
+        Self::Coins,
+        Self::Events,
+        Self::Search,
+        Self::TimestampStartIndex,
+        Self::RelatedTransactions,
+    ];
+
+    /// `introduced_in` returns the spec version that introduced the feature.
+    pub fn introduced_in(self) -> SpecVersion {
+        match self {
+            Self::SubNetworkIdentifiers => SpecVersion::new(1, 3, 0),
+            Self::Coins => SpecVersion::new(1, 4, 0),
+            Self::Events => SpecVersion::new(1, 4, 1),
+            Self::Search => SpecVersion::new(1, 4, 4),
+            Self::TimestampStartIndex => SpecVersion::new(1, 4, 6),
+            Self::RelatedTransactions => SpecVersion::new(1, 4, 10),
+        }
+    }
+}
+
+impl fmt::Display for SpecFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::SubNetworkIdentifiers => "sub network identifiers",
+            Self::Coins => "coins",
+            Self::Events => "events",
+            Self::Search => "search",
+            Self::TimestampStartIndex => "timestamp start index",
+            Self::RelatedTransactions => "related transactions",
+        };
+        f.write_str(name)
+    }
+}
+
+/// `FeatureSupport` says whether a server supports a [`SpecFeature`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FeatureSupport {
+    /// The feature.
+    pub feature: SpecFeature,
+    /// The spec version that introduced the feature.
+    pub introduced_in: SpecVersion,
+    /// Whether the server implements the feature.
+    pub supported: bool,
+}
+
+/// `CompatibilityReport` lists the [`SpecFeature`]s a server supports
+/// according to the spec version it implements.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompatibilityReport {
+    /// The spec version the server implements, if known. Servers of an
+    /// unknown version are assumed to implement every feature.
+    pub rosetta_version: Option<SpecVersion>,
+    /// The support of every feature, in the order they were introduced.
+    pub features: Vec<FeatureSupport>,
+}
+
+impl CompatibilityReport {
+    /// `new` creates the report of a server implementing `rosetta_version`.
+    pub fn new(rosetta_version: Option<SpecVersion>) -> Self {
+        let features = SpecFeature::ALL
+            .into_iter()
+            .map(|feature| FeatureSupport {
+                feature,
+                introduced_in: feature.introduced_in(),
+                supported: rosetta_version.map_or(true, |v| v >= feature.introduced_in()),
+            })
+            .collect();
+
+        Self {
+            rosetta_version,
+            features,
+        }
+    }
+
+    /// `from_options` creates the report of a server from its
+    /// `/network/options` response.
+    pub fn from_options(options: &UncheckedNetworkOptionsResponse) -> AssertResult<Self> {
+        Ok(Self::new(rosetta_version(options)?))
+    }
+
+    /// `supported` returns the features the server supports.
+    pub fn supported(&self) -> impl Iterator<Item = SpecFeature> + '_ {
+        self.features
+            .iter()
+            .filter(|support| support.supported)
+            .map(|support| support.feature)
+    }
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rosetta_version {
+            Some(version) => writeln!(f, "rosetta version {version}")?,
+            None => writeln!(f, "rosetta version unknown")?,
+        }
+        for support in &self.features {
+            writeln!(
+                f,
+                "{} (since {}): {}",
+                support.feature,
+                support.introduced_in,
+                if support.supported {
+                    "supported"
+                } else {
+                    "not supported"
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `rosetta_version` returns the spec version of a `/network/options`
+/// response, or `None` if the server does not report one.
+pub fn rosetta_version(
+    options: &UncheckedNetworkOptionsResponse,
+) -> AssertResult<Option<SpecVersion>> {
+    match options.version.as_ref().map(|v| v.rosetta_version.as_str()) {
+        None | Some("") => Ok(None),
+        Some(version) => version.parse().map(Some),
+    }
+}
+
+impl Asserter {
+    /// `with_spec_version` makes the asserter reject any [`SpecFeature`] the
+    /// given spec version does not support. Without a spec version every
+    /// feature is accepted.
+    pub fn with_spec_version(mut self, version: SpecVersion) -> Self {
+        self.spec_version = Some(version);
+        self
+    }
+
+    /// `spec_version` returns the spec version the asserter checks against,
+    /// if any.
+    pub fn spec_version(&self) -> Option<SpecVersion> {
+        self.spec_version
+    }
+
+    /// `supports` returns whether the spec version of the asserter includes
+    /// a feature.
+    pub fn supports(&self, feature: SpecFeature) -> bool {
+        self.spec_version
+            .map_or(true, |v| v >= feature.introduced_in())
+    }
+
+    /// `require_feature` returns an error if the spec version of the
+    /// asserter does not include a feature.
+    pub fn require_feature(&self, feature: SpecFeature) -> AssertResult<()> {
+        match self.spec_version {
+            Some(version) if !self.supports(feature) => Err(AsserterError::from(
+                SpecError::FeatureNotSupported,
+            )
+            .context(|e| {
+                format!(
+                    "{feature} were added in rosetta {} but the server implements {version}: {e}",
+                    feature.introduced_in()
+                )
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// `compatibility_report` lists the features the server supports
+    /// according to the spec version of the asserter.
+    pub fn compatibility_report(&self) -> CompatibilityReport {
+        CompatibilityReport::new(self.spec_version)
+    }
+}
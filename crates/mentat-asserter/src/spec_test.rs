@@ -0,0 +1,248 @@
+use super::*;
+
+#[test]
+fn test_spec_version() {
+    let tests = vec![
+        TestCase {
+            name: "full version",
+            payload: "1.4.10",
+            criteria: Some(SpecVersion::new(1, 4, 10)),
+        },
+        TestCase {
+            name: "without patch",
+            payload: "1.3",
+            criteria: Some(SpecVersion::new(1, 3, 0)),
+        },
+        TestCase {
+            name: "prefix and suffix",
+            payload: "v1.4.12-rc.1",
+            criteria: Some(SpecVersion::new(1, 4, 12)),
+        },
+        TestCase {
+            name: "too many parts",
+            payload: "1.4.10.1",
+            criteria: None,
+        },
+        TestCase {
+            name: "not a number",
+            payload: "one.four",
+            criteria: None,
+        },
+        TestCase {
+            name: "empty",
+            payload: "",
+            criteria: None,
+        },
+    ];
+
+    TestCase::run_output_match(tests, |v| v.parse::<SpecVersion>().ok());
+
+    assert!(SpecVersion::new(1, 4, 10) > SpecVersion::new(1, 4, 9));
+    assert_eq!(SpecVersion::LATEST.to_string(), "1.4.12");
+    assert_eq!(
+        serde_json::to_value(SpecVersion::new(1, 4, 6)).unwrap(),
+        json!("1.4.6")
+    );
+    let err = "1.x".parse::<SpecVersion>().unwrap_err();
+    assert_eq!(err.code(), "spec.version_invalid");
+    assert_eq!(err.value(), Some(&json!("1.x")));
+}
+
+fn options(rosetta_version: &str) -> UncheckedNetworkOptionsResponse {
+    UncheckedNetworkOptionsResponse {
+        version: Some(Version {
+            rosetta_version: rosetta_version.into(),
+            node_version: "1.0".into(),
+            middleware_version: None,
+            metadata: Default::default(),
+        }),
+        allow: Some(UncheckedAllow {
+            operation_statuses: vec![Some(OperationStatus {
+                status: "SUCCESS".into(),
+                successful: true,
+            })],
+            operation_types: vec!["PAYMENT".into()],
+            ..Default::default()
+        }),
+    }
+}
+
+fn spec_asserter(
+    network: Option<NetworkIdentifier>,
+    rosetta_version: &str,
+) -> AssertResult<Asserter> {
+    Asserter::new_client_with_responses(
+        network,
+        Some(UncheckedNetworkStatusResponse {
+            current_block_identifier: Some(UncheckedBlockIdentifier {
+                index: 100,
+                hash: "block 100".into(),
+            }),
+            current_block_timestamp: MIN_UNIX_EPOCH + 1,
+            genesis_block_identifier: Some(UncheckedBlockIdentifier {
+                index: 0,
+                hash: "block 0".into(),
+            }),
+            ..Default::default()
+        }),
+        Some(options(rosetta_version)),
+        None,
+    )
+}
+
+fn network(sub_network: bool) -> Option<NetworkIdentifier> {
+    Some(NetworkIdentifier {
+        blockchain: "HELLO".into(),
+        network: "WORLD".into(),
+        sub_network_identifier: sub_network.then(|| SubNetworkIdentifier {
+            network: "SHARD".into(),
+            metadata: Default::default(),
+        }),
+    })
+}
+
+#[test]
+fn test_compatibility_report() {
+    let report = CompatibilityReport::from_options(&options("1.4.4")).unwrap();
+    assert_eq!(report.rosetta_version, Some(SpecVersion::new(1, 4, 4)));
+    assert_eq!(
+        report.supported().collect::<Vec<_>>(),
+        vec![
+            SpecFeature::SubNetworkIdentifiers,
+            SpecFeature::Coins,
+            SpecFeature::Events,
+            SpecFeature::Search,
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        "rosetta version 1.4.4\n\
+         sub network identifiers (since 1.3.0): supported\n\
+         coins (since 1.4.0): supported\n\
+         events (since 1.4.1): supported\n\
+         search (since 1.4.4): supported\n\
+         timestamp start index (since 1.4.6): not supported\n\
+         related transactions (since 1.4.10): not supported\n"
+    );
+    assert_eq!(
+        serde_json::to_value(&report).unwrap()["features"][3],
+        json!({ "feature": "search", "introduced_in": "1.4.4", "supported": true })
+    );
+
+    let report = CompatibilityReport::from_options(&options("")).unwrap();
+    assert_eq!(report.rosetta_version, None);
+    assert_eq!(report.supported().count(), SpecFeature::ALL.len());
+
+    CompatibilityReport::from_options(&options("latest")).unwrap_err();
+
+    let asserter = spec_asserter(network(false), "1.4.0").unwrap();
+    assert_eq!(asserter.spec_version(), None);
+    let asserter = asserter.with_spec_version(SpecVersion::new(1, 4, 0));
+    assert_eq!(
+        asserter.compatibility_report(),
+        CompatibilityReport::new(Some(SpecVersion::new(1, 4, 0)))
+    );
+}
+
+#[test]
+fn test_spec_features() {
+    let tests = vec![
+        TestCase {
+            name: "features of a later version",
+            payload: (network(true), "1.2.5"),
+            criteria: None,
+        },
+        TestCase {
+            name: "unknown version",
+            payload: (network(true), ""),
+            criteria: None,
+        },
+        TestCase {
+            name: "invalid version",
+            payload: (network(false), "one"),
+            criteria: Some(SpecError::VersionInvalid.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |(network, version)| {
+        spec_asserter(network, version).map(|_| ())
+    });
+
+    // the reported version does not reject features on its own.
+    let asserter = spec_asserter(network(false), "1.3.0").unwrap();
+    assert!(asserter.supports(SpecFeature::Coins));
+    asserter.require_feature(SpecFeature::Events).unwrap();
+
+    let asserter = asserter.with_spec_version(SpecVersion::new(1, 3, 0));
+    assert!(!asserter.supports(SpecFeature::Coins));
+    let err = asserter.require_feature(SpecFeature::Events).unwrap_err();
+    assert_eq!(err.code(), "spec.feature_not_supported");
+    assert!(err
+        .to_string()
+        .starts_with("events were added in rosetta 1.4.1 but the server implements 1.3.0"));
+    Asserter::default()
+        .require_feature(SpecFeature::Events)
+        .unwrap();
+
+    let operation = UncheckedOperation {
+        operation_identifier: Some(UncheckedOperationIdentifier {
+            index: 0,
+            network_index: None,
+        }),
+        type_: "PAYMENT".into(),
+        status: Some("SUCCESS".into()),
+        account: Some(AccountIdentifier {
+            address: "test".into(),
+            ..Default::default()
+        }),
+        amount: Some(UncheckedAmount {
+            value: "1000".into(),
+            currency: Some(UncheckedCurrency {
+                symbol: "BTC".into(),
+                decimals: 8,
+                metadata: Default::default(),
+            }),
+            metadata: Default::default(),
+        }),
+        coin_change: Some(UncheckedCoinChange {
+            coin_identifier: Some(CoinIdentifier {
+                identifier: "coin".into(),
+            }),
+            coin_action: UncheckedCoinAction::COIN_CREATED.into(),
+        }),
+        ..Default::default()
+    };
+    let err = asserter.operation(Some(&operation), 0, false).unwrap_err();
+    assert_eq!(err.code(), "spec.feature_not_supported");
+    assert_eq!(err.json_path().as_deref(), Some("coin_change"));
+    asserter
+        .with_spec_version(SpecVersion::new(1, 4, 0))
+        .operation(Some(&operation), 0, false)
+        .unwrap();
+
+    // timestamps are valid from the genesis block on since 1.4.6.
+    let start_index = |version| {
+        spec_asserter(network(false), version)
+            .unwrap()
+            .response
+            .unwrap()
+            .timestamp_start_index
+    };
+    assert_eq!(start_index("1.4.5"), 1);
+    assert_eq!(start_index("1.4.6"), 0);
+    assert_eq!(start_index(""), 1);
+
+    let search = UncheckedSearchTransactionsResponse::default();
+    let asserter = spec_asserter(network(false), "1.4.0").unwrap();
+    asserter.search_transaction_response(Some(&search)).unwrap();
+    let err = asserter
+        .clone()
+        .with_spec_version(SpecVersion::new(1, 4, 3))
+        .search_transaction_response(Some(&search))
+        .unwrap_err();
+    assert_eq!(err.code(), "spec.feature_not_supported");
+    asserter
+        .with_spec_version(SpecVersion::new(1, 4, 4))
+        .search_transaction_response(Some(&search))
+        .unwrap();
+}
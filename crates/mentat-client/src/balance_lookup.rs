@@ -0,0 +1,72 @@
+//! A [`BalanceLookup`] that fetches live balances from a Rosetta node, for
+//! evaluating balance exemptions.
+
+use async_trait::async_trait;
+use mentat_asserter::account_balance_response;
+use mentat_parser::{BalanceLookup, ParserResult};
+use mentat_types::*;
+
+use crate::Client;
+
+/// A [`BalanceLookup`] that calls `/account/balance` at the requested block.
+#[derive(Clone)]
+pub struct ClientBalanceLookup {
+    /// The client connected to the node.
+    client: Client,
+    /// The network the balances are looked up on.
+    network_identifier: NetworkIdentifier,
+}
+
+impl ClientBalanceLookup {
+    /// Creates a lookup that fetches the balances on `network_identifier`
+    /// from the node `client` is connected to.
+    pub fn new(client: Client, network_identifier: NetworkIdentifier) -> Self {
+        Self {
+            client,
+            network_identifier,
+        }
+    }
+}
+
+#[async_trait]
+impl BalanceLookup for ClientBalanceLookup {
+    async fn balance(
+        &self,
+        account: &AccountIdentifier,
+        currency: &Currency,
+        block: &BlockIdentifier,
+    ) -> ParserResult<String> {
+        let partial_block = UncheckedPartialBlockIdentifier {
+            index: Some(block.index as isize),
+            hash: Some(block.hash.clone()),
+        };
+        let resp = self
+            .client
+            .account_balance(UncheckedAccountBalanceRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                account_identifier: Some(account.clone()),
+                block_identifier: Some(partial_block.clone()),
+                currencies: vec![Some(currency.clone().into())],
+            })
+            .await
+            .map_err(|e| format!("failed to fetch balance: {e}"))?;
+
+        account_balance_response(Some(&partial_block), &resp)
+            .map_err(|e| format!("node returned an invalid balance: {e}"))?;
+
+        let currency = UncheckedCurrency::from(currency.clone());
+        resp.balances
+            .into_iter()
+            .flatten()
+            .find(|amount| amount.currency.as_ref() == Some(&currency))
+            .map(|amount| amount.value)
+            .ok_or_else(|| {
+                format!(
+                    "node returned no balance of {} for {}",
+                    currency.symbol,
+                    account_string(account)
+                )
+                .into()
+            })
+    }
+}
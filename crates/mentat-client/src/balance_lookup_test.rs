@@ -0,0 +1,90 @@
+use mentat_parser::BalanceLookup;
+use serde_json::json;
+
+use super::*;
+
+fn lookup(response: serde_json::Value) -> ClientBalanceLookup {
+    let exchange = RecordedExchange {
+        path: "account/balance".into(),
+        request: json!({
+            "network_identifier": { "blockchain": "COSMOS", "network": "MAINNET" },
+            "account_identifier": { "address": "validator" },
+            "block_identifier": { "index": 10, "hash": "block 10" },
+            "currencies": [{ "symbol": "ATOM", "decimals": 6 }],
+        }),
+        status: 200,
+        response,
+        latency_ms: 0,
+    };
+
+    ClientBalanceLookup::new(
+        Client::new("http://localhost:1/")
+            .unwrap()
+            .with_interceptor(ReplayInterceptor::new(vec![exchange])),
+        ("cosmos", "mainnet").into(),
+    )
+}
+
+fn account() -> AccountIdentifier {
+    AccountIdentifier {
+        address: "validator".into(),
+        ..Default::default()
+    }
+}
+
+fn atom() -> Currency {
+    Currency {
+        symbol: "ATOM".into(),
+        decimals: 6,
+        ..Default::default()
+    }
+}
+
+fn block() -> BlockIdentifier {
+    BlockIdentifier {
+        index: 10,
+        hash: "block 10".into(),
+    }
+}
+
+#[tokio::test]
+async fn test_client_balance_lookup() {
+    let lookup = lookup(json!({
+        "block_identifier": { "index": 10, "hash": "block 10" },
+        "balances": [
+            { "value": "7", "currency": { "symbol": "OSMO", "decimals": 6 } },
+            { "value": "1200", "currency": { "symbol": "ATOM", "decimals": 6 } },
+        ],
+    }));
+
+    let balance = lookup.balance(&account(), &atom(), &block()).await.unwrap();
+    assert_eq!(balance, "1200");
+}
+
+#[tokio::test]
+async fn test_client_balance_lookup_missing_currency() {
+    let lookup = lookup(json!({
+        "block_identifier": { "index": 10, "hash": "block 10" },
+        "balances": [{ "value": "7", "currency": { "symbol": "OSMO", "decimals": 6 } }],
+    }));
+
+    let err = lookup
+        .balance(&account(), &atom(), &block())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("no balance of ATOM"));
+}
+
+#[tokio::test]
+async fn test_client_balance_lookup_wrong_block() {
+    let lookup = lookup(json!({
+        "block_identifier": { "index": 11, "hash": "block 11" },
+        "balances": [{ "value": "1200", "currency": { "symbol": "ATOM", "decimals": 6 } }],
+    }));
+
+    let err = lookup
+        .balance(&account(), &atom(), &block())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid balance"));
+}
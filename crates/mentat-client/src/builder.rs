@@ -0,0 +1,440 @@
+//! A [`TransactionBuilder`] that runs the whole Rosetta construction flow on
+//! top of a [`Client`] and a set of [`Signer`]s or [`RemoteSigner`]s.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use indexmap::IndexMap;
+use mentat_asserter::{
+    construction_combine_response,
+    construction_metadata_response,
+    construction_payloads_response,
+    construction_preprocess_response,
+    transaction_identifier_response,
+    AssertResult,
+    Asserter,
+};
+use mentat_keys::{Signer, SignerInterface};
+use mentat_parser::{expected_signers, Parser};
+use mentat_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Client, ClientError, RemoteSigner, Result, SignRequest};
+
+/// The output of the online half of the construction flow. It contains
+/// everything the offline half needs and can be serialized to carry it to an
+/// air-gapped machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PreparedTransaction {
+    /// The intended operations of the transaction.
+    pub operations: Vec<Operation>,
+    /// The metadata returned by /construction/metadata.
+    pub metadata: IndexMap<String, Value>,
+    /// The public keys the implementation required during preprocessing.
+    pub public_keys: Vec<PublicKey>,
+    /// The fee suggested by /construction/metadata, if any.
+    pub suggested_fee: Vec<Amount>,
+}
+
+/// A signed transaction whose parsed intent has been verified against the
+/// operations it was built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SignedTransaction {
+    /// The network-specific signed transaction blob.
+    pub signed_transaction: String,
+    /// The hash of the signed transaction.
+    pub transaction_identifier: TransactionIdentifier,
+    /// The operations parsed from the signed transaction.
+    pub operations: Vec<Operation>,
+    /// The accounts that signed the transaction.
+    pub signers: Vec<AccountIdentifier>,
+}
+
+/// Builds, signs and verifies transactions through the Rosetta construction
+/// API.
+///
+/// The online calls (/construction/preprocess, /construction/metadata and
+/// /construction/submit) and the offline calls (/construction/payloads,
+/// /construction/combine, /construction/parse and /construction/hash) can be
+/// sent to different servers with [`TransactionBuilder::split`].
+pub struct TransactionBuilder {
+    /// The client used for calls that require network access.
+    online: Client,
+    /// The client used for calls that can be answered offline.
+    offline: Client,
+    /// The network transactions are built for.
+    network_identifier: NetworkIdentifier,
+    /// The signers available to sign payloads, keyed by their account.
+    signers: Vec<(AccountIdentifier, Signer)>,
+    /// The remote signers available to sign payloads, keyed by their
+    /// account. Local signers take precedence.
+    remote_signers: Vec<(AccountIdentifier, Arc<dyn RemoteSigner>)>,
+    /// An optional client asserter used to validate every server response.
+    asserter: Option<Asserter>,
+    /// Metadata sent with /construction/preprocess.
+    preprocess_metadata: IndexMap<String, Value>,
+    /// The maximum fee the caller is willing to pay.
+    max_fee: Vec<Amount>,
+    /// A multiplier applied to the suggested fee.
+    suggested_fee_multiplier: Option<f64>,
+}
+
+impl TransactionBuilder {
+    /// Creates a builder that sends every call to the same server.
+    pub fn new(client: Client, network_identifier: NetworkIdentifier) -> Self {
+        Self::split(client.clone(), client, network_identifier)
+    }
+
+    /// Creates a builder that sends online calls to `online` and offline
+    /// calls to `offline`, i.e. for air-gapped signing.
+    pub fn split(online: Client, offline: Client, network_identifier: NetworkIdentifier) -> Self {
+        Self {
+            online,
+            offline,
+            network_identifier,
+            signers: Vec::new(),
+            remote_signers: Vec::new(),
+            asserter: None,
+            preprocess_metadata: IndexMap::new(),
+            max_fee: Vec::new(),
+            suggested_fee_multiplier: None,
+        }
+    }
+
+    /// Adds the signer for an account.
+    pub fn with_signer(mut self, account: AccountIdentifier, signer: Signer) -> Self {
+        self.signers.push((account, signer));
+        self
+    }
+
+    /// Adds a remote signer for an account, i.e. an [`HttpSigner`] connected
+    /// to a signing service.
+    ///
+    /// [`HttpSigner`]: crate::HttpSigner
+    pub fn with_remote_signer<S: RemoteSigner + 'static>(
+        mut self,
+        account: AccountIdentifier,
+        signer: S,
+    ) -> Self {
+        self.remote_signers.push((account, Arc::new(signer)));
+        self
+    }
+
+    /// Validates every server response with the given client asserter, i.e.
+    /// one built with `Asserter::new_client_with_responses`.
+    pub fn with_asserter(mut self, asserter: Asserter) -> Self {
+        self.asserter = Some(asserter);
+        self
+    }
+
+    /// Sets the metadata sent with /construction/preprocess.
+    pub fn with_preprocess_metadata(mut self, metadata: IndexMap<String, Value>) -> Self {
+        self.preprocess_metadata = metadata;
+        self
+    }
+
+    /// Sets the maximum fee sent with /construction/preprocess.
+    pub fn with_max_fee(mut self, max_fee: Vec<Amount>) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    /// Sets the suggested fee multiplier sent with /construction/preprocess.
+    pub fn with_suggested_fee_multiplier(mut self, multiplier: f64) -> Self {
+        self.suggested_fee_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Runs the full construction flow for `operations` and returns the
+    /// signed transaction and its hash. The transaction is not submitted.
+    pub async fn build(&self, operations: Vec<Operation>) -> Result<SignedTransaction> {
+        let prepared = self.prepare(operations).await?;
+        self.sign(prepared).await
+    }
+
+    /// Runs the online half of the construction flow:
+    /// /construction/preprocess and /construction/metadata.
+    pub async fn prepare(&self, operations: Vec<Operation>) -> Result<PreparedTransaction> {
+        let preprocess = self
+            .online
+            .construction_preprocess(UncheckedConstructionPreprocessRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                operations: operations.iter().cloned().map(|o| Some(o.into())).collect(),
+                metadata: self.preprocess_metadata.clone(),
+                max_fee: self
+                    .max_fee
+                    .iter()
+                    .cloned()
+                    .map(|a| Some(a.into()))
+                    .collect(),
+                suggested_fee_multiplier: self.suggested_fee_multiplier,
+            })
+            .await?;
+        self.check_response("preprocess", |_| {
+            construction_preprocess_response(Some(&preprocess))
+        })
+        .map_err(ClientError::BuilderError)?;
+
+        let mut public_keys = Vec::new();
+        for account in preprocess.required_public_keys.iter().flatten() {
+            let public_key = match self.signer(Some(account)) {
+                Ok(signer) => signer.public_key(),
+                Err(e) => match self.remote_signer(Some(account)) {
+                    Some(signer) => signer.public_key(account).await?,
+                    None => return Err(ClientError::BuilderError(e)),
+                },
+            };
+            public_keys.push(public_key);
+        }
+
+        // the unchecked response is fetched so a missing metadata object can
+        // be caught before it defaults to an empty one.
+        let metadata: UncheckedConstructionMetadataResponse = self
+            .online
+            .post(
+                "construction/metadata",
+                &UncheckedConstructionMetadataRequest {
+                    network_identifier: Some(self.network_identifier.clone()),
+                    options: Some(Value::Object(preprocess.options.into_iter().collect())),
+                    public_keys: public_keys
+                        .iter()
+                        .cloned()
+                        .map(|k| Some(k.into()))
+                        .collect(),
+                },
+            )
+            .await?;
+        self.check_response("metadata", |_| {
+            construction_metadata_response(Some(&metadata))
+        })
+        .map_err(ClientError::BuilderError)?;
+        let metadata = ConstructionMetadataResponse::from(metadata);
+
+        Ok(PreparedTransaction {
+            operations,
+            metadata: metadata.metadata,
+            public_keys,
+            suggested_fee: metadata.suggested_fee,
+        })
+    }
+
+    /// Runs the offline half of the construction flow:
+    /// /construction/payloads, /construction/combine, /construction/parse
+    /// and /construction/hash. Both the unsigned and the signed transaction
+    /// are parsed and checked against the intended operations, and the
+    /// signers of the signed transaction against the signing payloads.
+    pub async fn sign(&self, prepared: PreparedTransaction) -> Result<SignedTransaction> {
+        let payloads = self
+            .offline
+            .construction_payloads(UncheckedConstructionPayloadsRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                operations: prepared
+                    .operations
+                    .iter()
+                    .cloned()
+                    .map(|o| Some(o.into()))
+                    .collect(),
+                metadata: prepared.metadata.clone(),
+                public_keys: prepared
+                    .public_keys
+                    .iter()
+                    .cloned()
+                    .map(|k| Some(k.into()))
+                    .collect(),
+            })
+            .await?;
+        self.check_response("payloads", |_| {
+            construction_payloads_response(Some(&payloads))
+        })
+        .map_err(ClientError::BuilderError)?;
+
+        self.parse(&payloads.unsigned_transaction, false, &prepared.operations)
+            .await?;
+
+        let signing_payloads = payloads
+            .payloads
+            .into_iter()
+            .flatten()
+            .map(SigningPayload::from)
+            .collect::<Vec<_>>();
+        let mut signatures = Vec::new();
+        for payload in &signing_payloads {
+            let signature = match self.signer(payload.account_identifier.as_ref()) {
+                Ok(signer) => {
+                    let sign_err = |e| {
+                        ClientError::BuilderError(anyhow!(
+                            "failed to sign payload for {:?}: {e}",
+                            payload.account_identifier
+                        ))
+                    };
+                    let signature_type = match payload.signature_type {
+                        SignatureType::EmptyString => {
+                            signer.default_signature_type().map_err(sign_err)?
+                        }
+                        signature_type => signature_type,
+                    };
+                    let mut signature = signer
+                        .sign(payload.clone(), signature_type)
+                        .map_err(sign_err)?;
+                    signature.signature_type = signature_type;
+                    signature
+                }
+                Err(e) => match self.remote_signer(payload.account_identifier.as_ref()) {
+                    Some(signer) => {
+                        signer
+                            .sign(SignRequest {
+                                network_identifier: self.network_identifier.clone(),
+                                unsigned_transaction: payloads.unsigned_transaction.clone(),
+                                metadata: prepared.metadata.clone(),
+                                public_keys: prepared.public_keys.clone(),
+                                payload: payload.clone(),
+                                signature_type: payload.signature_type,
+                            })
+                            .await?
+                    }
+                    None => return Err(ClientError::BuilderError(e)),
+                },
+            };
+            signatures.push(Some(signature.into()));
+        }
+
+        let combined = self
+            .offline
+            .construction_combine(UncheckedConstructionCombineRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                unsigned_transaction: payloads.unsigned_transaction,
+                signatures,
+            })
+            .await?;
+        self.check_response("combine", |_| {
+            construction_combine_response(Some(&combined))
+        })
+        .map_err(ClientError::BuilderError)?;
+
+        let (operations, signers) = self
+            .parse(&combined.signed_transaction, true, &prepared.operations)
+            .await?;
+        expected_signers(&signing_payloads, &signers).map_err(|e| {
+            ClientError::BuilderError(anyhow!("signed transaction has unexpected signers: {e}"))
+        })?;
+
+        let hash = self
+            .offline
+            .construction_hash(UncheckedConstructionHashRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                signed_transaction: combined.signed_transaction.clone(),
+            })
+            .await?;
+        self.check_response("hash", |_| transaction_identifier_response(Some(&hash)))
+            .map_err(ClientError::BuilderError)?;
+        let transaction_identifier = hash.transaction_identifier.ok_or_else(|| {
+            ClientError::NotFound(anyhow!(
+                "transaction identifier was omitted from the hash response"
+            ))
+        })?;
+
+        Ok(SignedTransaction {
+            signed_transaction: combined.signed_transaction,
+            transaction_identifier,
+            operations,
+            signers,
+        })
+    }
+
+    /// Submits a signed transaction through /construction/submit.
+    pub async fn submit(&self, signed: &SignedTransaction) -> Result<TransactionIdentifier> {
+        let resp = self
+            .online
+            .construction_submit(UncheckedConstructionSubmitRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                signed_transaction: signed.signed_transaction.clone(),
+            })
+            .await?;
+        self.check_response("submit", |_| transaction_identifier_response(Some(&resp)))
+            .map_err(ClientError::BuilderError)?;
+        resp.transaction_identifier.ok_or_else(|| {
+            ClientError::NotFound(anyhow!(
+                "transaction identifier was omitted from the submit response"
+            ))
+        })
+    }
+
+    /// Parses a transaction through /construction/parse and checks that its
+    /// operations match the intent. Returns the parsed operations and
+    /// signers.
+    async fn parse(
+        &self,
+        transaction: &str,
+        signed: bool,
+        intent: &[Operation],
+    ) -> Result<(Vec<Operation>, Vec<AccountIdentifier>)> {
+        let parsed = self
+            .offline
+            .construction_parse(UncheckedConstructionParseRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                signed,
+                transaction: transaction.to_string(),
+            })
+            .await?;
+        self.check_response("parse", |asserter| {
+            asserter.construction_parse_response(Some(&parsed), signed)
+        })
+        .map_err(ClientError::BuilderError)?;
+
+        let operations = parsed
+            .operations
+            .into_iter()
+            .flatten()
+            .map(Operation::from)
+            .collect::<Vec<_>>();
+        // The transaction has not been included in a block yet so there is
+        // no status to confirm, and implementations may add fee operations.
+        Parser::new(None, None, Vec::new())
+            .expected_operations(intent, &operations, false, false)
+            .map_err(|e| {
+                ClientError::BuilderError(anyhow!("parsed operations do not match the intent: {e}"))
+            })?;
+
+        let signers = parsed
+            .account_identifier_signers
+            .into_iter()
+            .flatten()
+            .collect();
+        Ok((operations, signers))
+    }
+
+    /// Validates the response of a construction step with the asserter of
+    /// the builder, if it has one.
+    fn check_response(
+        &self,
+        step: &str,
+        assert: impl FnOnce(&Asserter) -> AssertResult<()>,
+    ) -> anyhow::Result<()> {
+        match &self.asserter {
+            Some(asserter) => {
+                assert(asserter).map_err(|e| anyhow!("{step} response is invalid: {e}"))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Finds the remote signer registered for `account`.
+    fn remote_signer(&self, account: Option<&AccountIdentifier>) -> Option<&dyn RemoteSigner> {
+        let account_hash = hash(account);
+        self.remote_signers
+            .iter()
+            .find(|(a, _)| hash(Some(a)) == account_hash)
+            .map(|(_, signer)| signer.as_ref())
+    }
+
+    /// Finds the signer registered for `account`.
+    fn signer(&self, account: Option<&AccountIdentifier>) -> anyhow::Result<&Signer> {
+        let account_hash = hash(account);
+        self.signers
+            .iter()
+            .find(|(a, _)| hash(Some(a)) == account_hash)
+            .map(|(_, signer)| signer)
+            .ok_or_else(|| anyhow!("no signer for account {account:?}"))
+    }
+}
@@ -0,0 +1,363 @@
+use mentat_asserter::Asserter;
+use mentat_keys::{types::KeyPair, Signer, SignerInterface};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::*;
+
+const PRIVATE_KEY: &str = "aeb121b4c545f0f850e1480492508c65a250e9965b0d90176fab4d7506398ebb";
+
+fn exchange<Q: Serialize>(path: &str, request: &Q, response: Value) -> RecordedExchange {
+    RecordedExchange {
+        path: path.into(),
+        request: serde_json::to_value(request).unwrap(),
+        status: 200,
+        response,
+        latency_ms: 0,
+    }
+}
+
+fn offline_client(exchanges: Vec<RecordedExchange>) -> Client {
+    Client::new("http://localhost:1/")
+        .unwrap()
+        .with_interceptor(ReplayInterceptor::new(exchanges))
+}
+
+fn network() -> NetworkIdentifier {
+    ("bitcoin", "mainnet").into()
+}
+
+fn signer() -> Signer {
+    KeyPair::import_private_key(PRIVATE_KEY.into(), CurveType::Edwards25519)
+        .unwrap()
+        .signer()
+        .unwrap()
+}
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+fn operation_json(index: usize, address: &str, value: &str) -> Value {
+    json!({
+        "operation_identifier": {"index": index},
+        "type": "transfer",
+        "account": {"address": address},
+        "amount": {"value": value, "currency": {"symbol": "BTC", "decimals": 8}},
+    })
+}
+
+fn operations_json() -> Value {
+    json!([
+        operation_json(0, "alice", "-10"),
+        operation_json(1, "bob", "10")
+    ])
+}
+
+fn operations() -> Vec<Operation> {
+    serde_json::from_value::<Vec<UncheckedOperation>>(operations_json())
+        .unwrap()
+        .into_iter()
+        .map(Operation::from)
+        .collect()
+}
+
+fn unchecked_operations() -> Vec<Option<UncheckedOperation>> {
+    operations().into_iter().map(|o| Some(o.into())).collect()
+}
+
+fn signing_payload() -> UncheckedSigningPayload {
+    UncheckedSigningPayload {
+        account_identifier: Some(account("alice")),
+        bytes: vec![1; 32],
+        ..Default::default()
+    }
+}
+
+/// The exchanges answered by the online server.
+fn online_exchanges() -> Vec<RecordedExchange> {
+    let public_key: UncheckedPublicKey = signer().public_key().into();
+    vec![
+        exchange(
+            "construction/preprocess",
+            &UncheckedConstructionPreprocessRequest {
+                network_identifier: Some(network()),
+                operations: unchecked_operations(),
+                ..Default::default()
+            },
+            json!({"options": {"fee": "low"}, "required_public_keys": [{"address": "alice"}]}),
+        ),
+        exchange(
+            "construction/metadata",
+            &UncheckedConstructionMetadataRequest {
+                network_identifier: Some(network()),
+                options: Some(json!({"fee": "low"})),
+                public_keys: vec![Some(public_key)],
+            },
+            json!({"metadata": {"nonce": 7}}),
+        ),
+    ]
+}
+
+/// The exchanges answered by the offline server. `parsed_signers` are the
+/// signers reported when parsing the signed transaction.
+fn offline_exchanges(signed_operations: Value, parsed_signers: Value) -> Vec<RecordedExchange> {
+    let signer = signer();
+    let mut signature = signer
+        .sign(signing_payload().into(), SignatureType::Ed25519)
+        .unwrap();
+    signature.signature_type = SignatureType::Ed25519;
+
+    vec![
+        exchange(
+            "construction/payloads",
+            &UncheckedConstructionPayloadsRequest {
+                network_identifier: Some(network()),
+                operations: unchecked_operations(),
+                metadata: [("nonce".to_string(), json!(7))].into_iter().collect(),
+                public_keys: vec![Some(signer.public_key().into())],
+            },
+            json!({"unsigned_transaction": "unsigned", "payloads": [signing_payload()]}),
+        ),
+        exchange(
+            "construction/parse",
+            &UncheckedConstructionParseRequest {
+                network_identifier: Some(network()),
+                signed: false,
+                transaction: "unsigned".into(),
+            },
+            json!({"operations": operations_json()}),
+        ),
+        exchange(
+            "construction/combine",
+            &UncheckedConstructionCombineRequest {
+                network_identifier: Some(network()),
+                unsigned_transaction: "unsigned".into(),
+                signatures: vec![Some(signature.into())],
+            },
+            json!({"signed_transaction": "signed"}),
+        ),
+        exchange(
+            "construction/parse",
+            &UncheckedConstructionParseRequest {
+                network_identifier: Some(network()),
+                signed: true,
+                transaction: "signed".into(),
+            },
+            json!({"operations": signed_operations, "account_identifier_signers": parsed_signers}),
+        ),
+        exchange(
+            "construction/hash",
+            &UncheckedConstructionHashRequest {
+                network_identifier: Some(network()),
+                signed_transaction: "signed".into(),
+            },
+            json!({"transaction_identifier": {"hash": "tx hash"}}),
+        ),
+    ]
+}
+
+fn builder(offline: Vec<RecordedExchange>) -> TransactionBuilder {
+    TransactionBuilder::split(
+        offline_client(online_exchanges()),
+        offline_client(offline),
+        network(),
+    )
+    .with_signer(account("alice"), signer())
+}
+
+#[tokio::test]
+async fn test_build() {
+    let builder = builder(offline_exchanges(
+        operations_json(),
+        json!([{"address": "alice"}]),
+    ));
+
+    let signed = builder.build(operations()).await.unwrap();
+
+    assert_eq!(signed.signed_transaction, "signed");
+    assert_eq!(signed.transaction_identifier.hash, "tx hash");
+    assert_eq!(signed.operations, operations());
+    assert_eq!(signed.signers, vec![account("alice")]);
+}
+
+#[tokio::test]
+async fn test_prepare_then_sign() {
+    let builder = builder(offline_exchanges(
+        operations_json(),
+        json!([{"address": "alice"}]),
+    ));
+
+    let prepared = builder.prepare(operations()).await.unwrap();
+    // The prepared transaction can be carried to another machine.
+    let prepared: PreparedTransaction =
+        serde_json::from_str(&serde_json::to_string(&prepared).unwrap()).unwrap();
+    let signed = builder.sign(prepared).await.unwrap();
+
+    assert_eq!(signed.transaction_identifier.hash, "tx hash");
+}
+
+#[tokio::test]
+async fn test_build_intent_mismatch() {
+    let builder = builder(offline_exchanges(
+        json!([
+            operation_json(0, "alice", "-10"),
+            operation_json(1, "mallory", "10")
+        ]),
+        json!([{"address": "alice"}]),
+    ));
+
+    match builder.build(operations()).await {
+        Err(ClientError::BuilderError(e)) => {
+            assert!(e.to_string().contains("do not match the intent"))
+        }
+        other => panic!("expected a builder error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_build_unexpected_signer() {
+    let builder = builder(offline_exchanges(
+        operations_json(),
+        json!([{"address": "alice"}, {"address": "mallory"}]),
+    ));
+
+    match builder.build(operations()).await {
+        Err(ClientError::BuilderError(e)) => {
+            assert!(e.to_string().contains("unexpected signers"))
+        }
+        other => panic!("expected a builder error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_build_missing_signer() {
+    let builder = TransactionBuilder::split(
+        offline_client(online_exchanges()),
+        offline_client(Vec::new()),
+        network(),
+    );
+
+    match builder.build(operations()).await {
+        Err(ClientError::BuilderError(e)) => assert!(e.to_string().contains("no signer")),
+        other => panic!("expected a builder error but got {:?}", other.map(|_| ())),
+    }
+}
+
+fn asserter() -> Asserter {
+    Asserter::new_client_with_options(
+        Some(network()),
+        Some(UncheckedBlockIdentifier {
+            index: 0,
+            hash: "block 0".into(),
+        }),
+        vec!["transfer".into()],
+        vec![Some(OperationStatus {
+            status: "SUCCESS".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_build_with_asserter() {
+    let asserted = builder(offline_exchanges(
+        operations_json(),
+        json!([{"address": "alice"}]),
+    ))
+    .with_asserter(asserter());
+    let signed = asserted.build(operations()).await.unwrap();
+    assert_eq!(signed.transaction_identifier.hash, "tx hash");
+
+    // every step is asserted, including /construction/metadata.
+    let mut online = online_exchanges();
+    online[1].response =
+        json!({"suggested_fee": [{"value": "1", "currency": {"symbol": "BTC", "decimals": 8}}]});
+    let online_only = TransactionBuilder::split(
+        offline_client(online),
+        offline_client(Vec::new()),
+        network(),
+    )
+    .with_signer(account("alice"), signer());
+    match online_only
+        .with_asserter(asserter())
+        .prepare(operations())
+        .await
+    {
+        Err(ClientError::BuilderError(e)) => {
+            assert!(e.to_string().contains("metadata response is invalid"))
+        }
+        other => panic!("expected a builder error but got {:?}", other.map(|_| ())),
+    }
+
+    // the parse response is checked against the asserter's operation types.
+    let asserted = builder(offline_exchanges(
+        json!([{"operation_identifier": {"index": 0}, "type": "burn"}]),
+        json!([{"address": "alice"}]),
+    ))
+    .with_asserter(asserter());
+    match asserted.build(operations()).await {
+        Err(ClientError::BuilderError(e)) => {
+            assert!(e.to_string().contains("parse response is invalid"))
+        }
+        other => panic!("expected a builder error but got {:?}", other.map(|_| ())),
+    }
+}
+
+/// A [`RemoteSigner`] backed by an in-process signer that records the
+/// transactions it was asked to sign.
+struct TestRemoteSigner {
+    /// The signer that produces the signatures.
+    signer: Signer,
+    /// The unsigned transactions of every sign request.
+    transactions: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl RemoteSigner for std::sync::Arc<TestRemoteSigner> {
+    async fn public_key(&self, _account: &AccountIdentifier) -> Result<PublicKey> {
+        Ok(self.signer.public_key())
+    }
+
+    async fn sign(&self, request: SignRequest) -> Result<Signature> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .push(request.unsigned_transaction);
+        let mut signature = self
+            .signer
+            .sign(request.payload, SignatureType::Ed25519)
+            .unwrap();
+        signature.signature_type = SignatureType::Ed25519;
+        Ok(signature)
+    }
+}
+
+#[tokio::test]
+async fn test_build_with_remote_signer() {
+    let remote = std::sync::Arc::new(TestRemoteSigner {
+        signer: signer(),
+        transactions: Default::default(),
+    });
+    let builder = TransactionBuilder::split(
+        offline_client(online_exchanges()),
+        offline_client(offline_exchanges(
+            operations_json(),
+            json!([{"address": "alice"}]),
+        )),
+        network(),
+    )
+    .with_remote_signer(account("alice"), remote.clone());
+
+    let signed = builder.build(operations()).await.unwrap();
+
+    assert_eq!(signed.transaction_identifier.hash, "tx hash");
+    assert_eq!(*remote.transactions.lock().unwrap(), vec!["unsigned"]);
+}
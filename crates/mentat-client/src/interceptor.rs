@@ -0,0 +1,339 @@
+//! Interceptors that can inspect and modify the requests made by a
+//! [`Client`](crate::Client) and observe the responses it receives.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::Mutex,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use mentat_types::Encoding;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry_http::HeaderInjector;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    Request,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// What the [`Client`](crate::Client) should do with a request once an
+/// [`Interceptor`] has seen it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intercepted {
+    /// Pass the request on to the next interceptor and then the network.
+    Continue,
+    /// Skip the network and answer the request with the given status code
+    /// and body instead.
+    Respond {
+        /// The http status code of the response.
+        status: u16,
+        /// The raw body of the response, in the encoding of the client.
+        body: Vec<u8>,
+    },
+}
+
+/// A completed call to a Rosetta endpoint as seen by an [`Interceptor`].
+#[derive(Debug, Clone, Copy)]
+pub struct Exchange<'a> {
+    /// The endpoint path relative to the origin, i.e. `network/list`.
+    pub path: &'a str,
+    /// The raw body of the request.
+    pub request: &'a [u8],
+    /// The http status code of the response.
+    pub status: u16,
+    /// The raw body of the response.
+    pub response: &'a [u8],
+    /// The encoding of the response body. The request body is in the
+    /// encoding of the client.
+    pub encoding: Option<Encoding>,
+    /// The time between the request being handed to the first interceptor
+    /// and the full response body being read.
+    pub latency: Duration,
+}
+
+/// An `Interceptor` is a hook in the [`Client`](crate::Client) middleware
+/// chain. Interceptors run in the order they were added for every request.
+pub trait Interceptor: Send + Sync {
+    /// Called before a request is sent. The request may be modified, i.e. to
+    /// add headers. Returning [`Intercepted::Respond`] skips any remaining
+    /// interceptors and the network call entirely.
+    fn before_request(&self, _path: &str, _request: &mut Request) -> anyhow::Result<Intercepted> {
+        Ok(Intercepted::Continue)
+    }
+
+    /// Called once a response has been received, before it is deserialized.
+    fn after_response(&self, _exchange: &Exchange<'_>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Adds a fixed set of headers to every request, i.e. for authentication.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderInterceptor {
+    /// The headers added to each request.
+    headers: HeaderMap,
+}
+
+impl HeaderInterceptor {
+    /// Creates an interceptor that adds the given headers to every request.
+    pub fn new(headers: HeaderMap) -> Self {
+        Self { headers }
+    }
+
+    /// Creates an interceptor that sets the `Authorization` header to
+    /// `Bearer <token>`.
+    pub fn bearer_token(token: &str) -> anyhow::Result<Self> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))?;
+        value.set_sensitive(true);
+        Ok(Self::new([(AUTHORIZATION, value)].into_iter().collect()))
+    }
+
+    /// Creates an interceptor that sets the header `name` to an api key.
+    pub fn api_key(name: &str, key: &str) -> anyhow::Result<Self> {
+        let mut value = HeaderValue::from_str(key)?;
+        value.set_sensitive(true);
+        Ok(Self::new(
+            [(HeaderName::from_bytes(name.as_bytes())?, value)]
+                .into_iter()
+                .collect(),
+        ))
+    }
+
+    /// Adds another header to the interceptor.
+    pub fn with_header(mut self, name: &str, value: &str) -> anyhow::Result<Self> {
+        self.headers.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+        Ok(self)
+    }
+}
+
+impl Interceptor for HeaderInterceptor {
+    fn before_request(&self, _path: &str, request: &mut Request) -> anyhow::Result<Intercepted> {
+        request.headers_mut().extend(self.headers.clone());
+        Ok(Intercepted::Continue)
+    }
+}
+
+/// Logs every request with its status and latency through `tracing`.
+/// Request and response bodies are logged at the `trace` level.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingInterceptor;
+
+impl Interceptor for LoggingInterceptor {
+    fn after_response(&self, exchange: &Exchange<'_>) -> anyhow::Result<()> {
+        tracing::debug!(
+            path = exchange.path,
+            status = exchange.status,
+            latency_ms = exchange.latency.as_millis() as u64,
+            "rosetta call completed"
+        );
+        tracing::trace!(
+            path = exchange.path,
+            request = %String::from_utf8_lossy(exchange.request),
+            response = %String::from_utf8_lossy(exchange.response),
+        );
+        Ok(())
+    }
+}
+
+/// Propagates the OpenTelemetry context of the current `tracing` span to the
+/// server through the request headers.
+#[derive(Debug, Default)]
+pub struct TracingInterceptor {
+    /// The propagator used to encode the context. If `None` the globally
+    /// registered propagator is used.
+    propagator: Option<Box<dyn TextMapPropagator + Send + Sync>>,
+}
+
+impl TracingInterceptor {
+    /// Creates an interceptor using the globally registered propagator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an interceptor that propagates the context using the Jaeger
+    /// `uber-trace-id` header, which is what a mentat server expects.
+    pub fn jaeger() -> Self {
+        Self::with_propagator(opentelemetry_jaeger::Propagator::new())
+    }
+
+    /// Creates an interceptor using a custom propagator.
+    pub fn with_propagator<P: TextMapPropagator + Send + Sync + 'static>(propagator: P) -> Self {
+        Self {
+            propagator: Some(Box::new(propagator)),
+        }
+    }
+}
+
+impl Interceptor for TracingInterceptor {
+    fn before_request(&self, _path: &str, request: &mut Request) -> anyhow::Result<Intercepted> {
+        let cx = tracing::Span::current().context();
+        let mut injector = HeaderInjector(request.headers_mut());
+        match &self.propagator {
+            Some(propagator) => propagator.inject_context(&cx, &mut injector),
+            None => opentelemetry::global::get_text_map_propagator(|propagator| {
+                propagator.inject_context(&cx, &mut injector)
+            }),
+        }
+        Ok(Intercepted::Continue)
+    }
+}
+
+/// A single request and response pair stored by a [`RecordingInterceptor`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RecordedExchange {
+    /// The endpoint path relative to the origin.
+    pub path: String,
+    /// The json body of the request.
+    pub request: Value,
+    /// The http status code of the response.
+    pub status: u16,
+    /// The json body of the response.
+    pub response: Value,
+    /// How long the call took in milliseconds.
+    #[serde(default)]
+    pub latency_ms: u64,
+}
+
+impl RecordedExchange {
+    /// Loads all exchanges from a recording file written by a
+    /// [`RecordingInterceptor`].
+    pub fn load(path: &Path) -> anyhow::Result<Vec<Self>> {
+        let file =
+            File::open(path).map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+        BufReader::new(file)
+            .lines()
+            .filter(|line| !matches!(line, Ok(l) if l.trim().is_empty()))
+            .map(|line| {
+                serde_json::from_str(&line?).map_err(|e| {
+                    anyhow!(
+                        "failed to deserialize contents of file {}: {e}",
+                        path.display()
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parses a raw body into json, falling back to `null` for an empty body.
+fn body_to_json(body: &[u8]) -> anyhow::Result<Value> {
+    if body.is_empty() {
+        Ok(Value::Null)
+    } else {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+/// Appends every exchange to a file as one json object per line so it can
+/// later be replayed with a [`ReplayInterceptor`].
+#[derive(Debug)]
+pub struct RecordingInterceptor {
+    /// The file being written to.
+    file: Mutex<File>,
+}
+
+impl RecordingInterceptor {
+    /// Creates a recording at `path`, truncating any existing file.
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| anyhow!("failed to create file {}: {e}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Opens a recording at `path`, appending to any existing exchanges.
+    pub fn append(path: &Path) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow!("failed to open file {}: {e}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl Interceptor for RecordingInterceptor {
+    fn after_response(&self, exchange: &Exchange<'_>) -> anyhow::Result<()> {
+        if exchange.encoding != Some(Encoding::Json) {
+            return Err(anyhow!("only json exchanges can be recorded"));
+        }
+        let recorded = RecordedExchange {
+            path: exchange.path.to_string(),
+            request: body_to_json(exchange.request)?,
+            status: exchange.status,
+            response: body_to_json(exchange.response)?,
+            latency_ms: exchange.latency.as_millis() as u64,
+        };
+        let mut line = serde_json::to_vec(&recorded)?;
+        line.push(b'\n');
+
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| anyhow!("recording file lock poisoned"))?;
+        file.write_all(&line)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Answers requests from previously recorded exchanges instead of the
+/// network. A request matches an exchange when both the path and the json
+/// request body are equal. Requests without a match are an error.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayInterceptor {
+    /// The exchanges available for replay.
+    exchanges: Vec<RecordedExchange>,
+}
+
+impl ReplayInterceptor {
+    /// Creates an interceptor replaying the given exchanges.
+    pub fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        Self { exchanges }
+    }
+
+    /// Creates an interceptor replaying a recording file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::new(RecordedExchange::load(path)?))
+    }
+}
+
+impl Interceptor for ReplayInterceptor {
+    fn before_request(&self, path: &str, request: &mut Request) -> anyhow::Result<Intercepted> {
+        let content_type = request
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok());
+        if Encoding::from_content_type(content_type) != Some(Encoding::Json) {
+            return Err(anyhow!("only json requests can be replayed"));
+        }
+        let body = body_to_json(
+            request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default(),
+        )?;
+
+        let exchange = self
+            .exchanges
+            .iter()
+            .find(|e| e.path == path && e.request == body)
+            .ok_or_else(|| anyhow!("no recorded exchange for {path} with request {body}"))?;
+
+        Ok(Intercepted::Respond {
+            status: exchange.status,
+            body: serde_json::to_vec(&exchange.response)?,
+        })
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use reqwest::{Method, Request};
+use serde_json::{json, Value};
+
+use super::*;
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mentat-client-{}-{name}", std::process::id()))
+}
+
+fn offline_client(exchanges: Vec<RecordedExchange>) -> Client {
+    Client::new("http://localhost:1/")
+        .unwrap()
+        .with_interceptor(ReplayInterceptor::new(exchanges))
+}
+
+fn network_list_exchange(status: u16, response: Value) -> RecordedExchange {
+    RecordedExchange {
+        path: "network/list".into(),
+        request: json!({}),
+        status,
+        response,
+        latency_ms: 0,
+    }
+}
+
+#[test]
+fn test_header_interceptor() {
+    let interceptor = HeaderInterceptor::bearer_token("secret")
+        .unwrap()
+        .with_header("x-api-key", "key")
+        .unwrap();
+    let mut request = Request::new(Method::POST, "http://localhost/block".parse().unwrap());
+
+    let out = interceptor.before_request("block", &mut request).unwrap();
+
+    assert_eq!(out, Intercepted::Continue);
+    assert_eq!(request.headers()["authorization"], "Bearer secret");
+    assert!(request.headers()["authorization"].is_sensitive());
+    assert_eq!(request.headers()["x-api-key"], "key");
+}
+
+#[tokio::test]
+async fn test_replay_and_record() {
+    let path = temp_file("record.jsonl");
+    let response = json!({
+        "network_identifiers": [{"blockchain": "bitcoin", "network": "mainnet"}]
+    });
+    let client = offline_client(vec![network_list_exchange(200, response.clone())])
+        .with_interceptor(RecordingInterceptor::create(&path).unwrap());
+
+    let resp = client
+        .network_list(UncheckedMetadataRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.network_identifiers,
+        vec![Some(NetworkIdentifier {
+            blockchain: "BITCOIN".into(),
+            network: "MAINNET".into(),
+            sub_network_identifier: None,
+        })]
+    );
+
+    let recorded = RecordedExchange::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].path, "network/list");
+    assert_eq!(recorded[0].request, json!({}));
+    assert_eq!(recorded[0].status, 200);
+    assert_eq!(recorded[0].response, response);
+}
+
+#[tokio::test]
+async fn test_replay_server_error() {
+    let client = offline_client(vec![network_list_exchange(
+        500,
+        json!({"code": 3, "message": "Node error", "retriable": false}),
+    )]);
+
+    match client
+        .network_list(UncheckedMetadataRequest::default())
+        .await
+    {
+        Err(ClientError::ServerError(e)) => {
+            assert_eq!(e.code, 3);
+            assert_eq!(e.message, "Node error");
+        }
+        other => panic!("expected a server error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_replay_unmatched() {
+    let client = offline_client(Vec::new());
+
+    match client
+        .network_status(UncheckedNetworkRequest::default())
+        .await
+    {
+        Err(ClientError::InterceptorError(e)) => {
+            assert!(e
+                .to_string()
+                .contains("no recorded exchange for network/status"))
+        }
+        other => panic!(
+            "expected an interceptor error but got {:?}",
+            other.map(|_| ())
+        ),
+    }
+}
+
+/// Answers `/network/list` in CBOR, checking the request was sent in CBOR.
+struct CborInterceptor;
+
+impl Interceptor for CborInterceptor {
+    fn before_request(&self, _path: &str, request: &mut Request) -> anyhow::Result<Intercepted> {
+        assert_eq!(request.headers()["content-type"], "application/cbor");
+        assert_eq!(request.headers()["accept"], "application/cbor");
+        let body = request.body().and_then(|b| b.as_bytes()).unwrap();
+        Encoding::Cbor
+            .decode::<UncheckedMetadataRequest>(body)
+            .map_err(|e| anyhow!(e))?;
+
+        let response = UncheckedNetworkListResponse {
+            network_identifiers: vec![Some(NetworkIdentifier {
+                blockchain: "bitcoin".into(),
+                network: "mainnet".into(),
+                sub_network_identifier: None,
+            })],
+        };
+        Ok(Intercepted::Respond {
+            status: 200,
+            body: Encoding::Cbor.encode(&response).map_err(|e| anyhow!(e))?,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_client_encoding() {
+    let client = Client::new("http://localhost:1/")
+        .unwrap()
+        .with_encoding(Encoding::Cbor)
+        .with_interceptor(CborInterceptor);
+    let resp = client
+        .network_list(UncheckedMetadataRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(resp.network_identifiers.len(), 1);
+
+    let client = offline_client(Vec::new()).with_encoding(Encoding::Cbor);
+    match client
+        .network_list(UncheckedMetadataRequest::default())
+        .await
+    {
+        Err(ClientError::InterceptorError(e)) => {
+            assert!(e.to_string().contains("only json requests can be replayed"))
+        }
+        other => panic!(
+            "expected an interceptor error but got {:?}",
+            other.map(|_| ())
+        ),
+    }
+}
@@ -1,18 +1,40 @@
 //! This module contains a cli client for making Rosetta calls.
 
 use core::fmt;
+use std::{sync::Arc, time::Instant};
 
 use anyhow::anyhow;
 use mentat_types::*;
-use reqwest::Url;
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Url,
+};
 use serde::{de::DeserializeOwned, Serialize};
 
+mod balance_lookup;
+pub use balance_lookup::*;
+mod builder;
+pub use builder::*;
+mod interceptor;
+pub use interceptor::*;
+mod remote_signer;
+pub use remote_signer::*;
+mod stream;
+pub use stream::*;
+mod transaction_lookup;
+pub use transaction_lookup::*;
+
 /// The client struct to call a rosetta API.
+#[derive(Clone)]
 pub struct Client {
     /// The actual request client to do so.
     inner: reqwest::Client,
     /// The URL of the rosetta API being called.
     origin: Url,
+    /// The interceptors every request is passed through, in order.
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    /// The encoding requests are sent in and responses are asked for.
+    encoding: Encoding,
 }
 
 /// The different types of Errors that can happen when using the CLI.
@@ -24,6 +46,12 @@ pub enum ClientError {
     NetworkError(anyhow::Error),
     /// A rosetta API error.
     ServerError(MentatError),
+    /// An error returned by an [`Interceptor`].
+    InterceptorError(anyhow::Error),
+    /// The server omitted a requested object from its response.
+    NotFound(anyhow::Error),
+    /// A transaction could not be built or did not match its intent.
+    BuilderError(anyhow::Error),
 }
 
 impl fmt::Display for ClientError {
@@ -32,6 +60,9 @@ impl fmt::Display for ClientError {
             ClientError::ServerError(e) => e.fmt(f),
             ClientError::NetworkError(e) => e.fmt(f),
             ClientError::ParseError(e) => e.fmt(f),
+            ClientError::InterceptorError(e) => e.fmt(f),
+            ClientError::NotFound(e) => e.fmt(f),
+            ClientError::BuilderError(e) => e.fmt(f),
         }
     }
 }
@@ -55,7 +86,26 @@ impl Client {
 
     /// `origin` should be of the form `http[s]://hostname:port/`.
     pub fn new_full(origin: Url, inner: reqwest::Client) -> Self {
-        Self { inner, origin }
+        Self {
+            inner,
+            origin,
+            interceptors: Vec::new(),
+            encoding: Encoding::default(),
+        }
+    }
+
+    /// Sends requests and asks for responses in the given [`Encoding`]
+    /// instead of JSON. Servers that do not support it answer in JSON, which
+    /// the client still understands.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Adds an [`Interceptor`] to the end of the client's middleware chain.
+    pub fn with_interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
     }
 
     /// Create a post request AP.
@@ -64,22 +114,91 @@ impl Client {
             Ok(url) => url.to_string(),
             Err(e) => return Err(ClientError::ParseError(anyhow!(e))),
         };
-        let out = self.inner.post(url).json(request).send().await;
-        match out {
-            Err(e) => Err(ClientError::NetworkError(anyhow!(e))),
-            Ok(response) => {
-                if response.status().is_success() {
-                    let out: R = match response.json().await {
-                        Ok(x) => x,
+        let body = self
+            .encoding
+            .encode(request)
+            .map_err(|e| ClientError::ParseError(anyhow!(e)))?;
+        let mime_type = self.encoding.mime_type();
+        let mut req = match self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, mime_type)
+            .header(ACCEPT, mime_type)
+            .body(body)
+            .build()
+        {
+            Ok(req) => req,
+            Err(e) => return Err(ClientError::ParseError(anyhow!(e))),
+        };
+
+        let start = Instant::now();
+        let mut reply = None;
+        for interceptor in &self.interceptors {
+            match interceptor
+                .before_request(path, &mut req)
+                .map_err(ClientError::InterceptorError)?
+            {
+                Intercepted::Continue => {}
+                Intercepted::Respond { status, body } => {
+                    reply = Some((status, body));
+                    break;
+                }
+            }
+        }
+        let request_body = req
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(<[u8]>::to_vec)
+            .unwrap_or_default();
+
+        // intercepted replies are in the encoding of the client.
+        let mut encoding = Some(self.encoding);
+        let (status, body) = match reply {
+            Some(reply) => reply,
+            None => match self.inner.execute(req).await {
+                Err(e) => return Err(ClientError::NetworkError(anyhow!(e))),
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    encoding = Encoding::from_content_type(
+                        response
+                            .headers()
+                            .get(CONTENT_TYPE)
+                            .and_then(|v| v.to_str().ok()),
+                    );
+                    match response.bytes().await {
+                        Ok(body) => (status, body.to_vec()),
                         Err(e) => return Err(ClientError::NetworkError(anyhow!(e))),
-                    };
-                    Ok(out)
-                } else {
-                    match response.json().await {
-                        Ok(e) => Err(ClientError::ServerError(e)),
-                        Err(e) => Err(ClientError::NetworkError(anyhow!(e))),
                     }
                 }
+            },
+        };
+
+        let exchange = Exchange {
+            path,
+            request: &request_body,
+            status,
+            response: &body,
+            encoding,
+            latency: start.elapsed(),
+        };
+        for interceptor in &self.interceptors {
+            interceptor
+                .after_response(&exchange)
+                .map_err(ClientError::InterceptorError)?;
+        }
+
+        let encoding = encoding.ok_or_else(|| {
+            ClientError::NetworkError(anyhow!("response has an unsupported content type"))
+        })?;
+        if (200..300).contains(&status) {
+            match encoding.decode(&body) {
+                Ok(out) => Ok(out),
+                Err(e) => Err(ClientError::NetworkError(anyhow!(e))),
+            }
+        } else {
+            match encoding.decode(&body) {
+                Ok(e) => Err(ClientError::ServerError(e)),
+                Err(e) => Err(ClientError::NetworkError(anyhow!(e))),
             }
         }
     }
@@ -263,3 +382,16 @@ impl Client {
         Ok(resp)
     }
 }
+
+#[cfg(test)]
+#[path = ""]
+mod tests {
+    use super::*;
+
+    mod balance_lookup_test;
+    mod builder_test;
+    mod interceptor_test;
+    mod remote_signer_test;
+    mod stream_test;
+    mod transaction_lookup_test;
+}
@@ -0,0 +1,106 @@
+//! A [`RemoteSigner`] abstraction for signing payloads with keys held outside
+//! of the process, and an [`HttpSigner`] that talks to a signing service over
+//! http.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use mentat_asserter::{public_key, signatures};
+use mentat_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Client, ClientError, Result};
+
+/// A request to sign a single [`SigningPayload`]. The transaction the
+/// payload was created for is sent along, with the metadata and public keys
+/// it was built from, so the signing service can parse it, rebuild its
+/// payloads and apply its policies.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SignRequest {
+    /// The network the transaction is built for.
+    pub network_identifier: NetworkIdentifier,
+    /// The unsigned transaction returned by /construction/payloads.
+    pub unsigned_transaction: String,
+    /// The metadata sent to /construction/payloads.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub metadata: IndexMap<String, Value>,
+    /// The public keys sent to /construction/payloads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub public_keys: Vec<PublicKey>,
+    /// The payload to sign.
+    pub payload: SigningPayload,
+    /// The signature type to produce. `EmptyString` lets the signer pick the
+    /// default type for its curve.
+    pub signature_type: SignatureType,
+}
+
+/// A request for the [`PublicKey`] of an account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PublicKeyRequest {
+    /// The account whose public key is requested.
+    pub account_identifier: AccountIdentifier,
+}
+
+/// A `RemoteSigner` signs payloads with private keys it does not expose,
+/// i.e. keys kept in a separate custody service. A signer may refuse to sign,
+/// in which case the refusal is returned as a [`ClientError::ServerError`].
+///
+/// [`ClientError::ServerError`]: crate::ClientError::ServerError
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    /// Returns the public key of `account`.
+    async fn public_key(&self, account: &AccountIdentifier) -> Result<PublicKey>;
+
+    /// Signs the payload of `request`. The returned signature has its
+    /// signature type set to the type that was actually produced.
+    async fn sign(&self, request: SignRequest) -> Result<Signature>;
+}
+
+/// A [`RemoteSigner`] that forwards every call to a signing service over
+/// http, i.e. the `mentat-signer` binary. The requests go through the
+/// [`Client`] and therefore through its interceptors.
+#[derive(Clone)]
+pub struct HttpSigner {
+    /// The client connected to the signing service.
+    client: Client,
+}
+
+impl HttpSigner {
+    /// Creates a signer that talks to the signing service `client` is
+    /// connected to.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for HttpSigner {
+    async fn public_key(&self, account: &AccountIdentifier) -> Result<PublicKey> {
+        let resp: UncheckedPublicKey = self
+            .client
+            .post(
+                "public_key",
+                &PublicKeyRequest {
+                    account_identifier: account.clone(),
+                },
+            )
+            .await?;
+        public_key(Some(&resp)).map_err(|e| {
+            ClientError::NetworkError(anyhow!(
+                "signing service returned an invalid public key: {e}"
+            ))
+        })?;
+        Ok(resp.into())
+    }
+
+    async fn sign(&self, request: SignRequest) -> Result<Signature> {
+        let resp: UncheckedSignature = self.client.post("sign", &request).await?;
+        signatures(&[Some(&resp)]).map_err(|e| {
+            ClientError::NetworkError(anyhow!(
+                "signing service returned an invalid signature: {e}"
+            ))
+        })?;
+        Ok(resp.into())
+    }
+}
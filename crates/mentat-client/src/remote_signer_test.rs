@@ -0,0 +1,119 @@
+use mentat_keys::{types::KeyPair, SignerInterface};
+use serde_json::json;
+
+use super::*;
+
+const PRIVATE_KEY: &str = "aeb121b4c545f0f850e1480492508c65a250e9965b0d90176fab4d7506398ebb";
+
+fn account() -> AccountIdentifier {
+    AccountIdentifier {
+        address: "alice".into(),
+        ..Default::default()
+    }
+}
+
+fn sign_request() -> SignRequest {
+    SignRequest {
+        network_identifier: ("bitcoin", "mainnet").into(),
+        unsigned_transaction: "unsigned".into(),
+        payload: SigningPayload {
+            account_identifier: Some(account()),
+            bytes: vec![1; 32],
+            signature_type: SignatureType::EmptyString,
+            ..Default::default()
+        },
+        signature_type: SignatureType::Ed25519,
+        ..Default::default()
+    }
+}
+
+fn http_signer(exchanges: Vec<RecordedExchange>) -> HttpSigner {
+    HttpSigner::new(
+        Client::new("http://localhost:1/")
+            .unwrap()
+            .with_interceptor(ReplayInterceptor::new(exchanges)),
+    )
+}
+
+fn exchange(
+    path: &str,
+    request: serde_json::Value,
+    status: u16,
+    response: serde_json::Value,
+) -> RecordedExchange {
+    RecordedExchange {
+        path: path.into(),
+        request,
+        status,
+        response,
+        latency_ms: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_http_signer() {
+    let signer = KeyPair::import_private_key(PRIVATE_KEY.into(), CurveType::Edwards25519)
+        .unwrap()
+        .signer()
+        .unwrap();
+    let mut signature = signer
+        .sign(sign_request().payload, SignatureType::Ed25519)
+        .unwrap();
+    signature.signature_type = SignatureType::Ed25519;
+
+    let remote = http_signer(vec![
+        exchange(
+            "public_key",
+            json!({"account_identifier": {"address": "alice"}}),
+            200,
+            serde_json::to_value(UncheckedPublicKey::from(signer.public_key())).unwrap(),
+        ),
+        exchange(
+            "sign",
+            serde_json::to_value(sign_request()).unwrap(),
+            200,
+            serde_json::to_value(UncheckedSignature::from(signature.clone())).unwrap(),
+        ),
+    ]);
+
+    assert_eq!(
+        remote.public_key(&account()).await.unwrap(),
+        signer.public_key()
+    );
+    assert_eq!(
+        remote.sign(sign_request()).await.unwrap().bytes,
+        signature.bytes
+    );
+}
+
+#[tokio::test]
+async fn test_http_signer_refused() {
+    let remote = http_signer(vec![exchange(
+        "sign",
+        serde_json::to_value(sign_request()).unwrap(),
+        403,
+        json!({"code": 101, "message": "Signing refused by policy", "retriable": false}),
+    )]);
+
+    match remote.sign(sign_request()).await {
+        Err(ClientError::ServerError(e)) => assert_eq!(e.code, 101),
+        other => panic!("expected a server error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_http_signer_invalid_signature() {
+    let remote = http_signer(vec![exchange(
+        "sign",
+        serde_json::to_value(sign_request()).unwrap(),
+        200,
+        json!({"signature_type": "ed25519"}),
+    )]);
+
+    match remote.sign(sign_request()).await {
+        Err(ClientError::NetworkError(e)) => {
+            assert!(e.to_string().contains("invalid signature"))
+        }
+        other => panic!("expected a network error but got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,160 @@
+//! Async [`Stream`] helpers that page through Rosetta endpoints on behalf of
+//! the caller.
+
+use anyhow::anyhow;
+use futures::{future::try_join_all, stream, Stream, StreamExt, TryStreamExt};
+use mentat_types::*;
+
+use crate::{Client, ClientError, Result};
+
+/// The default number of blocks fetched concurrently by [`Client::blocks`].
+pub const DEFAULT_BLOCK_CONCURRENCY: usize = 8;
+
+impl Client {
+    /// Returns a stream over every transaction matching a
+    /// /search/transactions request. The `offset` of the request is used as
+    /// the starting point and `next_offset` is followed until the server
+    /// stops returning one.
+    pub fn search_transactions_stream(
+        &self,
+        request: UncheckedSearchTransactionsRequest,
+    ) -> impl Stream<Item = Result<UncheckedBlockTransaction>> + '_ {
+        stream::try_unfold(Some(request), move |request| async move {
+            let request = match request {
+                Some(request) => request,
+                None => return Ok(None),
+            };
+
+            let resp = self.search_transactions(request.clone()).await?;
+            let next = resp
+                .next_offset
+                .map(|offset| UncheckedSearchTransactionsRequest {
+                    offset: Some(offset),
+                    ..request
+                });
+            Ok(Some((resp.transactions, next)))
+        })
+        .map_ok(|transactions| stream::iter(transactions.into_iter().flatten().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Returns a stream over the block events of a network starting at
+    /// sequence `start`, requesting at most `limit` events per call. The
+    /// stream ends once it has caught up with the `max_sequence` reported by
+    /// the server.
+    pub fn events_stream(
+        &self,
+        network_identifier: NetworkIdentifier,
+        start: isize,
+        limit: Option<isize>,
+    ) -> impl Stream<Item = Result<UncheckedBlockEvent>> + '_ {
+        stream::try_unfold(Some(start), move |offset| {
+            let network_identifier = network_identifier.clone();
+            async move {
+                let offset = match offset {
+                    Some(offset) => offset,
+                    None => return Ok(None),
+                };
+
+                let resp = self
+                    .events_blocks(UncheckedEventsBlocksRequest {
+                        network_identifier: Some(network_identifier),
+                        offset: Some(offset),
+                        limit,
+                    })
+                    .await?;
+                let next = resp
+                    .events
+                    .iter()
+                    .flatten()
+                    .map(|event| event.sequence)
+                    .max()
+                    .filter(|last| *last < resp.max_sequence)
+                    .map(|last| last + 1);
+                Ok(Some((resp.events, next)))
+            }
+        })
+        .map_ok(|events| stream::iter(events.into_iter().flatten().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Returns a stream over the blocks at the given indices, i.e.
+    /// `client.blocks(network, 100..200)`. Blocks are fetched
+    /// [`DEFAULT_BLOCK_CONCURRENCY`] at a time but are yielded in the order
+    /// of `indices`. Any `other_transactions` are fetched through
+    /// /block/transaction and appended to the block's transactions.
+    pub fn blocks<I: IntoIterator<Item = isize>>(
+        &self,
+        network_identifier: NetworkIdentifier,
+        indices: I,
+    ) -> impl Stream<Item = Result<UncheckedBlock>> + '_
+    where
+        I::IntoIter: 'static,
+    {
+        self.blocks_with_concurrency(network_identifier, indices, DEFAULT_BLOCK_CONCURRENCY)
+    }
+
+    /// The same as [`Client::blocks`] but with a custom number of blocks
+    /// fetched concurrently.
+    pub fn blocks_with_concurrency<I: IntoIterator<Item = isize>>(
+        &self,
+        network_identifier: NetworkIdentifier,
+        indices: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<UncheckedBlock>> + '_
+    where
+        I::IntoIter: 'static,
+    {
+        stream::iter(indices)
+            .map(move |index| self.full_block(network_identifier.clone(), index))
+            .buffered(concurrency.max(1))
+    }
+
+    /// Fetches the block at `index` along with all of its
+    /// `other_transactions`.
+    async fn full_block(
+        &self,
+        network_identifier: NetworkIdentifier,
+        index: isize,
+    ) -> Result<UncheckedBlock> {
+        let resp = self
+            .block(UncheckedBlockRequest {
+                network_identifier: Some(network_identifier.clone()),
+                block_identifier: Some(UncheckedPartialBlockIdentifier {
+                    index: Some(index),
+                    hash: None,
+                }),
+            })
+            .await?;
+        let mut block = resp.block.ok_or_else(|| {
+            ClientError::NotFound(anyhow!("block {index} was omitted from the response"))
+        })?;
+
+        let other_transactions = try_join_all(resp.other_transactions.into_iter().flatten().map(
+            |transaction_identifier| {
+                let request = UncheckedBlockTransactionRequest {
+                    network_identifier: Some(network_identifier.clone()),
+                    block_identifier: block.block_identifier.clone(),
+                    transaction_identifier: Some(transaction_identifier.clone()),
+                };
+                async move {
+                    self.block_transaction(request)
+                        .await?
+                        .transaction
+                        .ok_or_else(|| {
+                            ClientError::NotFound(anyhow!(
+                                "transaction {} of block {index} was omitted from the response",
+                                transaction_identifier.hash
+                            ))
+                        })
+                }
+            },
+        ))
+        .await?;
+
+        block
+            .transactions
+            .extend(other_transactions.into_iter().map(Some));
+        Ok(block)
+    }
+}
@@ -0,0 +1,228 @@
+use futures::TryStreamExt;
+use serde::Serialize;
+use serde_json::json;
+
+use super::*;
+
+fn exchange<Q: Serialize>(
+    path: &str,
+    request: &Q,
+    response: serde_json::Value,
+) -> RecordedExchange {
+    RecordedExchange {
+        path: path.into(),
+        request: serde_json::to_value(request).unwrap(),
+        status: 200,
+        response,
+        latency_ms: 0,
+    }
+}
+
+fn offline_client(exchanges: Vec<RecordedExchange>) -> Client {
+    Client::new("http://localhost:1/")
+        .unwrap()
+        .with_interceptor(ReplayInterceptor::new(exchanges))
+}
+
+fn network() -> NetworkIdentifier {
+    ("bitcoin", "mainnet").into()
+}
+
+fn block_json(index: isize, transactions: &[&str]) -> serde_json::Value {
+    json!({
+        "block_identifier": {"index": index, "hash": format!("block {index}")},
+        "parent_block_identifier": {"index": index - 1, "hash": format!("block {}", index - 1)},
+        "timestamp": 1_600_000_000_000_i64 + index as i64,
+        "transactions": transactions
+            .iter()
+            .map(|hash| json!({"transaction_identifier": {"hash": hash}, "operations": []}))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn block_request(index: isize) -> UncheckedBlockRequest {
+    UncheckedBlockRequest {
+        network_identifier: Some(network()),
+        block_identifier: Some(UncheckedPartialBlockIdentifier {
+            index: Some(index),
+            hash: None,
+        }),
+    }
+}
+
+fn transaction_hashes(block: &UncheckedBlock) -> Vec<String> {
+    block
+        .transactions
+        .iter()
+        .flatten()
+        .map(|tx| tx.transaction_identifier.as_ref().unwrap().hash.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_search_transactions_stream() {
+    let request = UncheckedSearchTransactionsRequest {
+        network_identifier: Some(network()),
+        limit: Some(2),
+        ..Default::default()
+    };
+    let page = |offset: Option<isize>, hashes: &[&str], next: Option<isize>| {
+        exchange(
+            "search/transactions",
+            &UncheckedSearchTransactionsRequest {
+                offset,
+                ..request.clone()
+            },
+            json!({
+                "transactions": hashes
+                    .iter()
+                    .map(|hash| json!({
+                        "block_identifier": {"index": 1, "hash": "block 1"},
+                        "transaction": {"transaction_identifier": {"hash": hash}, "operations": []},
+                    }))
+                    .collect::<Vec<_>>(),
+                "total_count": 5,
+                "next_offset": next,
+            }),
+        )
+    };
+    let client = offline_client(vec![
+        page(None, &["tx 0", "tx 1"], Some(2)),
+        page(Some(2), &["tx 2", "tx 3"], Some(4)),
+        page(Some(4), &["tx 4"], None),
+    ]);
+
+    let transactions: Vec<_> = client
+        .search_transactions_stream(request.clone())
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transactions
+            .iter()
+            .map(|tx| tx
+                .transaction
+                .as_ref()
+                .unwrap()
+                .transaction_identifier
+                .as_ref()
+                .unwrap()
+                .hash
+                .as_str())
+            .collect::<Vec<_>>(),
+        vec!["tx 0", "tx 1", "tx 2", "tx 3", "tx 4"]
+    );
+}
+
+#[tokio::test]
+async fn test_events_stream() {
+    let page = |offset: isize, sequences: &[isize]| {
+        exchange(
+            "events/blocks",
+            &UncheckedEventsBlocksRequest {
+                network_identifier: Some(network()),
+                offset: Some(offset),
+                limit: Some(2),
+            },
+            json!({
+                "max_sequence": 4,
+                "events": sequences
+                    .iter()
+                    .map(|sequence| json!({
+                        "sequence": sequence,
+                        "block_identifier": {"index": sequence, "hash": format!("block {sequence}")},
+                        "type": "block_added",
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        )
+    };
+    let client = offline_client(vec![page(1, &[1, 2]), page(3, &[3, 4])]);
+
+    let events: Vec<_> = client
+        .events_stream(network(), 1, Some(2))
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        events.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4]
+    );
+}
+
+#[tokio::test]
+async fn test_blocks() {
+    let client = offline_client(vec![
+        exchange(
+            "block",
+            &block_request(1),
+            json!({"block": block_json(1, &["a"])}),
+        ),
+        exchange(
+            "block",
+            &block_request(2),
+            json!({
+                "block": block_json(2, &["b"]),
+                "other_transactions": [{"hash": "c"}, {"hash": "d"}],
+            }),
+        ),
+        exchange(
+            "block",
+            &block_request(3),
+            json!({"block": block_json(3, &[])}),
+        ),
+        exchange(
+            "block/transaction",
+            &UncheckedBlockTransactionRequest {
+                network_identifier: Some(network()),
+                block_identifier: Some(UncheckedBlockIdentifier {
+                    index: 2,
+                    hash: "block 2".into(),
+                }),
+                transaction_identifier: Some(TransactionIdentifier { hash: "c".into() }),
+            },
+            json!({"transaction": {"transaction_identifier": {"hash": "c"}, "operations": []}}),
+        ),
+        exchange(
+            "block/transaction",
+            &UncheckedBlockTransactionRequest {
+                network_identifier: Some(network()),
+                block_identifier: Some(UncheckedBlockIdentifier {
+                    index: 2,
+                    hash: "block 2".into(),
+                }),
+                transaction_identifier: Some(TransactionIdentifier { hash: "d".into() }),
+            },
+            json!({"transaction": {"transaction_identifier": {"hash": "d"}, "operations": []}}),
+        ),
+    ]);
+
+    let blocks: Vec<_> = client
+        .blocks_with_concurrency(network(), 1..4, 3)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        blocks
+            .iter()
+            .map(|b| b.block_identifier.as_ref().unwrap().index)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(transaction_hashes(&blocks[0]), vec!["a"]);
+    assert_eq!(transaction_hashes(&blocks[1]), vec!["b", "c", "d"]);
+    assert!(transaction_hashes(&blocks[2]).is_empty());
+}
+
+#[tokio::test]
+async fn test_blocks_omitted() {
+    let client = offline_client(vec![exchange("block", &block_request(5), json!({}))]);
+
+    match client.blocks(network(), 5..6).try_collect::<Vec<_>>().await {
+        Err(ClientError::NotFound(e)) => assert!(e.to_string().contains("block 5")),
+        other => panic!("expected a not found error but got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,77 @@
+//! A [`TransactionLookup`] that searches a Rosetta node for related
+//! transactions, for tracing funds across blocks.
+
+use async_trait::async_trait;
+use mentat_asserter::Asserter;
+use mentat_parser::{ParserResult, TransactionLookup};
+use mentat_types::*;
+
+use crate::Client;
+
+/// A [`TransactionLookup`] that calls `/search/transactions` on the node of
+/// a single network.
+#[derive(Clone)]
+pub struct ClientTransactionLookup {
+    /// The client connected to the node.
+    client: Client,
+    /// The network the node serves.
+    network_identifier: NetworkIdentifier,
+    /// An optional asserter used to validate the server responses.
+    asserter: Option<Asserter>,
+}
+
+impl ClientTransactionLookup {
+    /// Creates a lookup that searches the transactions on
+    /// `network_identifier` with the node `client` is connected to.
+    pub fn new(client: Client, network_identifier: NetworkIdentifier) -> Self {
+        Self {
+            client,
+            network_identifier,
+            asserter: None,
+        }
+    }
+
+    /// Validates every server response with the given client asserter.
+    pub fn with_asserter(mut self, asserter: Asserter) -> Self {
+        self.asserter = Some(asserter);
+        self
+    }
+}
+
+#[async_trait]
+impl TransactionLookup for ClientTransactionLookup {
+    async fn transaction(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        transaction_identifier: &TransactionIdentifier,
+    ) -> ParserResult<Option<BlockTransaction>> {
+        // Transactions on other networks are not served by this node.
+        if hash(Some(network_identifier)) != hash(Some(&self.network_identifier)) {
+            return Ok(None);
+        }
+
+        let resp = self
+            .client
+            .search_transactions(UncheckedSearchTransactionsRequest {
+                network_identifier: Some(self.network_identifier.clone()),
+                transaction_identifier: Some(transaction_identifier.clone()),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("failed to search transactions: {e}"))?;
+
+        if let Some(asserter) = &self.asserter {
+            asserter
+                .search_transaction_response(Some(&resp))
+                .map_err(|e| format!("node returned invalid transactions: {e}"))?;
+        }
+
+        Ok(resp
+            .transactions
+            .into_iter()
+            .flatten()
+            .map(BlockTransaction::from)
+            .find(|found| found.transaction.transaction_identifier == *transaction_identifier))
+    }
+}
@@ -0,0 +1,92 @@
+use mentat_parser::TransactionLookup;
+use serde_json::json;
+
+use super::*;
+
+fn hub() -> NetworkIdentifier {
+    ("cosmos", "hub").into()
+}
+
+fn lookup(response: serde_json::Value) -> ClientTransactionLookup {
+    let request = UncheckedSearchTransactionsRequest {
+        network_identifier: Some(hub()),
+        transaction_identifier: Some(TransactionIdentifier { hash: "b".into() }),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let exchange = RecordedExchange {
+        path: "search/transactions".into(),
+        request: serde_json::to_value(request).unwrap(),
+        status: 200,
+        response,
+        latency_ms: 0,
+    };
+
+    ClientTransactionLookup::new(
+        Client::new("http://localhost:1/")
+            .unwrap()
+            .with_interceptor(ReplayInterceptor::new(vec![exchange])),
+        hub(),
+    )
+}
+
+#[tokio::test]
+async fn test_client_transaction_lookup() {
+    let lookup = lookup(json!({
+        "transactions": [{
+            "block_identifier": { "index": 2, "hash": "block 2" },
+            "transaction": {
+                "transaction_identifier": { "hash": "b" },
+                "operations": [],
+                "related_transactions": [{
+                    "transaction_identifier": { "hash": "a" },
+                    "direction": "backward",
+                }],
+            },
+        }],
+        "total_count": 1,
+    }));
+
+    let found = lookup
+        .transaction(&hub(), &TransactionIdentifier { hash: "b".into() })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        found.block_identifier,
+        BlockIdentifier {
+            index: 2,
+            hash: "block 2".into(),
+        }
+    );
+    assert_eq!(
+        found.transaction.related_transactions[0].direction,
+        Some(Direction::Backward)
+    );
+}
+
+#[tokio::test]
+async fn test_client_transaction_lookup_not_found() {
+    let lookup = lookup(json!({ "transactions": [], "total_count": 0 }));
+
+    let found = lookup
+        .transaction(&hub(), &TransactionIdentifier { hash: "b".into() })
+        .await
+        .unwrap();
+    assert_eq!(found, None);
+}
+
+#[tokio::test]
+async fn test_client_transaction_lookup_other_network() {
+    // No exchange is recorded for other networks, so the node is not called.
+    let lookup = lookup(json!({}));
+
+    let found = lookup
+        .transaction(
+            &("cosmos", "osmosis").into(),
+            &TransactionIdentifier { hash: "b".into() },
+        )
+        .await
+        .unwrap();
+    assert_eq!(found, None);
+}
@@ -0,0 +1,298 @@
+//! Address encodings derived from a [`PublicKey`] for the common chain
+//! families, so /construction/derive does not have to be written by hand for
+//! every implementation.
+//!
+//! Every [`AddressScheme`] can both derive an address and validate one it
+//! did not derive, i.e. an address found in an operation.
+
+use bech32::{FromBase32, ToBase32, Variant};
+use mentat_types::{CurveType, PublicKey};
+use ripemd::Ripemd160;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::{
+    encoding::{compress_public_key, decompress_public_key},
+    errors::{KeysError, KeysResult},
+    PallasPublicKey,
+};
+
+/// The length of a hash160 (sha256 followed by ripemd160) digest.
+const HASH160_LEN: usize = 20;
+/// The prefix of an Ethereum address.
+const ETHEREUM_PREFIX: &str = "0x";
+/// The witness version of a P2WPKH output.
+const P2WPKH_WITNESS_VERSION: u8 = 0;
+/// The string hashed before the payload of an SS58 checksum.
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+/// The length of an SS58 checksum.
+const SS58_CHECKSUM_LEN: usize = 2;
+/// The largest SS58 prefix encoded in a single byte.
+const SS58_SIMPLE_PREFIX_MAX: u16 = 63;
+/// The smallest SS58 prefix encoded in two bytes.
+const SS58_FULL_PREFIX_MIN: u16 = 64;
+/// The largest SS58 prefix that can be encoded.
+const SS58_PREFIX_MAX: u16 = 16383;
+/// The length of an `Edwards25519` public key.
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// `AddressScheme` is the way a network encodes the address of a public key.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AddressScheme {
+    /// The last 20 bytes of the keccak256 hash of an uncompressed
+    /// `Secp256k1` key, hex encoded with the EIP-55 checksum.
+    Ethereum,
+    /// The base58check encoded hash160 of a compressed `Secp256k1` key.
+    /// `version` is `0x00` on Bitcoin mainnet and `0x6f` on testnet.
+    BitcoinP2pkh {
+        /// The version byte of the address.
+        #[serde(default)]
+        version: u8,
+    },
+    /// The segwit v0 bech32 encoded hash160 of a compressed `Secp256k1` key.
+    /// `hrp` is `bc` on Bitcoin mainnet and `tb` on testnet.
+    BitcoinP2wpkh {
+        /// The human readable part of the address.
+        hrp: String,
+    },
+    /// The bech32 encoded hash160 of a compressed `Secp256k1` key, i.e.
+    /// `cosmos1...` on the Cosmos Hub.
+    Cosmos {
+        /// The human readable part of the address.
+        hrp: String,
+    },
+    /// The SS58 encoding of an `Edwards25519` key used by Substrate chains.
+    Ss58 {
+        /// The network prefix, `0` on Polkadot and `42` for generic
+        /// Substrate chains.
+        prefix: u16,
+    },
+    /// The base58 encoded `Edwards25519` key, i.e. a Solana address.
+    Base58,
+    /// The base58check `B62` address of a `Pallas` key used by Mina.
+    Mina,
+}
+
+impl AddressScheme {
+    /// The curve type of the keys the scheme encodes.
+    pub fn curve_type(&self) -> CurveType {
+        match self {
+            Self::Ethereum
+            | Self::BitcoinP2pkh { .. }
+            | Self::BitcoinP2wpkh { .. }
+            | Self::Cosmos { .. } => CurveType::Secp256k1,
+            Self::Ss58 { .. } | Self::Base58 => CurveType::Edwards25519,
+            Self::Mina => CurveType::Pallas,
+        }
+    }
+
+    /// `derive` returns the address of `public_key`.
+    pub fn derive(&self, public_key: &PublicKey) -> KeysResult<String> {
+        if public_key.curve_type != self.curve_type() {
+            Err(format!(
+                "{self} addresses require curve type {} but got {}: {}",
+                self.curve_type(),
+                public_key.curve_type,
+                KeysError::ErrCurveTypeNotSupported
+            ))?;
+        }
+
+        match self {
+            Self::Ethereum => {
+                let uncompressed = decompress_public_key(public_key)?;
+                Ok(eip55(&Keccak256::digest(&uncompressed.bytes[1..])[12..]))
+            }
+            Self::BitcoinP2pkh { version } => Ok(bs58::encode(compressed_hash160(public_key)?)
+                .with_check_version(*version)
+                .into_string()),
+            Self::BitcoinP2wpkh { hrp } => {
+                let witness_version =
+                    bech32::u5::try_from_u8(P2WPKH_WITNESS_VERSION).map_err(|e| e.to_string())?;
+                let mut data = vec![witness_version];
+                data.extend(compressed_hash160(public_key)?.to_base32());
+                encode_bech32(hrp, data)
+            }
+            Self::Cosmos { hrp } => encode_bech32(hrp, compressed_hash160(public_key)?.to_base32()),
+            Self::Ss58 { prefix } => {
+                let mut data = ss58_prefix(*prefix)?;
+                data.extend(ed25519_bytes(public_key)?);
+                let checksum = ss58_checksum(&data);
+                data.extend(&checksum[..SS58_CHECKSUM_LEN]);
+                Ok(bs58::encode(data).into_string())
+            }
+            Self::Base58 => Ok(bs58::encode(ed25519_bytes(public_key)?).into_string()),
+            Self::Mina => Ok(PallasPublicKey::from_bytes(&public_key.bytes)?.to_address()),
+        }
+    }
+
+    /// `validate` returns an error if `address` is not a well formed address
+    /// of the scheme. Checksums are verified where the scheme has one,
+    /// including the EIP-55 checksum of mixed case Ethereum addresses.
+    pub fn validate(&self, address: &str) -> KeysResult<()> {
+        let valid = match self {
+            Self::Ethereum => validate_ethereum(address),
+            Self::BitcoinP2pkh { version } => bs58::decode(address)
+                .with_check(Some(*version))
+                .into_vec()
+                .map_or(false, |decoded| decoded.len() == HASH160_LEN + 1),
+            Self::BitcoinP2wpkh { hrp } => decode_bech32(hrp, address).map_or(false, |data| {
+                data.split_first().map_or(false, |(version, program)| {
+                    version.to_u8() == P2WPKH_WITNESS_VERSION
+                        && Vec::<u8>::from_base32(program)
+                            .map_or(false, |program| program.len() == HASH160_LEN)
+                })
+            }),
+            Self::Cosmos { hrp } => decode_bech32(hrp, address).map_or(false, |data| {
+                Vec::<u8>::from_base32(&data).map_or(false, |data| data.len() == HASH160_LEN)
+            }),
+            Self::Ss58 { prefix } => validate_ss58(*prefix, address)?,
+            Self::Base58 => bs58::decode(address)
+                .into_vec()
+                .map_or(false, |decoded| decoded.len() == ED25519_PUBLIC_KEY_LEN),
+            Self::Mina => PallasPublicKey::from_address(address).is_ok(),
+        };
+
+        if !valid {
+            Err(format!(
+                "{address} is not a valid {self} address: {}",
+                KeysError::ErrAddressInvalid
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for AddressScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ethereum => write!(f, "ethereum"),
+            Self::BitcoinP2pkh { version } => write!(f, "bitcoin p2pkh (version {version:#04x})"),
+            Self::BitcoinP2wpkh { hrp } => write!(f, "bitcoin p2wpkh ({hrp})"),
+            Self::Cosmos { hrp } => write!(f, "cosmos ({hrp})"),
+            Self::Ss58 { prefix } => write!(f, "ss58 (prefix {prefix})"),
+            Self::Base58 => write!(f, "base58"),
+            Self::Mina => write!(f, "mina"),
+        }
+    }
+}
+
+/// The hash160 of the compressed form of a `Secp256k1` key.
+fn compressed_hash160(public_key: &PublicKey) -> KeysResult<Vec<u8>> {
+    let compressed = compress_public_key(public_key)?;
+    Ok(Ripemd160::digest(Sha256::digest(&compressed.bytes)).to_vec())
+}
+
+/// The raw bytes of an `Edwards25519` key.
+fn ed25519_bytes(public_key: &PublicKey) -> KeysResult<&[u8]> {
+    if public_key.bytes.len() != ED25519_PUBLIC_KEY_LEN {
+        Err(KeysError::ErrPubKeyNotOnCurve)?;
+    }
+    Ok(&public_key.bytes)
+}
+
+/// Hex encodes a 20 byte Ethereum address with the EIP-55 checksum.
+fn eip55(address: &[u8]) -> String {
+    let lower = address
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    format!("{ETHEREUM_PREFIX}{checksummed}")
+}
+
+/// Whether `address` is a `0x` prefixed 20 byte hex address. Addresses in a
+/// single case carry no checksum, mixed case addresses must match EIP-55.
+fn validate_ethereum(address: &str) -> bool {
+    let hex = match address.strip_prefix(ETHEREUM_PREFIX) {
+        Some(hex) if hex.len() == 2 * HASH160_LEN => hex,
+        _ => return false,
+    };
+    let bytes = match (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+    {
+        Some(bytes) => bytes,
+        None => return false,
+    };
+
+    let is_single_case = hex == hex.to_ascii_lowercase() || hex == hex.to_ascii_uppercase();
+    is_single_case || eip55(&bytes) == address
+}
+
+/// Bech32 encodes `data` with `hrp`.
+fn encode_bech32(hrp: &str, data: Vec<bech32::u5>) -> KeysResult<String> {
+    bech32::encode(hrp, data, Variant::Bech32).map_err(|e| {
+        KeysError::from(format!(
+            "failed to bech32 encode with hrp {hrp}: {e}: {}",
+            KeysError::ErrAddressInvalid
+        ))
+    })
+}
+
+/// Decodes a bech32 `address`, returning `None` if it is malformed or has a
+/// different human readable part.
+fn decode_bech32(hrp: &str, address: &str) -> Option<Vec<bech32::u5>> {
+    match bech32::decode(address) {
+        Ok((decoded_hrp, data, Variant::Bech32)) if decoded_hrp == hrp => Some(data),
+        _ => None,
+    }
+}
+
+/// The one or two byte encoding of an SS58 network prefix.
+fn ss58_prefix(prefix: u16) -> KeysResult<Vec<u8>> {
+    match prefix {
+        0..=SS58_SIMPLE_PREFIX_MAX => Ok(vec![prefix as u8]),
+        SS58_FULL_PREFIX_MIN..=SS58_PREFIX_MAX => Ok(vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            ((prefix >> 8) as u8) | (((prefix & 0b11) as u8) << 6),
+        ]),
+        _ => Err(format!(
+            "ss58 prefix {prefix} is larger than {SS58_PREFIX_MAX}: {}",
+            KeysError::ErrAddressInvalid
+        ))?,
+    }
+}
+
+/// The blake2b-512 hash an SS58 checksum is taken from.
+fn ss58_checksum(data: &[u8]) -> Vec<u8> {
+    let mut hasher = blake2::Blake2b512::new();
+    hasher.update(SS58_CHECKSUM_PREFIX);
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// Whether `address` is an SS58 address of an `Edwards25519` key with
+/// `prefix`.
+fn validate_ss58(prefix: u16, address: &str) -> KeysResult<bool> {
+    let prefix = ss58_prefix(prefix)?;
+    let decoded = match bs58::decode(address).into_vec() {
+        Ok(decoded) => decoded,
+        Err(_) => return Ok(false),
+    };
+    if decoded.len() != prefix.len() + ED25519_PUBLIC_KEY_LEN + SS58_CHECKSUM_LEN
+        || decoded[..prefix.len()] != prefix[..]
+    {
+        return Ok(false);
+    }
+
+    let (data, checksum) = decoded.split_at(decoded.len() - SS58_CHECKSUM_LEN);
+    Ok(ss58_checksum(data)[..SS58_CHECKSUM_LEN] == *checksum)
+}
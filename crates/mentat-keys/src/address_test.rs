@@ -0,0 +1,255 @@
+use mentat_test_utils::TestCase;
+use mentat_types::{CurveType, PublicKey};
+
+use crate::{address::AddressScheme, errors::KeysError, types::KeyPair};
+
+/// The compressed `Secp256k1` public key of the private key `1`.
+const SECP256K1_GENERATOR: &str =
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+/// The public key of the Substrate development account `Alice`.
+const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+fn public_key(hex: &str, curve_type: CurveType) -> PublicKey {
+    PublicKey {
+        bytes: mentat_types::decode_from_hex_string(hex.into()).unwrap(),
+        curve_type,
+    }
+}
+
+fn secp256k1() -> PublicKey {
+    public_key(SECP256K1_GENERATOR, CurveType::Secp256k1)
+}
+
+fn ed25519() -> PublicKey {
+    public_key(ALICE, CurveType::Edwards25519)
+}
+
+#[test]
+fn test_derive() {
+    let tests = vec![
+        TestCase {
+            name: "ethereum",
+            payload: (AddressScheme::Ethereum, secp256k1()),
+            criteria: Ok("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string()),
+        },
+        TestCase {
+            name: "ethereum uncompressed public key",
+            payload: (
+                AddressScheme::Ethereum,
+                crate::encoding::decompress_public_key(&secp256k1()).unwrap(),
+            ),
+            criteria: Ok("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string()),
+        },
+        TestCase {
+            name: "bitcoin p2pkh",
+            payload: (AddressScheme::BitcoinP2pkh { version: 0 }, secp256k1()),
+            criteria: Ok("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_string()),
+        },
+        TestCase {
+            name: "bitcoin p2wpkh",
+            payload: (
+                AddressScheme::BitcoinP2wpkh { hrp: "bc".into() },
+                secp256k1(),
+            ),
+            criteria: Ok("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string()),
+        },
+        TestCase {
+            name: "cosmos",
+            payload: (
+                AddressScheme::Cosmos {
+                    hrp: "cosmos".into(),
+                },
+                secp256k1(),
+            ),
+            criteria: Ok("cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c".to_string()),
+        },
+        TestCase {
+            name: "ss58 substrate",
+            payload: (AddressScheme::Ss58 { prefix: 42 }, ed25519()),
+            criteria: Ok("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()),
+        },
+        TestCase {
+            name: "ss58 polkadot",
+            payload: (AddressScheme::Ss58 { prefix: 0 }, ed25519()),
+            criteria: Ok("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string()),
+        },
+        TestCase {
+            name: "ss58 two byte prefix",
+            payload: (AddressScheme::Ss58 { prefix: 1000 }, ed25519()),
+            criteria: Ok("vji5kpxBaPKwct6PAdHiJUPCU1hqBEAPaLMF59sXAjn4NeEaJ".to_string()),
+        },
+        TestCase {
+            name: "base58",
+            payload: (
+                AddressScheme::Base58,
+                public_key(&"00".repeat(32), CurveType::Edwards25519),
+            ),
+            criteria: Ok("11111111111111111111111111111111".to_string()),
+        },
+        TestCase {
+            name: "curve type does not match scheme",
+            payload: (AddressScheme::Ethereum, ed25519()),
+            criteria: Err(KeysError::ErrCurveTypeNotSupported.to_string()),
+        },
+        TestCase {
+            name: "ss58 prefix too large",
+            payload: (AddressScheme::Ss58 { prefix: 16384 }, ed25519()),
+            criteria: Err(KeysError::ErrAddressInvalid.to_string()),
+        },
+    ];
+
+    TestCase::run_output_match(tests, |(scheme, public_key)| {
+        scheme.derive(&public_key).map_err(|e| {
+            // Only the cause at the end of the error is compared.
+            let e = e.to_string();
+            e.rsplit(": ").next().unwrap().to_string()
+        })
+    });
+}
+
+#[test]
+fn test_validate() {
+    let tests = vec![
+        TestCase {
+            name: "ethereum checksummed",
+            payload: (
+                AddressScheme::Ethereum,
+                "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "ethereum lowercase",
+            payload: (
+                AddressScheme::Ethereum,
+                "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "ethereum bad checksum",
+            payload: (
+                AddressScheme::Ethereum,
+                "0x7E5F4552091A69125d5DfCb7b8C2659029395BdF",
+            ),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "ethereum too short",
+            payload: (AddressScheme::Ethereum, "0x7e5f4552091a69125d5dfcb7"),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "bitcoin p2pkh",
+            payload: (
+                AddressScheme::BitcoinP2pkh { version: 0 },
+                "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "bitcoin p2pkh wrong version",
+            payload: (
+                AddressScheme::BitcoinP2pkh { version: 0x6f },
+                "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+            ),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "bitcoin p2wpkh",
+            payload: (
+                AddressScheme::BitcoinP2wpkh { hrp: "bc".into() },
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "bitcoin p2wpkh bad checksum",
+            payload: (
+                AddressScheme::BitcoinP2wpkh { hrp: "bc".into() },
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+            ),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "cosmos",
+            payload: (
+                AddressScheme::Cosmos {
+                    hrp: "cosmos".into(),
+                },
+                "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "cosmos wrong hrp",
+            payload: (
+                AddressScheme::Cosmos { hrp: "osmo".into() },
+                "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c",
+            ),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "ss58",
+            payload: (
+                AddressScheme::Ss58 { prefix: 42 },
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "ss58 wrong prefix",
+            payload: (
+                AddressScheme::Ss58 { prefix: 0 },
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            ),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+        TestCase {
+            name: "base58",
+            payload: (AddressScheme::Base58, "11111111111111111111111111111111"),
+            criteria: None,
+        },
+        TestCase {
+            name: "base58 invalid character",
+            payload: (AddressScheme::Base58, "0OIl1111111111111111111111111111"),
+            criteria: Some(KeysError::ErrAddressInvalid),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |(scheme, address)| scheme.validate(address));
+}
+
+#[test]
+fn test_derive_then_validate() {
+    let schemes = vec![
+        AddressScheme::Ethereum,
+        AddressScheme::BitcoinP2pkh { version: 0x6f },
+        AddressScheme::BitcoinP2wpkh { hrp: "tb".into() },
+        AddressScheme::Cosmos { hrp: "osmo".into() },
+        AddressScheme::Ss58 { prefix: 2 },
+        AddressScheme::Base58,
+        AddressScheme::Mina,
+    ];
+
+    for scheme in schemes {
+        let key_pair = KeyPair::generate(scheme.curve_type()).unwrap();
+        let address = scheme.derive(&key_pair.public_key).unwrap();
+        scheme
+            .validate(&address)
+            .unwrap_or_else(|e| panic!("{scheme}: {e}"));
+    }
+}
+
+#[test]
+fn test_scheme_from_json() {
+    let scheme: AddressScheme =
+        serde_json::from_str(r#"{"type": "cosmos", "hrp": "cosmos"}"#).unwrap();
+    assert_eq!(
+        scheme,
+        AddressScheme::Cosmos {
+            hrp: "cosmos".into()
+        }
+    );
+}
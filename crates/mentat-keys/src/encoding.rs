@@ -0,0 +1,390 @@
+//! Standard encodings of keys: PKCS#8 and SEC1 private keys as DER or PEM,
+//! JWK, Bitcoin WIF and SEC1 compressed/uncompressed public keys.
+//!
+//! Every import decodes the raw private key and passes it through
+//! [`KeyPair::import_private_key`], so the result is validated exactly like a
+//! hex encoded key. `Pallas` keys have no standard encoding in these formats
+//! and remain hex only.
+
+use base64ct::{Base64UrlUnpadded, Encoding};
+use mentat_types::{encode_to_hex_string, CurveType, PublicKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use pkcs8::{
+    der::{
+        pem::{self, LineEnding},
+        Encode,
+    },
+    AlgorithmIdentifier,
+    DecodePrivateKey,
+    EncodePrivateKey,
+    ObjectIdentifier,
+    PrivateKeyInfo,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    types::KeyPair,
+};
+
+/// The PEM label of a PKCS#8 private key.
+pub const PKCS8_PEM_LABEL: &str = "PRIVATE KEY";
+/// The PEM label of a SEC1 EC private key.
+pub const SEC1_PEM_LABEL: &str = "EC PRIVATE KEY";
+
+/// `id-ecPublicKey`, the PKCS#8 algorithm of `Secp256k1` and `Secp256r1`
+/// keys.
+const EC_PUBLIC_KEY_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+/// The named curve parameter of `Secp256k1` keys.
+const SECP256K1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+/// The named curve parameter of `Secp256r1` keys.
+const SECP256R1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+/// The PKCS#8 algorithm of `Edwards25519` keys (RFC 8410).
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+/// The DER header of the `CurvePrivateKey` octet string wrapping an
+/// `Edwards25519` seed.
+const ED25519_PRIVATE_KEY_HEADER: [u8; 2] = [0x04, 0x20];
+/// The WIF suffix marking a compressed public key.
+const WIF_COMPRESSED_FLAG: u8 = 0x01;
+
+/// `WifNetwork` is the network a WIF key is encoded for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WifNetwork {
+    /// Bitcoin mainnet, version byte `0x80`.
+    Mainnet,
+    /// Bitcoin testnet and regtest, version byte `0xef`.
+    Testnet,
+}
+
+impl WifNetwork {
+    /// `version` returns the version byte of the network.
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::Mainnet => 0x80,
+            Self::Testnet => 0xef,
+        }
+    }
+
+    /// `from_version` returns the network of a version byte.
+    pub fn from_version(version: u8) -> Option<Self> {
+        match version {
+            0x80 => Some(Self::Mainnet),
+            0xef => Some(Self::Testnet),
+            _ => None,
+        }
+    }
+}
+
+/// An `OKP` JSON Web Key (RFC 8037) for `Edwards25519` keys.
+#[derive(Debug, Deserialize, Serialize)]
+#[allow(clippy::missing_docs_in_private_items)]
+struct OkpJwk {
+    kty: String,
+    crv: String,
+    x: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    d: Option<String>,
+}
+
+/// Builds the error returned when a key cannot be decoded.
+fn undecodable(format: &str, err: impl std::fmt::Display) -> KeysError {
+    format!(
+        "failed to decode {format} private key: {err}: {}",
+        KeysError::ErrPrivKeyUndecodable
+    )
+    .into()
+}
+
+/// Builds the error returned for curves a format does not support.
+fn unsupported(format: &str, curve: CurveType) -> KeysError {
+    format!(
+        "{format} does not support curve type {curve}: {}",
+        KeysError::ErrCurveTypeNotSupported
+    )
+    .into()
+}
+
+impl KeyPair {
+    /// `from_pkcs8_der` imports a DER encoded PKCS#8 private key.
+    pub fn from_pkcs8_der(der: &[u8]) -> KeysResult<Self> {
+        let info = PrivateKeyInfo::try_from(der).map_err(|e| undecodable("PKCS#8", e))?;
+
+        match info.algorithm.oid {
+            EC_PUBLIC_KEY_OID => match info.algorithm.parameters_oid() {
+                Ok(SECP256K1_OID) => {
+                    let key = k256::SecretKey::from_pkcs8_der(der)
+                        .map_err(|e| undecodable("PKCS#8", e))?;
+                    Self::import_private_key(
+                        encode_to_hex_string(&key.to_be_bytes()),
+                        CurveType::Secp256k1,
+                    )
+                }
+                Ok(SECP256R1_OID) => {
+                    let key = p256::SecretKey::from_pkcs8_der(der)
+                        .map_err(|e| undecodable("PKCS#8", e))?;
+                    Self::import_private_key(
+                        encode_to_hex_string(&key.to_be_bytes()),
+                        CurveType::Secp256r1,
+                    )
+                }
+                Ok(oid) => Err(undecodable("PKCS#8", format!("unknown named curve {oid}"))),
+                Err(e) => Err(undecodable("PKCS#8", e)),
+            },
+            ED25519_OID => {
+                let seed = info
+                    .private_key
+                    .strip_prefix(&ED25519_PRIVATE_KEY_HEADER[..])
+                    .ok_or_else(|| undecodable("PKCS#8", "malformed Ed25519 private key"))?;
+                Self::import_private_key(encode_to_hex_string(seed), CurveType::Edwards25519)
+            }
+            oid => Err(undecodable("PKCS#8", format!("unknown algorithm {oid}"))),
+        }
+    }
+
+    /// `to_pkcs8_der` exports the private key as DER encoded PKCS#8.
+    pub fn to_pkcs8_der(&self) -> KeysResult<Vec<u8>> {
+        let encode_err = |e: pkcs8::Error| -> KeysError {
+            format!("failed to encode PKCS#8 private key: {e}").into()
+        };
+
+        match self.public_key.curve_type {
+            CurveType::Secp256k1 => Ok(self
+                .secp256k1_secret_key()?
+                .to_pkcs8_der()
+                .map_err(encode_err)?
+                .as_bytes()
+                .to_vec()),
+            CurveType::Secp256r1 => Ok(self
+                .secp256r1_secret_key()?
+                .to_pkcs8_der()
+                .map_err(encode_err)?
+                .as_bytes()
+                .to_vec()),
+            CurveType::Edwards25519 => {
+                let private_key = [&ED25519_PRIVATE_KEY_HEADER[..], &self.private_key].concat();
+                PrivateKeyInfo::new(
+                    AlgorithmIdentifier {
+                        oid: ED25519_OID,
+                        parameters: None,
+                    },
+                    &private_key,
+                )
+                .to_vec()
+                .map_err(|e| encode_err(e.into()))
+            }
+            curve => Err(unsupported("PKCS#8", curve)),
+        }
+    }
+
+    /// `from_sec1_der` imports a DER encoded SEC1 `ECPrivateKey`. The curve
+    /// must be given as SEC1 keys do not always name it.
+    pub fn from_sec1_der(der: &[u8], curve: CurveType) -> KeysResult<Self> {
+        let private_key = match curve {
+            CurveType::Secp256k1 => k256::SecretKey::from_sec1_der(der)
+                .map_err(|e| undecodable("SEC1", e))?
+                .to_be_bytes(),
+            CurveType::Secp256r1 => p256::SecretKey::from_sec1_der(der)
+                .map_err(|e| undecodable("SEC1", e))?
+                .to_be_bytes(),
+            curve => return Err(unsupported("SEC1", curve)),
+        };
+
+        Self::import_private_key(encode_to_hex_string(&private_key), curve)
+    }
+
+    /// `to_sec1_der` exports a `Secp256k1` or `Secp256r1` private key as a
+    /// DER encoded SEC1 `ECPrivateKey`.
+    pub fn to_sec1_der(&self) -> KeysResult<Vec<u8>> {
+        let der = match self.public_key.curve_type {
+            CurveType::Secp256k1 => self.secp256k1_secret_key()?.to_sec1_der(),
+            CurveType::Secp256r1 => self.secp256r1_secret_key()?.to_sec1_der(),
+            curve => return Err(unsupported("SEC1", curve)),
+        };
+
+        der.map(|der| Vec::from(der.as_slice()))
+            .map_err(|e| format!("failed to encode SEC1 private key: {e}").into())
+    }
+
+    /// `from_pem` imports a PEM encoded `PRIVATE KEY` (PKCS#8) or
+    /// `EC PRIVATE KEY` (SEC1). The curve is only used for SEC1 keys.
+    pub fn from_pem(pem: &str, curve: CurveType) -> KeysResult<Self> {
+        let (label, der) =
+            pem::decode_vec(pem.trim().as_bytes()).map_err(|e| undecodable("PEM", e))?;
+
+        match label {
+            PKCS8_PEM_LABEL => Self::from_pkcs8_der(&der),
+            SEC1_PEM_LABEL => Self::from_sec1_der(&der, curve),
+            label => Err(undecodable("PEM", format!("unknown label {label}"))),
+        }
+    }
+
+    /// `to_pkcs8_pem` exports the private key as PEM encoded PKCS#8.
+    pub fn to_pkcs8_pem(&self) -> KeysResult<String> {
+        pem::encode_string(PKCS8_PEM_LABEL, LineEnding::LF, &self.to_pkcs8_der()?)
+            .map_err(|e| format!("failed to encode PEM private key: {e}").into())
+    }
+
+    /// `from_jwk` imports a private JSON Web Key: an `EC` key on `secp256k1`
+    /// or `P-256`, or an `OKP` key on `Ed25519`.
+    pub fn from_jwk(jwk: &str) -> KeysResult<Self> {
+        let value: serde_json::Value =
+            serde_json::from_str(jwk).map_err(|e| undecodable("JWK", e))?;
+
+        match (value["kty"].as_str(), value["crv"].as_str()) {
+            (Some("EC"), Some("secp256k1")) => {
+                let key = k256::SecretKey::from_jwk_str(jwk).map_err(|e| undecodable("JWK", e))?;
+                Self::import_private_key(
+                    encode_to_hex_string(&key.to_be_bytes()),
+                    CurveType::Secp256k1,
+                )
+            }
+            (Some("EC"), Some("P-256")) => {
+                let key = p256::SecretKey::from_jwk_str(jwk).map_err(|e| undecodable("JWK", e))?;
+                Self::import_private_key(
+                    encode_to_hex_string(&key.to_be_bytes()),
+                    CurveType::Secp256r1,
+                )
+            }
+            (Some("OKP"), Some("Ed25519")) => {
+                let jwk: OkpJwk =
+                    serde_json::from_value(value).map_err(|e| undecodable("JWK", e))?;
+                let seed = Base64UrlUnpadded::decode_vec(
+                    jwk.d
+                        .as_deref()
+                        .ok_or_else(|| undecodable("JWK", "missing d"))?,
+                )
+                .map_err(|e| undecodable("JWK", e))?;
+                let key_pair =
+                    Self::import_private_key(encode_to_hex_string(&seed), CurveType::Edwards25519)?;
+
+                if Base64UrlUnpadded::decode_vec(&jwk.x).ok().as_ref()
+                    != Some(&key_pair.public_key.bytes)
+                {
+                    Err(undecodable("JWK", "x does not match d"))?;
+                }
+                Ok(key_pair)
+            }
+            (kty, crv) => Err(undecodable(
+                "JWK",
+                format!("unsupported key type {kty:?} and curve {crv:?}"),
+            )),
+        }
+    }
+
+    /// `to_jwk` exports the private key as a JSON Web Key.
+    pub fn to_jwk(&self) -> KeysResult<String> {
+        match self.public_key.curve_type {
+            CurveType::Secp256k1 => Ok(self.secp256k1_secret_key()?.to_jwk_string().to_string()),
+            CurveType::Secp256r1 => Ok(self.secp256r1_secret_key()?.to_jwk_string().to_string()),
+            CurveType::Edwards25519 => serde_json::to_string(&OkpJwk {
+                kty: "OKP".into(),
+                crv: "Ed25519".into(),
+                x: Base64UrlUnpadded::encode_string(&self.public_key.bytes),
+                d: Some(Base64UrlUnpadded::encode_string(&self.private_key)),
+            })
+            .map_err(|e| format!("failed to encode JWK: {e}").into()),
+            curve => Err(unsupported("JWK", curve)),
+        }
+    }
+
+    /// `from_wif` imports a `Secp256k1` key in Bitcoin's Wallet Import
+    /// Format. Keys without the compressed flag keep an uncompressed public
+    /// key.
+    pub fn from_wif(wif: &str) -> KeysResult<Self> {
+        let decoded = bs58::decode(wif)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| undecodable("WIF", e))?;
+
+        let (version, payload) = decoded
+            .split_first()
+            .ok_or_else(|| undecodable("WIF", "empty payload"))?;
+        if WifNetwork::from_version(*version).is_none() {
+            Err(undecodable(
+                "WIF",
+                format!("unknown version {version:#04x}"),
+            ))?;
+        }
+
+        let (private_key, compressed) = match payload {
+            [key @ .., WIF_COMPRESSED_FLAG] if key.len() == 32 => (key, true),
+            key => (key, false),
+        };
+        let mut key_pair =
+            Self::import_private_key(encode_to_hex_string(private_key), CurveType::Secp256k1)?;
+
+        if !compressed {
+            key_pair.public_key = decompress_public_key(&key_pair.public_key)?;
+            key_pair = crate::types::UncheckedKeyPair::from(key_pair)
+                .is_valid()
+                .map_err(|err| format!("key pair is invalid: {err}"))?;
+        }
+        Ok(key_pair)
+    }
+
+    /// `to_wif` exports a `Secp256k1` key in Bitcoin's Wallet Import Format.
+    /// The compressed flag follows the encoding of the public key.
+    pub fn to_wif(&self, network: WifNetwork) -> KeysResult<String> {
+        if self.public_key.curve_type != CurveType::Secp256k1 {
+            Err(unsupported("WIF", self.public_key.curve_type))?;
+        }
+
+        let mut payload = self.private_key.clone();
+        if self.public_key.bytes.len() == COMPRESSED_LEN {
+            payload.push(WIF_COMPRESSED_FLAG);
+        }
+        Ok(bs58::encode(payload)
+            .with_check_version(network.version())
+            .into_string())
+    }
+
+    /// The private key as a `k256` secret key.
+    fn secp256k1_secret_key(&self) -> KeysResult<k256::SecretKey> {
+        k256::SecretKey::from_be_bytes(&self.private_key)
+            .map_err(|_| KeysError::ErrPrivKeyUndecodable)
+    }
+
+    /// The private key as a `p256` secret key.
+    fn secp256r1_secret_key(&self) -> KeysResult<p256::SecretKey> {
+        p256::SecretKey::from_be_bytes(&self.private_key)
+            .map_err(|_| KeysError::ErrPrivKeyUndecodable)
+    }
+}
+
+/// The length of a SEC1 compressed public key.
+const COMPRESSED_LEN: usize = 33;
+
+/// `compress_public_key` returns the 33 byte SEC1 compressed form of a
+/// `Secp256k1` or `Secp256r1` public key.
+pub fn compress_public_key(public_key: &PublicKey) -> KeysResult<PublicKey> {
+    sec1_public_key(public_key, true)
+}
+
+/// `decompress_public_key` returns the 65 byte SEC1 uncompressed form of a
+/// `Secp256k1` or `Secp256r1` public key.
+pub fn decompress_public_key(public_key: &PublicKey) -> KeysResult<PublicKey> {
+    sec1_public_key(public_key, false)
+}
+
+/// Re-encodes a SEC1 public key.
+fn sec1_public_key(public_key: &PublicKey, compress: bool) -> KeysResult<PublicKey> {
+    let bytes = match public_key.curve_type {
+        CurveType::Secp256k1 => k256::PublicKey::from_sec1_bytes(&public_key.bytes)
+            .map_err(|_| KeysError::ErrPubKeyNotOnCurve)?
+            .to_encoded_point(compress)
+            .as_bytes()
+            .to_vec(),
+        CurveType::Secp256r1 => p256::PublicKey::from_sec1_bytes(&public_key.bytes)
+            .map_err(|_| KeysError::ErrPubKeyNotOnCurve)?
+            .to_encoded_point(compress)
+            .as_bytes()
+            .to_vec(),
+        curve => return Err(unsupported("SEC1", curve)),
+    };
+
+    Ok(PublicKey {
+        bytes,
+        curve_type: public_key.curve_type,
+    })
+}
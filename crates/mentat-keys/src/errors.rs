@@ -29,6 +29,50 @@ pub enum KeysError {
 
     #[error("payment not found in signingPayload")]
     ErrPaymentNotFound,
+    #[error("address is not a valid pallas public key")]
+    ErrPallasAddressInvalid,
+    #[error("schnorr poseidon signatures are not supported")]
+    ErrSchnorrPoseidonUnsupported,
+
+    #[error("mnemonic is invalid")]
+    ErrMnemonicInvalid,
+    #[error("derivation path is invalid")]
+    ErrDerivationPathInvalid,
+    #[error("curve type only supports hardened derivation")]
+    ErrHardenedDerivationRequired,
+    #[error("derived key is invalid")]
+    ErrDerivedKeyInvalid,
+
+    #[error("keystore file is invalid")]
+    ErrKeystoreInvalid,
+    #[error("could not decrypt key, is the password correct?")]
+    ErrKeystoreDecryptionFailed,
+    #[error("account not found in keystore")]
+    ErrKeystoreAccountNotFound,
+    #[error("account already exists in keystore")]
+    ErrKeystoreAccountExists,
+
+    #[error("multisig threshold must be between 1 and the number of public keys")]
+    ErrMultiSigThresholdInvalid,
+    #[error("signature does not match any signing payload")]
+    ErrMultiSigUnknownPayload,
+    #[error("signature type does not match the signing payload")]
+    ErrMultiSigSignatureTypeMismatch,
+    #[error("public key is not a member of the multisig account")]
+    ErrMultiSigUnexpectedPublicKey,
+    #[error("account is not registered with the multisig collector")]
+    ErrMultiSigUnregisteredAccount,
+    #[error("not enough signatures to reach the multisig threshold")]
+    ErrMultiSigIncomplete,
+    #[error("musig2 public keys are invalid")]
+    ErrMuSigKeyAggInvalid,
+    #[error("musig2 nonce is invalid")]
+    ErrMuSigNonceInvalid,
+    #[error("musig2 partial signature is invalid")]
+    ErrMuSigPartialSignatureInvalid,
+
+    #[error("address is invalid")]
+    ErrAddressInvalid,
     // #[error("sign: unable to sign")]
     // ErrSignFailed,
     // #[error("keygen: error generating pair for {0} curve type")]
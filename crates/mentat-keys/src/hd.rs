@@ -0,0 +1,368 @@
+//! Hierarchical deterministic keys: BIP39 mnemonics, BIP32 derivation for
+//! `Secp256k1` and SLIP-10 derivation for `Edwards25519` and `Secp256r1`.
+
+use std::{fmt, str::FromStr};
+
+use hmac::{Hmac, Mac};
+use mentat_types::{encode_to_hex_string, CurveType};
+use p256::elliptic_curve::{
+    ff::{Field, PrimeField},
+    sec1::ToEncodedPoint,
+};
+use sha2::Sha512;
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    types::KeyPair,
+    Signer,
+};
+
+/// Child indices at or above `HARDENED_OFFSET` use hardened derivation.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// A BIP39 mnemonic sentence using the English word list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// `generate` returns a new random mnemonic of 12, 15, 18, 21 or 24
+    /// words.
+    pub fn generate(word_count: usize) -> KeysResult<Self> {
+        bip39::Mnemonic::generate(word_count)
+            .map(Self)
+            .map_err(|e| {
+                format!(
+                    "failed to generate mnemonic of {word_count} words: {e}: {}",
+                    KeysError::ErrMnemonicInvalid
+                )
+                .into()
+            })
+    }
+
+    /// `from_entropy` returns the mnemonic encoding 16 to 32 bytes of
+    /// entropy.
+    pub fn from_entropy(entropy: &[u8]) -> KeysResult<Self> {
+        bip39::Mnemonic::from_entropy(entropy)
+            .map(Self)
+            .map_err(|e| {
+                format!("entropy is invalid: {e}: {}", KeysError::ErrMnemonicInvalid).into()
+            })
+    }
+
+    /// `parse` validates the words and checksum of a mnemonic sentence.
+    pub fn parse(phrase: &str) -> KeysResult<Self> {
+        bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map(Self)
+            .map_err(|e| {
+                format!(
+                    "mnemonic is invalid: {e}: {}",
+                    KeysError::ErrMnemonicInvalid
+                )
+                .into()
+            })
+    }
+
+    /// `entropy` returns the entropy encoded by the mnemonic.
+    pub fn entropy(&self) -> Vec<u8> {
+        self.0.to_entropy()
+    }
+
+    /// `to_seed` returns the 64 byte BIP39 seed for an optional passphrase.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        self.0.to_seed(passphrase)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = KeysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A derivation path such as `m/44'/60'/0'/0/0`. Hardened indices may be
+/// marked with `'`, `h` or `H`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// `new` returns a path from raw child indices, where hardened indices
+    /// already include [`HARDENED_OFFSET`].
+    pub fn new(indices: Vec<u32>) -> Self {
+        Self(indices)
+    }
+
+    /// `indices` returns the raw child indices of the path.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = KeysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| -> KeysError {
+            format!(
+                "derivation path {s} {reason}: {}",
+                KeysError::ErrDerivationPathInvalid
+            )
+            .into()
+        };
+
+        let mut segments = s.trim().split('/');
+        if segments.next() != Some("m") {
+            return Err(invalid("must start with m"));
+        }
+
+        segments
+            .map(|segment| {
+                let (number, hardened) = match segment.strip_suffix(['\'', 'h', 'H']) {
+                    Some(number) => (number, true),
+                    None => (segment, false),
+                };
+                let index = number
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|i| *i < HARDENED_OFFSET)
+                    .ok_or_else(|| invalid(&format!("has invalid segment {segment:?}")))?;
+                Ok(if hardened {
+                    index + HARDENED_OFFSET
+                } else {
+                    index
+                })
+            })
+            .collect::<KeysResult<_>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if *index >= HARDENED_OFFSET {
+                write!(f, "/{}'", index - HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{index}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An extended private key: a private key together with the chain code
+/// needed to derive its children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedKey {
+    /// The curve the key belongs to.
+    pub curve: CurveType,
+    /// The raw private key.
+    pub private_key: [u8; 32],
+    /// The chain code.
+    pub chain_code: [u8; 32],
+    /// The number of derivations from the master key.
+    pub depth: u8,
+}
+
+impl ExtendedKey {
+    /// `master` returns the master key for a seed, i.e. one returned by
+    /// [`Mnemonic::to_seed`].
+    pub fn master(seed: &[u8], curve: CurveType) -> KeysResult<Self> {
+        let hmac_key: &[u8] = match curve {
+            CurveType::Secp256k1 => b"Bitcoin seed",
+            CurveType::Edwards25519 => b"ed25519 seed",
+            CurveType::Secp256r1 => b"Nist256p1 seed",
+            _ => Err(format!(
+                "curve type {curve} is invalid: {}",
+                KeysError::ErrCurveTypeNotSupported
+            ))?,
+        };
+
+        let mut data = seed.to_vec();
+        loop {
+            let (il, ir) = hmac_sha512(hmac_key, &data);
+            // SLIP-10: if the key is not a valid scalar retry with I as the
+            // data.
+            if curve == CurveType::Edwards25519 || scalar_add(curve, &il, None).is_some() {
+                return Ok(Self {
+                    curve,
+                    private_key: il,
+                    chain_code: ir,
+                    depth: 0,
+                });
+            }
+            data = [il, ir].concat();
+        }
+    }
+
+    /// `derive_child` returns the child key at `index`. `Edwards25519`
+    /// only supports hardened indices.
+    pub fn derive_child(&self, index: u32) -> KeysResult<Self> {
+        let hardened = index >= HARDENED_OFFSET;
+        if !hardened && self.curve == CurveType::Edwards25519 {
+            Err(format!(
+                "index {index} is not hardened: {}",
+                KeysError::ErrHardenedDerivationRequired
+            ))?;
+        }
+
+        let mut data = if hardened {
+            [&[0][..], &self.private_key].concat()
+        } else {
+            self.compressed_public_key()?
+        };
+        data.extend_from_slice(&index.to_be_bytes());
+
+        loop {
+            let (il, ir) = hmac_sha512(&self.chain_code, &data);
+            let private_key = if self.curve == CurveType::Edwards25519 {
+                Some(il)
+            } else {
+                scalar_add(self.curve, &il, Some(&self.private_key))
+            };
+
+            match private_key {
+                Some(private_key) => {
+                    return Ok(Self {
+                        curve: self.curve,
+                        private_key,
+                        chain_code: ir,
+                        depth: self.depth.checked_add(1).ok_or_else(|| {
+                            format!(
+                                "maximum depth exceeded: {}",
+                                KeysError::ErrDerivedKeyInvalid
+                            )
+                        })?,
+                    });
+                }
+                // SLIP-10: if the key is not a valid scalar retry with
+                // 0x01 || IR || ser32(i).
+                None => data = [&[1][..], &ir, &index.to_be_bytes()].concat(),
+            }
+        }
+    }
+
+    /// `derive_path` derives every index of `path` in turn.
+    pub fn derive_path(&self, path: &DerivationPath) -> KeysResult<Self> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+            .map_err(|e| format!("failed to derive path {path}: {e}").into())
+    }
+
+    /// `key_pair` returns the `KeyPair` of the extended key.
+    pub fn key_pair(&self) -> KeysResult<KeyPair> {
+        KeyPair::import_private_key(encode_to_hex_string(&self.private_key), self.curve)
+    }
+
+    /// `signer` returns a `Signer` for the extended key.
+    pub fn signer(&self) -> KeysResult<Signer> {
+        self.key_pair()?.signer()
+    }
+
+    /// The SEC1 compressed public key used for non-hardened derivation.
+    fn compressed_public_key(&self) -> KeysResult<Vec<u8>> {
+        match self.curve {
+            CurveType::Secp256k1 => {
+                let secp = secp256k1::Secp256k1::signing_only();
+                let private_key =
+                    secp256k1::SecretKey::from_slice(&self.private_key).map_err(|e| {
+                        format!(
+                            "private key is invalid: {e}: {}",
+                            KeysError::ErrDerivedKeyInvalid
+                        )
+                    })?;
+                Ok(private_key.public_key(&secp).serialize().to_vec())
+            }
+            CurveType::Secp256r1 => {
+                let private_key =
+                    p256::SecretKey::from_be_bytes(&self.private_key).map_err(|e| {
+                        format!(
+                            "private key is invalid: {e}: {}",
+                            KeysError::ErrDerivedKeyInvalid
+                        )
+                    })?;
+                Ok(private_key
+                    .public_key()
+                    .to_encoded_point(true)
+                    .as_bytes()
+                    .to_vec())
+            }
+            _ => Err(format!(
+                "curve type {} is invalid: {}",
+                self.curve,
+                KeysError::ErrCurveTypeNotSupported
+            ))?,
+        }
+    }
+}
+
+impl KeyPair {
+    /// `from_mnemonic` derives the `KeyPair` at `path` from a BIP39
+    /// mnemonic and passphrase.
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        path: &DerivationPath,
+        curve: CurveType,
+    ) -> KeysResult<Self> {
+        ExtendedKey::master(&mnemonic.to_seed(passphrase), curve)?
+            .derive_path(path)?
+            .key_pair()
+    }
+}
+
+/// Splits HMAC-SHA512(key, data) into its left and right halves.
+fn hmac_sha512(key: &[u8], data: &[u8]) -> ([u8; 32], [u8; 32]) {
+    // HMAC accepts keys of any length.
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).unwrap();
+    mac.update(data);
+    let out = mac.finalize().into_bytes();
+
+    let mut il = [0; 32];
+    let mut ir = [0; 32];
+    il.copy_from_slice(&out[..32]);
+    ir.copy_from_slice(&out[32..]);
+    (il, ir)
+}
+
+/// Returns `il + parent` modulo the curve order, or `None` if `il` is not
+/// below the curve order or the sum is zero.
+fn scalar_add(curve: CurveType, il: &[u8; 32], parent: Option<&[u8; 32]>) -> Option<[u8; 32]> {
+    match curve {
+        CurveType::Secp256k1 => {
+            let tweak = secp256k1::Scalar::from_be_bytes(*il).ok()?;
+            let key = match parent {
+                Some(parent) => secp256k1::SecretKey::from_slice(parent)
+                    .ok()?
+                    .add_tweak(&tweak)
+                    .ok()?,
+                None => secp256k1::SecretKey::from_slice(il).ok()?,
+            };
+            Some(key.secret_bytes())
+        }
+        CurveType::Secp256r1 => {
+            let tweak = Option::<p256::Scalar>::from(p256::Scalar::from_repr((*il).into()))?;
+            let sum = match parent {
+                Some(parent) => {
+                    tweak + Option::<p256::Scalar>::from(p256::Scalar::from_repr((*parent).into()))?
+                }
+                None => tweak,
+            };
+            if bool::from(sum.is_zero()) {
+                None
+            } else {
+                Some(sum.to_repr().into())
+            }
+        }
+        _ => None,
+    }
+}
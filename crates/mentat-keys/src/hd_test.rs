@@ -0,0 +1,347 @@
+use mentat_test_utils::TestCase;
+use mentat_types::{decode_from_hex_string, encode_to_hex_string, CurveType};
+
+use crate::{
+    errors::KeysError,
+    hd::{DerivationPath, ExtendedKey, Mnemonic, HARDENED_OFFSET},
+    types::KeyPair,
+    SignerInterface,
+};
+
+/// The seed of test vector 1 in BIP32 and SLIP-10.
+const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+struct Payload {
+    curve: CurveType,
+    path: &'static str,
+}
+
+/// Derives `path` from [`SEED`] and returns the hex chain code and private
+/// key.
+fn derive(payload: Payload) -> (String, String) {
+    let seed = decode_from_hex_string(SEED.to_string()).unwrap();
+    let key = ExtendedKey::master(&seed, payload.curve)
+        .unwrap()
+        .derive_path(&payload.path.parse().unwrap())
+        .unwrap();
+    (
+        encode_to_hex_string(&key.chain_code),
+        encode_to_hex_string(&key.private_key),
+    )
+}
+
+fn vector(
+    name: &'static str,
+    curve: CurveType,
+    path: &'static str,
+    chain_code: &str,
+    private_key: &str,
+) -> TestCase<Payload, (String, String)> {
+    TestCase {
+        name,
+        payload: Payload { curve, path },
+        criteria: (chain_code.to_string(), private_key.to_string()),
+    }
+}
+
+#[test]
+fn test_bip32_secp256k1() {
+    let tests = vec![
+        vector(
+            "m",
+            CurveType::Secp256k1,
+            "m",
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        ),
+        vector(
+            "m/0H",
+            CurveType::Secp256k1,
+            "m/0H",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        ),
+        vector(
+            "m/0H/1",
+            CurveType::Secp256k1,
+            "m/0H/1",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        vector(
+            "m/0H/1/2H",
+            CurveType::Secp256k1,
+            "m/0H/1/2H",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        ),
+        vector(
+            "m/0H/1/2H/2",
+            CurveType::Secp256k1,
+            "m/0H/1/2H/2",
+            "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+        ),
+        vector(
+            "m/0H/1/2H/2/1000000000",
+            CurveType::Secp256k1,
+            "m/0H/1/2H/2/1000000000",
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ),
+    ];
+
+    TestCase::run_output_match(tests, derive);
+}
+
+#[test]
+fn test_slip10_edwards25519() {
+    let tests = vec![
+        vector(
+            "m",
+            CurveType::Edwards25519,
+            "m",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        ),
+        vector(
+            "m/0H",
+            CurveType::Edwards25519,
+            "m/0H",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        ),
+        vector(
+            "m/0H/1H",
+            CurveType::Edwards25519,
+            "m/0H/1H",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        ),
+        vector(
+            "m/0H/1H/2H",
+            CurveType::Edwards25519,
+            "m/0H/1H/2H",
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+        ),
+        vector(
+            "m/0H/1H/2H/2H",
+            CurveType::Edwards25519,
+            "m/0H/1H/2H/2H",
+            "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+        ),
+        vector(
+            "m/0H/1H/2H/2H/1000000000H",
+            CurveType::Edwards25519,
+            "m/0H/1H/2H/2H/1000000000H",
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        ),
+    ];
+
+    TestCase::run_output_match(tests, derive);
+}
+
+#[test]
+fn test_slip10_secp256r1() {
+    let tests = vec![
+        vector(
+            "m",
+            CurveType::Secp256r1,
+            "m",
+            "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+            "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+        ),
+        vector(
+            "m/0H",
+            CurveType::Secp256r1,
+            "m/0H",
+            "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+            "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+        ),
+        vector(
+            "m/0H/1",
+            CurveType::Secp256r1,
+            "m/0H/1",
+            "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+            "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+        ),
+        vector(
+            "derivation retry",
+            CurveType::Secp256r1,
+            "m/28578H/33941",
+            "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071",
+            "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a",
+        ),
+    ];
+
+    TestCase::run_output_match(tests, derive);
+}
+
+#[test]
+fn test_edwards25519_requires_hardened() {
+    let seed = decode_from_hex_string(SEED.to_string()).unwrap();
+    let err = ExtendedKey::master(&seed, CurveType::Edwards25519)
+        .unwrap()
+        .derive_child(1)
+        .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrHardenedDerivationRequired.to_string()));
+}
+
+#[test]
+fn test_derivation_path() {
+    let tests = vec![
+        TestCase {
+            name: "master",
+            payload: "m",
+            criteria: Some(vec![]),
+        },
+        TestCase {
+            name: "bip44",
+            payload: "m/44'/60'/0'/0/7",
+            criteria: Some(vec![
+                44 + HARDENED_OFFSET,
+                60 + HARDENED_OFFSET,
+                HARDENED_OFFSET,
+                0,
+                7,
+            ]),
+        },
+        TestCase {
+            name: "h suffix",
+            payload: "m/0h/1H",
+            criteria: Some(vec![HARDENED_OFFSET, 1 + HARDENED_OFFSET]),
+        },
+        TestCase {
+            name: "missing m",
+            payload: "44'/0'",
+            criteria: None,
+        },
+        TestCase {
+            name: "not a number",
+            payload: "m/a",
+            criteria: None,
+        },
+        TestCase {
+            name: "index too large",
+            payload: "m/2147483648",
+            criteria: None,
+        },
+        TestCase {
+            name: "empty segment",
+            payload: "m//1",
+            criteria: None,
+        },
+    ];
+
+    TestCase::run_ok_match(tests, |p| {
+        p.parse::<DerivationPath>()
+            .map(|path| path.indices().to_vec())
+    });
+    assert_eq!(
+        "m/44'/60'/0'/0/7"
+            .parse::<DerivationPath>()
+            .unwrap()
+            .to_string(),
+        "m/44'/60'/0'/0/7"
+    );
+}
+
+#[test]
+fn test_bip39_vectors() {
+    // Vectors from the reference implementation, all using the passphrase
+    // "TREZOR".
+    let tests = vec![
+        TestCase {
+            name: "zero entropy",
+            payload: "00000000000000000000000000000000",
+            criteria: (
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+        },
+        TestCase {
+            name: "7f entropy",
+            payload: "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            criteria: (
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+        },
+        TestCase {
+            name: "ff entropy",
+            payload: "ffffffffffffffffffffffffffffffff",
+            criteria: (
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+                "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+            ),
+        },
+    ];
+
+    for test in tests {
+        let entropy = decode_from_hex_string(test.payload.to_string()).unwrap();
+        let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
+        assert_eq!(mnemonic.to_string(), test.criteria.0, "{}", test.name);
+
+        let parsed: Mnemonic = test.criteria.0.parse().unwrap();
+        assert_eq!(parsed.entropy(), entropy, "{}", test.name);
+        assert_eq!(
+            encode_to_hex_string(&parsed.to_seed("TREZOR")),
+            test.criteria.1,
+            "{}",
+            test.name
+        );
+    }
+}
+
+#[test]
+fn test_mnemonic_validation() {
+    let tests = vec![
+        TestCase {
+            name: "valid",
+            payload: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            criteria: None,
+        },
+        TestCase {
+            name: "bad checksum",
+            payload: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+            criteria: Some(KeysError::ErrMnemonicInvalid),
+        },
+        TestCase {
+            name: "unknown word",
+            payload: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon mentat",
+            criteria: Some(KeysError::ErrMnemonicInvalid),
+        },
+        TestCase {
+            name: "bad word count",
+            payload: "abandon abandon about",
+            criteria: Some(KeysError::ErrMnemonicInvalid),
+        },
+    ];
+
+    TestCase::run_err_match(tests, Mnemonic::parse);
+}
+
+#[test]
+fn test_from_mnemonic() {
+    let mnemonic = Mnemonic::generate(24).unwrap();
+    assert_eq!(mnemonic.to_string().split(' ').count(), 24);
+
+    let path: DerivationPath = "m/44'/0'/0'/0/0".parse().unwrap();
+    let key_pair = KeyPair::from_mnemonic(&mnemonic, "", &path, CurveType::Secp256k1).unwrap();
+    let again = KeyPair::from_mnemonic(&mnemonic, "", &path, CurveType::Secp256k1).unwrap();
+    assert_eq!(key_pair, again);
+
+    let signer = ExtendedKey::master(&mnemonic.to_seed(""), CurveType::Secp256k1)
+        .unwrap()
+        .derive_path(&path)
+        .unwrap()
+        .signer()
+        .unwrap();
+    assert_eq!(signer.public_key(), key_pair.public_key);
+}
@@ -1,8 +1,10 @@
 use mentat_types::{decode_from_hex_string, CurveType, UncheckedPublicKey};
+use pasta_curves::group::ff::{Field, PrimeField};
 
 use crate::{
     errors::{KeysError, KeysResult},
     types::{KeyPair, UncheckedKeyPair},
+    PallasPublicKey,
     Signer,
     SignerEdwards25519,
     SignerPallas,
@@ -105,7 +107,17 @@ impl KeyPair {
                     private_key: private_key.to_bytes().as_slice().to_vec(),
                 }
             }
-            CurveType::Pallas => unimplemented!("no good library exists for this in rust"),
+            CurveType::Pallas => {
+                let public_key = PallasPublicKey::from_private_key(&private_key)?;
+
+                UncheckedKeyPair {
+                    public_key: Some(UncheckedPublicKey {
+                        bytes: public_key.to_bytes(),
+                        curve_type: curve.into(),
+                    }),
+                    private_key: private_key.to_vec(),
+                }
+            }
             _ => {
                 return Err(KeysError::from(format!(
                     "curve type {curve} is invalid: {}",
@@ -164,7 +176,19 @@ impl KeyPair {
                     private_key: private_key.to_bytes().as_slice().to_vec(),
                 }
             }
-            CurveType::Pallas => unimplemented!("no good library exists for this in rust"),
+            CurveType::Pallas => {
+                let private_key =
+                    pasta_curves::pallas::Scalar::random(rand::thread_rng()).to_repr();
+                let public_key = PallasPublicKey::from_private_key(&private_key)?;
+
+                UncheckedKeyPair {
+                    public_key: Some(UncheckedPublicKey {
+                        bytes: public_key.to_bytes(),
+                        curve_type: curve.into(),
+                    }),
+                    private_key: private_key.to_vec(),
+                }
+            }
             _ => {
                 return Err(KeysError::from(format!(
                     "curve type {curve} is invalid: {}",
@@ -188,6 +212,7 @@ impl KeyPair {
             }),
             CurveType::Secp256k1 => Signer::Secp256k1(SignerSecp256k1 {
                 key_pair: self.into(),
+                bip340_mode: Default::default(),
             }),
             CurveType::Secp256r1 => Signer::Secp256r1(SignerSecp256r1 {
                 key_pair: self.into(),
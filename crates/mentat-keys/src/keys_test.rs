@@ -46,14 +46,13 @@ fn test_generate_key_pair_edwards25519() {
     assert_eq!(kp.private_key.len(), PRIV_KEY_BYTES_LEN);
 }
 
-// TODO enable when Pallas works
-// #[test]
-// fn test_generate_key_pair_pallas() {
-//     let kp = KeyPair::generate(CurveType::Pallas).unwrap();
+#[test]
+fn test_generate_key_pair_pallas() {
+    let kp = KeyPair::generate(CurveType::Pallas).unwrap();
 
-//     assert_eq!(kp.public_key.curve_type, CurveType::Pallas);
-//     assert_eq!(kp.private_key.len(), PRIV_KEY_BYTES_LEN);
-// }
+    assert_eq!(kp.public_key.curve_type, CurveType::Pallas);
+    assert_eq!(kp.private_key.len(), PRIV_KEY_BYTES_LEN);
+}
 
 fn mock_keypair(private_key: Vec<u8>, curve: CurveType) -> UncheckedKeyPair {
     let mut key_pair = KeyPair::generate(curve).unwrap();
@@ -133,15 +132,15 @@ fn test_import_private_key() {
             },
             criteria: None,
         },
-        // TestCase {
-        //     name: "simple Pallas",
-        //     payload: Payload {
-        //         private_key: "92D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F37"
-        //             .to_string(),
-        //         curve: CurveType::Pallas,
-        //     },
-        //     criteria: None,
-        // },
+        TestCase {
+            name: "simple Pallas",
+            payload: Payload {
+                private_key: "92D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F37"
+                    .to_string(),
+                curve: CurveType::Pallas,
+            },
+            criteria: None,
+        },
         TestCase {
             name: "short ed25519",
             payload: Payload {
@@ -158,14 +157,14 @@ fn test_import_private_key() {
             },
             criteria: Some(KeysError::ErrPrivKeyUndecodable),
         },
-        // TestCase {
-        //     name: "short pallas",
-        //     payload: Payload {
-        //         private_key: "asd".to_string(),
-        //         curve: CurveType::Secp256k1,
-        //     },
-        //     criteria: Some(KeysError::ErrPrivKeyUndecodable),
-        // },
+        TestCase {
+            name: "short pallas",
+            payload: Payload {
+                private_key: "asd".to_string(),
+                curve: CurveType::Pallas,
+            },
+            criteria: Some(KeysError::ErrPrivKeyUndecodable),
+        },
         TestCase {
             name: "long ed25519",
             payload: Payload {
@@ -184,15 +183,15 @@ fn test_import_private_key() {
             },
             criteria: Some(KeysError::ErrPrivKeyLengthInvalid),
         },
-        // TestCase {
-        //     name: "long Pallas",
-        //     payload: Payload {
-        //         private_key: "92D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F3792D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F37"
-        //             .to_string(),
-        //         curve: CurveType::Pallas,
-        //     },
-        //     criteria: Some(KeysError::ErrPrivKeyLengthInvalid),
-        // },
+        TestCase {
+            name: "long Pallas",
+            payload: Payload {
+                private_key: "92D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F3792D872DA7B3C90CF69D347908C3D3D692EA033A1D6E4A1695FCDCF6BBED87F37"
+                    .to_string(),
+                curve: CurveType::Pallas,
+            },
+            criteria: Some(KeysError::ErrPrivKeyLengthInvalid),
+        },
     ];
 
     TestCase::run_err_match(tests, |p| {
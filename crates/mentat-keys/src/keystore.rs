@@ -0,0 +1,451 @@
+//! An encrypted on-disk keystore modelled on the Ethereum v3 keystore.
+//!
+//! Every key is stored in its own json file. The private key is encrypted
+//! with AES-256-GCM under a key derived from a password with scrypt, while
+//! the account, curve type and public key are kept in the clear so keys can
+//! be listed without a password.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm,
+    Nonce,
+};
+use mentat_types::{
+    bytes_to_hex_str,
+    encode_to_hex_string,
+    hash,
+    null_default_bytes_to_hex,
+    AccountIdentifier,
+    PublicKey,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    types::{KeyPair, UncheckedKeyPair},
+};
+
+/// The version of the keystore file format.
+pub const KEYSTORE_VERSION: u32 = 1;
+/// The only supported cipher.
+const CIPHER: &str = "aes-256-gcm";
+/// The only supported key derivation function.
+const KDF: &str = "scrypt";
+/// The length of the derived encryption key.
+const DK_LEN: usize = 32;
+
+/// The scrypt parameters used to derive the encryption key from a password.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ScryptParams {
+    /// The log2 of the scrypt cost parameter `N`.
+    pub log_n: u8,
+    /// The scrypt block size.
+    pub r: u32,
+    /// The scrypt parallelization parameter.
+    pub p: u32,
+}
+
+impl ScryptParams {
+    /// `LIGHT` parameters trade security for speed, i.e. for test networks.
+    pub const LIGHT: Self = Self {
+        log_n: 12,
+        r: 8,
+        p: 6,
+    };
+    /// The largest `log_n` accepted, 1 GiB of memory with `r = 8`.
+    pub const MAX_LOG_N: u8 = 20;
+    /// The most memory, in bytes, a single scrypt lane may use.
+    const MAX_MEMORY: u64 = 1 << 30;
+    /// The largest parallelization accepted.
+    pub const MAX_P: u32 = 16;
+    /// The largest block size accepted.
+    pub const MAX_R: u32 = 32;
+    /// `STANDARD` parameters match the Ethereum v3 keystore defaults.
+    pub const STANDARD: Self = Self {
+        log_n: 18,
+        r: 8,
+        p: 1,
+    };
+
+    /// `is_bounded` checks the parameters against the maximums above, so a
+    /// crafted keystore file cannot make decryption use unbounded memory or
+    /// time.
+    pub fn is_bounded(&self) -> KeysResult<()> {
+        // scrypt uses 128 * r * N bytes for each of the p lanes.
+        if self.log_n > Self::MAX_LOG_N
+            || self.r > Self::MAX_R
+            || self.p > Self::MAX_P
+            || (128 * u64::from(self.r)) << self.log_n > Self::MAX_MEMORY
+        {
+            Err(format!(
+                "scrypt params {self:?} exceed the maximum of log_n {}, r {} and p {}: {}",
+                Self::MAX_LOG_N,
+                Self::MAX_R,
+                Self::MAX_P,
+                KeysError::ErrKeystoreInvalid
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// The key derivation parameters stored with an encrypted key.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KdfParams {
+    /// The scrypt cost parameters.
+    #[serde(flatten)]
+    pub scrypt: ScryptParams,
+    /// The length of the derived key.
+    pub dklen: usize,
+    /// The random salt.
+    #[serde(
+        serialize_with = "bytes_to_hex_str",
+        deserialize_with = "null_default_bytes_to_hex"
+    )]
+    pub salt: Vec<u8>,
+}
+
+/// The cipher parameters stored with an encrypted key.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CipherParams {
+    /// The random AES-GCM nonce.
+    #[serde(
+        serialize_with = "bytes_to_hex_str",
+        deserialize_with = "null_default_bytes_to_hex"
+    )]
+    pub nonce: Vec<u8>,
+}
+
+/// The encrypted private key and everything needed to decrypt it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Crypto {
+    /// The cipher used, always `aes-256-gcm`.
+    pub cipher: String,
+    /// The parameters of the cipher.
+    pub cipherparams: CipherParams,
+    /// The encrypted private key followed by the authentication tag.
+    #[serde(
+        serialize_with = "bytes_to_hex_str",
+        deserialize_with = "null_default_bytes_to_hex"
+    )]
+    pub ciphertext: Vec<u8>,
+    /// The key derivation function used, always `scrypt`.
+    pub kdf: String,
+    /// The parameters of the key derivation function.
+    pub kdfparams: KdfParams,
+}
+
+/// A single encrypted key as stored in a keystore file.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EncryptedKey {
+    /// The version of the file format.
+    pub version: u32,
+    /// The account the key belongs to.
+    pub account_identifier: AccountIdentifier,
+    /// The public key, including its curve type.
+    pub public_key: PublicKey,
+    /// The encrypted private key.
+    pub crypto: Crypto,
+}
+
+impl EncryptedKey {
+    /// `encrypt` encrypts a `KeyPair` with a password. The account and
+    /// public key are authenticated along with the private key so they
+    /// cannot be swapped out without failing decryption.
+    pub fn encrypt(
+        account: AccountIdentifier,
+        key_pair: &KeyPair,
+        password: &str,
+        params: ScryptParams,
+    ) -> KeysResult<Self> {
+        let mut rng = rand::thread_rng();
+        let mut salt = vec![0; 32];
+        rng.fill_bytes(&mut salt);
+        let mut nonce = vec![0; 12];
+        rng.fill_bytes(&mut nonce);
+
+        let kdfparams = KdfParams {
+            scrypt: params,
+            dklen: DK_LEN,
+            salt,
+        };
+        let cipher = cipher(password, &kdfparams)?;
+        let aad = associated_data(&account, &key_pair.public_key);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &key_pair.private_key,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| format!("failed to encrypt key: {e}"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            account_identifier: account,
+            public_key: key_pair.public_key.clone(),
+            crypto: Crypto {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams { nonce },
+                ciphertext,
+                kdf: KDF.to_string(),
+                kdfparams,
+            },
+        })
+    }
+
+    /// `decrypt` returns the `KeyPair` if the password is correct.
+    pub fn decrypt(&self, password: &str) -> KeysResult<KeyPair> {
+        if self.version != KEYSTORE_VERSION
+            || self.crypto.cipher != CIPHER
+            || self.crypto.kdf != KDF
+            || self.crypto.cipherparams.nonce.len() != 12
+        {
+            Err(format!(
+                "version {} with cipher {} and kdf {} is not supported: {}",
+                self.version,
+                self.crypto.cipher,
+                self.crypto.kdf,
+                KeysError::ErrKeystoreInvalid
+            ))?;
+        }
+
+        let cipher = cipher(password, &self.crypto.kdfparams)?;
+        let aad = associated_data(&self.account_identifier, &self.public_key);
+        let private_key = cipher
+            .decrypt(
+                Nonce::from_slice(&self.crypto.cipherparams.nonce),
+                Payload {
+                    msg: &self.crypto.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| KeysError::ErrKeystoreDecryptionFailed)?;
+
+        UncheckedKeyPair {
+            public_key: Some(self.public_key.clone().into()),
+            private_key,
+        }
+        .is_valid()
+        .map_err(|e| format!("decrypted key pair is invalid: {e}").into())
+    }
+}
+
+/// Derives the AES key for `password` and returns the cipher.
+fn cipher(password: &str, kdfparams: &KdfParams) -> KeysResult<Aes256Gcm> {
+    kdfparams.scrypt.is_bounded()?;
+    let params = scrypt::Params::new(
+        kdfparams.scrypt.log_n,
+        kdfparams.scrypt.r,
+        kdfparams.scrypt.p,
+    )
+    .map_err(|e| {
+        format!(
+            "scrypt params are invalid: {e}: {}",
+            KeysError::ErrKeystoreInvalid
+        )
+    })?;
+    if kdfparams.dklen != DK_LEN {
+        Err(format!(
+            "expected dklen {DK_LEN} but got {}: {}",
+            kdfparams.dklen,
+            KeysError::ErrKeystoreInvalid
+        ))?;
+    }
+
+    let mut key = [0; DK_LEN];
+    scrypt::scrypt(password.as_bytes(), &kdfparams.salt, &params, &mut key)
+        .map_err(|e| format!("failed to derive key: {e}"))?;
+    // The key is always 32 bytes.
+    Ok(Aes256Gcm::new_from_slice(&key).unwrap())
+}
+
+/// The data authenticated along with the private key.
+fn associated_data(account: &AccountIdentifier, public_key: &PublicKey) -> Vec<u8> {
+    let mut aad = hash(Some(account)).into_bytes();
+    aad.extend_from_slice(public_key.curve_type.to_string().as_bytes());
+    aad.extend_from_slice(&public_key.bytes);
+    aad
+}
+
+/// Creates a new temporary file. On unix only the owner can read or write
+/// it.
+fn create_new(path: &Path) -> io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)
+}
+
+/// Writes `contents` to a temporary file next to `path` and syncs it, then
+/// links it into place so `path` never holds a partially written key. A hard
+/// link is used instead of a rename since it fails rather than replacing an
+/// existing file. The temporary file is always removed.
+fn write_new(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut suffix = [0; 8];
+    rand::thread_rng().fill_bytes(&mut suffix);
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(
+        ".{file_name}.{}.tmp",
+        encode_to_hex_string(&suffix)
+    ));
+
+    let result = create_new(&tmp)
+        .and_then(|mut file| file.write_all(contents).and_then(|_| file.sync_all()))
+        .and_then(|_| fs::hard_link(&tmp, path));
+    let removed = fs::remove_file(&tmp);
+    result?;
+    removed?;
+    sync_dir(path)
+}
+
+/// Syncs the directory of `path` so a newly linked file survives a crash.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(dir) => fs::File::open(dir)?.sync_all(),
+        None => Ok(()),
+    }
+}
+
+/// Directories cannot be opened to be synced outside of unix.
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// A directory of [`EncryptedKey`] files, one per [`AccountIdentifier`].
+#[derive(Clone, Debug)]
+pub struct Keystore {
+    /// The directory the key files are stored in.
+    dir: PathBuf,
+    /// The scrypt parameters used for newly imported keys.
+    params: ScryptParams,
+}
+
+impl Keystore {
+    /// `open` opens the keystore in `dir`, creating the directory if needed.
+    pub fn open(dir: impl AsRef<Path>) -> KeysResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("failed to create keystore {}: {e}", dir.display()))?;
+        Ok(Self {
+            dir,
+            params: ScryptParams::default(),
+        })
+    }
+
+    /// `with_params` sets the scrypt parameters used for newly imported
+    /// keys. Existing keys keep the parameters they were encrypted with.
+    pub fn with_params(mut self, params: ScryptParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// `import` encrypts and stores the `KeyPair` of an account. It is an
+    /// error if the account already has a key. The key file is only created
+    /// once it is completely written, so a failed import leaves no file
+    /// behind.
+    pub fn import(
+        &self,
+        account: &AccountIdentifier,
+        key_pair: &KeyPair,
+        password: &str,
+    ) -> KeysResult<()> {
+        let key = EncryptedKey::encrypt(account.clone(), key_pair, password, self.params)?;
+        let contents = serde_json::to_vec_pretty(&key)
+            .map_err(|e| format!("failed to serialize key for account {account:?}: {e}"))?;
+
+        let path = self.path(account);
+        write_new(&path, &contents).map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => format!(
+                "account {account:?}: {}",
+                KeysError::ErrKeystoreAccountExists
+            )
+            .into(),
+            _ => format!("failed to write file {}: {e}", path.display()).into(),
+        })
+    }
+
+    /// `export` decrypts the `KeyPair` of an account.
+    pub fn export(&self, account: &AccountIdentifier, password: &str) -> KeysResult<KeyPair> {
+        self.get(account)?
+            .decrypt(password)
+            .map_err(|e| format!("failed to decrypt key for account {account:?}: {e}").into())
+    }
+
+    /// `get` returns the encrypted key of an account.
+    pub fn get(&self, account: &AccountIdentifier) -> KeysResult<EncryptedKey> {
+        let path = self.path(account);
+        if !path.exists() {
+            Err(format!(
+                "account {account:?}: {}",
+                KeysError::ErrKeystoreAccountNotFound
+            ))?;
+        }
+        read_key(&path)
+    }
+
+    /// `remove` deletes the key of an account.
+    pub fn remove(&self, account: &AccountIdentifier) -> KeysResult<()> {
+        let path = self.path(account);
+        if !path.exists() {
+            Err(format!(
+                "account {account:?}: {}",
+                KeysError::ErrKeystoreAccountNotFound
+            ))?;
+        }
+        fs::remove_file(&path)
+            .map_err(|e| format!("failed to remove file {}: {e}", path.display()).into())
+    }
+
+    /// `list` returns every stored key without decrypting it, sorted by
+    /// file name.
+    pub fn list(&self) -> KeysResult<Vec<EncryptedKey>> {
+        let mut paths = fs::read_dir(&self.dir)
+            .map_err(|e| format!("failed to read keystore {}: {e}", self.dir.display()))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("failed to read keystore {}: {e}", self.dir.display()))?;
+        paths.retain(|path| path.extension().map_or(false, |ext| ext == "json"));
+        paths.sort();
+        paths.iter().map(|path| read_key(path)).collect()
+    }
+
+    /// The path of the key file of an account.
+    fn path(&self, account: &AccountIdentifier) -> PathBuf {
+        let digest = Sha256::digest(hash(Some(account)).as_bytes());
+        self.dir
+            .join(format!("{}.json", encode_to_hex_string(&digest)))
+    }
+}
+
+/// Reads an [`EncryptedKey`] file.
+fn read_key(path: &Path) -> KeysResult<EncryptedKey> {
+    let contents =
+        fs::read(path).map_err(|e| format!("failed to read file {}: {e}", path.display()))?;
+    serde_json::from_slice(&contents).map_err(|e| {
+        format!(
+            "failed to deserialize file {}: {e}: {}",
+            path.display(),
+            KeysError::ErrKeystoreInvalid
+        )
+        .into()
+    })
+}
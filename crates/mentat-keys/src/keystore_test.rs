@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use mentat_types::{AccountIdentifier, CurveType};
+
+use crate::{
+    errors::KeysError,
+    keystore::{EncryptedKey, Keystore, ScryptParams},
+    types::KeyPair,
+};
+
+/// Cheap parameters so the tests run quickly.
+const TEST_PARAMS: ScryptParams = ScryptParams {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mentat-keystore-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    for curve in [
+        CurveType::Secp256k1,
+        CurveType::Secp256r1,
+        CurveType::Edwards25519,
+    ] {
+        let key_pair = KeyPair::generate(curve).unwrap();
+        let key =
+            EncryptedKey::encrypt(account("alice"), &key_pair, "password", TEST_PARAMS).unwrap();
+
+        assert_eq!(key.public_key, key_pair.public_key);
+        assert_ne!(key.crypto.ciphertext, key_pair.private_key);
+        assert_eq!(key.decrypt("password").unwrap(), key_pair);
+
+        let err = key.decrypt("wrong").unwrap_err();
+        assert!(matches!(err, KeysError::ErrKeystoreDecryptionFailed));
+    }
+}
+
+#[test]
+fn test_json_format() {
+    let key_pair = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let key = EncryptedKey::encrypt(account("alice"), &key_pair, "password", TEST_PARAMS).unwrap();
+
+    let json = serde_json::to_value(&key).unwrap();
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["crypto"]["cipher"], "aes-256-gcm");
+    assert_eq!(json["crypto"]["kdf"], "scrypt");
+    assert_eq!(json["crypto"]["kdfparams"]["log_n"], 4);
+    assert_eq!(json["crypto"]["kdfparams"]["dklen"], 32);
+    assert!(json["crypto"]["kdfparams"]["salt"].is_string());
+    assert!(json["crypto"]["ciphertext"].is_string());
+
+    let parsed: EncryptedKey = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.decrypt("password").unwrap(), key_pair);
+}
+
+#[test]
+fn test_tampered_metadata() {
+    let key_pair = KeyPair::generate(CurveType::Edwards25519).unwrap();
+    let mut key =
+        EncryptedKey::encrypt(account("alice"), &key_pair, "password", TEST_PARAMS).unwrap();
+
+    key.account_identifier = account("mallory");
+    let err = key.decrypt("password").unwrap_err();
+    assert!(matches!(err, KeysError::ErrKeystoreDecryptionFailed));
+}
+
+#[test]
+fn test_unsupported_version() {
+    let key_pair = KeyPair::generate(CurveType::Edwards25519).unwrap();
+    let mut key =
+        EncryptedKey::encrypt(account("alice"), &key_pair, "password", TEST_PARAMS).unwrap();
+
+    key.version = 3;
+    let err = key.decrypt("password").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrKeystoreInvalid.to_string()));
+}
+
+#[test]
+fn test_unbounded_scrypt_params() {
+    let key_pair = KeyPair::generate(CurveType::Edwards25519).unwrap();
+    let key = EncryptedKey::encrypt(account("alice"), &key_pair, "password", TEST_PARAMS).unwrap();
+
+    for params in [
+        ScryptParams {
+            log_n: 40,
+            ..TEST_PARAMS
+        },
+        ScryptParams {
+            r: u32::MAX,
+            ..TEST_PARAMS
+        },
+        ScryptParams {
+            p: u32::MAX,
+            ..TEST_PARAMS
+        },
+        ScryptParams {
+            log_n: ScryptParams::MAX_LOG_N,
+            r: ScryptParams::MAX_R,
+            ..TEST_PARAMS
+        },
+    ] {
+        let mut json = serde_json::to_value(&key).unwrap();
+        json["crypto"]["kdfparams"]["log_n"] = params.log_n.into();
+        json["crypto"]["kdfparams"]["r"] = params.r.into();
+        json["crypto"]["kdfparams"]["p"] = params.p.into();
+        let crafted: EncryptedKey = serde_json::from_value(json).unwrap();
+
+        let err = crafted.decrypt("password").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&KeysError::ErrKeystoreInvalid.to_string()),
+            "{params:?}"
+        );
+    }
+
+    ScryptParams::LIGHT.is_bounded().unwrap();
+    ScryptParams::STANDARD.is_bounded().unwrap();
+}
+
+#[test]
+fn test_keystore() {
+    let dir = temp_dir("store");
+    let keystore = Keystore::open(&dir).unwrap().with_params(TEST_PARAMS);
+    let alice = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let bob = KeyPair::generate(CurveType::Edwards25519).unwrap();
+
+    keystore.import(&account("alice"), &alice, "a").unwrap();
+    keystore.import(&account("bob"), &bob, "b").unwrap();
+
+    let err = keystore.import(&account("alice"), &bob, "a").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrKeystoreAccountExists.to_string()));
+
+    #[cfg(unix)]
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = entry.unwrap().metadata().unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let mut listed = keystore
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|key| (key.account_identifier.address, key.public_key.curve_type))
+        .collect::<Vec<_>>();
+    listed.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        listed,
+        vec![
+            ("alice".to_string(), CurveType::Secp256k1),
+            ("bob".to_string(), CurveType::Edwards25519),
+        ]
+    );
+
+    // Keys survive reopening the keystore.
+    let keystore = Keystore::open(&dir).unwrap();
+    assert_eq!(keystore.export(&account("alice"), "a").unwrap(), alice);
+    assert_eq!(keystore.export(&account("bob"), "b").unwrap(), bob);
+    assert!(keystore.export(&account("bob"), "a").is_err());
+
+    keystore.remove(&account("alice")).unwrap();
+    let err = keystore.export(&account("alice"), "a").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrKeystoreAccountNotFound.to_string()));
+    assert_eq!(keystore.list().unwrap().len(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_failed_import() {
+    let dir = temp_dir("failed");
+    let keystore = Keystore::open(&dir).unwrap().with_params(TEST_PARAMS);
+    let alice = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let bob = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    keystore.import(&account("alice"), &alice, "a").unwrap();
+
+    // The failed import neither replaces the key nor leaves its temporary
+    // file behind.
+    let err = keystore.import(&account("alice"), &bob, "b").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrKeystoreAccountExists.to_string()));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    assert_eq!(keystore.export(&account("alice"), "a").unwrap(), alice);
+
+    // Nothing is written when the keystore directory is gone.
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(keystore.import(&account("bob"), &bob, "b").is_err());
+    assert!(!dir.exists());
+}
@@ -1,13 +1,31 @@
+pub mod address;
+pub mod encoding;
 mod errors;
+pub use errors::*;
+pub mod hd;
 pub mod keys;
+pub mod keystore;
+pub mod multisig;
+pub mod musig2;
+pub mod schnorr;
 mod signers;
 pub use signers::*;
 pub mod types;
+mod verifier;
+pub use verifier::*;
 
 #[cfg(test)]
 #[path = ""]
 mod tests {
 
+    mod address_test;
+    mod encoding_test;
     mod errors_test;
+    mod hd_test;
     mod keys_test;
+    mod keystore_test;
+    mod multisig_test;
+    mod musig2_test;
+    mod schnorr_test;
+    mod verifier_test;
 }
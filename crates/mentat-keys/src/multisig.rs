@@ -0,0 +1,266 @@
+//! Collects the signatures of several [`Signer`]s for the payloads returned
+//! by /construction/payloads and tracks the m-of-n completion of every
+//! multisig account before /construction/combine.
+
+use mentat_types::{hash, AccountIdentifier, PublicKey, Signature, SignatureType, SigningPayload};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    Signer,
+    SignerInterface,
+    Verifier,
+};
+
+/// The public keys of an m-of-n multisig account. The order of the keys is
+/// the order signatures are returned in, which is usually the order of the
+/// keys in the locking script.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MultiSigAccount {
+    /// The number of signatures required.
+    pub threshold: usize,
+    /// The public keys that may sign.
+    pub public_keys: Vec<PublicKey>,
+}
+
+impl MultiSigAccount {
+    /// `new` returns an error if `threshold` is 0 or larger than the number
+    /// of public keys.
+    pub fn new(threshold: usize, public_keys: Vec<PublicKey>) -> KeysResult<Self> {
+        if threshold == 0 || threshold > public_keys.len() {
+            Err(format!(
+                "threshold {threshold} of {} public keys: {}",
+                public_keys.len(),
+                KeysError::ErrMultiSigThresholdInvalid
+            ))?;
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// The position of `public_key` in the account, if it is a member.
+    fn position(&self, public_key: &PublicKey) -> Option<usize> {
+        self.public_keys.iter().position(|k| k == public_key)
+    }
+}
+
+/// The signing progress of a single account.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MultiSigStatus {
+    /// The account being signed for.
+    pub account_identifier: AccountIdentifier,
+    /// The number of signatures required for every payload of the account.
+    pub threshold: usize,
+    /// The number of payloads the account has to sign.
+    pub payloads: usize,
+    /// The fewest signatures collected for any payload of the account.
+    pub signatures: usize,
+}
+
+impl MultiSigStatus {
+    /// Whether every payload of the account has enough signatures.
+    pub fn is_complete(&self) -> bool {
+        self.signatures >= self.threshold
+    }
+}
+
+/// `MultiSigCollector` gathers signatures for a set of signing payloads.
+///
+/// Every account that signs a payload has to be registered with
+/// [`MultiSigCollector::with_account`], single-key accounts as 1-of-1
+/// accounts, and needs `threshold` signatures from its member keys for
+/// every payload. Every signature is verified when it is added, and
+/// signatures for unregistered accounts are refused.
+#[derive(Clone, Debug, Default)]
+pub struct MultiSigCollector {
+    /// The payloads to sign.
+    payloads: Vec<SigningPayload>,
+    /// The multisig accounts, keyed by their account.
+    accounts: Vec<(AccountIdentifier, MultiSigAccount)>,
+    /// The signatures collected for each payload.
+    signatures: Vec<Vec<Signature>>,
+}
+
+impl MultiSigCollector {
+    /// Creates a collector for the payloads of /construction/payloads.
+    pub fn new(payloads: Vec<SigningPayload>) -> Self {
+        let signatures = vec![Vec::new(); payloads.len()];
+        Self {
+            payloads,
+            accounts: Vec::new(),
+            signatures,
+        }
+    }
+
+    /// Registers `account` as a multisig account.
+    pub fn with_account(mut self, account: AccountIdentifier, multisig: MultiSigAccount) -> Self {
+        self.accounts.push((account, multisig));
+        self
+    }
+
+    /// Finds the multisig account registered for `account`.
+    fn account(&self, account: Option<&AccountIdentifier>) -> Option<&MultiSigAccount> {
+        let account_hash = hash(account);
+        self.accounts
+            .iter()
+            .find(|(a, _)| hash(Some(a)) == account_hash)
+            .map(|(_, multisig)| multisig)
+    }
+
+    /// The number of signatures required for the payloads of `account`.
+    /// Unregistered accounts can never be signed for, but are reported as
+    /// needing one signature.
+    fn threshold(&self, account: Option<&AccountIdentifier>) -> usize {
+        self.account(account).map_or(1, |m| m.threshold)
+    }
+
+    /// `add_signature` verifies a signature and adds it to the payload it
+    /// signs, which must have the signature type of the payload if it has
+    /// one. Signing the same payload twice with the same key is a no-op.
+    pub fn add_signature(&mut self, signature: Signature) -> KeysResult<()> {
+        let index = self
+            .payloads
+            .iter()
+            .position(|p| {
+                p.bytes == signature.signing_payload.bytes
+                    && hash(p.account_identifier.as_ref())
+                        == hash(signature.signing_payload.account_identifier.as_ref())
+            })
+            .ok_or(KeysError::ErrMultiSigUnknownPayload)?;
+        let payload_type = self.payloads[index].signature_type;
+        if payload_type != SignatureType::EmptyString && signature.signature_type != payload_type {
+            Err(format!(
+                "expected signature type {payload_type} but got {}: {}",
+                signature.signature_type,
+                KeysError::ErrMultiSigSignatureTypeMismatch
+            ))?;
+        }
+        Verifier::verify(&signature.clone().into())?;
+
+        let account = self.payloads[index].account_identifier.as_ref();
+        let multisig = self.account(account).ok_or_else(|| {
+            format!(
+                "account {account:?}: {}",
+                KeysError::ErrMultiSigUnregisteredAccount
+            )
+        })?;
+        if multisig.position(&signature.public_key).is_none() {
+            Err(format!(
+                "public key {:?} of account {account:?}: {}",
+                signature.public_key,
+                KeysError::ErrMultiSigUnexpectedPublicKey
+            ))?;
+        }
+
+        let signatures = &mut self.signatures[index];
+        if !signatures
+            .iter()
+            .any(|s| s.public_key == signature.public_key)
+        {
+            signatures.push(signature);
+        }
+        Ok(())
+    }
+
+    /// `sign_with` signs every payload of a registered multisig account
+    /// that `signer` is a member of and returns the number of signatures
+    /// added.
+    pub fn sign_with(&mut self, signer: &Signer) -> KeysResult<usize> {
+        let public_key = signer.public_key();
+        let mut signed = 0;
+        for index in 0..self.payloads.len() {
+            let payload = &self.payloads[index];
+            let is_member = self
+                .account(payload.account_identifier.as_ref())
+                .map_or(false, |m| m.position(&public_key).is_some());
+            if !is_member
+                || self.signatures[index]
+                    .iter()
+                    .any(|s| s.public_key == public_key)
+            {
+                continue;
+            }
+
+            let signature_type = match payload.signature_type {
+                SignatureType::EmptyString => signer.default_signature_type()?,
+                signature_type => signature_type,
+            };
+            let mut signature = signer.sign(payload.clone(), signature_type)?;
+            signature.signature_type = signature_type;
+            self.add_signature(signature)?;
+            signed += 1;
+        }
+        Ok(signed)
+    }
+
+    /// `status` returns the signing progress of every account, in the
+    /// order the accounts first appear in the payloads.
+    pub fn status(&self) -> Vec<MultiSigStatus> {
+        let mut status: Vec<MultiSigStatus> = Vec::new();
+        for (payload, signatures) in self.payloads.iter().zip(&self.signatures) {
+            let account = payload.account_identifier.clone().unwrap_or_default();
+            let account_hash = hash(Some(&account));
+            match status
+                .iter_mut()
+                .find(|s| hash(Some(&s.account_identifier)) == account_hash)
+            {
+                Some(s) => {
+                    s.payloads += 1;
+                    s.signatures = s.signatures.min(signatures.len());
+                }
+                None => status.push(MultiSigStatus {
+                    threshold: self.threshold(Some(&account)),
+                    account_identifier: account,
+                    payloads: 1,
+                    signatures: signatures.len(),
+                }),
+            }
+        }
+        status
+    }
+
+    /// Whether every payload has enough signatures.
+    pub fn is_complete(&self) -> bool {
+        self.status().iter().all(MultiSigStatus::is_complete)
+    }
+
+    /// `signatures` returns the signatures to send to
+    /// /construction/combine, or an error naming the accounts that are
+    /// still missing signatures. For every payload the first `threshold`
+    /// signatures are returned in the order of the account's public keys.
+    pub fn signatures(&self) -> KeysResult<Vec<Signature>> {
+        let incomplete = self
+            .status()
+            .into_iter()
+            .filter(|s| !s.is_complete())
+            .map(|s| {
+                format!(
+                    "{:?} has {} of {} signatures",
+                    s.account_identifier, s.signatures, s.threshold
+                )
+            })
+            .collect::<Vec<_>>();
+        if !incomplete.is_empty() {
+            Err(format!(
+                "{}: {}",
+                incomplete.join(", "),
+                KeysError::ErrMultiSigIncomplete
+            ))?;
+        }
+
+        let mut out = Vec::new();
+        for (payload, signatures) in self.payloads.iter().zip(&self.signatures) {
+            let account = payload.account_identifier.as_ref();
+            let mut signatures = signatures.clone();
+            if let Some(multisig) = self.account(account) {
+                signatures.sort_by_key(|s| multisig.position(&s.public_key));
+            }
+            signatures.truncate(self.threshold(account));
+            out.extend(signatures);
+        }
+        Ok(out)
+    }
+}
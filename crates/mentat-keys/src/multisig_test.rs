@@ -0,0 +1,225 @@
+use mentat_test_utils::TestCase;
+use mentat_types::{AccountIdentifier, CurveType, Signature, SignatureType, SigningPayload};
+
+use crate::{errors::KeysError, multisig::*, types::KeyPair, Signer, SignerInterface};
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+fn payload(address: &str, byte: u8) -> SigningPayload {
+    SigningPayload {
+        account_identifier: Some(account(address)),
+        bytes: vec![byte; 32],
+        signature_type: SignatureType::EmptyString,
+        ..Default::default()
+    }
+}
+
+fn sign(signer: &Signer, payload: SigningPayload, signature_type: SignatureType) -> Signature {
+    let mut signature = signer.sign(payload, signature_type).unwrap();
+    signature.signature_type = signature_type;
+    signature
+}
+
+fn signers(curve: CurveType, count: usize) -> Vec<Signer> {
+    (0..count)
+        .map(|_| KeyPair::generate(curve).unwrap().signer().unwrap())
+        .collect()
+}
+
+/// A collector for two payloads of a 2-of-3 account and one payload of a
+/// single-key account.
+fn collector(multisig: &[Signer], single: &Signer) -> MultiSigCollector {
+    MultiSigCollector::new(vec![
+        payload("multisig", 1),
+        payload("multisig", 2),
+        payload("single", 3),
+    ])
+    .with_account(
+        account("multisig"),
+        MultiSigAccount::new(2, multisig.iter().map(|s| s.public_key()).collect()).unwrap(),
+    )
+    .with_account(
+        account("single"),
+        MultiSigAccount::new(1, vec![single.public_key()]).unwrap(),
+    )
+}
+
+#[test]
+fn test_collect() {
+    let multisig = signers(CurveType::Secp256k1, 3);
+    let single = signers(CurveType::Edwards25519, 1).remove(0);
+    let mut collector = collector(&multisig, &single);
+
+    assert_eq!(collector.sign_with(&multisig[2]).unwrap(), 2);
+    // Signing again with the same key adds nothing.
+    assert_eq!(collector.sign_with(&multisig[2]).unwrap(), 0);
+    assert!(!collector.is_complete());
+    assert_eq!(
+        collector.status(),
+        vec![
+            MultiSigStatus {
+                account_identifier: account("multisig"),
+                threshold: 2,
+                payloads: 2,
+                signatures: 1,
+            },
+            MultiSigStatus {
+                account_identifier: account("single"),
+                threshold: 1,
+                payloads: 1,
+                signatures: 0,
+            },
+        ]
+    );
+
+    let err = collector.signatures().unwrap_err().to_string();
+    assert!(err.contains(&KeysError::ErrMultiSigIncomplete.to_string()));
+    assert!(err.contains("has 1 of 2 signatures"));
+
+    assert_eq!(collector.sign_with(&multisig[0]).unwrap(), 2);
+    collector
+        .add_signature(sign(&single, payload("single", 3), SignatureType::Ed25519))
+        .unwrap();
+    assert!(collector.is_complete());
+
+    let signatures = collector.signatures().unwrap();
+    let keys = signatures
+        .iter()
+        .map(|s| s.public_key.clone())
+        .collect::<Vec<_>>();
+    // Signatures follow the order of the account's public keys.
+    assert_eq!(
+        keys,
+        vec![
+            multisig[0].public_key(),
+            multisig[2].public_key(),
+            multisig[0].public_key(),
+            multisig[2].public_key(),
+            single.public_key(),
+        ]
+    );
+    assert!(signatures
+        .iter()
+        .take(4)
+        .all(|s| s.signature_type == SignatureType::Ecdsa));
+}
+
+#[test]
+fn test_add_signature_errors() {
+    let multisig = signers(CurveType::Secp256k1, 3);
+    let single = signers(CurveType::Edwards25519, 1).remove(0);
+    let outsider = signers(CurveType::Secp256k1, 1).remove(0);
+    let collector = std::cell::RefCell::new(collector(&multisig, &single));
+
+    let mut tampered = sign(&multisig[0], payload("multisig", 1), SignatureType::Ecdsa);
+    tampered.public_key = multisig[1].public_key();
+
+    let tests = vec![
+        TestCase {
+            name: "unknown payload",
+            payload: sign(&multisig[0], payload("multisig", 9), SignatureType::Ecdsa),
+            criteria: Some(KeysError::ErrMultiSigUnknownPayload),
+        },
+        TestCase {
+            name: "unknown account",
+            payload: sign(&multisig[0], payload("other", 1), SignatureType::Ecdsa),
+            criteria: Some(KeysError::ErrMultiSigUnknownPayload),
+        },
+        TestCase {
+            name: "not a member",
+            payload: sign(&outsider, payload("multisig", 1), SignatureType::Ecdsa),
+            criteria: Some(KeysError::ErrMultiSigUnexpectedPublicKey),
+        },
+        TestCase {
+            name: "not a member of the single-key account",
+            payload: sign(&outsider, payload("single", 3), SignatureType::Ecdsa),
+            criteria: Some(KeysError::ErrMultiSigUnexpectedPublicKey),
+        },
+        TestCase {
+            name: "invalid signature",
+            payload: tampered,
+            criteria: Some(KeysError::ErrVerifyFailed),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |s| collector.borrow_mut().add_signature(s));
+
+    // A signature must have the signature type of its payload.
+    let mut typed = MultiSigCollector::new(vec![SigningPayload {
+        signature_type: SignatureType::Ecdsa,
+        ..payload("multisig", 1)
+    }])
+    .with_account(
+        account("multisig"),
+        MultiSigAccount::new(1, vec![multisig[0].public_key()]).unwrap(),
+    );
+    let err = typed
+        .add_signature(sign(
+            &multisig[0],
+            payload("multisig", 1),
+            SignatureType::EcdsaRecovery,
+        ))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrMultiSigSignatureTypeMismatch.to_string()));
+    assert!(!typed.is_complete());
+    typed
+        .add_signature(sign(
+            &multisig[0],
+            payload("multisig", 1),
+            SignatureType::Ecdsa,
+        ))
+        .unwrap();
+    assert!(typed.is_complete());
+
+    // A payload of an account that is not registered accepts no signatures.
+    let mut unregistered = MultiSigCollector::new(vec![payload("single", 3)]);
+    let err = unregistered
+        .add_signature(sign(&single, payload("single", 3), SignatureType::Ed25519))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrMultiSigUnregisteredAccount.to_string()));
+    assert!(!unregistered.is_complete());
+}
+
+#[test]
+fn test_threshold() {
+    let keys = signers(CurveType::Secp256k1, 2)
+        .iter()
+        .map(|s| s.public_key())
+        .collect::<Vec<_>>();
+
+    let tests = vec![
+        TestCase {
+            name: "1 of 2",
+            payload: 1,
+            criteria: None,
+        },
+        TestCase {
+            name: "2 of 2",
+            payload: 2,
+            criteria: None,
+        },
+        TestCase {
+            name: "0 of 2",
+            payload: 0,
+            criteria: Some(KeysError::ErrMultiSigThresholdInvalid),
+        },
+        TestCase {
+            name: "3 of 2",
+            payload: 3,
+            criteria: Some(KeysError::ErrMultiSigThresholdInvalid),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |threshold| {
+        MultiSigAccount::new(threshold, keys.clone())
+    });
+}
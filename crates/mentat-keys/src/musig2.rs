@@ -0,0 +1,530 @@
+//! MuSig2 ([BIP-327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki))
+//! key aggregation and multi-signing for `Secp256k1`. The signers of an
+//! n-of-n account aggregate their public keys into a single key and jointly
+//! produce one BIP-340 Schnorr signature for it, i.e. for taproot key path
+//! spends.
+//!
+//! Signing takes two rounds. Every signer generates a [`SecretNonce`] and
+//! shares the matching [`PublicNonce`]. Once all public nonces are known
+//! every signer creates a [`PartialSignature`] in a [`Session`], and the
+//! partial signatures are aggregated into the final [`Signature`].
+
+use k256::{
+    elliptic_curve::{
+        bigint::U256,
+        group::Group,
+        ops::Reduce,
+        sec1::ToEncodedPoint,
+        AffineXCoordinate,
+        PrimeField,
+    },
+    ProjectivePoint,
+    Scalar,
+};
+use mentat_types::{CurveType, PublicKey, Signature, SignatureType, SigningPayload};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    types::KeyPair,
+};
+
+/// The length of a compressed public key.
+const PUBLIC_KEY_LEN: usize = 33;
+/// The length of a public or aggregate nonce.
+const NONCE_LEN: usize = 2 * PUBLIC_KEY_LEN;
+
+/// Computes `SHA256(SHA256(tag) || SHA256(tag) || parts)` as defined by
+/// BIP-340.
+fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Interprets 32 big endian bytes as a scalar modulo the curve order.
+fn reduce(bytes: [u8; 32]) -> Scalar {
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(bytes.into())
+}
+
+/// Parses 32 big endian bytes as a scalar smaller than the curve order.
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Option::from(Scalar::from_repr(bytes.into()))
+}
+
+/// Parses a compressed point.
+fn parse_point(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.len() != PUBLIC_KEY_LEN {
+        return None;
+    }
+    k256::PublicKey::from_sec1_bytes(bytes)
+        .ok()
+        .map(|key| key.to_projective())
+}
+
+/// Parses a compressed point, where 33 zero bytes encode infinity.
+fn parse_point_ext(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.iter().all(|b| *b == 0) {
+        Some(ProjectivePoint::IDENTITY)
+    } else {
+        parse_point(bytes)
+    }
+}
+
+/// Serializes a point in compressed form, or as 33 zero bytes for
+/// infinity.
+fn cbytes(point: &ProjectivePoint) -> [u8; PUBLIC_KEY_LEN] {
+    let mut out = [0; PUBLIC_KEY_LEN];
+    if !bool::from(point.is_identity()) {
+        out.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes());
+    }
+    out
+}
+
+/// The x coordinate of a point.
+fn xbytes(point: &ProjectivePoint) -> [u8; 32] {
+    point.to_affine().x().into()
+}
+
+/// Whether the y coordinate of a point is even.
+fn has_even_y(point: &ProjectivePoint) -> bool {
+    cbytes(point)[0] == 0x02
+}
+
+/// `1` if the y coordinate of `point` is even, `-1` otherwise.
+fn parity(point: &ProjectivePoint) -> Scalar {
+    if has_even_y(point) {
+        Scalar::ONE
+    } else {
+        -Scalar::ONE
+    }
+}
+
+/// The result of aggregating the public keys of the signers, including any
+/// tweaks applied to the aggregate key.
+#[derive(Clone, Debug)]
+pub struct KeyAggContext {
+    /// The compressed public keys of the signers, in signing order.
+    public_keys: Vec<[u8; PUBLIC_KEY_LEN]>,
+    /// The key aggregation coefficient of each public key.
+    coefficients: Vec<Scalar>,
+    /// The aggregate public key.
+    q: ProjectivePoint,
+    /// The accumulated sign of the tweaks.
+    gacc: Scalar,
+    /// The accumulated tweak.
+    tacc: Scalar,
+}
+
+impl KeyAggContext {
+    /// `new` aggregates the compressed `Secp256k1` public keys of the
+    /// signers. The order of the keys matters, so every signer has to use
+    /// the same order.
+    pub fn new(public_keys: &[PublicKey]) -> KeysResult<Self> {
+        let public_keys = public_keys
+            .iter()
+            .map(|key| {
+                if key.curve_type != CurveType::Secp256k1 {
+                    Err(format!(
+                        "curve type {}: {}",
+                        key.curve_type,
+                        KeysError::ErrCurveTypeNotSupported
+                    ))?;
+                }
+                key.bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| KeysError::ErrMuSigKeyAggInvalid)
+            })
+            .collect::<KeysResult<Vec<[u8; PUBLIC_KEY_LEN]>>>()?;
+        if public_keys.is_empty() {
+            Err(KeysError::ErrMuSigKeyAggInvalid)?;
+        }
+
+        let list = tagged_hash("KeyAgg list", &[&public_keys.concat()]);
+        let second = public_keys.iter().find(|key| **key != public_keys[0]);
+        let coefficients = public_keys
+            .iter()
+            .map(|key| {
+                if Some(key) == second {
+                    Scalar::ONE
+                } else {
+                    reduce(tagged_hash("KeyAgg coefficient", &[&list, key]))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut q = ProjectivePoint::IDENTITY;
+        for (key, coefficient) in public_keys.iter().zip(&coefficients) {
+            let point = parse_point(key).ok_or_else(|| {
+                format!(
+                    "public key {}: {}",
+                    mentat_types::encode_to_hex_string(key),
+                    KeysError::ErrPubKeyNotOnCurve
+                )
+            })?;
+            q += point * coefficient;
+        }
+        if bool::from(q.is_identity()) {
+            Err(KeysError::ErrMuSigKeyAggInvalid)?;
+        }
+
+        Ok(Self {
+            public_keys,
+            coefficients,
+            q,
+            gacc: Scalar::ONE,
+            tacc: Scalar::ZERO,
+        })
+    }
+
+    /// Tweaks the aggregate key for a taproot output as described by
+    /// BIP-341, committing to the script tree `merkle_root` if there is one.
+    pub fn with_taproot_tweak(self, merkle_root: Option<&[u8; 32]>) -> KeysResult<Self> {
+        let x = xbytes(&self.q);
+        let tweak = match merkle_root {
+            Some(root) => tagged_hash("TapTweak", &[&x, root]),
+            None => tagged_hash("TapTweak", &[&x]),
+        };
+        self.with_xonly_tweak(&tweak)
+    }
+
+    /// Applies an x-only tweak to the aggregate key.
+    pub fn with_xonly_tweak(self, tweak: &[u8; 32]) -> KeysResult<Self> {
+        self.with_tweak(tweak, true)
+    }
+
+    /// Applies a plain tweak to the aggregate key, i.e. for BIP-32
+    /// derivation.
+    pub fn with_plain_tweak(self, tweak: &[u8; 32]) -> KeysResult<Self> {
+        self.with_tweak(tweak, false)
+    }
+
+    /// The BIP-327 `ApplyTweak` algorithm.
+    fn with_tweak(mut self, tweak: &[u8; 32], is_xonly: bool) -> KeysResult<Self> {
+        let t = parse_scalar(tweak).ok_or(KeysError::ErrMuSigKeyAggInvalid)?;
+        let g = if is_xonly {
+            parity(&self.q)
+        } else {
+            Scalar::ONE
+        };
+
+        let q = self.q * g + ProjectivePoint::GENERATOR * t;
+        if bool::from(q.is_identity()) {
+            Err(KeysError::ErrMuSigKeyAggInvalid)?;
+        }
+        self.q = q;
+        self.gacc *= g;
+        self.tacc = t + g * self.tacc;
+        Ok(self)
+    }
+
+    /// The compressed aggregate public key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            bytes: cbytes(&self.q).to_vec(),
+            curve_type: CurveType::Secp256k1,
+        }
+    }
+
+    /// The x-only aggregate public key that BIP-340 signatures verify
+    /// against.
+    pub fn x_only_public_key(&self) -> [u8; 32] {
+        xbytes(&self.q)
+    }
+
+    /// The key aggregation coefficient of `public_key`.
+    fn coefficient(&self, public_key: &[u8]) -> KeysResult<Scalar> {
+        self.public_keys
+            .iter()
+            .position(|key| key == public_key)
+            .map(|index| self.coefficients[index])
+            .ok_or_else(|| {
+                format!(
+                    "public key {} is not aggregated: {}",
+                    mentat_types::encode_to_hex_string(public_key),
+                    KeysError::ErrMuSigKeyAggInvalid
+                )
+                .into()
+            })
+    }
+}
+
+/// The secret half of a signer's nonce. It can only be used once, so
+/// [`Session::partial_sign`] consumes it.
+pub struct SecretNonce {
+    /// The two secret nonce scalars.
+    k: [Scalar; 2],
+    /// The compressed public key of the signer.
+    public_key: [u8; PUBLIC_KEY_LEN],
+}
+
+impl SecretNonce {
+    /// `generate` creates a fresh nonce pair for signing `message` with
+    /// `key_pair` under the aggregate key of `ctx`. The [`PublicNonce`] has
+    /// to be shared with the other signers.
+    pub fn generate(
+        key_pair: &KeyPair,
+        ctx: &KeyAggContext,
+        message: &[u8],
+    ) -> KeysResult<(Self, PublicNonce)> {
+        let mut rand = [0; 32];
+        rand::thread_rng().fill_bytes(&mut rand);
+        Self::generate_with_rand(rand, key_pair, ctx, message, &[])
+    }
+
+    /// The BIP-327 `NonceGen` algorithm with explicit randomness.
+    pub(crate) fn generate_with_rand(
+        rand: [u8; 32],
+        key_pair: &KeyPair,
+        ctx: &KeyAggContext,
+        message: &[u8],
+        extra: &[u8],
+    ) -> KeysResult<(Self, PublicNonce)> {
+        let public_key: [u8; PUBLIC_KEY_LEN] = key_pair
+            .public_key
+            .bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeysError::ErrMuSigKeyAggInvalid)?;
+
+        let aux = tagged_hash("MuSig/aux", &[&rand]);
+        let mut seed = [0; 32];
+        for (i, byte) in seed.iter_mut().enumerate() {
+            *byte = key_pair.private_key.get(i).copied().unwrap_or_default() ^ aux[i];
+        }
+
+        let aggregate_key = ctx.x_only_public_key();
+        let message_len = (message.len() as u64).to_be_bytes();
+        let extra_len = (extra.len() as u32).to_be_bytes();
+        let mut k = [Scalar::ZERO; 2];
+        for (i, k) in k.iter_mut().enumerate() {
+            *k = reduce(tagged_hash(
+                "MuSig/nonce",
+                &[
+                    &seed,
+                    &[PUBLIC_KEY_LEN as u8],
+                    &public_key,
+                    &[aggregate_key.len() as u8],
+                    &aggregate_key,
+                    &[1],
+                    &message_len,
+                    message,
+                    &extra_len,
+                    extra,
+                    &[i as u8],
+                ],
+            ));
+            if bool::from(k.is_zero()) {
+                Err(KeysError::ErrMuSigNonceInvalid)?;
+            }
+        }
+
+        let mut public_nonce = [0; NONCE_LEN];
+        public_nonce[..PUBLIC_KEY_LEN]
+            .copy_from_slice(&cbytes(&(ProjectivePoint::GENERATOR * k[0])));
+        public_nonce[PUBLIC_KEY_LEN..]
+            .copy_from_slice(&cbytes(&(ProjectivePoint::GENERATOR * k[1])));
+
+        Ok((Self { k, public_key }, PublicNonce(public_nonce)))
+    }
+
+    /// Parses the 97 byte `k1 || k2 || pk` encoding of a secret nonce.
+    #[cfg(test)]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> KeysResult<Self> {
+        if bytes.len() != 64 + PUBLIC_KEY_LEN {
+            Err(KeysError::ErrMuSigNonceInvalid)?;
+        }
+        let mut k = [Scalar::ZERO; 2];
+        for (i, k) in k.iter_mut().enumerate() {
+            *k = parse_scalar(&bytes[i * 32..(i + 1) * 32])
+                .filter(|k| !bool::from(k.is_zero()))
+                .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+        }
+        let mut public_key = [0; PUBLIC_KEY_LEN];
+        public_key.copy_from_slice(&bytes[64..]);
+        Ok(Self { k, public_key })
+    }
+}
+
+/// The public half of a signer's nonce, shared with the other signers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicNonce(pub [u8; NONCE_LEN]);
+
+/// The sum of the public nonces of all signers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregateNonce(pub [u8; NONCE_LEN]);
+
+impl AggregateNonce {
+    /// `new` aggregates the public nonces of all signers.
+    pub fn new(nonces: &[PublicNonce]) -> KeysResult<Self> {
+        let mut r = [ProjectivePoint::IDENTITY; 2];
+        for nonce in nonces {
+            for (j, r) in r.iter_mut().enumerate() {
+                *r += parse_point(&nonce.0[j * PUBLIC_KEY_LEN..(j + 1) * PUBLIC_KEY_LEN])
+                    .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+            }
+        }
+
+        let mut out = [0; NONCE_LEN];
+        out[..PUBLIC_KEY_LEN].copy_from_slice(&cbytes(&r[0]));
+        out[PUBLIC_KEY_LEN..].copy_from_slice(&cbytes(&r[1]));
+        Ok(Self(out))
+    }
+}
+
+/// A signer's share of the final signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialSignature(pub [u8; 32]);
+
+/// The state shared by all signers for signing one message.
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// The aggregated public keys.
+    ctx: KeyAggContext,
+    /// The message being signed.
+    message: Vec<u8>,
+    /// The nonce coefficient.
+    b: Scalar,
+    /// The final nonce.
+    r: ProjectivePoint,
+    /// The BIP-340 challenge.
+    e: Scalar,
+}
+
+impl Session {
+    /// Starts a session for signing `message` once every public nonce has
+    /// been aggregated into `aggregate_nonce`.
+    pub fn new(
+        ctx: &KeyAggContext,
+        aggregate_nonce: &AggregateNonce,
+        message: &[u8],
+    ) -> KeysResult<Self> {
+        let r1 = parse_point_ext(&aggregate_nonce.0[..PUBLIC_KEY_LEN])
+            .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+        let r2 = parse_point_ext(&aggregate_nonce.0[PUBLIC_KEY_LEN..])
+            .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+
+        let q = xbytes(&ctx.q);
+        let b = reduce(tagged_hash(
+            "MuSig/noncecoef",
+            &[&aggregate_nonce.0, &q, message],
+        ));
+        let mut r = r1 + r2 * b;
+        if bool::from(r.is_identity()) {
+            r = ProjectivePoint::GENERATOR;
+        }
+        let e = reduce(tagged_hash(
+            "BIP0340/challenge",
+            &[&xbytes(&r), &q, message],
+        ));
+
+        Ok(Self {
+            ctx: ctx.clone(),
+            message: message.to_vec(),
+            b,
+            r,
+            e,
+        })
+    }
+
+    /// `partial_sign` creates the partial signature of `key_pair`, using up
+    /// its secret nonce.
+    pub fn partial_sign(
+        &self,
+        nonce: SecretNonce,
+        key_pair: &KeyPair,
+    ) -> KeysResult<PartialSignature> {
+        let d = parse_scalar(&key_pair.private_key)
+            .filter(|d| !bool::from(d.is_zero()))
+            .ok_or(KeysError::ErrPrivKeyUndecodable)?;
+        if cbytes(&(ProjectivePoint::GENERATOR * d)) != nonce.public_key {
+            Err(format!(
+                "secret nonce was generated for another key: {}",
+                KeysError::ErrMuSigNonceInvalid
+            ))?;
+        }
+        let a = self.ctx.coefficient(&nonce.public_key)?;
+
+        let [mut k1, mut k2] = nonce.k;
+        if !has_even_y(&self.r) {
+            k1 = -k1;
+            k2 = -k2;
+        }
+        let d = parity(&self.ctx.q) * self.ctx.gacc * d;
+        let s = k1 + self.b * k2 + self.e * a * d;
+
+        Ok(PartialSignature(s.to_bytes().into()))
+    }
+
+    /// `partial_verify` checks the partial signature of the signer with
+    /// `public_key` and `public_nonce`.
+    pub fn partial_verify(
+        &self,
+        signature: &PartialSignature,
+        public_nonce: &PublicNonce,
+        public_key: &PublicKey,
+    ) -> KeysResult<()> {
+        let s = parse_scalar(&signature.0).ok_or(KeysError::ErrMuSigPartialSignatureInvalid)?;
+        let r1 = parse_point(&public_nonce.0[..PUBLIC_KEY_LEN])
+            .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+        let r2 = parse_point(&public_nonce.0[PUBLIC_KEY_LEN..])
+            .ok_or(KeysError::ErrMuSigNonceInvalid)?;
+        let p = parse_point(&public_key.bytes).ok_or(KeysError::ErrPubKeyNotOnCurve)?;
+        let a = self.ctx.coefficient(&public_key.bytes)?;
+
+        let mut r = r1 + r2 * self.b;
+        if !has_even_y(&self.r) {
+            r = -r;
+        }
+        let g = parity(&self.ctx.q) * self.ctx.gacc;
+        if ProjectivePoint::GENERATOR * s != r + p * (self.e * a * g) {
+            Err(KeysError::ErrMuSigPartialSignatureInvalid)?;
+        }
+        Ok(())
+    }
+
+    /// `aggregate` combines the partial signatures of all signers into a
+    /// 64 byte BIP-340 signature.
+    pub fn aggregate(&self, signatures: &[PartialSignature]) -> KeysResult<[u8; 64]> {
+        let mut s = self.e * parity(&self.ctx.q) * self.ctx.tacc;
+        for signature in signatures {
+            s += parse_scalar(&signature.0).ok_or(KeysError::ErrMuSigPartialSignatureInvalid)?;
+        }
+
+        let mut out = [0; 64];
+        out[..32].copy_from_slice(&xbytes(&self.r));
+        out[32..].copy_from_slice(&s.to_bytes());
+        Ok(out)
+    }
+
+    /// `signature` aggregates the partial signatures into the `SchnorrBip340`
+    /// [`Signature`] of `payload` for /construction/combine. The payload
+    /// bytes must be the message of the session.
+    pub fn signature(
+        &self,
+        payload: SigningPayload,
+        signatures: &[PartialSignature],
+    ) -> KeysResult<Signature> {
+        if payload.bytes != self.message {
+            Err(format!(
+                "signing payload does not match the session message: {}",
+                KeysError::ErrMuSigPartialSignatureInvalid
+            ))?;
+        }
+
+        Ok(Signature {
+            signing_payload: payload,
+            public_key: self.ctx.public_key(),
+            signature_type: SignatureType::SchnorrBip340,
+            bytes: self.aggregate(signatures)?.to_vec(),
+        })
+    }
+}
@@ -0,0 +1,693 @@
+use mentat_test_utils::TestCase;
+use mentat_types::{
+    decode_from_hex_string,
+    encode_to_hex_string,
+    AccountIdentifier,
+    CurveType,
+    PublicKey,
+    SignatureType,
+    SigningPayload,
+};
+
+use crate::{errors::KeysError, musig2::*, types::KeyPair, Verifier};
+
+fn public_key(hex: &str) -> PublicKey {
+    PublicKey {
+        bytes: decode_from_hex_string(hex.to_string()).unwrap(),
+        curve_type: CurveType::Secp256k1,
+    }
+}
+
+/// The BIP-327 key aggregation test vectors.
+#[test]
+fn test_key_agg_vectors() {
+    let keys = [
+        public_key("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+        public_key("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+        public_key("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+    ];
+
+    let tests = vec![
+        TestCase {
+            name: "three keys",
+            payload: vec![0, 1, 2],
+            criteria: "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c"
+                .to_string(),
+        },
+        TestCase {
+            name: "reversed order",
+            payload: vec![2, 1, 0],
+            criteria: "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b"
+                .to_string(),
+        },
+        TestCase {
+            name: "duplicate keys",
+            payload: vec![0, 0, 0],
+            criteria: "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935"
+                .to_string(),
+        },
+        TestCase {
+            name: "duplicate pairs",
+            payload: vec![0, 0, 1, 1],
+            criteria: "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e"
+                .to_string(),
+        },
+    ];
+
+    TestCase::run_output_match(tests, |indices: Vec<usize>| {
+        let keys = indices.iter().map(|i| keys[*i].clone()).collect::<Vec<_>>();
+        encode_to_hex_string(&KeyAggContext::new(&keys).unwrap().x_only_public_key())
+    });
+}
+
+/// Runs a full signing round for `signers` and returns the session and the
+/// public nonces and partial signatures of every signer.
+fn sign(
+    ctx: &KeyAggContext,
+    signers: &[KeyPair],
+    message: &[u8],
+) -> (Session, Vec<PublicNonce>, Vec<PartialSignature>) {
+    let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .map(|kp| SecretNonce::generate(kp, ctx, message).unwrap())
+        .unzip();
+    let session =
+        Session::new(ctx, &AggregateNonce::new(&public_nonces).unwrap(), message).unwrap();
+    let partial_signatures = secret_nonces
+        .into_iter()
+        .zip(signers)
+        .map(|(nonce, kp)| session.partial_sign(nonce, kp).unwrap())
+        .collect();
+    (session, public_nonces, partial_signatures)
+}
+
+fn payload(message: &[u8]) -> SigningPayload {
+    SigningPayload {
+        account_identifier: Some(AccountIdentifier {
+            address: "taproot".into(),
+            ..Default::default()
+        }),
+        bytes: message.to_vec(),
+        signature_type: SignatureType::SchnorrBip340,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sign() {
+    let signers = (0..3)
+        .map(|_| KeyPair::generate(CurveType::Secp256k1).unwrap())
+        .collect::<Vec<_>>();
+    let keys = signers
+        .iter()
+        .map(|kp| kp.public_key.clone())
+        .collect::<Vec<_>>();
+    let message = [7; 32];
+
+    for ctx in [
+        KeyAggContext::new(&keys).unwrap(),
+        KeyAggContext::new(&keys)
+            .unwrap()
+            .with_taproot_tweak(None)
+            .unwrap(),
+        KeyAggContext::new(&keys)
+            .unwrap()
+            .with_taproot_tweak(Some(&[3; 32]))
+            .unwrap(),
+    ] {
+        let (session, public_nonces, partial_signatures) = sign(&ctx, &signers, &message);
+        for ((signature, nonce), key) in partial_signatures.iter().zip(&public_nonces).zip(&keys) {
+            session.partial_verify(signature, nonce, key).unwrap();
+        }
+
+        let signature = session
+            .signature(payload(&message), &partial_signatures)
+            .unwrap();
+        assert_eq!(signature.public_key, ctx.public_key());
+        Verifier::verify(&signature.into()).unwrap();
+    }
+}
+
+#[test]
+fn test_sign_errors() {
+    let signers = (0..2)
+        .map(|_| KeyPair::generate(CurveType::Secp256k1).unwrap())
+        .collect::<Vec<_>>();
+    let keys = signers
+        .iter()
+        .map(|kp| kp.public_key.clone())
+        .collect::<Vec<_>>();
+    let ctx = KeyAggContext::new(&keys).unwrap();
+    let message = [7; 32];
+    let (session, public_nonces, mut partial_signatures) = sign(&ctx, &signers, &message);
+
+    // A partial signature from the wrong signer is detected.
+    let err = session
+        .partial_verify(&partial_signatures[0], &public_nonces[0], &keys[1])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrMuSigPartialSignatureInvalid.to_string()));
+
+    // A missing partial signature produces an invalid signature.
+    partial_signatures.pop();
+    let signature = session
+        .signature(payload(&message), &partial_signatures)
+        .unwrap();
+    assert!(Verifier::verify(&signature.into()).is_err());
+
+    let outsider = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let (nonce, _) = SecretNonce::generate(&outsider, &ctx, &message).unwrap();
+    let ed25519 = KeyPair::generate(CurveType::Edwards25519).unwrap();
+
+    let tests = vec![
+        TestCase {
+            name: "signer is not aggregated",
+            payload: session.partial_sign(nonce, &outsider).map(|_| ()),
+            criteria: Some(KeysError::ErrMuSigKeyAggInvalid),
+        },
+        TestCase {
+            name: "nonce of another signer",
+            payload: SecretNonce::generate(&signers[0], &ctx, &message)
+                .and_then(|(nonce, _)| session.partial_sign(nonce, &signers[1]))
+                .map(|_| ()),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "payload does not match message",
+            payload: session.signature(payload(&[8; 32]), &[]).map(|_| ()),
+            criteria: Some(KeysError::ErrMuSigPartialSignatureInvalid),
+        },
+        TestCase {
+            name: "no public keys",
+            payload: KeyAggContext::new(&[]).map(|_| ()),
+            criteria: Some(KeysError::ErrMuSigKeyAggInvalid),
+        },
+        TestCase {
+            name: "edwards25519 key",
+            payload: KeyAggContext::new(&[ed25519.public_key]).map(|_| ()),
+            criteria: Some(KeysError::ErrCurveTypeNotSupported),
+        },
+        TestCase {
+            name: "invalid nonce",
+            payload: AggregateNonce::new(&[PublicNonce([1; 66])]).map(|_| ()),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |result| result);
+}
+
+fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+    decode_from_hex_string(hex.to_string())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+/// The indices of the tweaks to apply and whether each tweak is x-only.
+type Tweaks = Vec<(usize, bool)>;
+
+fn public_nonce(hex: &str) -> PublicNonce {
+    PublicNonce(bytes(hex))
+}
+
+/// The BIP-327 nonce aggregation test vectors.
+#[test]
+fn test_nonce_agg_vectors() {
+    let nonces = [
+        "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E66603BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+        "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+        "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E6660279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60379BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "04FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+        "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B831",
+        "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A602FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+    ]
+    .map(public_nonce);
+    let aggregate = |indices: [usize; 2]| AggregateNonce::new(&indices.map(|i| nonces[i]));
+
+    let tests = vec![
+        TestCase {
+            name: "two nonces",
+            payload: [0, 1],
+            criteria: Some(AggregateNonce(bytes(
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8",
+            ))),
+        },
+        TestCase {
+            name: "second half is infinity",
+            payload: [2, 3],
+            criteria: Some(AggregateNonce(bytes(
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B000000000000000000000000000000000000000000000000000000000000000000",
+            ))),
+        },
+    ];
+    TestCase::run_ok_match(tests, aggregate);
+
+    let tests = vec![
+        TestCase {
+            name: "invalid prefix",
+            payload: [0, 4],
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "x is not on the curve",
+            payload: [5, 1],
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "x exceeds the field size",
+            payload: [6, 1],
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+    ];
+    TestCase::run_err_match(tests, |indices| aggregate(indices).map(|_| ()));
+}
+
+/// The BIP-327 partial signing and verification test vectors.
+#[test]
+fn test_sign_verify_vectors() {
+    let key_pair = KeyPair::import_private_key(
+        "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671".into(),
+        CurveType::Secp256k1,
+    )
+    .unwrap();
+    let keys = [
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+        "020000000000000000000000000000000000000000000000000000000000000007",
+    ]
+    .map(public_key);
+    let secret_nonces = [
+        "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F703935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+    ]
+    .map(|hex| decode_from_hex_string(hex.to_string()).unwrap());
+    let public_nonces = [
+        "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0200000000000000000000000000000000000000000000000000000000000000090287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+    ]
+    .map(public_nonce);
+    let aggregate_nonces = [
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "048465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61020000000000000000000000000000000000000000000000000000000000000009",
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD6102FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+    ]
+    .map(|hex| AggregateNonce(bytes(hex)));
+    let message = bytes::<32>("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+    let ctx = |indices: &[usize]| {
+        KeyAggContext::new(&indices.iter().map(|i| keys[*i].clone()).collect::<Vec<_>>())
+    };
+
+    // The signer's public key and nonce are always the first ones.
+    let tests = vec![
+        TestCase {
+            name: "signer is first",
+            payload: (vec![0, 1, 2], vec![0, 1, 2], 0),
+            criteria: Some(PartialSignature(bytes(
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ))),
+        },
+        TestCase {
+            name: "signer is second",
+            payload: (vec![1, 0, 2], vec![1, 0, 2], 0),
+            criteria: Some(PartialSignature(bytes(
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ))),
+        },
+        TestCase {
+            name: "signer is last",
+            payload: (vec![1, 2, 0], vec![1, 2, 0], 0),
+            criteria: Some(PartialSignature(bytes(
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ))),
+        },
+        TestCase {
+            name: "aggregate nonce is infinity",
+            payload: (vec![0, 1], vec![0, 3], 1),
+            criteria: Some(PartialSignature(bytes(
+                "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531",
+            ))),
+        },
+    ];
+    TestCase::run_ok_match(
+        tests,
+        |(key_indices, nonce_indices, aggregate_index): (Vec<usize>, Vec<usize>, usize)| {
+            let nonces = nonce_indices
+                .iter()
+                .map(|i| public_nonces[*i])
+                .collect::<Vec<_>>();
+            assert_eq!(
+                AggregateNonce::new(&nonces)?,
+                aggregate_nonces[aggregate_index]
+            );
+
+            let session = Session::new(
+                &ctx(&key_indices)?,
+                &aggregate_nonces[aggregate_index],
+                &message,
+            )?;
+            let signature =
+                session.partial_sign(SecretNonce::from_bytes(&secret_nonces[0])?, &key_pair)?;
+            session.partial_verify(&signature, &public_nonces[0], &keys[0])?;
+            Ok::<_, KeysError>(signature)
+        },
+    );
+
+    let tests = vec![
+        TestCase {
+            name: "signer is not aggregated",
+            payload: (vec![1, 2], 0, 0),
+            criteria: Some(KeysError::ErrMuSigKeyAggInvalid),
+        },
+        TestCase {
+            name: "invalid public key",
+            payload: (vec![1, 0, 3], 0, 0),
+            criteria: Some(KeysError::ErrPubKeyNotOnCurve),
+        },
+        TestCase {
+            name: "aggregate nonce with invalid prefix",
+            payload: (vec![1, 2, 0], 2, 0),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "aggregate nonce is not on the curve",
+            payload: (vec![1, 2, 0], 3, 0),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "aggregate nonce exceeds the field size",
+            payload: (vec![1, 2, 0], 4, 0),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "zero secret nonce",
+            payload: (vec![0, 1, 2], 0, 1),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+    ];
+    TestCase::run_err_match(
+        tests,
+        |(key_indices, aggregate_index, secret_index): (Vec<usize>, usize, usize)| {
+            Session::new(
+                &ctx(&key_indices)?,
+                &aggregate_nonces[aggregate_index],
+                &message,
+            )?
+            .partial_sign(
+                SecretNonce::from_bytes(&secret_nonces[secret_index])?,
+                &key_pair,
+            )
+        },
+    );
+
+    let tests = vec![
+        TestCase {
+            name: "wrong signature",
+            payload: (
+                "FED54434AD4CFE953FC527DC6A5E5BE8F6234907B7C187559557CE87A0541C46",
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                0,
+            ),
+            criteria: Some(KeysError::ErrMuSigPartialSignatureInvalid),
+        },
+        TestCase {
+            name: "wrong signer",
+            payload: (
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                1,
+            ),
+            criteria: Some(KeysError::ErrMuSigPartialSignatureInvalid),
+        },
+        TestCase {
+            name: "signature exceeds the group size",
+            payload: (
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                0,
+            ),
+            criteria: Some(KeysError::ErrMuSigPartialSignatureInvalid),
+        },
+        TestCase {
+            name: "invalid public nonce",
+            payload: (
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+                vec![0, 1, 2],
+                vec![4, 1, 2],
+                0,
+            ),
+            criteria: Some(KeysError::ErrMuSigNonceInvalid),
+        },
+        TestCase {
+            name: "invalid public key",
+            payload: (
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+                vec![3, 1, 2],
+                vec![0, 1, 2],
+                0,
+            ),
+            criteria: Some(KeysError::ErrPubKeyNotOnCurve),
+        },
+    ];
+    TestCase::run_err_match(
+        tests,
+        |(signature, key_indices, nonce_indices, signer): (&str, Vec<usize>, Vec<usize>, usize)| {
+            let nonces = nonce_indices
+                .iter()
+                .map(|i| public_nonces[*i])
+                .collect::<Vec<_>>();
+            Session::new(
+                &ctx(&key_indices)?,
+                &AggregateNonce::new(&nonces)?,
+                &message,
+            )?
+            .partial_verify(
+                &PartialSignature(bytes(signature)),
+                &nonces[signer],
+                &keys[key_indices[signer]],
+            )
+        },
+    );
+}
+
+/// The BIP-327 tweak test vectors.
+#[test]
+fn test_tweak_vectors() {
+    let key_pair = KeyPair::import_private_key(
+        "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671".into(),
+        CurveType::Secp256k1,
+    )
+    .unwrap();
+    let secret_nonce = decode_from_hex_string(
+        "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F703935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"
+            .to_string(),
+    )
+    .unwrap();
+    let public_nonce = public_nonce(
+        "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+    );
+    let aggregate_nonce = AggregateNonce(bytes(
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+    ));
+    let message = bytes::<32>("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+    // The signer's key is last.
+    let keys = [
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+    ]
+    .map(public_key);
+    let tweaks = [
+        "E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB",
+        "AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455",
+        "F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0",
+        "1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+    ]
+    .map(bytes::<32>);
+
+    let sign = |applied: Tweaks| {
+        let mut ctx = KeyAggContext::new(&keys)?;
+        for (i, is_xonly) in applied {
+            ctx = if is_xonly {
+                ctx.with_xonly_tweak(&tweaks[i])?
+            } else {
+                ctx.with_plain_tweak(&tweaks[i])?
+            };
+        }
+        let session = Session::new(&ctx, &aggregate_nonce, &message)?;
+        let signature = session.partial_sign(SecretNonce::from_bytes(&secret_nonce)?, &key_pair)?;
+        session.partial_verify(&signature, &public_nonce, &keys[2])?;
+        Ok::<_, KeysError>(signature)
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "x-only tweak",
+            payload: vec![(0, true)],
+            criteria: Some(PartialSignature(bytes(
+                "E28A5C66E61E178C2BA19DB77B6CF9F7E2F0F56C17918CD13135E60CC848FE91",
+            ))),
+        },
+        TestCase {
+            name: "plain tweak",
+            payload: vec![(0, false)],
+            criteria: Some(PartialSignature(bytes(
+                "38B0767798252F21BF5702C48028B095428320F73A4B14DB1E25DE58543D2D2D",
+            ))),
+        },
+        TestCase {
+            name: "plain and x-only tweaks",
+            payload: vec![(0, false), (1, true)],
+            criteria: Some(PartialSignature(bytes(
+                "408A0A21C4A0F5DACAF9646AD6EB6FECD7F7A11F03ED1F48DFFF2185BC2C2408",
+            ))),
+        },
+        TestCase {
+            name: "four tweaks",
+            payload: vec![(0, false), (1, false), (2, true), (3, true)],
+            criteria: Some(PartialSignature(bytes(
+                "45ABD206E61E3DF2EC9E264A6FEC8292141A633C28586388235541F9ADE75435",
+            ))),
+        },
+        TestCase {
+            name: "alternating tweaks",
+            payload: vec![(0, true), (1, false), (2, true), (3, false)],
+            criteria: Some(PartialSignature(bytes(
+                "B255FDCAC27B40C7CE7848E2D3B7BF5EA0ED756DA81565AC804CCCA3E1D5D239",
+            ))),
+        },
+    ];
+    TestCase::run_ok_match(tests, sign);
+
+    let tests = vec![TestCase {
+        name: "tweak exceeds the group size",
+        payload: vec![(4, false)],
+        criteria: Some(KeysError::ErrMuSigKeyAggInvalid),
+    }];
+    TestCase::run_err_match(tests, sign);
+}
+
+/// The BIP-327 signature aggregation test vectors.
+#[test]
+fn test_sig_agg_vectors() {
+    let keys = [
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "02D2DC6F5DF7C56ACF38C7FA0AE7A759AE30E19B37359DFDE015872324C7EF6E05",
+        "03C7FB101D97FF930ACD0C6760852EF64E69083DE0B06AC6335724754BB4B0522C",
+        "02352433B21E7E05D3B452B81CAE566E06D2E003ECE16D1074AABA4289E0E3D581",
+    ]
+    .map(public_key);
+    let tweaks = [
+        "B511DA492182A91B0FFB9A98020D55F260AE86D7ECBD0399C7383D59A5F2AF7C",
+        "A815FE049EE3C5AAB66310477FBC8BCCCAC2F3395F59F921C364ACD78A2F48DC",
+        "75448A87274B056468B977BE06EB1E9F657577B7320B0A3376EA51FD420D18A8",
+    ]
+    .map(bytes::<32>);
+    let partial_signatures = [
+        "B15D2CD3C3D22B04DAE438CE653F6B4ECF042F42CFDED7C41B64AAF9B4AF53FB",
+        "6193D6AC61B354E9105BBDC8937A3454A6D705B6D57322A5A472A02CE99FCB64",
+        "9A87D3B79EC67228CB97878B76049B15DBD05B8158D17B5B9114D3C226887505",
+        "66F82EA90923689B855D36C6B7E032FB9970301481B99E01CDB4D6AC7C347A15",
+        "4F5AEE41510848A6447DCD1BBC78457EF69024944C87F40250D3EF2C25D33EFE",
+        "DDEF427BBB847CC027BEFF4EDB01038148917832253EBC355FC33F4A8E2FCCE4",
+        "97B890A26C981DA8102D3BC294159D171D72810FDF7C6A691DEF02F0F7AF3FDC",
+        "53FA9E08BA5243CBCB0D797C5EE83BC6728E539EB76C2D0BF0F971EE4E909971",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+    ]
+    .map(|hex| PartialSignature(bytes(hex)));
+    let message = bytes::<32>("599C67EA410D005B9DA90817CF03ED3B1C868E4DA4EDF00A5880B0082C237869");
+
+    let aggregate = |(key_indices, applied, aggregate_nonce, signature_indices): (
+        [usize; 2],
+        Tweaks,
+        &str,
+        [usize; 2],
+    )| {
+        let mut ctx = KeyAggContext::new(&key_indices.map(|i| keys[i].clone()))?;
+        for (i, is_xonly) in applied {
+            ctx = if is_xonly {
+                ctx.with_xonly_tweak(&tweaks[i])?
+            } else {
+                ctx.with_plain_tweak(&tweaks[i])?
+            };
+        }
+        Session::new(&ctx, &AggregateNonce(bytes(aggregate_nonce)), &message)?
+            .aggregate(&signature_indices.map(|i| partial_signatures[i]))
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "no tweaks",
+            payload: (
+                [0, 1],
+                vec![],
+                "0341432722C5CD0268D829C702CF0D1CBCE57033EED201FD335191385227C3210C03D377F2D258B64AADC0E16F26462323D701D286046A2EA93365656AFD9875982B",
+                [0, 1],
+            ),
+            criteria: Some(bytes(
+                "041DA22223CE65C92C9A0D6C2CAC828AAF1EEE56304FEC371DDF91EBB2B9EF0912F1038025857FEDEB3FF696F8B99FA4BB2C5812F6095A2E0004EC99CE18DE1E",
+            )),
+        },
+        TestCase {
+            name: "other signers",
+            payload: (
+                [0, 2],
+                vec![],
+                "0224AFD36C902084058B51B5D36676BBA4DC97C775873768E58822F87FE437D792028CB15929099EEE2F5DAE404CD39357591BA32E9AF4E162B8D3E7CB5EFE31CB20",
+                [2, 3],
+            ),
+            criteria: Some(bytes(
+                "1069B67EC3D2F3C7C08291ACCB17A9C9B8F2819A52EB5DF8726E17E7D6B52E9F01800260A7E9DAC450F4BE522DE4CE12BA91AEAF2B4279219EF74BE1D286ADD9",
+            )),
+        },
+        TestCase {
+            name: "plain tweak",
+            payload: (
+                [0, 2],
+                vec![(0, false)],
+                "0208C5C438C710F4F96A61E9FF3C37758814B8C3AE12BFEA0ED2C87FF6954FF186020B1816EA104B4FCA2D304D733E0E19CEAD51303FF6420BFD222335CAA402916D",
+                [4, 5],
+            ),
+            criteria: Some(bytes(
+                "5C558E1DCADE86DA0B2F02626A512E30A22CF5255CAEA7EE32C38E9A71A0E9148BA6C0E6EC7683B64220F0298696F1B878CD47B107B81F7188812D593971E0CC",
+            )),
+        },
+        TestCase {
+            name: "mixed tweaks",
+            payload: (
+                [0, 3],
+                vec![(0, true), (1, false), (2, true)],
+                "02B5AD07AFCD99B6D92CB433FBD2A28FDEB98EAE2EB09B6014EF0F8197CD58403302E8616910F9293CF692C49F351DB86B25E352901F0E237BAFDA11F1C1CEF29FFD",
+                [6, 7],
+            ),
+            criteria: Some(bytes(
+                "839B08820B681DBA8DAF4CC7B104E8F2638F9388F8D7A555DC17B6E6971D7426CE07BF6AB01F1DB50E4E33719295F4094572B79868E440FB3DEFD3FAC1DB589E",
+            )),
+        },
+    ];
+    TestCase::run_ok_match(tests, aggregate);
+
+    let tests = vec![TestCase {
+        name: "signature exceeds the group size",
+        payload: (
+            [0, 3],
+            vec![(0, true), (1, false), (2, true)],
+            "02B5AD07AFCD99B6D92CB433FBD2A28FDEB98EAE2EB09B6014EF0F8197CD58403302E8616910F9293CF692C49F351DB86B25E352901F0E237BAFDA11F1C1CEF29FFD",
+            [7, 8],
+        ),
+        criteria: Some(KeysError::ErrMuSigPartialSignatureInvalid),
+    }];
+    TestCase::run_err_match(tests, aggregate);
+}
@@ -0,0 +1,191 @@
+//! The two Schnorr signature schemes over `Secp256k1`.
+//!
+//! - `Schnorr1` is the Zilliqa scheme Rosetta calls `schnorr_1`. The signature
+//!   is `r || s` where `r = SHA256(Q || P || m)` for the nonce commitment `Q =
+//!   kG` and the compressed public key `P`, and `s = k - r * d`.
+//! - `SchnorrBip340` is [BIP-340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki)
+//!   with tagged hashes and x-only public keys, as used by taproot. The nonce
+//!   is derived from the key, the message and 32 bytes of auxiliary randomness,
+//!   see [`Bip340Mode`].
+
+use ::secp256k1::{schnorr, KeyPair as SecpKeyPair, Message, Secp256k1, XOnlyPublicKey};
+use k256::{
+    elliptic_curve::{bigint::U256, ops::Reduce, sec1::ToEncodedPoint, Field, PrimeField},
+    ProjectivePoint,
+    Scalar,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::{KeysError, KeysResult};
+
+/// The length of a private key, a scalar or an x-only public key.
+const SCALAR_LEN: usize = 32;
+/// The length of a Schnorr signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// `Bip340Mode` selects the auxiliary randomness mixed into the nonce of a
+/// BIP-340 signature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bip340Mode {
+    /// 32 fresh random bytes per signature, as recommended by BIP-340 to
+    /// protect against side channel attacks.
+    #[default]
+    Randomized,
+    /// 32 zero bytes, so signing the same message twice gives the same
+    /// signature.
+    Deterministic,
+}
+
+impl Bip340Mode {
+    /// The auxiliary randomness of a signature.
+    pub fn aux_rand(self) -> [u8; SCALAR_LEN] {
+        let mut aux_rand = [0; SCALAR_LEN];
+        if self == Self::Randomized {
+            rand::thread_rng().fill_bytes(&mut aux_rand);
+        }
+        aux_rand
+    }
+}
+
+/// Parses a private key as a non zero scalar.
+fn private_key_scalar(private_key: &[u8]) -> KeysResult<Scalar> {
+    let bytes: [u8; SCALAR_LEN] = private_key
+        .try_into()
+        .map_err(|_| KeysError::ErrPrivKeyLengthInvalid)?;
+    Option::<Scalar>::from(Scalar::from_repr(bytes.into()))
+        .filter(|d| !bool::from(d.is_zero()))
+        .ok_or(KeysError::ErrPrivKeyUndecodable)
+}
+
+/// Parses 32 big endian bytes as a non zero scalar smaller than the curve
+/// order.
+fn parse_nonzero_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; SCALAR_LEN] = bytes.try_into().ok()?;
+    Option::<Scalar>::from(Scalar::from_repr(bytes.into())).filter(|s| !bool::from(s.is_zero()))
+}
+
+/// Serializes a point in compressed form.
+fn compressed(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// The `Schnorr1` challenge `SHA256(Q || P || m)` reduced modulo the curve
+/// order.
+fn schnorr1_challenge(commitment: &[u8], public_key: &[u8], message: &[u8]) -> Scalar {
+    let hash: [u8; SCALAR_LEN] = Sha256::new()
+        .chain_update(commitment)
+        .chain_update(public_key)
+        .chain_update(message)
+        .finalize()
+        .into();
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(hash.into())
+}
+
+/// `sign_schnorr1` signs `message` with the Zilliqa Schnorr scheme using a
+/// random nonce. Unlike the other `Secp256k1` schemes the message may have
+/// any length.
+pub fn sign_schnorr1(private_key: &[u8], message: &[u8]) -> KeysResult<[u8; SIGNATURE_LEN]> {
+    let d = private_key_scalar(private_key)?;
+    loop {
+        let k = Scalar::random(&mut rand::thread_rng());
+        if let Some(signature) = sign_schnorr1_with_nonce(&d, &k, message) {
+            return Ok(signature);
+        }
+    }
+}
+
+/// Signs with the nonce `k`, returning `None` if the nonce gives a zero `r`
+/// or `s` and a new one must be drawn.
+pub(crate) fn sign_schnorr1_with_nonce(
+    d: &Scalar,
+    k: &Scalar,
+    message: &[u8],
+) -> Option<[u8; SIGNATURE_LEN]> {
+    if bool::from(k.is_zero()) {
+        return None;
+    }
+
+    let public_key = compressed(&(ProjectivePoint::GENERATOR * d));
+    let commitment = compressed(&(ProjectivePoint::GENERATOR * k));
+    let r = schnorr1_challenge(&commitment, &public_key, message);
+    let s = *k - r * d;
+    if bool::from(r.is_zero()) || bool::from(s.is_zero()) {
+        return None;
+    }
+
+    let mut signature = [0; SIGNATURE_LEN];
+    signature[..SCALAR_LEN].copy_from_slice(&r.to_bytes());
+    signature[SCALAR_LEN..].copy_from_slice(&s.to_bytes());
+    Some(signature)
+}
+
+/// `verify_schnorr1` returns whether `signature` is a valid Zilliqa Schnorr
+/// signature of `message` by the compressed or uncompressed `public_key`.
+pub fn verify_schnorr1(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match k256::PublicKey::from_sec1_bytes(public_key) {
+        Ok(public_key) => public_key.to_projective(),
+        Err(_) => return false,
+    };
+    if signature.len() != SIGNATURE_LEN {
+        return false;
+    }
+    let (r, s) = match (
+        parse_nonzero_scalar(&signature[..SCALAR_LEN]),
+        parse_nonzero_scalar(&signature[SCALAR_LEN..]),
+    ) {
+        (Some(r), Some(s)) => (r, s),
+        _ => return false,
+    };
+
+    let commitment = ProjectivePoint::GENERATOR * s + public_key * r;
+    if commitment == ProjectivePoint::IDENTITY {
+        return false;
+    }
+    schnorr1_challenge(&compressed(&commitment), &compressed(&public_key), message) == r
+}
+
+/// `sign_bip340` signs the 32 byte `message` as defined by BIP-340 with the
+/// auxiliary randomness `aux_rand`.
+pub fn sign_bip340(
+    private_key: &[u8],
+    message: &[u8],
+    aux_rand: &[u8; SCALAR_LEN],
+) -> KeysResult<[u8; SIGNATURE_LEN]> {
+    let secp = Secp256k1::new();
+    let msg =
+        Message::from_slice(message).map_err(|err| format!("failed to sign for bip340: {err}"))?;
+    let key_pair = SecpKeyPair::from_seckey_slice(&secp, private_key)
+        .map_err(|_| KeysError::ErrPrivKeyUndecodable)?;
+
+    Ok(*secp
+        .sign_schnorr_with_aux_rand(&msg, &key_pair, aux_rand)
+        .as_ref())
+}
+
+/// `verify_bip340` returns whether `signature` is a valid BIP-340 signature
+/// of the 32 byte `message` by `public_key`. The key may be given x-only or
+/// as a compressed or uncompressed point, of which only the x coordinate is
+/// used.
+pub fn verify_bip340(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match public_key.len() {
+        SCALAR_LEN => XOnlyPublicKey::from_slice(public_key).ok(),
+        _ => ::secp256k1::PublicKey::from_slice(public_key)
+            .ok()
+            .map(|key| key.x_only_public_key().0),
+    };
+    let (public_key, msg, sig) = match (
+        public_key,
+        Message::from_slice(message),
+        schnorr::Signature::from_slice(signature),
+    ) {
+        (Some(public_key), Ok(msg), Ok(sig)) => (public_key, msg, sig),
+        _ => return false,
+    };
+
+    Secp256k1::verification_only()
+        .verify_schnorr(&sig, &msg, &public_key)
+        .is_ok()
+}
@@ -0,0 +1,360 @@
+use k256::{elliptic_curve::PrimeField, Scalar};
+use mentat_test_utils::TestCase;
+use mentat_types::{
+    decode_from_hex_string,
+    encode_to_hex_string,
+    AccountIdentifier,
+    CurveType,
+    SignatureType,
+    SigningPayload,
+};
+
+use crate::{
+    schnorr::{
+        sign_bip340,
+        sign_schnorr1,
+        sign_schnorr1_with_nonce,
+        verify_bip340,
+        verify_schnorr1,
+        Bip340Mode,
+    },
+    types::KeyPair,
+    Signer,
+    SignerInterface,
+    Verifier,
+};
+
+fn bytes(hex: &str) -> Vec<u8> {
+    decode_from_hex_string(hex.into()).unwrap()
+}
+
+fn scalar(hex: &str) -> Scalar {
+    let bytes: [u8; 32] = bytes(hex).try_into().unwrap();
+    Scalar::from_repr(bytes.into()).unwrap()
+}
+
+/// A BIP-340 signing test vector.
+struct SignVector {
+    secret_key: &'static str,
+    public_key: &'static str,
+    aux_rand: &'static str,
+    message: &'static str,
+}
+
+/// A BIP-340 verification test vector.
+struct VerifyVector {
+    public_key: &'static str,
+    message: &'static str,
+    signature: &'static str,
+}
+
+/// The signing vectors 0-3 of the BIP-340 `test-vectors.csv`.
+fn bip340_sign_vectors() -> Vec<TestCase<SignVector, String>> {
+    vec![
+        TestCase {
+            name: "vector 0",
+            payload: SignVector {
+                secret_key: "0000000000000000000000000000000000000000000000000000000000000003",
+                public_key: "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                aux_rand: "0000000000000000000000000000000000000000000000000000000000000000",
+                message: "0000000000000000000000000000000000000000000000000000000000000000",
+            },
+            criteria: "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0".into(),
+        },
+        TestCase {
+            name: "vector 1",
+            payload: SignVector {
+                secret_key: "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+                public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                aux_rand: "0000000000000000000000000000000000000000000000000000000000000001",
+                message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            },
+            criteria: "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A".into(),
+        },
+        TestCase {
+            name: "vector 2",
+            payload: SignVector {
+                secret_key: "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+                public_key: "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+                aux_rand: "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+                message: "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+            },
+            criteria: "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7".into(),
+        },
+        TestCase {
+            name: "vector 3 (message is not reduced modulo p or n)",
+            payload: SignVector {
+                secret_key: "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+                public_key: "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+                aux_rand: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                message: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            },
+            criteria: "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3".into(),
+        },
+    ]
+}
+
+#[test]
+fn test_bip340_sign_vectors() {
+    TestCase::run_output_match(bip340_sign_vectors(), |v| {
+        let aux_rand: [u8; 32] = bytes(v.aux_rand).try_into().unwrap();
+        let signature = sign_bip340(&bytes(v.secret_key), &bytes(v.message), &aux_rand).unwrap();
+        assert!(verify_bip340(
+            &bytes(v.public_key),
+            &bytes(v.message),
+            &signature
+        ));
+        encode_to_hex_string(&signature).to_uppercase()
+    });
+}
+
+#[test]
+fn test_bip340_verify_vectors() {
+    let verify_vector = |public_key, message, signature| VerifyVector {
+        public_key,
+        message,
+        signature,
+    };
+    let tests = vec![
+        TestCase {
+            name: "vector 4",
+            payload: verify_vector(
+                "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+                "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+                "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4",
+            ),
+            criteria: true,
+        },
+        TestCase {
+            name: "vector 5 (public key not on the curve)",
+            payload: verify_vector(
+                "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 6 (has_even_y(R) is false)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 7 (negated message)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 8 (negated s value)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 9 (sG - eP is infinite, x(inf) as 0)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 10 (sG - eP is infinite, x(inf) as 1)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 11 (sig[0:32] is not an x coordinate on the curve)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 12 (sig[0:32] is equal to the field size)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 13 (sig[32:64] is equal to the curve order)",
+            payload: verify_vector(
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            ),
+            criteria: false,
+        },
+        TestCase {
+            name: "vector 14 (public key exceeds the field size)",
+            payload: verify_vector(
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            ),
+            criteria: false,
+        },
+    ];
+
+    TestCase::run_output_match(tests, |v| {
+        verify_bip340(&bytes(v.public_key), &bytes(v.message), &bytes(v.signature))
+    });
+}
+
+#[test]
+fn test_bip340_modes() {
+    let message = [7; 32];
+    let signer = |mode| {
+        KeyPair::generate(CurveType::Secp256k1)
+            .unwrap()
+            .signer()
+            .unwrap()
+            .with_bip340_mode(mode)
+    };
+    let sign = |signer: &Signer| {
+        let payload = SigningPayload {
+            account_identifier: Some(AccountIdentifier {
+                address: "test".into(),
+                ..Default::default()
+            }),
+            bytes: message.to_vec(),
+            signature_type: SignatureType::SchnorrBip340,
+            ..Default::default()
+        };
+        let mut signature = signer.sign(payload, SignatureType::SchnorrBip340).unwrap();
+        signature.signature_type = SignatureType::SchnorrBip340;
+        Verifier::verify(&signature.clone().into()).unwrap();
+        signature.bytes
+    };
+
+    let deterministic = signer(Bip340Mode::Deterministic);
+    assert_eq!(sign(&deterministic), sign(&deterministic));
+
+    let randomized = signer(Bip340Mode::Randomized);
+    assert_ne!(sign(&randomized), sign(&randomized));
+}
+
+/// A `Schnorr1` signing test vector with a fixed nonce.
+struct Schnorr1Vector {
+    secret_key: &'static str,
+    public_key: &'static str,
+    nonce: &'static str,
+    message: &'static str,
+}
+
+/// The signing fixtures of the Zilliqa reference implementation, from
+/// `packages/zilliqa-js-crypto/test/schnorr.spec.ts` in zilliqa-js.
+fn schnorr1_sign_vectors() -> Vec<TestCase<Schnorr1Vector, String>> {
+    vec![
+        TestCase {
+            name: "fixture 0",
+            payload: Schnorr1Vector {
+                secret_key: "0F494B8312E8D257E51730C78F8FE3B47B6840C59AAAEC7C2EBE404A2DE8B25A",
+                public_key: "039E43C9810E6CC09F46AAD38E716DAE3191629534967DC457D3A687D2E2CDDC6A",
+                nonce: "532B2267C4A3054F380B3357339BDFB379E88366FE61B42ACA05F69BC3F6F54E",
+                message: "A7F1D92A82C8D8FE434D98558CE2B347171198542F112D0558F56BD68807999248336241F30D23E55F30D1C8ED610C4B0235398184B814A29CB45A672ACAE548E9C5F1B0C4158AE59B4D39F6F7E8A105D3FEEDA5D5F3D9E45BFA6CC351E220AE0CE106986D61FF34A11E19FD3650E9B7818FC33A1E0FC02C44557AC8AB50C9B2DEB2F6B5E24C4FDD9F8867BDCE1FF261008E7897970E346207D75E47A158298E5BA2F56246869CC42E362A02731264E60687EF5309D108534F51F8658FB4F080B7CB19EE9AEBD718CC4FA27C8C37DFC1ADA5D133D13ABE03F021E9B1B78CCBD82F7FF2B38C6D48D01E481B2D4FAF7171805FD7F2D39EF4C4F19B9496E81DAB8193B3737E1B27D9C43957166441B93515E8F03C95D8E8CE1E1864FAAD68DDFC5932130109390B0F1FE5CA716805F8362E98DCCAADC86ADBED25801A9A9DCFA6264319DDAFE83A89C51F3C6D199D38DE10E660C37BE872C3F2B31660DE8BC95902B9103262CDB941F77376F5D3DBB7A3D5A387797FC4819A035ECA704CEDB37110EE7F206B0C8805AAEBF4963E7C4708CE8D4E092366E71792A8A3B2BBCDEE321B3E15380C541EF0930888969F7457AFE18588826A419D58311C1784B5484EECDB393F6A0ACA11B91DF0866B500B8DEE501FD7EB9BCE09A17D74124B4605ADFC0777BED9816D8D7E8488544A18D8045CB3283B0A752B881B5F500FADB59010E63D",
+            },
+            criteria: "3AF3D288E830E96FF8ED0769F45ABDA774CD989E2AE32EF9E985C8505F14FF98E191EB14A70B5B53ADA45AFFF4A04578F5D8BB2B1C8A22985EA159B53826CDE7".into(),
+        },
+        TestCase {
+            name: "fixture 1",
+            payload: Schnorr1Vector {
+                secret_key: "8D566BB87EF69FFDA622E0A59FBAAFE57F486CE65844343A5D9B97DE9C4F619A",
+                public_key: "0245DC2911EDC02F2774E0A40FBEB0112EA60BF513F9EC50889D59FC94C97EC18F",
+                nonce: "948AFFFF6E068CA2F2757BFD6085D6E4C3084B038E5533C5927ECB19EA0D329C",
+                message: "1B664F8BDA2DBF33CB6BE21C8EB3ECA9D9D5BF144C08E9577ED0D1E5E560875109B340980580473DBC2E689A3BE838E77A0A3348FE960EC9BF81DA36F1868CA5D24788FA4C0C778BF0D12314285495636516CF40861B3D737FD35DBB591C5B5D25916EB1D86176B14E0E67D2D03957F0CF6C87834BF328540588360BA7C7C5F88541634FB7BADE5F94FF671D1FEBDCBDA116D2DA779038ED7679896C29198B2657B58C50EA054F644F4129C8BA8D8D544B727633DD40754398046796E038626FEF9237CE5B615BC08677EE5ABFBD85F73F7F8868CB1B5FBA4C1309F16061AA133821FBE2A758D2BBE6AA040A940D41B7D3B869CEE945150AA4A40E6FF719EEC24B2681CD5CE06B50273436584066046656D5EFED7315759189D68815DDB9E5F8D7FD53B6EC096616A773B9421F6704CED36EF4E484BA0C6C5A4855C71C33A54AC82BE803E5CFD175779FC444B7E6AA9001EEFABEBC0CF99754887C7B0A27AFDDC415F8A02C5AF1EFEA26AD1E5D92B1E29A8FAF5B2186C3094F4A137BCFAA65D7B274214DB64C86F3085B24938E1832FB310A6F064181E298D23062ABC817BA173023C8C04C5C3A1ECBF4AF72372B381FF69865C8F0E3C70B931C45A7419B3C441842EBFACC3D070AC3B433CD120B6E85B72DADCF40B23B173C34F6BE1B1901F6621F1497B085CF8E999D986EF8FF3A889A0238979983A8686F69E10EF9249A87",
+            },
+            criteria: "DFEE66E2C4799E73F0F778126A23032608408C27C2E7B3FA45A626BB9BDEB53C75445CC9DBFE4E7BC64E020FA22CACFA4C40D5AA84DD6AEF661564FCA9746C40".into(),
+        },
+        TestCase {
+            name: "fixture 2",
+            payload: Schnorr1Vector {
+                secret_key: "009755F442D66585A10B80A49850C77764AD029D1BEA73F4DA45AB331306E6E5",
+                public_key: "02237627FE7374061FBD80AEA842DCE76D9206F0DDC7B319F3B30FA75DBD4F009A",
+                nonce: "2D78C77B736AD0A00FDF60695C01E96520656C13DC890A5B864672C6CED1C49A",
+                message: "3444C8501F19A8A78670F748FA401C4020AE086D7157A3837EC721DEF0D6E095928C5B78ED9B95560CE33D5B22778BE66DCEF2D21878D481DFF41A4DEDCAFDCAEAB4BD78629D7EC40FD26F1DD954CA84A3B53B84E9903056E840837A1390F37BB8ADE799DAC1E465D811916547EB4B6A163082E9833634A1224C54F681B8DC70A792C0CB4671D4970CCC80E2168CE920CC8FA07B1F90E9898D16019913ED5B8EE8A8DE7AB6F7895601FD20E49FD73E6F5D24C0D97E67871539F0E4E32CCB6677AFF03356D1F3790945E94039E51A63B3C840B74E3053D95CA71C0D3AC20A9065828D30AB5BFB6188A8F291FB1EB4E1EED03E2F5F558C00D8E3084120DEEB8BFE908429B36A896A45D624E79372CC18DF37DB2D20C9726D4FEF7BECF220138B53BC54C2DA461A9955AFF33F2F93DD96464BF3E883FC5750BDBE79BC2F82427F41DE42659AC4B111D7CEF8085003469DF8C9D3541480C6841707CE4C8F3D003AF982AD35C2733D0FA3B1EE52A6DAB36203D99AEC179A565B5050F480235C3BC560AA28EF5DD5525BFA254E584A86FDBD4BCC5B56551BAD00255CB72F806D7F3C533321B0864007AFBA4E0FF9638517FA8D788F52766F3A28C57C428BFDD4234AA760CE8044DF1E1FBA58E8B1D9C5A79D2AC4592FC31702F7E83351D2160C09C5CEA554F2C93A61C040E225612DF2B550900B097E18638350E3BA15C9AD53CE1861",
+            },
+            criteria: "4B73D4D919D7B4DEF330391899EA02023851CABE044E34E18EAE3E10588CECCDD5DE85C4BDEA5910DC36AEF5660774D65291322C1E87FDA0D00C864E8C5FED29".into(),
+        },
+    ]
+}
+
+#[test]
+fn test_schnorr1_sign_vectors() {
+    TestCase::run_output_match(schnorr1_sign_vectors(), |v| {
+        let signature =
+            sign_schnorr1_with_nonce(&scalar(v.secret_key), &scalar(v.nonce), &bytes(v.message))
+                .unwrap();
+        assert!(verify_schnorr1(
+            &bytes(v.public_key),
+            &bytes(v.message),
+            &signature
+        ));
+        encode_to_hex_string(&signature).to_uppercase()
+    });
+}
+
+#[test]
+fn test_schnorr1_is_not_bip340() {
+    let key_pair = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let message = [7; 32];
+
+    let schnorr1 = sign_schnorr1(&key_pair.private_key, &message).unwrap();
+    let bip340 = sign_bip340(&key_pair.private_key, &message, &[0; 32]).unwrap();
+
+    assert!(verify_schnorr1(
+        &key_pair.public_key.bytes,
+        &message,
+        &schnorr1
+    ));
+    assert!(!verify_bip340(
+        &key_pair.public_key.bytes,
+        &message,
+        &schnorr1
+    ));
+    assert!(verify_bip340(&key_pair.public_key.bytes, &message, &bip340));
+    assert!(!verify_schnorr1(
+        &key_pair.public_key.bytes,
+        &message,
+        &bip340
+    ));
+}
+
+#[test]
+fn test_schnorr1_any_message_length() {
+    let key_pair = KeyPair::generate(CurveType::Secp256k1).unwrap();
+    let message = b"a zilliqa transaction is signed without hashing it first";
+
+    let signature = sign_schnorr1(&key_pair.private_key, message).unwrap();
+    assert!(verify_schnorr1(
+        &key_pair.public_key.bytes,
+        message,
+        &signature
+    ));
+    assert!(!verify_schnorr1(
+        &key_pair.public_key.bytes,
+        &message[1..],
+        &signature
+    ));
+}
@@ -1,4 +1,4 @@
-use ed25519_compact::{KeyPair as EdKeyPair, Seed, Signature as EdSignature};
+use ed25519_compact::{KeyPair as EdKeyPair, Seed};
 use mentat_types::{
     PublicKey,
     Signature,
@@ -71,17 +71,6 @@ impl SignerInterface for SignerEdwards25519 {
             ))?;
         }
 
-        mentat_asserter::signatures(&[Some(&signature)])
-            .map_err(|err| format!("signature is invalid: {err}"))?;
-        let signature: Signature = signature.into();
-
-        let pk = ed25519_compact::PublicKey::from_slice(&signature.public_key.bytes)
-            .map_err(|_| KeysError::ErrVerifyFailed)?;
-        let ed_signature =
-            EdSignature::from_slice(&signature.bytes).map_err(|_| KeysError::ErrVerifyFailed)?;
-        pk.verify(&signature.signing_payload.bytes, &ed_signature)
-            .map_err(|_| KeysError::ErrVerifyFailed)?;
-
-        Ok(())
+        Verifier::verify(&signature)
     }
 }
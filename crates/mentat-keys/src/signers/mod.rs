@@ -2,7 +2,7 @@ mod edwards25519;
 pub use edwards25519::SignerEdwards25519;
 
 mod pallas;
-pub use self::pallas::SignerPallas;
+pub use self::pallas::{PallasPublicKey, SignerPallas};
 
 mod secp256k1;
 pub use self::secp256k1::*;
@@ -15,8 +15,8 @@ pub use signer::*;
 
 use crate::{
     errors::{KeysError, KeysResult},
-    signers::signer::SignerInterface,
     types::{KeyPair, UncheckedKeyPair},
+    verifier::Verifier,
 };
 
 #[cfg(test)]
@@ -6,11 +6,126 @@ use mentat_types::{
     UncheckedSignature,
     UncheckedSignatureType,
 };
+use pasta_curves::{
+    arithmetic::CurveAffine,
+    group::{ff::PrimeField, Curve, GroupEncoding},
+    pallas,
+};
 use serde::Deserialize;
 
 use super::*;
 
+/// The base58check version byte of a Mina address.
+const ADDRESS_VERSION: u8 = 0xcb;
+/// The version bytes of a compressed curve point within an address.
+const ADDRESS_POINT_VERSION: [u8; 2] = [0x01, 0x01];
+/// The length of a decoded address without its version byte and checksum.
+const ADDRESS_PAYLOAD_LEN: usize = 35;
+/// The longest memo a Mina transaction can carry.
+const MAX_MEMO_LEN: usize = 32;
+/// The y coordinate of the Mina generator `(1, y)`.
+const GENERATOR_Y: &str =
+    "12418654782883325593414442427049395787963493412651469444558597405572177144507";
+
+/// `PallasPublicKey` is a point on the Pallas curve used by Mina.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PallasPublicKey(pallas::Affine);
+
+impl PallasPublicKey {
+    /// `from_private_key` returns the public key of a 32 byte little endian
+    /// private key.
+    pub fn from_private_key(private_key: &[u8]) -> KeysResult<Self> {
+        let private_key = <[u8; 32]>::try_from(private_key).map_err(|_| {
+            format!(
+                "expected 32 bytes for private key but got {}: {}",
+                private_key.len(),
+                KeysError::ErrPrivKeyLengthInvalid
+            )
+        })?;
+        let scalar = Option::<pallas::Scalar>::from(pallas::Scalar::from_repr(private_key))
+            .ok_or_else(|| {
+                format!(
+                    "private key is not a pallas scalar: {}",
+                    KeysError::ErrPrivKeyUndecodable
+                )
+            })?;
+
+        Ok(Self((generator() * scalar).to_affine()))
+    }
+
+    /// `from_bytes` parses the 32 byte compressed form returned by
+    /// [`PallasPublicKey::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> KeysResult<Self> {
+        let bytes = <[u8; 32]>::try_from(bytes).map_err(|_| KeysError::ErrPubKeyNotOnCurve)?;
+        Option::from(pallas::Affine::from_bytes(&bytes))
+            .map(Self)
+            .ok_or(KeysError::ErrPubKeyNotOnCurve)
+    }
+
+    /// `to_bytes` returns the little endian x coordinate with the parity of y
+    /// in the most significant bit.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes().to_vec()
+    }
+
+    /// `from_address` parses a base58check `B62` Mina address.
+    pub fn from_address(address: &str) -> KeysResult<Self> {
+        let invalid = || -> KeysError {
+            format!(
+                "address {address} is invalid: {}",
+                KeysError::ErrPallasAddressInvalid
+            )
+            .into()
+        };
+
+        let decoded = bs58::decode(address)
+            .with_check(Some(ADDRESS_VERSION))
+            .into_vec()
+            .map_err(|_| invalid())?;
+        // The decoded bytes still include the version byte.
+        let payload = &decoded[1..];
+        if payload.len() != ADDRESS_PAYLOAD_LEN || payload[..2] != ADDRESS_POINT_VERSION {
+            return Err(invalid());
+        }
+
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&payload[2..34]);
+        match payload[34] {
+            0 => {}
+            1 => bytes[31] |= 0x80,
+            _ => return Err(invalid()),
+        }
+        Self::from_bytes(&bytes).map_err(|_| invalid())
+    }
+
+    /// `to_address` returns the base58check `B62` Mina address.
+    pub fn to_address(&self) -> String {
+        // Safe to unwrap, the identity is not a valid public key.
+        let coordinates = self.0.coordinates().unwrap();
+        let mut payload = ADDRESS_POINT_VERSION.to_vec();
+        payload.extend_from_slice(&coordinates.x().to_repr());
+        payload.push(coordinates.y().to_repr()[0] & 1);
+
+        bs58::encode(payload)
+            .with_check_version(ADDRESS_VERSION)
+            .into_string()
+    }
+}
+
+/// The generator of the Pallas group used by Mina.
+fn generator() -> pallas::Affine {
+    // Safe to unwrap, the constants are a point on the curve.
+    let y = pallas::Base::from_str_vartime(GENERATOR_Y).unwrap();
+    pallas::Affine::from_xy(pallas::Base::one(), y).unwrap()
+}
+
 /// `SignerPallas` is initialized from a `UncheckedKeyPair`.
+///
+/// Pallas keys can be generated, imported and encoded as Mina addresses, but
+/// `SchnorrPoseidon` signing and verification are not supported: they need
+/// the Poseidon parameters of Mina's legacy transactions, which no crate
+/// this workspace can depend on provides. Both return
+/// [`KeysError::ErrSchnorrPoseidonUnsupported`] once the request is checked.
 pub struct SignerPallas {
     pub key_pair: UncheckedKeyPair,
 }
@@ -48,7 +163,13 @@ impl SignerInterface for SignerPallas {
             ))?;
         }
 
-        unimplemented!("no good library exists for this in rust")
+        PallasSigningPayload::payment(&payload)
+            .map_err(|err| format!("failed to parse signing payload: {err}"))?;
+        // TODO: hash the payment with Mina's legacy Poseidon and sign it with
+        // SchnorrPoseidon, checked against the mina-signer test vectors. This
+        // needs the legacy Poseidon round constants and MDS matrix, which are
+        // not published in any crate this workspace can depend on.
+        Err(KeysError::ErrSchnorrPoseidonUnsupported)
     }
 
     fn verify(&self, signature: UncheckedSignature) -> KeysResult<()> {
@@ -61,82 +182,10 @@ impl SignerInterface for SignerPallas {
             ))?;
         }
 
-        mentat_asserter::signatures(&[Some(&signature)])
-            .map_err(|err| format!("signature is invalid: {err}"))?;
-        let _signature: Signature = signature.into();
-
-        unimplemented!("no good library exists for this in rust")
-
-        // Ok(())
+        Verifier::verify(&signature)
     }
 }
 
-#[allow(clippy::enum_variant_names)]
-#[derive(Debug, Clone)]
-#[repr(u8)]
-pub enum NetworkType {
-    TestNet,
-    // MainNet,
-    // NullNet,
-}
-
-type PallasPublicKey = ();
-
-// https://github.com/coinbase/kryptology/blob/master/pkg/signatures/schnorr/mina/txn.go
-#[derive(Debug, Clone)]
-struct Transaction {
-    _fee: u64,
-    _fee_token: u64,
-    _fee_payer_pk: PallasPublicKey,
-    _nonce: u32,
-    _valid_until: u32,
-    _memo: String,
-    _tag: [bool; 3],
-    _source_pk: PallasPublicKey,
-    _receiver_pk: PallasPublicKey,
-    _token_id: u64,
-    _amount: u64,
-    _locked: bool,
-    _network_id: NetworkType,
-}
-
-// impl Hashable for Transaction {
-//     type D = NetworkId;
-
-//     fn to_roinput(&self) -> mina_hasher::ROInput {
-//         let mut roi = ROInput::new();
-
-//         roi = roi.append_field(self.fee_payer_pk.x);
-//         roi = roi.append_field(self.source_pk.x);
-//         roi = roi.append_field(self.receiver_pk.x);
-
-//         roi = roi.append_u64(self.fee);
-//         roi = roi.append_u64(self.fee_token);
-//         roi = roi.append_u32(self.nonce);
-//         roi = roi.append_u32(self.valid_until);
-//         roi = roi.append_bytes(self.memo.as_bytes());
-
-//         for b in self.tag {
-//             roi = roi.append_bool(b);
-//         }
-
-//         roi = roi.append_u64(self.token_id);
-//         roi = roi.append_u64(self.amount);
-//         roi = roi.append_bool(self.locked);
-
-//         roi
-//     }
-
-//     fn domain_string(domain_param: Self::D) -> Option<String> {
-//         match domain_param {
-//             NetworkId::MAINNET => "MinaSignatureMainnet",
-//             NetworkId::TESTNET => "CodaSignature",
-//         }
-//         .to_string()
-//         .into()
-//     }
-// }
-
 #[derive(Debug, Deserialize, Default)]
 pub struct PayloadFields {
     pub to: String,
@@ -151,85 +200,45 @@ pub struct PayloadFields {
     pub memo: Option<String>,
 }
 
-impl TryFrom<PayloadFields> for Transaction {
-    type Error = KeysError;
-
-    fn try_from(value: PayloadFields) -> Result<Self, Self::Error> {
-        // let from_public_key = PubKey::from_address(&value.from)
-        // .map_err(|err| format!("failed to parse \"from\" address: {err}"))?;
-        //
-        // let to_public_key = PubKey::from_address(&value.to)
-        // .map_err(|err| format!("failed to parse \"to\" address: {err}"))?;
-
-        let _fee = value
-            .fee
-            .parse()
-            .map_err(|err| format!("failed to parse uint for fee: {err}"))?;
-
-        let _amount = if let Some(amt) = value.amount {
-            amt.parse()
-                .map_err(|err| format!("failed to parse uint for fee: {err}"))?
-        } else {
-            0
-        };
-
-        let _nonce = value
-            .nonce
-            .parse()
-            .map_err(|err| format!("failed to parse uint for nonce: {err}"))?;
-
-        let _valid_until = if let Some(valid_until) = value.valid_until {
-            valid_until
-                .parse()
-                .map_err(|err| format!("failed to parse uint for valid until memo: {err}"))?
-        } else {
-            0
-        };
-
-        let _memo = if let Some(memo) = value.memo {
-            memo
-        } else {
-            String::new()
-        };
-
-        Ok(Transaction {
-            _fee,
-            _fee_token: 1,
-            _fee_payer_pk: (),
-            _nonce,
-            _valid_until,
-            _memo,
-            _tag: [false, false, false],
-            _source_pk: (),
-            _receiver_pk: (),
-            _token_id: 1,
-            _amount,
-            _locked: false,
-            _network_id: NetworkType::TestNet,
-        })
-    }
-}
-
 #[derive(Debug, Deserialize, Default)]
 pub struct PallasSigningPayload {
     pub payment: Option<PayloadFields>,
 }
 
-impl TryFrom<SigningPayload> for Transaction {
-    type Error = KeysError;
-
-    fn try_from(raw_payload: SigningPayload) -> Result<Self, Self::Error> {
-        let signing_payload: PallasSigningPayload = serde_json::from_slice(&raw_payload.bytes)
+impl PallasSigningPayload {
+    /// `payment` decodes the payment of a Mina signing payload and checks
+    /// that its addresses and amounts are valid.
+    pub fn payment(raw_payload: &SigningPayload) -> KeysResult<PayloadFields> {
+        let signing_payload: Self = serde_json::from_slice(&raw_payload.bytes)
             .map_err(|err| format!("failed to unmarshal payload: {err}"))?;
-
-        let payload_fields = signing_payload
+        let payment = signing_payload
             .payment
             .ok_or(KeysError::ErrPaymentNotFound)?;
 
-        let tx = payload_fields
-            .try_into()
-            .map_err(|err| format!("failed to construct transaction: {err}"))?;
-
-        Ok(tx)
+        PallasPublicKey::from_address(&payment.from)
+            .map_err(|err| format!("failed to parse \"from\" address: {err}"))?;
+        PallasPublicKey::from_address(&payment.to)
+            .map_err(|err| format!("failed to parse \"to\" address: {err}"))?;
+
+        let uint = |name: &str, value: Option<&String>| -> KeysResult<()> {
+            if let Some(value) = value {
+                value
+                    .parse::<u64>()
+                    .map_err(|err| format!("failed to parse uint for {name}: {err}"))?;
+            }
+            Ok(())
+        };
+        uint("fee", Some(&payment.fee))?;
+        uint("amount", payment.amount.as_ref())?;
+        uint("nonce", Some(&payment.nonce))?;
+        uint("valid until", payment.valid_until.as_ref())?;
+        if payment
+            .memo
+            .as_ref()
+            .map_or(false, |memo| memo.len() > MAX_MEMO_LEN)
+        {
+            Err(format!("memo is longer than {MAX_MEMO_LEN} bytes"))?;
+        }
+        Ok(payment)
     }
 }
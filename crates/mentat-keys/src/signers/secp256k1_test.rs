@@ -50,6 +50,15 @@ fn test_sign_secp256k1() {
             },
             criteria: None,
         },
+        TestCase {
+            name: "secp256k1 schnorr bip340 success",
+            payload: TestSecp256k1Payload {
+                payload: mock_payload(hash("hello1234"), SignatureType::SchnorrBip340),
+                sig_type: SignatureType::SchnorrBip340,
+                sig_len: 64,
+            },
+            criteria: None,
+        },
     ];
 
     TestCase::run_err_match(tests, |p| {
@@ -107,6 +116,20 @@ fn test_verify_secp256k1() {
             SignatureType::Schnorr1,
         )
         .unwrap();
+    let signature_bip340 = signer
+        .sign(
+            SigningPayload {
+                account_identifier: Some(AccountIdentifier {
+                    address: "test".to_string(),
+                    ..Default::default()
+                }),
+                bytes: hash("hello"),
+                signature_type: SignatureType::SchnorrBip340,
+                ..Default::default()
+            },
+            SignatureType::SchnorrBip340,
+        )
+        .unwrap();
 
     let mut simple_bytes = vec![0; 33];
     let hello = "hello".as_bytes();
@@ -169,10 +192,40 @@ fn test_verify_secp256k1() {
                 SignatureType::Schnorr1,
                 signer.public_key(),
                 hash("hello"),
-                signature_schnorr1.bytes,
+                signature_schnorr1.bytes.clone(),
             ),
             criteria: None,
         },
+        TestCase {
+            name: "good schnorr bip340 signature",
+            payload: mock_signature(
+                SignatureType::SchnorrBip340,
+                signer.public_key(),
+                hash("hello"),
+                signature_bip340.bytes.clone(),
+            ),
+            criteria: None,
+        },
+        TestCase {
+            name: "schnorr1 signature verified as bip340",
+            payload: mock_signature(
+                SignatureType::SchnorrBip340,
+                signer.public_key(),
+                hash("hello"),
+                signature_schnorr1.bytes,
+            ),
+            criteria: Some(KeysError::ErrVerifyFailed),
+        },
+        TestCase {
+            name: "bip340 signature verified as schnorr1",
+            payload: mock_signature(
+                SignatureType::Schnorr1,
+                signer.public_key(),
+                hash("hello"),
+                signature_bip340.bytes,
+            ),
+            criteria: Some(KeysError::ErrVerifyFailed),
+        },
     ];
 
     TestCase::run_err_match(tests, |p| signer.verify(p.into()))
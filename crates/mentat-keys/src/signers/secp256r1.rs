@@ -6,15 +6,7 @@ use mentat_types::{
     UncheckedSignature,
     UncheckedSignatureType,
 };
-use p256::{
-    ecdsa::{
-        signature::{Signer, Verifier},
-        Signature as PSignature,
-        SigningKey,
-        VerifyingKey,
-    },
-    elliptic_curve::generic_array::GenericArray,
-};
+use p256::ecdsa::{signature::Signer, Signature as PSignature, SigningKey};
 
 use super::*;
 
@@ -23,11 +15,6 @@ pub struct SignerSecp256r1 {
     pub key_pair: UncheckedKeyPair,
 }
 
-// The Ecdsa signature is the couple (R, S), both R and S are 32 bytes
-const ECDSA_R_LEN: usize = 32;
-const _ECDSA_S_LEN: usize = 32;
-const ECDSA_MSG_LEN: usize = 32;
-
 impl SignerInterface for SignerSecp256r1 {
     fn public_key(&self) -> PublicKey {
         let kp: KeyPair = self.key_pair.clone().into();
@@ -79,35 +66,7 @@ impl SignerInterface for SignerSecp256r1 {
                 KeysError::ErrVerifyUnsupportedSignatureType
             ))?;
         }
-        // dbg!("foo");
-
-        mentat_asserter::signatures(&[Some(&signature)])
-            .map_err(|err| format!("signature is invalid: {err}"))?;
-        let signature: Signature = signature.into();
-        // dbg!("bar");
-
-        if signature.signing_payload.bytes.len() != ECDSA_MSG_LEN {
-            return Err(KeysError::ErrVerifyFailed);
-        }
-        // dbg!("sex");
-
-        let public_key = VerifyingKey::from_sec1_bytes(&signature.public_key.bytes)
-            .map_err(|_| KeysError::ErrPubKeyNotOnCurve)?;
-        // dbg!("pk");
-        let message = signature.signing_payload.bytes;
-        let mut signature = signature.bytes;
-        signature.resize(64, 0);
-        let r = GenericArray::from_slice(&signature[0..ECDSA_R_LEN]);
-        // dbg!("r", r);
-        let s = GenericArray::from_slice(&signature[ECDSA_R_LEN..]);
-        // dbg!("s", s);
-        let sig: PSignature =
-            PSignature::from_scalars(*r, *s).map_err(|_| KeysError::ErrVerifyFailed)?;
-        // dbg!(&sig);
-        public_key
-            .verify(&message, &sig)
-            .map_err(|_| KeysError::ErrVerifyFailed)?;
 
-        Ok(())
+        Verifier::verify(&signature)
     }
 }
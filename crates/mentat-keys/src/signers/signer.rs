@@ -1,6 +1,7 @@
 use mentat_types::{PublicKey, Signature, SignatureType, SigningPayload, UncheckedSignature};
 
 use super::*;
+use crate::schnorr::Bip340Mode;
 
 /// `SignerInterface` is an interface for different curve signers
 pub trait SignerInterface {
@@ -46,3 +47,28 @@ impl SignerInterface for Signer {
         }
     }
 }
+
+impl Signer {
+    /// Sets the auxiliary randomness used for `SchnorrBip340` signatures.
+    /// Only `Secp256k1` signers produce them, other signers are returned
+    /// unchanged.
+    pub fn with_bip340_mode(mut self, mode: Bip340Mode) -> Self {
+        if let Self::Secp256k1(s) = &mut self {
+            s.bip340_mode = mode;
+        }
+        self
+    }
+
+    /// The [`SignatureType`] used when a payload does not restrict it. It is
+    /// an error for `Pallas` signers, which cannot sign any type yet.
+    pub fn default_signature_type(&self) -> KeysResult<SignatureType> {
+        match self {
+            Self::Edwards25519(_) => Ok(SignatureType::Ed25519),
+            Self::Pallas(_) => Err(format!(
+                "pallas keys have no supported signature type: {}",
+                KeysError::ErrSchnorrPoseidonUnsupported
+            ))?,
+            Self::Secp256k1(_) | Self::Secp256r1(_) => Ok(SignatureType::Ecdsa),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Signature verification using only the `PublicKey` embedded in a
+//! `Signature`, without access to any private key.
+
+use ::secp256k1::{
+    ecdsa::{self, RecoverableSignature, RecoveryId},
+    Message,
+    Secp256k1,
+};
+use ed25519_compact::Signature as EdSignature;
+use mentat_asserter::SignatureVerifier;
+use mentat_types::{CurveType, PublicKey, Signature, SignatureType, UncheckedSignature};
+use p256::ecdsa::{signature::Verifier as _, Signature as PSignature, VerifyingKey};
+
+use crate::{
+    errors::{KeysError, KeysResult},
+    schnorr::{verify_bip340, verify_schnorr1},
+};
+
+/// The length of the message signed by the ECDSA and SchnorrBip340
+/// signature types.
+const MESSAGE_LEN: usize = 32;
+/// The length of a compact `(R, S)` signature.
+const COMPACT_SIGNATURE_LEN: usize = 64;
+/// Ethereum style recovery ids are offset by 27.
+const RECOVERY_ID_OFFSET: u8 = 27;
+
+/// `Verifier` verifies signatures against the `PublicKey` they embed. Every
+/// `Signer` uses it to implement `verify`, and it can be plugged into an
+/// `Asserter` with `with_signature_verifier` to check the signatures of a
+/// combine request. As a `SignatureVerifier` it lets `Pallas`
+/// `SchnorrPoseidon` signatures through unchecked, since they cannot be
+/// verified yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Verifier;
+
+impl Verifier {
+    /// `verify` returns an error if the signature was not produced by the
+    /// private key of its public key. The supported curve and signature type
+    /// pairs are:
+    ///
+    /// - `Secp256k1`: `Ecdsa`, `EcdsaRecovery`, `Schnorr1` and `SchnorrBip340`
+    /// - `Secp256r1`: `Ecdsa`
+    /// - `Edwards25519`: `Ed25519`
+    ///
+    /// `Pallas` `SchnorrPoseidon` signatures are recognized but cannot be
+    /// verified yet, see [`crate::SignerPallas`].
+    pub fn verify(signature: &UncheckedSignature) -> KeysResult<()> {
+        mentat_asserter::signatures(&[Some(signature)])
+            .map_err(|err| format!("signature is invalid: {err}"))?;
+        let signature: Signature = signature.clone().into();
+
+        let valid = match (signature.public_key.curve_type, signature.signature_type) {
+            (CurveType::Secp256k1, SignatureType::Ecdsa) => verify_secp256k1_ecdsa(&signature)?,
+            (CurveType::Secp256k1, SignatureType::EcdsaRecovery) => {
+                recover_secp256k1(&signature)? == parse_secp256k1(&signature.public_key)?
+            }
+            (CurveType::Secp256k1, SignatureType::Schnorr1) => verify_schnorr1(
+                &parse_secp256k1(&signature.public_key)?.serialize(),
+                &signature.signing_payload.bytes,
+                &signature.bytes,
+            ),
+            (CurveType::Secp256k1, SignatureType::SchnorrBip340) => verify_bip340(
+                &parse_secp256k1(&signature.public_key)?.serialize(),
+                &signature.signing_payload.bytes,
+                &signature.bytes,
+            ),
+            (CurveType::Secp256r1, SignatureType::Ecdsa) => verify_secp256r1_ecdsa(&signature)?,
+            (CurveType::Edwards25519, SignatureType::Ed25519) => verify_ed25519(&signature)?,
+            (CurveType::Pallas, SignatureType::SchnorrPoseidon) => {
+                Err(KeysError::ErrSchnorrPoseidonUnsupported)?
+            }
+            (curve_type, signature_type) => Err(format!(
+                "signature type {signature_type} is invalid for curve type {curve_type}: {}",
+                KeysError::ErrVerifyUnsupportedSignatureType
+            ))?,
+        };
+
+        if !valid {
+            Err(KeysError::ErrVerifyFailed)?;
+        }
+
+        Ok(())
+    }
+
+    /// `recover_public_key` returns the compressed `Secp256k1` public key
+    /// that produced an `EcdsaRecovery` signature. The recovery id may be
+    /// given either as `0..=3` or in the Ethereum `27..=30` form.
+    pub fn recover_public_key(signature: &UncheckedSignature) -> KeysResult<PublicKey> {
+        mentat_asserter::signatures(&[Some(signature)])
+            .map_err(|err| format!("signature is invalid: {err}"))?;
+        let signature: Signature = signature.clone().into();
+
+        if signature.signature_type != SignatureType::EcdsaRecovery {
+            Err(format!(
+                "expected signature type {} but got {}: {}",
+                SignatureType::EcdsaRecovery,
+                signature.signature_type,
+                KeysError::ErrVerifyUnsupportedSignatureType
+            ))?;
+        }
+
+        Ok(PublicKey {
+            bytes: recover_secp256k1(&signature)?.serialize().to_vec(),
+            curve_type: CurveType::Secp256k1,
+        })
+    }
+}
+
+impl SignatureVerifier for Verifier {
+    fn verify_signature(&self, signature: &UncheckedSignature) -> Result<(), String> {
+        match Self::verify(signature) {
+            Err(KeysError::ErrSchnorrPoseidonUnsupported) => Ok(()),
+            result => result.map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Parses the 32 byte message signed by a `Secp256k1` signature.
+fn secp256k1_message(signature: &Signature) -> KeysResult<Message> {
+    Message::from_slice(&signature.signing_payload.bytes).map_err(|_| KeysError::ErrVerifyFailed)
+}
+
+/// Parses a compressed or uncompressed `Secp256k1` public key.
+fn parse_secp256k1(public_key: &PublicKey) -> KeysResult<::secp256k1::PublicKey> {
+    ::secp256k1::PublicKey::from_slice(&public_key.bytes).map_err(|err| {
+        format!(
+            "failed to parse public key: {err}: {}",
+            KeysError::ErrPubKeyNotOnCurve
+        )
+        .into()
+    })
+}
+
+/// Verifies a compact `Secp256k1` ECDSA signature.
+fn verify_secp256k1_ecdsa(signature: &Signature) -> KeysResult<bool> {
+    let msg = secp256k1_message(signature)?;
+    let public_key = parse_secp256k1(&signature.public_key)?;
+    let sig =
+        ecdsa::Signature::from_compact(&signature.bytes).map_err(|_| KeysError::ErrVerifyFailed)?;
+
+    Ok(Secp256k1::verification_only()
+        .verify_ecdsa(&msg, &sig, &public_key)
+        .is_ok())
+}
+
+/// Recovers the public key of a 65 byte `Secp256k1` recoverable signature.
+fn recover_secp256k1(signature: &Signature) -> KeysResult<::secp256k1::PublicKey> {
+    let msg = secp256k1_message(signature)?;
+    if signature.bytes.len() != COMPACT_SIGNATURE_LEN + 1 {
+        Err(KeysError::ErrVerifyFailed)?;
+    }
+
+    let mut recovery_id = signature.bytes[COMPACT_SIGNATURE_LEN];
+    if recovery_id >= RECOVERY_ID_OFFSET {
+        recovery_id -= RECOVERY_ID_OFFSET;
+    }
+    let recovery_id =
+        RecoveryId::from_i32(recovery_id.into()).map_err(|_| KeysError::ErrVerifyFailed)?;
+    let sig =
+        RecoverableSignature::from_compact(&signature.bytes[..COMPACT_SIGNATURE_LEN], recovery_id)
+            .map_err(|_| KeysError::ErrVerifyFailed)?;
+
+    Secp256k1::verification_only()
+        .recover_ecdsa(&msg, &sig)
+        .map_err(|_| KeysError::ErrVerifyFailed)
+}
+
+/// Verifies a `Secp256r1` ECDSA signature given as `R || S`.
+fn verify_secp256r1_ecdsa(signature: &Signature) -> KeysResult<bool> {
+    if signature.signing_payload.bytes.len() != MESSAGE_LEN {
+        Err(KeysError::ErrVerifyFailed)?;
+    }
+
+    let public_key = VerifyingKey::from_sec1_bytes(&signature.public_key.bytes)
+        .map_err(|_| KeysError::ErrPubKeyNotOnCurve)?;
+    let sig =
+        PSignature::try_from(signature.bytes.as_slice()).map_err(|_| KeysError::ErrVerifyFailed)?;
+
+    Ok(public_key
+        .verify(&signature.signing_payload.bytes, &sig)
+        .is_ok())
+}
+
+/// Verifies an `Edwards25519` signature.
+fn verify_ed25519(signature: &Signature) -> KeysResult<bool> {
+    let public_key = ed25519_compact::PublicKey::from_slice(&signature.public_key.bytes)
+        .map_err(|_| KeysError::ErrPubKeyNotOnCurve)?;
+    let sig = EdSignature::from_slice(&signature.bytes).map_err(|_| KeysError::ErrVerifyFailed)?;
+
+    Ok(public_key
+        .verify(&signature.signing_payload.bytes, &sig)
+        .is_ok())
+}
@@ -0,0 +1,205 @@
+use mentat_asserter::{Asserter, ConstructionError};
+use mentat_test_utils::TestCase;
+use mentat_types::{
+    AccountIdentifier,
+    CurveType,
+    NetworkIdentifier,
+    PublicKey,
+    Signature,
+    SignatureType,
+    SigningPayload,
+    UncheckedConstructionCombineRequest,
+    UncheckedSignature,
+};
+
+use crate::{errors::KeysError, types::KeyPair, SignerInterface, Verifier};
+
+fn sign(curve: CurveType, signature_type: SignatureType, message: &[u8]) -> Signature {
+    KeyPair::generate(curve)
+        .unwrap()
+        .signer()
+        .unwrap()
+        .sign(
+            SigningPayload {
+                account_identifier: Some(AccountIdentifier {
+                    address: "test".into(),
+                    ..Default::default()
+                }),
+                bytes: message.to_vec(),
+                signature_type,
+                ..Default::default()
+            },
+            signature_type,
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_verify() {
+    let message = [7; 32];
+    let ecdsa = sign(CurveType::Secp256k1, SignatureType::Ecdsa, &message);
+    let recovery = sign(CurveType::Secp256k1, SignatureType::EcdsaRecovery, &message);
+
+    let mut tampered = ecdsa.clone();
+    tampered.signing_payload.bytes = vec![8; 32];
+
+    let mut wrong_key = recovery.clone();
+    wrong_key.public_key = sign(CurveType::Secp256k1, SignatureType::Ecdsa, &message).public_key;
+
+    let mut wrong_curve = sign(CurveType::Edwards25519, SignatureType::Ed25519, &message);
+    wrong_curve.public_key = ecdsa.public_key.clone();
+    wrong_curve.signature_type = SignatureType::Ed25519;
+
+    // Uncompressed keys are accepted as well.
+    let mut uncompressed = ecdsa.clone();
+    uncompressed.public_key.bytes = ::secp256k1::PublicKey::from_slice(&ecdsa.public_key.bytes)
+        .unwrap()
+        .serialize_uncompressed()
+        .to_vec();
+
+    let tests = vec![
+        TestCase {
+            name: "secp256k1 ecdsa",
+            payload: ecdsa,
+            criteria: None,
+        },
+        TestCase {
+            name: "secp256k1 ecdsa uncompressed public key",
+            payload: uncompressed,
+            criteria: None,
+        },
+        TestCase {
+            name: "secp256k1 ecdsa recovery",
+            payload: recovery,
+            criteria: None,
+        },
+        TestCase {
+            name: "secp256k1 schnorr1",
+            payload: sign(CurveType::Secp256k1, SignatureType::Schnorr1, &message),
+            criteria: None,
+        },
+        TestCase {
+            name: "secp256r1 ecdsa",
+            payload: sign(CurveType::Secp256r1, SignatureType::Ecdsa, &message),
+            criteria: None,
+        },
+        TestCase {
+            name: "edwards25519 ed25519",
+            payload: sign(CurveType::Edwards25519, SignatureType::Ed25519, &message),
+            criteria: None,
+        },
+        TestCase {
+            name: "tampered message",
+            payload: tampered,
+            criteria: Some(KeysError::ErrVerifyFailed),
+        },
+        TestCase {
+            name: "recovered key does not match",
+            payload: wrong_key,
+            criteria: Some(KeysError::ErrVerifyFailed),
+        },
+        TestCase {
+            name: "signature type does not match curve",
+            payload: wrong_curve,
+            criteria: Some(KeysError::ErrVerifyUnsupportedSignatureType),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |s| Verifier::verify(&s.into()));
+}
+
+#[test]
+fn test_recover_public_key() {
+    let signature = sign(CurveType::Secp256k1, SignatureType::EcdsaRecovery, &[7; 32]);
+    let public_key = signature.public_key.clone();
+
+    assert_eq!(
+        Verifier::recover_public_key(&signature.clone().into()).unwrap(),
+        public_key
+    );
+
+    // Ethereum style recovery ids.
+    let mut ethereum = signature.clone();
+    ethereum.bytes[64] += 27;
+    assert_eq!(
+        Verifier::recover_public_key(&ethereum.clone().into()).unwrap(),
+        public_key
+    );
+    assert!(Verifier::verify(&ethereum.into()).is_ok());
+
+    let mut ecdsa = signature;
+    ecdsa.signature_type = SignatureType::Ecdsa;
+    ecdsa.signing_payload.signature_type = SignatureType::Ecdsa;
+    let err = Verifier::recover_public_key(&ecdsa.into()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&KeysError::ErrVerifyUnsupportedSignatureType.to_string()));
+}
+
+#[test]
+fn test_combine_request_verification() {
+    let network = NetworkIdentifier {
+        blockchain: "bitcoin".into(),
+        network: "mainnet".into(),
+        sub_network_identifier: None,
+    };
+    let asserter = Asserter::new_server(
+        vec!["PAYMENT".into()],
+        false,
+        vec![network.clone()],
+        Vec::new(),
+        false,
+        None,
+    )
+    .unwrap()
+    .with_signature_verifier(Verifier);
+
+    let valid = sign(CurveType::Edwards25519, SignatureType::Ed25519, b"hello");
+    let mut forged = valid.clone();
+    forged.public_key = PublicKey {
+        bytes: KeyPair::generate(CurveType::Edwards25519)
+            .unwrap()
+            .public_key
+            .bytes,
+        curve_type: CurveType::Edwards25519,
+    };
+
+    let request = |signature: Signature| UncheckedConstructionCombineRequest {
+        network_identifier: Some(network.clone()),
+        unsigned_transaction: "blah".into(),
+        signatures: vec![Some(UncheckedSignature::from(signature))],
+    };
+
+    assert!(asserter
+        .construction_combine_request(Some(&request(valid)))
+        .is_ok());
+
+    // Pallas signatures cannot be verified yet, so they are let through.
+    let pallas = Signature {
+        signing_payload: SigningPayload {
+            account_identifier: Some(AccountIdentifier {
+                address: "test".into(),
+                ..Default::default()
+            }),
+            bytes: b"hello".to_vec(),
+            signature_type: SignatureType::SchnorrPoseidon,
+            ..Default::default()
+        },
+        public_key: KeyPair::generate(CurveType::Pallas).unwrap().public_key,
+        signature_type: SignatureType::SchnorrPoseidon,
+        bytes: vec![1; 64],
+    };
+    assert!(matches!(
+        Verifier::verify(&pallas.clone().into()),
+        Err(KeysError::ErrSchnorrPoseidonUnsupported)
+    ));
+    assert!(asserter
+        .construction_combine_request(Some(&request(pallas)))
+        .is_ok());
+    let err = asserter
+        .construction_combine_request(Some(&request(forged)))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ConstructionError::SignatureVerificationFailed.to_string()));
+}
@@ -0,0 +1,138 @@
+//! Benchmarks computing the balance changes of large synthetic blocks.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use indexmap::IndexMap;
+use mentat_asserter::{Asserter, Validations};
+use mentat_parser::Parser;
+use mentat_types::*;
+
+/// A parser whose asserter only accepts successful `Transfer` operations.
+fn parser() -> Parser {
+    let asserter = Asserter::new_client_with_options(
+        Some(NetworkIdentifier {
+            blockchain: "bitcoin".into(),
+            network: "mainnet".into(),
+            ..Default::default()
+        }),
+        Some(UncheckedBlockIdentifier {
+            hash: "block 0".into(),
+            index: 0,
+        }),
+        vec!["Transfer".into()],
+        vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Validations::default(),
+    )
+    .unwrap();
+
+    Parser::new(Some(asserter), None, Vec::new())
+}
+
+/// A block of `txs` two-operation transfers between `accounts` accounts.
+fn block(txs: usize, accounts: usize) -> Block {
+    let currency = Currency {
+        symbol: "BTC".into(),
+        decimals: 8,
+        ..Default::default()
+    };
+    let operation = |index, address: String, value: String| Operation {
+        operation_identifier: OperationIdentifier {
+            index,
+            network_index: None,
+        },
+        type_: "Transfer".into(),
+        status: Some("Success".into()),
+        account: Some(AccountIdentifier {
+            address,
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value,
+            currency: currency.clone(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    Block {
+        block_identifier: BlockIdentifier {
+            index: 1,
+            hash: "block 1".into(),
+        },
+        transactions: (0..txs)
+            .map(|i| Transaction {
+                transaction_identifier: TransactionIdentifier {
+                    hash: format!("tx {i}"),
+                },
+                operations: vec![
+                    operation(0, format!("addr {}", i % accounts), format!("-{i}")),
+                    operation(1, format!("addr {}", (i * 7 + 3) % accounts), i.to_string()),
+                ],
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Sums the balance changes of a block keyed by the JSON hashes of the
+/// account and currency, as a baseline for the structural keys.
+fn json_keyed(parser: &Parser, block: &Block) -> usize {
+    let mut changes = IndexMap::<String, String>::new();
+    for op in block.transactions.iter().flat_map(|tx| &tx.operations) {
+        if parser.skip_operation(op).unwrap() {
+            continue;
+        }
+        let amount = op.amount.as_ref().unwrap();
+        let key = format!(
+            "{}/{}",
+            hash(op.account.as_ref()),
+            hash(Some(&amount.currency))
+        );
+        let difference = match changes.get(&key) {
+            Some(difference) => add_values(difference, &amount.value).unwrap(),
+            None => amount.value.clone(),
+        };
+        changes.insert(key, difference);
+    }
+    changes.len()
+}
+
+/// Benchmarks balance changes with increasing numbers of workers.
+fn balance_changes(c: &mut Criterion) {
+    let parser = parser();
+    let mut group = c.benchmark_group("balance_changes");
+    group.sample_size(10);
+
+    for txs in [10_000, 100_000] {
+        let block = block(txs, txs / 10);
+        group.throughput(Throughput::Elements(2 * txs as u64));
+        group.bench_with_input(BenchmarkId::new("json keys", txs), &block, |b, block| {
+            b.iter(|| json_keyed(&parser, black_box(block)))
+        });
+        for workers in [1, 2, 4, 8] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{workers} workers"), txs),
+                &block,
+                |b, block| b.iter(|| parser.par_balance_changes(black_box(block), false, workers)),
+            );
+        }
+        group.bench_with_input(BenchmarkId::new("stream", txs), &block, |b, block| {
+            b.iter(|| {
+                parser
+                    .stream_balance_changes(black_box(block), false)
+                    .map(|changes| changes.map(|c| c.len()))
+                    .sum::<Result<usize, _>>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, balance_changes);
+criterion_main!(benches);
@@ -1,5 +1,8 @@
 //! The balance change file contains code for parsing changed balances.
 
+use num_bigint_dig::BigInt;
+use tokio::runtime::RuntimeFlavor;
+
 use super::*;
 use crate::{Parser, ParserResult};
 
@@ -17,6 +20,10 @@ pub struct BalanceChange {
     pub block: BlockIdentifier,
     /// Represents the changed balance of the txs.
     pub difference: String,
+    /// The exemption that justifies the change, if it happened outside of
+    /// any operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exemption: Option<BalanceExemption>,
 }
 
 impl Parser {
@@ -49,65 +56,243 @@ impl Parser {
     /// [`BalanceChange`] struct. If a block is being
     /// orphaned, the opposite of each balance change is
     /// returned.
+    ///
+    /// Large blocks are split across worker threads, see
+    /// [`Parser::par_balance_changes`]. The calling thread waits for the
+    /// workers, so on a multi-threaded tokio runtime it is handed over with
+    /// [`tokio::task::block_in_place`] to keep other tasks running. Other
+    /// executors should call [`Parser::par_balance_changes`] on a blocking
+    /// thread instead.
     pub async fn balance_changes(
         &self,
         block: &Block,
         block_removed: bool,
     ) -> ParserResult<Vec<BalanceChange>> {
-        let mut balance_changes: IndexMap<String, BalanceChange> = IndexMap::new();
+        let workers = num_cpus::get().min(block.transactions.len() / MIN_TRANSACTIONS_PER_WORKER);
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if workers > 1 && handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| {
+                    self.par_balance_changes(block, block_removed, workers)
+                })
+            }
+            _ => self.par_balance_changes(block, block_removed, workers),
+        }
+    }
+
+    /// `par_balance_changes` returns the same balance changes as
+    /// [`Parser::balance_changes`], summing the transactions of the block on
+    /// up to `workers` threads. Each thread sums a contiguous range of
+    /// transactions, and the partial sums are merged in block order, so the
+    /// result does not depend on the number of workers.
+    pub fn par_balance_changes(
+        &self,
+        block: &Block,
+        block_removed: bool,
+        workers: usize,
+    ) -> ParserResult<Vec<BalanceChange>> {
+        let transactions = &block.transactions;
+        if workers <= 1 || transactions.len() <= 1 {
+            let mut deltas = BalanceDeltas::new();
+            for tx in transactions {
+                self.add_transaction_deltas(&mut deltas, tx, block_removed)?;
+            }
+            return Ok(into_balance_changes(deltas, &block.block_identifier));
+        }
+
+        let chunk_size = (transactions.len() + workers - 1) / workers;
+        let partials = crossbeam::scope(|scope| {
+            let handles = transactions
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move |_| {
+                        let mut deltas = BalanceDeltas::new();
+                        for tx in chunk {
+                            self.add_transaction_deltas(&mut deltas, tx, block_removed)?;
+                        }
+                        Ok(deltas)
+                    })
+                })
+                .collect::<Vec<_>>();
 
-        for tx in block.transactions.iter() {
-            for op in tx.operations.iter() {
-                let skip = self
-                    .skip_operation(op)
-                    .map_err(|e| format!("failed to skip operation {op:?}: {e}"))?;
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .map_err(|_| "a balance change worker panicked".to_string())?
+                })
+                .collect::<ParserResult<Vec<_>>>()
+        })
+        .map_err(|_| "a balance change worker panicked".to_string())??;
 
-                if skip {
-                    // Continue the inner loop.
-                    continue;
+        let mut partials = partials.into_iter();
+        let mut deltas = partials.next().unwrap_or_default();
+        for partial in partials {
+            for (key, delta) in partial {
+                match deltas.get_mut(&key) {
+                    Some(existing) => existing.add(delta.difference),
+                    None => {
+                        deltas.insert(key, delta);
+                    }
                 }
+            }
+        }
+
+        Ok(into_balance_changes(deltas, &block.block_identifier))
+    }
+
+    /// `transaction_balance_changes` returns the balance changes of a single
+    /// transaction in a block, summed per account and currency like
+    /// [`Parser::balance_changes`].
+    pub fn transaction_balance_changes(
+        &self,
+        block_identifier: &BlockIdentifier,
+        transaction: &Transaction,
+        block_removed: bool,
+    ) -> ParserResult<Vec<BalanceChange>> {
+        let mut deltas = BalanceDeltas::new();
+        self.add_transaction_deltas(&mut deltas, transaction, block_removed)?;
+        Ok(into_balance_changes(deltas, block_identifier))
+    }
+
+    /// `stream_balance_changes` lazily returns the balance changes of each
+    /// transaction in a block, in order, so that only one transaction is
+    /// summed in memory at a time.
+    pub fn stream_balance_changes<'a>(
+        &'a self,
+        block: &'a Block,
+        block_removed: bool,
+    ) -> impl Iterator<Item = ParserResult<Vec<BalanceChange>>> + 'a {
+        block.transactions.iter().map(move |tx| {
+            self.transaction_balance_changes(&block.block_identifier, tx, block_removed)
+        })
+    }
 
-                // We create a copy of Amount.Value
-                // here to ensure we don't accidentally overwrite
-                // the value of op.Amount.
-                // Safe to unwrap here otherwise we would have skipped.
-
-                let amount_value = if block_removed {
-                    let value = &op.amount.as_ref().unwrap().value;
-                    negate_value(value)
-                        .map_err(|e| format!("failed to flip the sign of {value:?}: {e}"))?
-                } else {
-                    op.amount.clone().unwrap().value
-                };
-
-                let key = format!(
-                    "{}/{}",
-                    hash(op.account.as_ref()),
-                    hash(op.amount.as_ref().map(|amt| &amt.currency)),
-                );
-
-                let val = balance_changes.get_mut(&key);
-                if val.is_none() {
-                    balance_changes.insert(
-                        key,
-                        BalanceChange {
-                            account: op.account.clone(),
-                            currency: op.amount.as_ref().map(|amt| amt.currency.clone()),
-                            block: block.block_identifier.clone(),
-                            difference: amount_value,
+    /// `add_transaction_deltas` adds the amounts of the operations of a
+    /// transaction that are not skipped to `deltas`.
+    fn add_transaction_deltas<'a>(
+        &self,
+        deltas: &mut BalanceDeltas<'a>,
+        tx: &'a Transaction,
+        block_removed: bool,
+    ) -> ParserResult<()> {
+        for op in tx.operations.iter() {
+            let skip = self
+                .skip_operation(op)
+                .map_err(|e| format!("failed to skip operation {op:?}: {e}"))?;
+
+            let (account, amount) = match (&op.account, &op.amount) {
+                (Some(account), Some(amount)) if !skip => (account, amount),
+                _ => continue,
+            };
+
+            let mut value = amount_value(Some(amount))
+                .map_err(|e| format!("failed to parse the amount of operation {op:?}: {e}"))?;
+            if block_removed {
+                value = -value;
+            }
+
+            match deltas.get_mut(&BalanceKey::new(account, &amount.currency)) {
+                Some(delta) => delta.add(value),
+                None => {
+                    deltas.insert(
+                        BalanceKey::new(account, &amount.currency),
+                        BalanceDelta {
+                            account,
+                            currency: &amount.currency,
+                            difference: value,
+                            value: (!block_removed).then_some(amount.value.as_str()),
                         },
                     );
-                } else {
-                    let mut val = val.unwrap();
-                    let new_diff =
-                        add_values(val.difference.as_ref(), &amount_value).map_err(|e| {
-                            format!("failed to add {} and {amount_value:?}: {e}", val.difference)
-                        })?;
-                    val.difference = new_diff;
                 }
             }
         }
 
-        Ok(balance_changes.into_iter().map(|(_, bc)| bc).collect())
+        Ok(())
     }
 }
+
+/// The number of transactions below which summing balance changes on another
+/// thread costs more than it saves.
+const MIN_TRANSACTIONS_PER_WORKER: usize = 1024;
+
+/// `BalanceKey` identifies an [`AccountIdentifier`] and [`Currency`] pair by
+/// their fields, which is much cheaper than hashing their JSON.
+#[derive(PartialEq, Eq, Hash)]
+struct BalanceKey<'a> {
+    /// The address of the account.
+    address: &'a str,
+    /// The address of the sub account, if any.
+    sub_account: Option<&'a str>,
+    /// The symbol of the currency.
+    symbol: &'a str,
+    /// The decimals of the currency.
+    decimals: usize,
+    /// The hashes of the account and currency when either has metadata, so
+    /// that metadata is compared regardless of the order of its keys.
+    metadata: Option<(String, String)>,
+}
+
+impl<'a> BalanceKey<'a> {
+    /// creates a new `BalanceKey`.
+    fn new(account: &'a AccountIdentifier, currency: &'a Currency) -> Self {
+        let has_metadata = !account.metadata.is_empty()
+            || !currency.metadata.is_empty()
+            || account
+                .sub_account
+                .as_ref()
+                .map_or(false, |sub_account| !sub_account.metadata.is_empty());
+
+        Self {
+            address: &account.address,
+            sub_account: account.sub_account.as_ref().map(|s| s.address.as_str()),
+            symbol: &currency.symbol,
+            decimals: currency.decimals,
+            metadata: has_metadata.then(|| (hash(Some(account)), hash(Some(currency)))),
+        }
+    }
+}
+
+/// `BalanceDelta` is the running sum of a balance change.
+struct BalanceDelta<'a> {
+    /// The account identifier.
+    account: &'a AccountIdentifier,
+    /// The currency.
+    currency: &'a Currency,
+    /// The sum of the amounts so far.
+    difference: BigInt,
+    /// The amount value as written in the operation while only one
+    /// operation has been summed. It is returned unchanged, i.e. `+5` is not
+    /// normalized to `5`, like the sum of a single value always was.
+    value: Option<&'a str>,
+}
+
+impl BalanceDelta<'_> {
+    /// adds the amount of another operation.
+    fn add(&mut self, value: BigInt) {
+        self.difference += value;
+        self.value = None;
+    }
+}
+
+/// The running sums of balance changes in the order they first appear.
+type BalanceDeltas<'a> = IndexMap<BalanceKey<'a>, BalanceDelta<'a>>;
+
+/// `into_balance_changes` converts running sums into [`BalanceChange`]s.
+fn into_balance_changes(
+    deltas: BalanceDeltas<'_>,
+    block_identifier: &BlockIdentifier,
+) -> Vec<BalanceChange> {
+    deltas
+        .into_values()
+        .map(|delta| BalanceChange {
+            account: Some(delta.account.clone()),
+            currency: Some(delta.currency.clone()),
+            block: block_identifier.clone(),
+            difference: delta
+                .value
+                .map_or_else(|| delta.difference.to_string(), str::to_string),
+            exemption: None,
+        })
+        .collect()
+}
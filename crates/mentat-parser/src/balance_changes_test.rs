@@ -1,3 +1,5 @@
+use serde_json::Value;
+
 use super::*;
 
 pub fn simple_asserter_configuration(
@@ -176,6 +178,7 @@ fn test_balance_changes() {
                     index: 1,
                 },
                 difference: "100".into(),
+                exemption: None,
             }],
         },
         TestCase {
@@ -243,6 +246,7 @@ fn test_balance_changes() {
                         hash: "1".into(),
                     },
                     difference: "250".into(),
+                    exemption: None,
                 },
                 BalanceChange {
                     account: Some(AccountIdentifier {
@@ -255,6 +259,7 @@ fn test_balance_changes() {
                         hash: "1".into(),
                     },
                     difference: "150".into(),
+                    exemption: None,
                 },
             ],
         },
@@ -295,6 +300,7 @@ fn test_balance_changes() {
                         hash: "1".into(),
                     },
                     difference: "-250".into(),
+                    exemption: None,
                 },
                 BalanceChange {
                     account: Some(AccountIdentifier {
@@ -307,6 +313,7 @@ fn test_balance_changes() {
                         hash: "1".into(),
                     },
                     difference: "-150".into(),
+                    exemption: None,
                 },
             ],
         },
@@ -319,3 +326,258 @@ fn test_balance_changes() {
             .unwrap()
     });
 }
+
+/// A block of `txs` transactions moving `ATOM` between `accounts` accounts.
+fn synthetic_block(txs: usize, accounts: usize) -> Block {
+    let currency = Currency {
+        symbol: "ATOM".into(),
+        decimals: 6,
+        ..Default::default()
+    };
+
+    Block {
+        block_identifier: BlockIdentifier {
+            index: 1,
+            hash: "1".into(),
+        },
+        transactions: (0..txs)
+            .map(|i| {
+                let mut tx = simple_transaction_factory(
+                    &format!("tx{i}"),
+                    &format!("addr{}", i % accounts),
+                    &format!("-{}", i + 1),
+                    currency.clone(),
+                );
+                let mut credit = tx.operations[0].clone();
+                credit.operation_identifier.index = 1;
+                credit.account = Some(AccountIdentifier {
+                    address: format!("addr{}", (i * 7 + 3) % accounts),
+                    ..Default::default()
+                });
+                credit.amount.as_mut().unwrap().value = (i + 1).to_string();
+                tx.operations.push(credit);
+                tx
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_par_balance_changes() {
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })]),
+        None,
+        Vec::new(),
+    );
+
+    let tests = [1, 2, 3, 8, 64]
+        .into_iter()
+        .flat_map(|workers| {
+            [false, true].into_iter().map(move |orphan| TestCase {
+                name: "par matches sequential",
+                payload: MethodPayload {
+                    caller: (workers, orphan),
+                    payload: synthetic_block(501, 13),
+                },
+                criteria: true,
+            })
+        })
+        .collect();
+
+    TestCase::run_output_match(tests, |t| {
+        let (workers, orphan) = t.caller;
+        let expected = parser.par_balance_changes(&t.payload, orphan, 1).unwrap();
+        expected.len() == 13
+            && parser
+                .par_balance_changes(&t.payload, orphan, workers)
+                .unwrap()
+                == expected
+    });
+}
+
+#[test]
+fn test_balance_changes_metadata_order() {
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })]),
+        None,
+        Vec::new(),
+    );
+
+    let currency = |metadata: &[(&str, Value)]| Currency {
+        symbol: "ERC20".into(),
+        decimals: 18,
+        metadata: metadata
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect(),
+    };
+    let block = Block {
+        block_identifier: BlockIdentifier {
+            index: 1,
+            hash: "1".into(),
+        },
+        transactions: vec![
+            simple_transaction_factory(
+                "tx1",
+                "addr1",
+                "100",
+                currency(&[("contract", "0xa".into()), ("chain", 1.into())]),
+            ),
+            simple_transaction_factory(
+                "tx2",
+                "addr1",
+                "50",
+                currency(&[("chain", 1.into()), ("contract", "0xa".into())]),
+            ),
+            simple_transaction_factory(
+                "tx3",
+                "addr1",
+                "7",
+                currency(&[("contract", "0xb".into()), ("chain", 1.into())]),
+            ),
+        ],
+        ..Default::default()
+    };
+
+    let tests = vec![TestCase {
+        name: "sums",
+        payload: block.clone(),
+        criteria: vec!["150".to_string(), "7".to_string()],
+    }];
+
+    TestCase::run_output_match(tests, |t| {
+        parser
+            .par_balance_changes(&t, false, 2)
+            .unwrap()
+            .into_iter()
+            .map(|bc| bc.difference)
+            .collect::<Vec<_>>()
+    });
+}
+
+#[test]
+fn test_stream_balance_changes() {
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })]),
+        None,
+        Vec::new(),
+    );
+
+    let tests = vec![
+        TestCase {
+            name: "one delta per account per transaction",
+            payload: synthetic_block(3, 2),
+            criteria: vec![
+                vec![
+                    ("addr0".to_string(), "-1".to_string()),
+                    ("addr1".into(), "1".into()),
+                ],
+                vec![("addr1".into(), "-2".into()), ("addr0".into(), "2".into())],
+                vec![("addr0".into(), "-3".into()), ("addr1".into(), "3".into())],
+            ],
+        },
+        TestCase {
+            name: "empty block",
+            payload: synthetic_block(0, 1),
+            criteria: vec![],
+        },
+    ];
+
+    TestCase::run_output_match(tests, |t| {
+        parser
+            .stream_balance_changes(&t, false)
+            .map(|changes| {
+                changes
+                    .unwrap()
+                    .into_iter()
+                    .map(|bc| (bc.account.unwrap().address, bc.difference))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    });
+}
+
+#[test]
+fn test_balance_changes_single_value() {
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })]),
+        None,
+        Vec::new(),
+    );
+    let currency = Currency {
+        symbol: "ATOM".into(),
+        decimals: 6,
+        ..Default::default()
+    };
+    let block = Block {
+        block_identifier: BlockIdentifier {
+            index: 1,
+            hash: "1".into(),
+        },
+        transactions: vec![
+            simple_transaction_factory("tx1", "addr1", "+5", currency.clone()),
+            simple_transaction_factory("tx2", "addr2", "+5", currency.clone()),
+            simple_transaction_factory("tx3", "addr2", "+1", currency),
+        ],
+        ..Default::default()
+    };
+
+    let tests = [1, 2]
+        .into_iter()
+        .flat_map(|workers| {
+            [
+                TestCase {
+                    name: "a single value is kept as is",
+                    payload: (workers, false),
+                    criteria: vec!["+5".to_string(), "6".to_string()],
+                },
+                TestCase {
+                    name: "orphaned values are negated",
+                    payload: (workers, true),
+                    criteria: vec!["-5".to_string(), "-6".to_string()],
+                },
+            ]
+        })
+        .collect();
+
+    TestCase::run_output_match(tests, |(workers, orphan)| {
+        parser
+            .par_balance_changes(&block, orphan, workers)
+            .unwrap()
+            .into_iter()
+            .map(|bc| bc.difference)
+            .collect::<Vec<_>>()
+    });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_balance_changes_multi_thread() {
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![Some(OperationStatus {
+            status: "Success".into(),
+            successful: true,
+        })]),
+        None,
+        Vec::new(),
+    );
+    // enough transactions for several workers.
+    let block = synthetic_block(4096, 13);
+
+    assert_eq!(
+        parser.balance_changes(&block, false).await.unwrap(),
+        parser.par_balance_changes(&block, false, 1).unwrap()
+    );
+}
@@ -0,0 +1,198 @@
+//! The classify file contains code for labelling the operation groups of a
+//! transaction as payments or fees.
+
+use num_bigint_dig::BigInt;
+use num_traits::{Signed, Zero};
+
+use super::*;
+use crate::{Parser, ParserResult};
+
+/// `OperationGroupKind` is the label of a group of related operations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationGroupKind {
+    /// The group moves value between accounts.
+    Payment,
+    /// The group pays a fee.
+    Fee,
+    /// The group matches neither shape.
+    #[default]
+    Unknown,
+}
+
+/// `ClassifiedGroup` is a group of related operations and its label.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClassifiedGroup {
+    /// The label of the group.
+    pub kind: OperationGroupKind,
+    /// The operations of the group.
+    pub operations: Vec<Operation>,
+}
+
+/// `AccountValue` is the net value of a [`Currency`] moved by a
+/// [`AccountIdentifier`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AccountValue {
+    /// The account identifier.
+    pub account: AccountIdentifier,
+    /// The currency.
+    pub currency: Currency,
+    /// The sum of all amounts of the account in the currency.
+    pub value: String,
+}
+
+/// `TransactionClassification` describes what the operations of a
+/// transaction do.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TransactionClassification {
+    /// The groups of related operations, as returned by
+    /// [`group_operations`], and their labels.
+    pub groups: Vec<ClassifiedGroup>,
+    /// The net value moved by each account of a successful operation.
+    pub account_values: Vec<AccountValue>,
+    /// The fees paid in each currency, as positive amounts.
+    pub fees: Vec<Amount>,
+}
+
+/// `group_descriptions` returns the [`Descriptions`] a group of `len`
+/// operations must meet to have the shape of a [`ValidationOperation`], if one
+/// is configured.
+fn group_descriptions(validation: &ValidationOperation, len: usize) -> Option<Descriptions> {
+    if validation.name.is_empty()
+        || validation.operation.count >= 0 && validation.operation.count as usize != len
+    {
+        return None;
+    }
+
+    Some(Descriptions {
+        operation_descriptions: vec![Some(OperationDescription {
+            type_: validation.name.clone(),
+            account: Some(AccountDescription {
+                exists: true,
+                sub_account_optional: true,
+                ..Default::default()
+            }),
+            amount: Some(AmountDescription {
+                exists: true,
+                ..Default::default()
+            }),
+            allow_repeats: true,
+            ..Default::default()
+        })],
+        sum_to_zero: if validation.operation.should_balance {
+            vec![vec![0]]
+        } else {
+            Vec::new()
+        },
+        err_unmatched: true,
+        ..Default::default()
+    })
+}
+
+/// `group_kind` labels a group of related operations using the payment and
+/// fee shapes of the [`Validations`].
+fn group_kind(validations: &Validations, group: &OperationGroup) -> OperationGroupKind {
+    let matches = |validation| {
+        group_descriptions(validation, group.operations.len()).map_or(false, |descriptions| {
+            match_operations(
+                descriptions,
+                group.operations.iter().cloned().map(Some).collect(),
+            )
+            .is_ok()
+        })
+    };
+
+    if matches(&validations.payment) {
+        OperationGroupKind::Payment
+    } else if matches(&validations.fee) {
+        OperationGroupKind::Fee
+    } else {
+        OperationGroupKind::Unknown
+    }
+}
+
+impl Parser {
+    /// `classify_transaction` labels each group of related operations in a
+    /// transaction as a payment, a fee or unknown, using the payment and fee
+    /// shapes of the asserter [`Validations`]. It also returns the net value
+    /// moved by each account and the fees paid in each currency, only
+    /// counting successful operations and those without a status.
+    pub fn classify_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> ParserResult<TransactionClassification> {
+        let asserter = self
+            .asserter
+            .as_ref()
+            .ok_or(AsserterError::NotInitialized)?;
+        let validations = asserter.validations();
+
+        let mut account_values: IndexMap<String, (AccountValue, BigInt)> = IndexMap::new();
+        let mut fees: IndexMap<String, (Currency, BigInt)> = IndexMap::new();
+        let mut groups = Vec::new();
+        for group in group_operations(transaction) {
+            let kind = group_kind(validations, &group);
+
+            for op in &group.operations {
+                // Operations that are not on-chain yet have no status.
+                let successful = op.status.is_none()
+                    || asserter.operation_successful(op).map_err(|e| {
+                        format!("failed to check the status of operation {op:?}: {e}")
+                    })?;
+                let (account, amount) = match (&op.account, &op.amount) {
+                    (Some(account), Some(amount)) if successful => (account, amount),
+                    _ => continue,
+                };
+                let value = amount_value(Some(amount)).map_err(|e| {
+                    format!("failed to return big int representation of {amount:?}: {e}")
+                })?;
+
+                if kind == OperationGroupKind::Fee && value.is_negative() {
+                    fees.entry(hash(Some(&amount.currency)))
+                        .or_insert_with(|| (amount.currency.clone(), BigInt::zero()))
+                        .1 -= &value;
+                }
+
+                account_values
+                    .entry(format!(
+                        "{}/{}",
+                        hash(Some(account)),
+                        hash(Some(&amount.currency))
+                    ))
+                    .or_insert_with(|| {
+                        let account_value = AccountValue {
+                            account: account.clone(),
+                            currency: amount.currency.clone(),
+                            value: String::new(),
+                        };
+                        (account_value, BigInt::zero())
+                    })
+                    .1 += value;
+            }
+
+            groups.push(ClassifiedGroup {
+                kind,
+                operations: group.operations,
+            });
+        }
+
+        Ok(TransactionClassification {
+            groups,
+            account_values: account_values
+                .into_values()
+                .map(|(account_value, value)| AccountValue {
+                    value: value.to_string(),
+                    ..account_value
+                })
+                .collect(),
+            fees: fees
+                .into_values()
+                .map(|(currency, value)| Amount {
+                    value: value.to_string(),
+                    currency,
+                    ..Default::default()
+                })
+                .collect(),
+        })
+    }
+}
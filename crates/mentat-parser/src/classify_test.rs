@@ -0,0 +1,205 @@
+use super::*;
+
+fn eth() -> Currency {
+    Currency {
+        symbol: "ETH".into(),
+        decimals: 18,
+        ..Default::default()
+    }
+}
+
+fn classify_asserter(validations: Validations) -> Option<Asserter> {
+    Some(
+        Asserter::new_client_with_options(
+            Some(NetworkIdentifier {
+                blockchain: "ethereum".to_string(),
+                network: "mainnet".to_string(),
+                ..Default::default()
+            }),
+            Some(UncheckedBlockIdentifier {
+                hash: "block 0".to_string(),
+                index: 0,
+            }),
+            vec!["PAYMENT".to_string(), "FEE".to_string(), "CALL".to_string()],
+            vec![
+                Some(OperationStatus {
+                    status: "SUCCESS".into(),
+                    successful: true,
+                }),
+                Some(OperationStatus {
+                    status: "FAILURE".into(),
+                    successful: false,
+                }),
+            ],
+            Vec::new(),
+            None,
+            validations,
+        )
+        .unwrap(),
+    )
+}
+
+fn validations() -> Validations {
+    Validations {
+        enabled: true,
+        chain_type: "account".into(),
+        payment: ValidationOperation {
+            name: "PAYMENT".into(),
+            operation: AsserterOperation {
+                count: 2,
+                should_balance: true,
+            },
+        },
+        fee: ValidationOperation {
+            name: "FEE".into(),
+            operation: AsserterOperation {
+                count: -1,
+                should_balance: false,
+            },
+        },
+        ..Default::default()
+    }
+}
+
+fn classify_operation(
+    index: usize,
+    related: Option<usize>,
+    type_: &str,
+    address: &str,
+    value: &str,
+    status: Option<&str>,
+) -> Operation {
+    Operation {
+        operation_identifier: OperationIdentifier {
+            index,
+            ..Default::default()
+        },
+        related_operations: related
+            .map(|index| {
+                vec![OperationIdentifier {
+                    index,
+                    ..Default::default()
+                }]
+            })
+            .unwrap_or_default(),
+        type_: type_.into(),
+        status: status.map(Into::into),
+        account: Some(AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value: value.into(),
+            currency: eth(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn account_value(address: &str, value: &str) -> AccountValue {
+    AccountValue {
+        account: AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        },
+        currency: eth(),
+        value: value.into(),
+    }
+}
+
+#[test]
+fn test_classify_transaction() {
+    let success = Some("SUCCESS");
+    let transaction = Transaction {
+        transaction_identifier: TransactionIdentifier { hash: "tx".into() },
+        operations: vec![
+            classify_operation(0, None, "PAYMENT", "addr1", "-100", success),
+            classify_operation(1, Some(0), "PAYMENT", "addr2", "100", success),
+            classify_operation(2, None, "FEE", "addr1", "-3", success),
+            classify_operation(3, None, "CALL", "addr3", "0", success),
+            classify_operation(4, None, "PAYMENT", "addr1", "-7", Some("FAILURE")),
+            classify_operation(5, Some(4), "PAYMENT", "addr4", "7", Some("FAILURE")),
+        ],
+        ..Default::default()
+    };
+
+    let parser = Parser::new(classify_asserter(validations()), None, Vec::new());
+    let classification = parser.classify_transaction(&transaction).unwrap();
+
+    assert_eq!(
+        classification
+            .groups
+            .iter()
+            .map(|g| (g.kind, g.operations.len()))
+            .collect::<Vec<_>>(),
+        vec![
+            (OperationGroupKind::Payment, 2),
+            (OperationGroupKind::Fee, 1),
+            (OperationGroupKind::Unknown, 1),
+            (OperationGroupKind::Payment, 2),
+        ]
+    );
+    assert_eq!(
+        classification.account_values,
+        vec![
+            account_value("addr1", "-103"),
+            account_value("addr2", "100"),
+            account_value("addr3", "0"),
+        ]
+    );
+    assert_eq!(
+        classification.fees,
+        vec![Amount {
+            value: "3".into(),
+            currency: eth(),
+            ..Default::default()
+        }]
+    );
+}
+
+#[test]
+fn test_classify_transaction_unbalanced_payment() {
+    let transaction = Transaction {
+        transaction_identifier: TransactionIdentifier { hash: "tx".into() },
+        operations: vec![
+            classify_operation(0, None, "PAYMENT", "addr1", "-100", None),
+            classify_operation(1, Some(0), "PAYMENT", "addr2", "99", None),
+            classify_operation(2, None, "PAYMENT", "addr1", "-1", None),
+        ],
+        ..Default::default()
+    };
+
+    let parser = Parser::new(classify_asserter(validations()), None, Vec::new());
+    let classification = parser.classify_transaction(&transaction).unwrap();
+
+    // Neither group balances or has the configured count of operations.
+    assert!(classification
+        .groups
+        .iter()
+        .all(|g| g.kind == OperationGroupKind::Unknown));
+    assert_eq!(
+        classification.account_values,
+        vec![account_value("addr1", "-101"), account_value("addr2", "99")]
+    );
+    assert!(classification.fees.is_empty());
+}
+
+#[test]
+fn test_classify_transaction_without_validations() {
+    let transaction = Transaction {
+        transaction_identifier: TransactionIdentifier { hash: "tx".into() },
+        operations: vec![classify_operation(0, None, "PAYMENT", "addr1", "-1", None)],
+        ..Default::default()
+    };
+
+    let parser = Parser::new(classify_asserter(Validations::default()), None, Vec::new());
+    let classification = parser.classify_transaction(&transaction).unwrap();
+    assert_eq!(classification.groups[0].kind, OperationGroupKind::Unknown);
+
+    let parser = Parser::new(None, None, Vec::new());
+    assert_eq!(
+        parser.classify_transaction(&transaction).unwrap_err(),
+        AsserterError::NotInitialized.into()
+    );
+}
@@ -0,0 +1,140 @@
+//! The coin changes file contains code for parsing created and spent coins.
+
+use num_traits::Signed;
+
+use super::*;
+use crate::{CoinChangesError, Parser, ParserResult};
+
+/// `CoinChanges` represents the [`Coin`]s created and spent by
+/// a [`AccountIdentifier`] in a block.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CoinChanges {
+    /// The account identifier.
+    pub account: AccountIdentifier,
+    /// The block identifier.
+    pub block: BlockIdentifier,
+    /// The coins created by the account.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<Coin>,
+    /// The coins spent by the account.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spent: Vec<Coin>,
+}
+
+impl Parser {
+    /// `coin_changes` returns all [`Coin`]s created and spent in a
+    /// particular block, grouped by [`AccountIdentifier`]. Coin amounts are
+    /// always positive. If a block is being orphaned, the coins it created
+    /// are returned as spent and the coins it spent as created again.
+    ///
+    /// An error is returned if a coin is created or spent more than once in
+    /// the block, spent before it is created, or spent by a different
+    /// account than the one that created it.
+    ///
+    /// Only coins created in the same block can be checked this way. A
+    /// spend of a coin from an earlier block is accepted as is, so flagging
+    /// spends of unknown coins, or of coins owned by another account, needs
+    /// a coin store that tracks the coins of every previous block.
+    pub fn coin_changes(
+        &self,
+        block: &Block,
+        block_removed: bool,
+    ) -> ParserResult<Vec<CoinChanges>> {
+        let mut coin_changes: IndexMap<String, CoinChanges> = IndexMap::new();
+        // The account of each coin and whether it was created and spent in
+        // the block so far.
+        let mut seen: IndexMap<&str, (String, bool, bool)> = IndexMap::new();
+
+        for tx in block.transactions.iter() {
+            for op in tx.operations.iter() {
+                let coin_change = if let Some(coin_change) = &op.coin_change {
+                    coin_change
+                } else {
+                    continue;
+                };
+
+                let successful = self
+                    .asserter
+                    .as_ref()
+                    .ok_or(AsserterError::NotInitialized)?
+                    .operation_successful(op)
+                    .map_err(|e| format!("failed to check the status of operation {op:?}: {e}"))?;
+                if !successful {
+                    continue;
+                }
+
+                let coin_identifier = &coin_change.coin_identifier.identifier;
+                let account = op.account.as_ref().ok_or_else(|| {
+                    format!(
+                        "coin {coin_identifier} is invalid: {}",
+                        CoinChangesError::AccountMissing
+                    )
+                })?;
+                let amount = op.amount.as_ref().ok_or_else(|| {
+                    format!(
+                        "coin {coin_identifier} is invalid: {}",
+                        CoinChangesError::AmountMissing
+                    )
+                })?;
+                let value = amount_value(Some(amount)).map_err(|e| {
+                    format!("failed to parse amount of coin {coin_identifier}: {e}")
+                })?;
+
+                let account_hash = hash(Some(account));
+                let (owner, created, spent) = seen
+                    .entry(coin_identifier)
+                    .or_insert_with(|| (account_hash.clone(), false, false));
+                match coin_change.coin_action {
+                    CoinAction::CoinCreated if *created => Err(format!(
+                        "coin {coin_identifier} is invalid: {}",
+                        CoinChangesError::CreatedTwice
+                    ))?,
+                    // A coin spent before it is created in the same block
+                    // is unknown at the time it is spent.
+                    CoinAction::CoinCreated if *spent => Err(format!(
+                        "coin {coin_identifier} is invalid: {}",
+                        CoinChangesError::SpentBeforeCreated
+                    ))?,
+                    CoinAction::CoinCreated => *created = true,
+                    CoinAction::CoinSpent if *spent => Err(format!(
+                        "coin {coin_identifier} is invalid: {}",
+                        CoinChangesError::DoubleSpend
+                    ))?,
+                    CoinAction::CoinSpent if *owner != account_hash => Err(format!(
+                        "coin {coin_identifier} spent by {} is invalid: {}",
+                        account_string(account),
+                        CoinChangesError::AccountMismatch
+                    ))?,
+                    CoinAction::CoinSpent => *spent = true,
+                }
+
+                let coin = Coin {
+                    coin_identifier: coin_change.coin_identifier.clone(),
+                    amount: Amount {
+                        value: value.abs().to_string(),
+                        ..amount.clone()
+                    },
+                };
+
+                let changes = coin_changes
+                    .entry(account_hash)
+                    .or_insert_with(|| CoinChanges {
+                        account: account.clone(),
+                        block: block.block_identifier.clone(),
+                        created: Vec::new(),
+                        spent: Vec::new(),
+                    });
+                match (coin_change.coin_action, block_removed) {
+                    (CoinAction::CoinCreated, false) | (CoinAction::CoinSpent, true) => {
+                        changes.created.push(coin)
+                    }
+                    (CoinAction::CoinSpent, false) | (CoinAction::CoinCreated, true) => {
+                        changes.spent.push(coin)
+                    }
+                }
+            }
+        }
+
+        Ok(coin_changes.into_iter().map(|(_, cc)| cc).collect())
+    }
+}
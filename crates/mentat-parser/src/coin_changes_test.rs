@@ -0,0 +1,259 @@
+use super::{balance_changes_test::simple_asserter_configuration, *};
+
+fn btc() -> Currency {
+    Currency {
+        symbol: "BTC".into(),
+        decimals: 8,
+        ..Default::default()
+    }
+}
+
+fn coin_operation(
+    address: &str,
+    value: &str,
+    coin: &str,
+    coin_action: CoinAction,
+    status: &str,
+) -> Operation {
+    Operation {
+        type_: "Transfer".into(),
+        status: Some(status.into()),
+        account: Some(AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value: value.into(),
+            currency: btc(),
+            ..Default::default()
+        }),
+        coin_change: Some(CoinChange {
+            coin_identifier: CoinIdentifier {
+                identifier: coin.into(),
+            },
+            coin_action,
+        }),
+        ..Default::default()
+    }
+}
+
+fn coin(identifier: &str, value: &str) -> Coin {
+    Coin {
+        coin_identifier: CoinIdentifier {
+            identifier: identifier.into(),
+        },
+        amount: Amount {
+            value: value.into(),
+            currency: btc(),
+            ..Default::default()
+        },
+    }
+}
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+fn coin_block(operations: Vec<Operation>) -> Block {
+    Block {
+        block_identifier: BlockIdentifier {
+            hash: "1".into(),
+            index: 1,
+        },
+        parent_block_identifier: BlockIdentifier {
+            hash: "0".into(),
+            index: 0,
+        },
+        transactions: vec![Transaction {
+            transaction_identifier: TransactionIdentifier { hash: "tx1".into() },
+            operations,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn block_identifier() -> BlockIdentifier {
+    coin_block(Vec::new()).block_identifier
+}
+
+#[derive(Default)]
+struct CoinChangesTest {
+    operations: Vec<Operation>,
+    block_removed: bool,
+}
+
+#[test]
+fn test_coin_changes() {
+    let transfer = || {
+        vec![
+            coin_operation("addr1", "-100", "tx0:0", CoinAction::CoinSpent, "success"),
+            coin_operation("addr2", "60", "tx1:0", CoinAction::CoinCreated, "success"),
+            coin_operation("addr1", "40", "tx1:1", CoinAction::CoinCreated, "success"),
+            coin_operation("addr3", "-5", "tx0:1", CoinAction::CoinSpent, "failure"),
+        ]
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "transfer",
+            payload: CoinChangesTest {
+                operations: transfer(),
+                ..Default::default()
+            },
+            criteria: Ok(vec![
+                CoinChanges {
+                    account: account("addr1"),
+                    block: block_identifier(),
+                    created: vec![coin("tx1:1", "40")],
+                    spent: vec![coin("tx0:0", "100")],
+                },
+                CoinChanges {
+                    account: account("addr2"),
+                    block: block_identifier(),
+                    created: vec![coin("tx1:0", "60")],
+                    spent: vec![],
+                },
+            ]),
+        },
+        TestCase {
+            name: "orphaned transfer",
+            payload: CoinChangesTest {
+                operations: transfer(),
+                block_removed: true,
+            },
+            criteria: Ok(vec![
+                CoinChanges {
+                    account: account("addr1"),
+                    block: block_identifier(),
+                    created: vec![coin("tx0:0", "100")],
+                    spent: vec![coin("tx1:1", "40")],
+                },
+                CoinChanges {
+                    account: account("addr2"),
+                    block: block_identifier(),
+                    created: vec![],
+                    spent: vec![coin("tx1:0", "60")],
+                },
+            ]),
+        },
+        TestCase {
+            name: "created and spent in block",
+            payload: CoinChangesTest {
+                operations: vec![
+                    coin_operation("addr1", "10", "tx1:0", CoinAction::CoinCreated, "success"),
+                    coin_operation("addr1", "-10", "tx1:0", CoinAction::CoinSpent, "success"),
+                ],
+                ..Default::default()
+            },
+            criteria: Ok(vec![CoinChanges {
+                account: account("addr1"),
+                block: block_identifier(),
+                created: vec![coin("tx1:0", "10")],
+                spent: vec![coin("tx1:0", "10")],
+            }]),
+        },
+        TestCase {
+            name: "double spend",
+            payload: CoinChangesTest {
+                operations: vec![
+                    coin_operation("addr1", "-10", "tx0:0", CoinAction::CoinSpent, "success"),
+                    coin_operation("addr1", "-10", "tx0:0", CoinAction::CoinSpent, "success"),
+                ],
+                ..Default::default()
+            },
+            criteria: Err(CoinChangesError::DoubleSpend.to_string()),
+        },
+        TestCase {
+            name: "created twice",
+            payload: CoinChangesTest {
+                operations: vec![
+                    coin_operation("addr1", "10", "tx1:0", CoinAction::CoinCreated, "success"),
+                    coin_operation("addr2", "10", "tx1:0", CoinAction::CoinCreated, "success"),
+                ],
+                ..Default::default()
+            },
+            criteria: Err(CoinChangesError::CreatedTwice.to_string()),
+        },
+        TestCase {
+            name: "spent before created",
+            payload: CoinChangesTest {
+                operations: vec![
+                    coin_operation("addr1", "-10", "tx1:0", CoinAction::CoinSpent, "success"),
+                    coin_operation("addr1", "10", "tx1:0", CoinAction::CoinCreated, "success"),
+                ],
+                ..Default::default()
+            },
+            criteria: Err(CoinChangesError::SpentBeforeCreated.to_string()),
+        },
+        TestCase {
+            name: "spent by another account",
+            payload: CoinChangesTest {
+                operations: vec![
+                    coin_operation("addr1", "10", "tx1:0", CoinAction::CoinCreated, "success"),
+                    coin_operation("addr2", "-10", "tx1:0", CoinAction::CoinSpent, "success"),
+                ],
+                ..Default::default()
+            },
+            criteria: Err(CoinChangesError::AccountMismatch.to_string()),
+        },
+        TestCase {
+            name: "missing amount",
+            payload: CoinChangesTest {
+                operations: vec![Operation {
+                    amount: None,
+                    ..coin_operation("addr1", "10", "tx1:0", CoinAction::CoinCreated, "success")
+                }],
+                ..Default::default()
+            },
+            criteria: Err(CoinChangesError::AmountMissing.to_string()),
+        },
+    ];
+
+    let parser = Parser::new(
+        simple_asserter_configuration(vec![
+            Some(OperationStatus {
+                status: "success".into(),
+                successful: true,
+            }),
+            Some(OperationStatus {
+                status: "failure".into(),
+                successful: false,
+            }),
+        ]),
+        None,
+        Vec::new(),
+    );
+
+    TestCase::run_output_match(tests, |t| {
+        parser
+            .coin_changes(&coin_block(t.operations), t.block_removed)
+            .map_err(|e| {
+                // Only the cause at the end of the error is compared.
+                let e = e.to_string();
+                e.rsplit(": ").next().unwrap().to_string()
+            })
+    });
+}
+
+#[test]
+fn test_coin_changes_not_initialized() {
+    let parser = Parser::new(None, None, Vec::new());
+    let block = coin_block(vec![coin_operation(
+        "addr1",
+        "10",
+        "tx1:0",
+        CoinAction::CoinCreated,
+        "success",
+    )]);
+
+    assert_eq!(
+        parser.coin_changes(&block, false).unwrap_err(),
+        AsserterError::NotInitialized.into()
+    );
+    // without coin changes there is no status to check.
+    assert!(parser.coin_changes(&coin_block(Vec::new()), false).is_ok());
+}
@@ -1,8 +1,19 @@
 //! The errors for the Parser module.
 
+use indexmap::IndexMap;
 use mentat_asserter::AsserterError;
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::IntentDiff;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub enum ExemptionsError {
+    #[error("balance change is not covered by a balance exemption")]
+    BalanceChangeNotExempt,
+}
+
 #[derive(Debug, Error, PartialEq, Eq)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub enum IntentError {
@@ -20,6 +31,23 @@ pub enum IntentError {
     ExpectedSignerMissing,
 }
 
+#[derive(Debug, Error, PartialEq, Eq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub enum CoinChangesError {
+    #[error("account of coin change is missing")]
+    AccountMissing,
+    #[error("amount of coin change is missing")]
+    AmountMissing,
+    #[error("coin is created more than once")]
+    CreatedTwice,
+    #[error("coin is spent more than once")]
+    DoubleSpend,
+    #[error("coin is spent before it is created")]
+    SpentBeforeCreated,
+    #[error("coin is spent by a different account than created it")]
+    AccountMismatch,
+}
+
 #[derive(Debug, Error, PartialEq, Eq)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub enum MatchOperationsError {
@@ -31,6 +59,10 @@ pub enum MatchOperationsError {
     AccountMatchSubAccountPopulated,
     #[error("unexpected SubAccountIdentifier.Address")]
     AccountMatchUnexpectedSubAccountAddr,
+    #[error("AccountIdentifier.Address is not valid for the address scheme")]
+    AccountMatchInvalidAddress,
+    #[error("AccountIdentifier.Address does not match the address pattern")]
+    AccountMatchAddressPatternMismatch,
     #[error("key is not present in metadata")]
     MetadataMatchKeyNotFound,
     #[error("unexpected value associated with key")]
@@ -43,6 +75,10 @@ pub enum MatchOperationsError {
     AmountMatchUnexpectedSign,
     #[error("unexpected currency")]
     AmountMatchUnexpectedCurrency,
+    #[error("amount is less than the minimum")]
+    AmountMatchBelowMin,
+    #[error("amount is greater than the maximum")]
+    AmountMatchAboveMax,
     #[error("coin change is nil")]
     CoinActionMatchCoinChangeIsNil,
     #[error("unexpected coin action")]
@@ -61,6 +97,22 @@ pub enum MatchOperationsError {
     EqualAddressesAccountIsNil,
     #[error("addresses do not match")]
     EqualAddressesAddrMismatch,
+    #[error("cannot check equality of 0 operations")]
+    EqualCurrenciesNoOperations,
+    #[error("operation is nil")]
+    EqualCurrenciesOperationIsNil,
+    #[error("amount is nil")]
+    EqualCurrenciesAmountIsNil,
+    #[error("currencies are not equal")]
+    EqualCurrenciesNotEqual,
+    #[error("cannot sum 0 operations")]
+    SumToZeroNoOperations,
+    #[error("operation is nil")]
+    SumToZeroOperationIsNil,
+    #[error("amount is nil")]
+    SumToZeroAmountIsNil,
+    #[error("amounts do not sum to zero")]
+    SumToZeroNonZero,
     #[error("match index out of range")]
     MatchIndexValidIndexOutOfRange,
     #[error("match index is nil")]
@@ -73,22 +125,65 @@ pub enum MatchOperationsError {
     MatchOperationsMatchNotFound,
     #[error("could not find match for description")]
     MatchOperationsDescriptionNotMatched,
+    #[error("metadata value kind is not supported")]
+    MetadataDescriptionKindUnsupported,
+    #[error("descriptions must be json or toml")]
+    DescriptionsFormatUnsupported,
+    #[error("toml cannot represent a missing description")]
+    DescriptionsTomlEntryMissing,
+    #[error("pattern is not a valid regular expression")]
+    PatternInvalid,
+}
+
+/// The errors of building an operation graph.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub enum OperationGraphError {
+    #[error("operation index is used more than once")]
+    DuplicateOperation,
+    #[error("related operation does not exist")]
+    RelatedOperationMissing,
+    #[error("related operations form a cycle")]
+    Cycle,
 }
 
 /// `ParserError` type.
 #[derive(Debug, Error, PartialEq, Eq)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub enum ParserError {
+    #[error(transparent)]
+    CoinChanges(#[from] CoinChangesError),
+    #[error(transparent)]
+    Exemptions(#[from] ExemptionsError),
     #[error(transparent)]
     Intent(#[from] IntentError),
     #[error(transparent)]
     MatchOperations(#[from] MatchOperationsError),
     #[error(transparent)]
+    OperationGraph(#[from] OperationGraphError),
+    #[error(transparent)]
     Asserter(#[from] AsserterError),
+    #[error("{message}")]
+    IntentMismatch {
+        message: String,
+        diff: Box<IntentDiff>,
+    },
     #[error("{0}")]
     String(String),
 }
 
+impl ParserError {
+    /// `details` returns the [`IntentDiff`] of an intent mismatch as the
+    /// `details` of a [`mentat_types::MentatError`], and nothing for any
+    /// other error.
+    pub fn details(&self) -> IndexMap<String, Value> {
+        match self {
+            Self::IntentMismatch { diff, .. } => diff.details(),
+            _ => IndexMap::new(),
+        }
+    }
+}
+
 impl From<String> for ParserError {
     fn from(s: String) -> Self {
         Self::String(s)
@@ -7,10 +7,16 @@ use super::*;
 /// whether or not the error is one thrown by the asserter
 /// along with the specific source of the error
 pub fn err(err: Box<dyn std::error::Error>) -> (bool, &'static str) {
-    if err.is::<IntentError>() {
+    if err.is::<CoinChangesError>() {
+        (true, "coin changes error")
+    } else if err.is::<ExemptionsError>() {
+        (true, "exemptions error")
+    } else if err.is::<IntentError>() {
         (true, "intent error")
     } else if err.is::<MatchOperationsError>() {
         (true, "match operations error")
+    } else if err.is::<OperationGraphError>() {
+        (true, "operation graph error")
     } else {
         (false, "")
     }
@@ -19,6 +25,16 @@ pub fn err(err: Box<dyn std::error::Error>) -> (bool, &'static str) {
 #[test]
 fn test_err() {
     let tests = vec![
+        TestCase {
+            name: "coin changes error",
+            payload: CoinChangesError::DoubleSpend.into(),
+            criteria: (true, "coin changes error"),
+        },
+        TestCase {
+            name: "exemptions error",
+            payload: ExemptionsError::BalanceChangeNotExempt.into(),
+            criteria: (true, "exemptions error"),
+        },
         TestCase {
             name: "intent error",
             payload: IntentError::ExpectedOperationAccountMismatch.into(),
@@ -29,6 +45,11 @@ fn test_err() {
             payload: MatchOperationsError::AccountMatchAccountMissing.into(),
             criteria: (true, "match operations error"),
         },
+        TestCase {
+            name: "operation graph error",
+            payload: OperationGraphError::Cycle.into(),
+            criteria: (true, "operation graph error"),
+        },
     ];
 
     TestCase::run_output_match(tests, err);
@@ -2,10 +2,25 @@
 
 use std::str::FromStr;
 
+use async_trait::async_trait;
 use num_bigint_dig::*;
 
 use super::*;
 
+/// `BalanceLookup` fetches live balances, i.e. from a node, for evaluating
+/// [`BalanceExemption`]s on balance changes that happen outside of any
+/// operation.
+#[async_trait]
+pub trait BalanceLookup: Send + Sync {
+    /// returns the live balance of `currency` held by `account` at `block`.
+    async fn balance(
+        &self,
+        account: &AccountIdentifier,
+        currency: &Currency,
+        block: &BlockIdentifier,
+    ) -> ParserResult<String>;
+}
+
 /// `match_balance_exemption` returns a [`BalanceExemption`]
 /// associated with the [`AccountIdentifier`], [`Currency`],
 /// and difference, if it exists. The provided exemptions
@@ -50,4 +65,64 @@ impl Parser {
             })
             .collect()
     }
+
+    /// `exempt_balance_change` compares the live balance of an
+    /// [`AccountIdentifier`] and [`Currency`] at a block, fetched through
+    /// `lookup`, with `computed_balance`, the balance computed from the
+    /// operations up to and including that block. The out-of-band delta, i.e.
+    /// from staking rewards or a rebasing token, is returned as a synthetic
+    /// [`BalanceChange`] tagged with the [`BalanceExemption`] that justifies
+    /// it.
+    ///
+    /// Nothing is fetched and `None` is returned if no exemption applies to
+    /// the account and currency, and `None` is returned if the balances are
+    /// equal. An error is returned if no applicable exemption allows the
+    /// delta.
+    pub async fn exempt_balance_change(
+        &self,
+        lookup: &dyn BalanceLookup,
+        account: &AccountIdentifier,
+        currency: &Currency,
+        block: &BlockIdentifier,
+        computed_balance: &str,
+    ) -> ParserResult<Option<BalanceChange>> {
+        let exemptions = self.find_exemptions(account, Some(currency));
+        if exemptions.is_empty() {
+            return Ok(None);
+        }
+
+        let live_balance = lookup
+            .balance(account, currency, block)
+            .await
+            .map_err(|e| {
+                format!(
+                    "failed to look up balance of {} at block {}: {e}",
+                    account_string(account),
+                    block.index
+                )
+            })?;
+        let difference = sub_values(&live_balance, computed_balance).map_err(|e| {
+            format!("failed to subtract {computed_balance} from {live_balance}: {e}")
+        })?;
+        if BigInt::from_str(&difference).map_or(false, |d| d.sign() == Sign::NoSign) {
+            return Ok(None);
+        }
+
+        let exemption = match_balance_exemption(&exemptions, &difference).ok_or_else(|| {
+            format!(
+                "difference {difference} of {} at block {}: {}",
+                account_string(account),
+                block.index,
+                ExemptionsError::BalanceChangeNotExempt
+            )
+        })?;
+
+        Ok(Some(BalanceChange {
+            account: Some(account.clone()),
+            currency: Some(currency.clone()),
+            block: block.clone(),
+            difference,
+            exemption: Some(exemption.clone()),
+        }))
+    }
 }
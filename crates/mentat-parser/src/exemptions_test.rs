@@ -600,3 +600,148 @@ fn test_match_balance_exemption() {
         match_balance_exemption(&exemptions, &t.payload.difference).cloned()
     });
 }
+
+/// A [`BalanceLookup`] returning a fixed live balance.
+struct FixedBalance(ParserResult<String>);
+
+#[async_trait::async_trait]
+impl BalanceLookup for FixedBalance {
+    async fn balance(
+        &self,
+        _account: &AccountIdentifier,
+        _currency: &Currency,
+        _block: &BlockIdentifier,
+    ) -> ParserResult<String> {
+        match &self.0 {
+            Ok(balance) => Ok(balance.clone()),
+            Err(e) => Err(e.to_string().into()),
+        }
+    }
+}
+
+struct ExemptBalanceChangeTest {
+    exemptions: Vec<BalanceExemption>,
+    live_balance: ParserResult<String>,
+    computed_balance: &'static str,
+}
+
+#[test]
+fn test_exempt_balance_change() {
+    let account = AccountIdentifier {
+        address: "validator".into(),
+        ..Default::default()
+    };
+    let currency = Currency {
+        symbol: "ATOM".into(),
+        decimals: 6,
+        ..Default::default()
+    };
+    let block = BlockIdentifier {
+        index: 10,
+        hash: "block 10".into(),
+    };
+    let exemption = |exemption_type| BalanceExemption {
+        currency: Some(currency.clone()),
+        exemption_type: Some(exemption_type),
+        ..Default::default()
+    };
+    let change = |difference: &str, exemption_type| {
+        Ok(Some(BalanceChange {
+            account: Some(account.clone()),
+            currency: Some(currency.clone()),
+            block: block.clone(),
+            difference: difference.into(),
+            exemption: Some(exemption(exemption_type)),
+        }))
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "no exemptions",
+            payload: ExemptBalanceChangeTest {
+                exemptions: Vec::new(),
+                live_balance: Err("should not be looked up".into()),
+                computed_balance: "100",
+            },
+            criteria: Ok(None),
+        },
+        TestCase {
+            name: "dynamic reward",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![exemption(ExemptionType::Dynamic)],
+                live_balance: Ok("120".into()),
+                computed_balance: "100",
+            },
+            criteria: change("20", ExemptionType::Dynamic),
+        },
+        TestCase {
+            name: "dynamic rebase down",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![exemption(ExemptionType::Dynamic)],
+                live_balance: Ok("95".into()),
+                computed_balance: "100",
+            },
+            criteria: change("-5", ExemptionType::Dynamic),
+        },
+        TestCase {
+            name: "balances equal",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![exemption(ExemptionType::Dynamic)],
+                live_balance: Ok("100".into()),
+                computed_balance: "100",
+            },
+            criteria: Ok(None),
+        },
+        TestCase {
+            name: "greater or equal picked over less or equal",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![
+                    exemption(ExemptionType::LessOrEqual),
+                    exemption(ExemptionType::GreaterOrEqual),
+                ],
+                live_balance: Ok("101".into()),
+                computed_balance: "100",
+            },
+            criteria: change("1", ExemptionType::GreaterOrEqual),
+        },
+        TestCase {
+            name: "not exempt",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![exemption(ExemptionType::GreaterOrEqual)],
+                live_balance: Ok("90".into()),
+                computed_balance: "100",
+            },
+            criteria: Err(ExemptionsError::BalanceChangeNotExempt.to_string()),
+        },
+        TestCase {
+            name: "lookup fails",
+            payload: ExemptBalanceChangeTest {
+                exemptions: vec![exemption(ExemptionType::Dynamic)],
+                live_balance: Err("node unavailable".into()),
+                computed_balance: "100",
+            },
+            criteria: Err("node unavailable".into()),
+        },
+    ];
+
+    TestCase::run_async_output_match(tests, |t| {
+        let (account, currency, block) = (account.clone(), currency.clone(), block.clone());
+        async move {
+            let parser = Parser::new(None, None, t.exemptions);
+            parser
+                .exempt_balance_change(
+                    &FixedBalance(t.live_balance),
+                    &account,
+                    &currency,
+                    &block,
+                    t.computed_balance,
+                )
+                .await
+                .map_err(|e| {
+                    // Only the cause at the end of the error is compared.
+                    let e = e.to_string();
+                    e.rsplit(": ").next().unwrap().to_string()
+                })
+        }
+    });
+}
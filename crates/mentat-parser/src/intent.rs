@@ -2,10 +2,13 @@
 
 use std::fmt::Write;
 
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
+use mentat_asserter::AsserterError;
 use mentat_types::{hash, AccountIdentifier, Operation, SigningPayload};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{IntentError, Parser, ParserResult};
+use crate::{IntentError, Parser, ParserError, ParserResult};
 
 /// `expected_operation` returns an error if an observed operation
 /// differs from the intended operation. An operation is considered
@@ -38,7 +41,243 @@ pub fn expected_operation(intent: &Operation, observed: &Operation) -> ParserRes
     }
 }
 
+/// `FieldDiff` is a single field that differs between an intended and an
+/// observed operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FieldDiff {
+    /// `field` is the path of the field, i.e. `account`, `amount`,
+    /// `amount.value`, `amount.currency`, `type`, `status` or
+    /// `metadata.<key>`.
+    pub field: String,
+    /// `intended` is the value of the field in the intended operation.
+    pub intended: Value,
+    /// `observed` is the value of the field in the observed operation.
+    pub observed: Value,
+}
+
+impl FieldDiff {
+    /// creates a new [`FieldDiff`] from the values of both operations.
+    fn new<T: Serialize>(field: impl Into<String>, intended: &T, observed: &T) -> Self {
+        Self {
+            field: field.into(),
+            intended: serde_json::to_value(intended).unwrap_or_default(),
+            observed: serde_json::to_value(observed).unwrap_or_default(),
+        }
+    }
+}
+
+/// `OperationDiff` pairs an intended operation with the observed operation
+/// that matches it best.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OperationDiff {
+    /// `intent_index` is the index of the intended operation.
+    pub intent_index: usize,
+    /// `observed_index` is the index of the observed operation.
+    pub observed_index: usize,
+    /// `differences` lists every field that differs between the operations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<FieldDiff>,
+    /// `successful` is whether the observed operation was successful, if that
+    /// was checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successful: Option<bool>,
+}
+
+impl OperationDiff {
+    /// `is_match` returns whether the observed operation meets the intent.
+    /// Like [`expected_operation`], only the [`AccountIdentifier`],
+    /// [`Amount`] and [`Type`] are considered.
+    pub fn is_match(&self) -> bool {
+        self.successful != Some(false)
+            && self.differences.iter().all(|d| {
+                !(d.field == "type" || d.field == "account" || d.field.starts_with("amount"))
+            })
+    }
+}
+
+/// `IntentDiff` is the structured result of comparing intended operations with
+/// observed operations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IntentDiff {
+    /// `operations` pairs each intended operation with its best matching
+    /// observed operation, ordered by `intent_index`.
+    pub operations: Vec<OperationDiff>,
+    /// `missing` are the indices of intended operations no observed operation
+    /// was left for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<usize>,
+    /// `extra` are the indices of observed operations that are not paired
+    /// with an intended operation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<usize>,
+}
+
+impl IntentDiff {
+    /// `is_match` returns whether every intended operation is met by an
+    /// observed operation. Optionally, extra observed operations are
+    /// considered a mismatch as well.
+    pub fn is_match(&self, err_extra: bool) -> bool {
+        self.missing.is_empty()
+            && (!err_extra || self.extra.is_empty())
+            && self.operations.iter().all(OperationDiff::is_match)
+    }
+
+    /// `details` returns the diff as the `details` of a
+    /// [`mentat_types::MentatError`].
+    pub fn details(&self) -> IndexMap<String, Value> {
+        [(
+            "intent_diff".to_string(),
+            serde_json::to_value(self).unwrap_or_default(),
+        )]
+        .into()
+    }
+}
+
+/// `operation_differences` returns every field that differs between an
+/// intended and an observed operation. The status and metadata are only
+/// compared if they are populated in the intent, as they are usually only
+/// added once an operation is observed.
+pub fn operation_differences(intent: &Operation, observed: &Operation) -> Vec<FieldDiff> {
+    let mut differences = Vec::new();
+
+    if hash(intent.account.as_ref()) != hash(observed.account.as_ref()) {
+        differences.push(FieldDiff::new(
+            "account",
+            &intent.account,
+            &observed.account,
+        ));
+    }
+
+    match (&intent.amount, &observed.amount) {
+        (Some(intended), Some(obs)) => {
+            if intended.value != obs.value {
+                differences.push(FieldDiff::new("amount.value", &intended.value, &obs.value));
+            }
+            if hash(Some(&intended.currency)) != hash(Some(&obs.currency)) {
+                differences.push(FieldDiff::new(
+                    "amount.currency",
+                    &intended.currency,
+                    &obs.currency,
+                ));
+            }
+        }
+        (None, None) => {}
+        (intended, obs) => differences.push(FieldDiff::new("amount", intended, obs)),
+    }
+
+    if intent.type_ != observed.type_ {
+        differences.push(FieldDiff::new("type", &intent.type_, &observed.type_));
+    }
+
+    if intent.status.is_some() && intent.status != observed.status {
+        differences.push(FieldDiff::new("status", &intent.status, &observed.status));
+    }
+
+    for (key, value) in &intent.metadata {
+        let observed_value = observed.metadata.get(key);
+        if observed_value != Some(value) {
+            differences.push(FieldDiff::new(
+                format!("metadata.{key}"),
+                &Some(value),
+                &observed_value,
+            ));
+        }
+    }
+
+    differences
+}
+
 impl Parser {
+    /// `intent_diff` compares a slice of intended operations with observed
+    /// operations. Unlike [`Parser::expected_operations`] it does not stop at
+    /// the first mismatch: each intended operation is paired with its best
+    /// matching observed operation and every differing field is reported,
+    /// along with the operations left unpaired on both sides. Optionally, the
+    /// success of observed operations is checked as well.
+    pub fn intent_diff(
+        &self,
+        intent: &[Operation],
+        observed: &[Operation],
+        confirm_success: bool,
+    ) -> ParserResult<IntentDiff> {
+        let successful = observed
+            .iter()
+            .map(|obs| {
+                if !confirm_success {
+                    return Ok(None);
+                }
+
+                self.asserter
+                    .as_ref()
+                    .ok_or(AsserterError::NotInitialized)?
+                    .operation_successful(obs)
+                    .map(Some)
+                    .map_err(|e| {
+                        format!("failed to check the status of operation {obs:?}: {e}").into()
+                    })
+            })
+            .collect::<ParserResult<Vec<_>>>()?;
+
+        let mut paired_intent = vec![false; intent.len()];
+        let mut paired_observed = vec![false; observed.len()];
+        let mut pairs = Vec::new();
+
+        // Pair the operations that match exactly first, in the same order
+        // as `expected_operations`.
+        for (o, obs) in observed.iter().enumerate() {
+            if successful[o] == Some(false) {
+                continue;
+            }
+
+            let found = (0..intent.len())
+                .find(|i| !paired_intent[*i] && expected_operation(&intent[*i], obs).is_ok());
+            if let Some(i) = found {
+                paired_intent[i] = true;
+                paired_observed[o] = true;
+                pairs.push((i, o));
+            }
+        }
+
+        // Then pair the remaining operations with the observed operation
+        // that differs the least.
+        for (i, int) in intent.iter().enumerate() {
+            if paired_intent[i] {
+                continue;
+            }
+
+            let best = (0..observed.len())
+                .filter(|o| !paired_observed[*o])
+                .min_by_key(|o| {
+                    (
+                        operation_differences(int, &observed[*o]).len(),
+                        successful[*o] == Some(false),
+                    )
+                });
+            if let Some(o) = best {
+                paired_intent[i] = true;
+                paired_observed[o] = true;
+                pairs.push((i, o));
+            }
+        }
+
+        pairs.sort_unstable();
+        Ok(IntentDiff {
+            operations: pairs
+                .into_iter()
+                .map(|(i, o)| OperationDiff {
+                    intent_index: i,
+                    observed_index: o,
+                    differences: operation_differences(&intent[i], &observed[o]),
+                    successful: successful[o],
+                })
+                .collect(),
+            missing: (0..intent.len()).filter(|i| !paired_intent[*i]).collect(),
+            extra: (0..observed.len())
+                .filter(|o| !paired_observed[*o])
+                .collect(),
+        })
+    }
+
     /// `expected_operations` returns an error if a slice of intended
     /// operations differ from observed operations. Optionally,
     /// it is possible to error if any extra observed operations
@@ -93,11 +332,16 @@ impl Parser {
             }
 
             if !found_match && err_extra {
-                Err(format!(
-                    "{}: {}",
-                    IntentError::ExpectedOperationsExtraOperation,
-                    serde_json::to_string_pretty(obs).unwrap()
-                ))?;
+                return Err(self.intent_mismatch(
+                    format!(
+                        "{}: {}",
+                        IntentError::ExpectedOperationsExtraOperation,
+                        serde_json::to_string_pretty(obs).unwrap()
+                    ),
+                    intent,
+                    observed,
+                    confirm_success,
+                ));
             }
         }
 
@@ -119,11 +363,30 @@ impl Parser {
                 .unwrap();
             }
 
-            Err(err_string)?
+            Err(self.intent_mismatch(err_string, intent, observed, confirm_success))
         } else {
             Ok(())
         }
     }
+
+    /// Attaches the [`IntentDiff`] of the operations to an error of
+    /// [`Parser::expected_operations`], so the error details show every
+    /// difference and not only the first one.
+    fn intent_mismatch(
+        &self,
+        message: String,
+        intent: &[Operation],
+        observed: &[Operation],
+        confirm_success: bool,
+    ) -> ParserError {
+        match self.intent_diff(intent, observed, confirm_success) {
+            Ok(diff) => ParserError::IntentMismatch {
+                message,
+                diff: Box::new(diff),
+            },
+            Err(_) => message.into(),
+        }
+    }
 }
 
 /// ExpectedSigners returns an error if a slice of SigningPayload
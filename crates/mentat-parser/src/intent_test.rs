@@ -815,3 +815,193 @@ fn test_expected_signers() {
 
     TestCase::run_is_err(tests, |t| expected_signers(&t.0, &t.1))
 }
+
+fn diff_operation(type_: &str, address: &str, value: &str, status: Option<&str>) -> Operation {
+    Operation {
+        type_: type_.into(),
+        status: status.map(Into::into),
+        account: Some(AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value: value.into(),
+            currency: Currency {
+                symbol: "BTC".into(),
+                decimals: 8,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_intent_diff() {
+    let asserter = simple_asserter_configuration(vec![
+        Some(OperationStatus {
+            status: "success".into(),
+            successful: true,
+        }),
+        Some(OperationStatus {
+            status: "failure".into(),
+            successful: false,
+        }),
+    ]);
+    let parser = Parser::new(asserter, None, Vec::new());
+
+    let mut fee = diff_operation("fee", "addr2", "-5", None);
+    fee.metadata.insert("memo".into(), "intended".into());
+    let intent = vec![
+        diff_operation("transfer", "addr1", "-100", None),
+        fee,
+        diff_operation("transfer", "addr3", "100", None),
+    ];
+
+    let mut observed_fee = diff_operation("fee", "addr2", "-6", Some("success"));
+    observed_fee.amount.as_mut().unwrap().currency.symbol = "ETH".into();
+    let observed = vec![
+        observed_fee,
+        diff_operation("transfer", "addr1", "-100", Some("success")),
+        diff_operation("transfer", "addr3", "100", Some("failure")),
+        diff_operation("reward", "addr4", "1", Some("success")),
+    ];
+
+    let diff = parser.intent_diff(&intent, &observed, true).unwrap();
+    assert_eq!(
+        diff,
+        IntentDiff {
+            operations: vec![
+                OperationDiff {
+                    intent_index: 0,
+                    observed_index: 1,
+                    differences: vec![],
+                    successful: Some(true),
+                },
+                OperationDiff {
+                    intent_index: 1,
+                    observed_index: 0,
+                    differences: vec![
+                        FieldDiff {
+                            field: "amount.value".into(),
+                            intended: "-5".into(),
+                            observed: "-6".into(),
+                        },
+                        FieldDiff {
+                            field: "amount.currency".into(),
+                            intended: serde_json::json!({"symbol": "BTC", "decimals": 8}),
+                            observed: serde_json::json!({"symbol": "ETH", "decimals": 8}),
+                        },
+                        FieldDiff {
+                            field: "metadata.memo".into(),
+                            intended: "intended".into(),
+                            observed: serde_json::Value::Null,
+                        },
+                    ],
+                    successful: Some(true),
+                },
+                OperationDiff {
+                    intent_index: 2,
+                    observed_index: 2,
+                    differences: vec![],
+                    successful: Some(false),
+                },
+            ],
+            missing: vec![],
+            extra: vec![3],
+        }
+    );
+    assert!(!diff.is_match(false));
+    assert!(!diff.operations[1].is_match());
+    assert!(!diff.operations[2].is_match());
+
+    let json = serde_json::to_value(diff.details()).unwrap();
+    assert_eq!(json["intent_diff"]["extra"], serde_json::json!([3]));
+    assert_eq!(
+        json["intent_diff"]["operations"][1]["differences"][0]["field"],
+        "amount.value"
+    );
+}
+
+#[test]
+fn test_intent_diff_missing_and_extra() {
+    let parser = Parser::new(None, None, Vec::new());
+    let intent = vec![
+        diff_operation("transfer", "addr1", "-100", None),
+        diff_operation("transfer", "addr2", "100", None),
+    ];
+
+    let diff = parser.intent_diff(&intent, &intent[..1], false).unwrap();
+    assert_eq!(diff.missing, vec![1]);
+    assert!(diff.extra.is_empty());
+    assert!(!diff.is_match(false));
+
+    let mut observed = intent.clone();
+    observed.push(diff_operation("fee", "addr1", "-1", None));
+    let diff = parser.intent_diff(&intent, &observed, false).unwrap();
+    assert_eq!(diff.extra, vec![2]);
+    assert!(diff.is_match(false));
+    assert!(!diff.is_match(true));
+
+    let mut observed = intent.clone();
+    observed[1].type_ = "fee".into();
+    observed[1].account = None;
+    let diff = parser.intent_diff(&intent, &observed, false).unwrap();
+    assert_eq!(
+        diff.operations[1]
+            .differences
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect::<Vec<_>>(),
+        vec!["account", "type"]
+    );
+}
+
+#[test]
+fn test_intent_diff_not_initialized() {
+    let parser = Parser::new(None, None, Vec::new());
+    let intent = vec![diff_operation("transfer", "addr1", "-100", None)];
+
+    assert_eq!(
+        parser.intent_diff(&intent, &intent, true).unwrap_err(),
+        AsserterError::NotInitialized.into()
+    );
+    assert!(parser.intent_diff(&intent, &intent, false).is_ok());
+}
+
+#[test]
+fn test_expected_operations_details() {
+    let parser = Parser::new(None, None, Vec::new());
+    let intent = vec![
+        diff_operation("transfer", "addr1", "-100", None),
+        diff_operation("transfer", "addr2", "100", None),
+    ];
+
+    let mut observed = intent.clone();
+    observed[1].amount.as_mut().unwrap().value = "99".into();
+    let err = parser
+        .expected_operations(&intent, &observed, false, false)
+        .unwrap_err();
+    assert!(err.to_string().contains("could not intent match [1]"));
+    let details = serde_json::to_value(err.details()).unwrap();
+    assert_eq!(details["intent_diff"]["operations"][1]["observed_index"], 1);
+    assert_eq!(
+        details["intent_diff"]["operations"][1]["differences"][0]["field"],
+        "amount.value"
+    );
+
+    let mut observed = intent.clone();
+    observed.push(diff_operation("fee", "addr1", "-1", None));
+    let err = parser
+        .expected_operations(&intent, &observed, true, false)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&IntentError::ExpectedOperationsExtraOperation.to_string()));
+    let details = serde_json::to_value(err.details()).unwrap();
+    assert_eq!(details["intent_diff"]["extra"], serde_json::json!([2]));
+
+    // errors that are not intent mismatches have no details.
+    assert!(ParserError::from("other").details().is_empty());
+}
@@ -6,6 +6,12 @@
 mod balance_changes;
 pub use balance_changes::*;
 
+mod classify;
+pub use classify::*;
+
+mod coin_changes;
+pub use coin_changes::*;
+
 mod errors;
 pub use errors::*;
 
@@ -21,6 +27,9 @@ pub use intent::*;
 mod match_operations;
 pub use match_operations::*;
 
+mod operation_graph;
+pub use operation_graph::*;
+
 mod parser;
 use indexmap::IndexMap;
 use mentat_asserter::*;
@@ -36,9 +45,12 @@ pub mod tests {
     use super::*;
 
     mod balance_changes_test;
+    mod classify_test;
+    mod coin_changes_test;
     mod errors_test;
     mod exemptions_test;
     mod group_operations_test;
     mod intent_test;
     mod match_operations_test;
+    mod operation_graph_test;
 }
@@ -3,18 +3,25 @@
 use std::{
     any::{type_name, Any},
     fmt,
+    fs,
+    ops::Deref,
+    path::Path,
+    str::FromStr,
 };
 
+use mentat_keys::address::AddressScheme;
 use num_bigint_dig::{BigInt, Sign};
 use num_traits::{sign::Signed, Zero};
-use serde::de::DeserializeOwned;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 use super::*;
 
 /// AmountSign is used to represent possible signedness
 /// of an amount.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AmountSign {
     #[default]
     /// `ANY` is a positive or negative amount.
@@ -101,6 +108,23 @@ pub struct MetadataDescription {
     pub value_kind: Box<dyn JsonType>,
 }
 
+/// Creates the [`JsonType`] of a named value kind.
+type ValueKindFn = fn() -> Box<dyn JsonType>;
+
+/// The names of the value kinds a [`MetadataDescription`] can have when it is
+/// serialized, and the types their values are deserialized into.
+const METADATA_VALUE_KINDS: &[(&str, ValueKindFn)] = &[
+    ("any", || Box::<Value>::default()),
+    ("array", || Box::<Vec<Value>>::default()),
+    ("bool", || Box::<bool>::default()),
+    ("integer", || Box::<i64>::default()),
+    ("null", || Box::<()>::default()),
+    ("number", || Box::<f64>::default()),
+    ("object", || Box::<IndexMap<String, Value>>::default()),
+    ("string", || Box::<String>::default()),
+    ("unsigned", || Box::<u64>::default()),
+];
+
 impl MetadataDescription {
     /// creates a new instance that contains type T
     pub fn new<T: Default + JsonType + 'static>(key: String) -> Self {
@@ -109,6 +133,69 @@ impl MetadataDescription {
             value_kind: Box::<T>::default(),
         }
     }
+
+    /// creates a new instance from the name of a value kind, i.e. `string`,
+    /// `bool`, `integer`, `unsigned`, `number`, `array`, `object`, `null`
+    /// or `any`.
+    pub fn from_kind(key: String, value_kind: &str) -> ParserResult<Self> {
+        let (_, kind) = METADATA_VALUE_KINDS
+            .iter()
+            .find(|(name, _)| *name == value_kind)
+            .ok_or_else(|| {
+                format!(
+                    "metadata value kind {value_kind} of key {key} is invalid: {}",
+                    MatchOperationsError::MetadataDescriptionKindUnsupported
+                )
+            })?;
+
+        Ok(Self {
+            key,
+            value_kind: kind(),
+        })
+    }
+
+    /// returns the name of the value kind, if it is one that can be
+    /// serialized.
+    pub fn kind(&self) -> Option<&'static str> {
+        METADATA_VALUE_KINDS
+            .iter()
+            .find(|(_, kind)| kind().display() == self.value_kind.display())
+            .map(|(name, _)| *name)
+    }
+}
+
+/// The serialized form of a [`MetadataDescription`].
+#[derive(Deserialize, Serialize)]
+#[allow(clippy::missing_docs_in_private_items)]
+struct MetadataDescriptionPre {
+    key: String,
+    value_kind: String,
+}
+
+impl Serialize for MetadataDescription {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value_kind = self.kind().ok_or_else(|| {
+            serde::ser::Error::custom(format!(
+                "metadata value kind {} of key {} is invalid: {}",
+                self.value_kind.display(),
+                self.key,
+                MatchOperationsError::MetadataDescriptionKindUnsupported
+            ))
+        })?;
+
+        MetadataDescriptionPre {
+            key: self.key.clone(),
+            value_kind: value_kind.into(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MetadataDescription {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pre = MetadataDescriptionPre::deserialize(deserializer)?;
+        Self::from_kind(pre.key, &pre.value_kind).map_err(serde::de::Error::custom)
+    }
 }
 
 impl PartialEq for MetadataDescription {
@@ -128,8 +215,79 @@ impl fmt::Debug for MetadataDescription {
     }
 }
 
+/// `Pattern` is a regular expression a string must match. It is serialized
+/// as the expression it was compiled from.
+#[derive(Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// compiles a new [`Pattern`].
+    pub fn new(pattern: &str) -> ParserResult<Self> {
+        Regex::new(pattern).map(Self).map_err(|e| {
+            format!(
+                "failed to compile pattern {pattern}: {e}: {}",
+                MatchOperationsError::PatternInvalid
+            )
+            .into()
+        })
+    }
+}
+
+impl Deref for Pattern {
+    type Target = Regex;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pattern({:?})", self.0.as_str())
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// custom serializer that writes an optional [`BigInt`] as a decimal string,
+/// like [`Amount`] values.
+fn bigint_to_str<S: Serializer>(value: &Option<BigInt>, serializer: S) -> Result<S::Ok, S::Error> {
+    value.as_ref().map(BigInt::to_string).serialize(serializer)
+}
+
+/// custom deserializer that reads an optional [`BigInt`] from a decimal
+/// string.
+fn bigint_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<BigInt>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| {
+            BigInt::from_str(&value)
+                .map_err(|_| serde::de::Error::custom(format!("{value} is not an integer")))
+        })
+        .transpose()
+}
+
 /// AccountDescription is used to describe a [`AccountIdentifier`].
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub struct AccountDescription {
     pub exists: bool,
@@ -139,19 +297,32 @@ pub struct AccountDescription {
     pub sub_account_exists: bool,
     pub sub_account_address: String,
     pub sub_account_metadata_keys: Vec<Option<MetadataDescription>>,
+    // AddressScheme if set requires AccountIdentifier.Address to be a valid
+    // address of the scheme
+    pub address_scheme: Option<AddressScheme>,
+    // AddressPattern if set requires AccountIdentifier.Address to match it
+    pub address_pattern: Option<Pattern>,
 }
 
 /// AmountDescription is used to describe a [`Amount`].
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub struct AmountDescription {
     pub exists: bool,
     pub sign: AmountSign,
     pub currency: Option<Currency>,
+    /// `min` is the smallest value the amount may have, if any.
+    #[serde(serialize_with = "bigint_to_str", deserialize_with = "bigint_from_str")]
+    pub min: Option<BigInt>,
+    /// `max` is the largest value the amount may have, if any.
+    #[serde(serialize_with = "bigint_to_str", deserialize_with = "bigint_from_str")]
+    pub max: Option<BigInt>,
 }
 
 /// OperationDescription is used to describe a [`Operation`].
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub struct OperationDescription {
     pub account: Option<AccountDescription>,
@@ -159,7 +330,13 @@ pub struct OperationDescription {
     pub metadata: Vec<Option<MetadataDescription>>,
     /// `type_` is the [`Type`] that must match. If this is left empty,
     /// any type is considered a match.
+    #[serde(rename = "type")]
     pub type_: String,
+    /// `type_pattern` if set must match the [`Type`] as well.
+    pub type_pattern: Option<Pattern>,
+    /// `status` is the status the operation must have. If this is left
+    /// empty, any status (or none) is considered a match.
+    pub status: String,
     /// `allow_repeats` indicates that multiple operations can be matched
     /// to a particular description.
     pub allow_repeats: bool,
@@ -175,7 +352,8 @@ pub struct OperationDescription {
 
 /// Descriptions contains a slice of [`OperationDescription`]s and
 /// high-level requirements enforced across multiple [`Operation`]s.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub struct Descriptions {
     pub operation_descriptions: Vec<Option<OperationDescription>>,
@@ -199,11 +377,102 @@ pub struct Descriptions {
     /// [`match_operations`] will error if all groups of operations
     /// addresses aren't equal.
     pub equal_addresses: Vec<Vec<usize>>,
+    /// `equal_currencies` are specified using the operation indices of
+    /// [`OperationDescription`]s to handle out of order matches.
+    /// [`match_operations`] will error if all groups of operations
+    /// currencies aren't equal.
+    pub equal_currencies: Vec<Vec<usize>>,
+    /// `sum_to_zero` are specified using the operation indices of
+    /// [`OperationDescription`]s to handle out of order matches.
+    /// [`match_operations`] will error if the amounts of each currency in
+    /// all groups of operations don't sum to zero.
+    pub sum_to_zero: Vec<Vec<usize>>,
     /// `err_unmatched` indicates that an error should be returned
     /// if all operations cannot be matched to a description.
     pub err_unmatched: bool,
 }
 
+impl Descriptions {
+    /// `load` reads [`Descriptions`] from a JSON or TOML file, picked by the
+    /// extension of `path`. This allows the rules used to match operations
+    /// to be shipped and versioned separately from the code using them.
+    pub fn load(path: &Path) -> ParserResult<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read file {}: {e}", path.display()))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&content),
+            Some("toml") => Self::from_toml(&content),
+            _ => Err(format!(
+                "file {} is not a json or toml file: {}",
+                path.display(),
+                MatchOperationsError::DescriptionsFormatUnsupported
+            ))?,
+        }
+    }
+
+    /// `from_json` parses [`Descriptions`] from JSON.
+    pub fn from_json(content: &str) -> ParserResult<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| format!("failed to deserialize descriptions: {e}").into())
+    }
+
+    /// `from_toml` parses [`Descriptions`] from TOML.
+    pub fn from_toml(content: &str) -> ParserResult<Self> {
+        toml::from_str(content)
+            .map_err(|e| format!("failed to deserialize descriptions: {e}").into())
+    }
+
+    /// `to_json` serializes the [`Descriptions`] to pretty printed JSON.
+    pub fn to_json(&self) -> ParserResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("failed to serialize descriptions: {e}").into())
+    }
+
+    /// `to_toml` serializes the [`Descriptions`] to TOML. TOML has no null,
+    /// so `None` entries of the description lists cannot be written and are
+    /// an error. They are not skipped either, because that would shift the
+    /// indices the group requirements refer to.
+    pub fn to_toml(&self) -> ParserResult<String> {
+        if let Some(entry) = self.missing_entry() {
+            Err(format!(
+                "{entry} is none: {}",
+                MatchOperationsError::DescriptionsTomlEntryMissing
+            ))?;
+        }
+
+        // Going through a `toml::Value` orders the fields of every table so
+        // that plain values come before nested tables, as TOML requires.
+        toml::Value::try_from(self)
+            .and_then(|value| toml::to_string(&value))
+            .map_err(|e| format!("failed to serialize descriptions: {e}").into())
+    }
+
+    /// The location of the first `None` entry of the description lists.
+    fn missing_entry(&self) -> Option<String> {
+        for (i, description) in self.operation_descriptions.iter().enumerate() {
+            let description = match description {
+                Some(description) => description,
+                None => return Some(format!("operation_descriptions[{i}]")),
+            };
+            if let Some(j) = description.metadata.iter().position(Option::is_none) {
+                return Some(format!("operation_descriptions[{i}].metadata[{j}]"));
+            }
+            if let Some(j) = description.account.as_ref().and_then(|account| {
+                account
+                    .sub_account_metadata_keys
+                    .iter()
+                    .position(Option::is_none)
+            }) {
+                return Some(format!(
+                    "operation_descriptions[{i}].account.sub_account_metadata_keys[{j}]"
+                ));
+            }
+        }
+        None
+    }
+}
+
 /// metadata_match returns an error if a `IndexMap<String, Value>` does not meet
 /// a slice of [`MetadataDescription`].
 pub fn metadata_match(
@@ -251,6 +520,18 @@ pub fn account_match(
         return Ok(());
     };
 
+    verify_account_address(req.address_scheme.as_ref(), account)?;
+    if let Some(pattern) = &req.address_pattern {
+        if !pattern.is_match(&account.address) {
+            Err(format!(
+                "address {} does not match {}: {}",
+                account.address,
+                pattern.as_str(),
+                MatchOperationsError::AccountMatchAddressPatternMismatch
+            ))?
+        }
+    }
+
     if req.sub_account_optional {
         // Optionally can require a certain subaccount address if subaccount is present
         if account.sub_account.is_some() {
@@ -310,6 +591,20 @@ pub fn verify_sub_account_address(
     }
 }
 
+/// [`verify_account_address`] verifies the account address is a valid address
+/// of the address scheme if one is given.
+pub fn verify_account_address(
+    address_scheme: Option<&AddressScheme>,
+    account: &AccountIdentifier,
+) -> ParserResult<()> {
+    match address_scheme {
+        Some(address_scheme) => address_scheme.validate(&account.address).map_err(|e| {
+            format!("{e}: {}", MatchOperationsError::AccountMatchInvalidAddress).into()
+        }),
+        None => Ok(()),
+    }
+}
+
 /// [`amount_match`] returns an error if an [`Amount`] does not meet an
 /// [`AmountDescription`].
 pub fn amount_match(req: Option<&AmountDescription>, amount: Option<&Amount>) -> ParserResult<()> {
@@ -339,6 +634,23 @@ pub fn amount_match(req: Option<&AmountDescription>, amount: Option<&Amount>) ->
         ))?
     }
 
+    if req.min.is_some() || req.max.is_some() {
+        let val = amount_value(amount)
+            .map_err(|e| format!("failed to return big int representation of {amount:?}: {e}"))?;
+        if let Some(min) = req.min.as_ref().filter(|min| &val < min) {
+            Err(format!(
+                "amount {val} is less than {min}: {}",
+                MatchOperationsError::AmountMatchBelowMin,
+            ))?
+        }
+        if let Some(max) = req.max.as_ref().filter(|max| &val > max) {
+            Err(format!(
+                "amount {val} is greater than {max}: {}",
+                MatchOperationsError::AmountMatchAboveMax,
+            ))?
+        }
+    }
+
     // If no currency is provided, anything is ok.
     if req.currency.is_none() {
         return Ok(());
@@ -400,6 +712,8 @@ pub fn operation_match(
         let des = des.as_ref().unwrap();
         if matches[i].is_some() && !des.allow_repeats
             || !des.type_.is_empty() && des.type_ != operation.type_
+            || matches!(&des.type_pattern, Some(p) if !p.is_match(&operation.type_))
+            || !des.status.is_empty() && operation.status.as_deref() != Some(des.status.as_str())
             || account_match(des.account.as_ref(), operation.account.as_ref()).is_err()
             || amount_match(des.amount.as_ref(), operation.amount.as_ref()).is_err()
             || metadata_match(&des.metadata, &operation.metadata).is_err()
@@ -574,6 +888,71 @@ pub fn equal_addresses(ops: &[Option<&Operation>]) -> ParserResult<()> {
     Ok(())
 }
 
+/// equalCurrencies returns an error if a slice of operations do not have
+/// equal currencies.
+pub fn equal_currencies(ops: &[Option<&Operation>]) -> ParserResult<()> {
+    if ops.is_empty() {
+        Err(MatchOperationsError::EqualCurrenciesNoOperations)?;
+    }
+
+    let mut base = None;
+    for op in ops {
+        let amount = op
+            .ok_or(MatchOperationsError::EqualCurrenciesOperationIsNil)?
+            .amount
+            .as_ref()
+            .ok_or(MatchOperationsError::EqualCurrenciesAmountIsNil)?;
+
+        match base {
+            None => base = Some(&amount.currency),
+            Some(currency) if hash(Some(currency)) != hash(Some(&amount.currency)) => {
+                Err(format!(
+                    "currency {:?} is not equal to currency {currency:?}: {}",
+                    amount.currency,
+                    MatchOperationsError::EqualCurrenciesNotEqual,
+                ))?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// sumToZero returns an error if the amounts of each currency in a slice of
+/// operations do not sum to zero.
+pub fn sum_to_zero(ops: &[Option<&Operation>]) -> ParserResult<()> {
+    if ops.is_empty() {
+        Err(MatchOperationsError::SumToZeroNoOperations)?;
+    }
+
+    let mut sums = IndexMap::<String, (&Currency, BigInt)>::new();
+    for op in ops {
+        let amount = op
+            .ok_or(MatchOperationsError::SumToZeroOperationIsNil)?
+            .amount
+            .as_ref()
+            .ok_or(MatchOperationsError::SumToZeroAmountIsNil)?;
+        let val = amount_value(Some(amount))
+            .map_err(|e| format!("failed to return big int representation of {amount:?}: {e}"))?;
+        let currency = &amount.currency;
+        sums.entry(hash(Some(currency)))
+            .or_insert_with(|| (currency, BigInt::zero()))
+            .1 += val;
+    }
+
+    for (currency, sum) in sums.values() {
+        if !sum.is_zero() {
+            Err(format!(
+                "amounts of currency {currency:?} sum to {sum}: {}",
+                MatchOperationsError::SumToZeroNonZero,
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::missing_docs_in_private_items)]
 pub fn match_index_valid(matches: &[Option<Match>], index: usize) -> ParserResult<()> {
     match matches.get(index) {
@@ -657,12 +1036,17 @@ pub fn compare_opposite_matches(
 }
 
 /// [`comparison_matches`] ensures collections of [`Operation`]
-/// have either equal or opposite amounts.
+/// have either equal or opposite amounts, and satisfy the other
+/// group descriptions.
 pub fn comparison_match(descriptions: Descriptions, matches: &[Option<Match>]) -> ParserResult<()> {
     check_ops(&descriptions.equal_amounts, matches, equal_amounts)
         .map_err(|e| format!("operation amounts are not equal: {e}"))?;
     check_ops(&descriptions.equal_addresses, matches, equal_addresses)
         .map_err(|e| format!("operation addresses are not equal: {e}"))?;
+    check_ops(&descriptions.equal_currencies, matches, equal_currencies)
+        .map_err(|e| format!("operation currencies are not equal: {e}"))?;
+    check_ops(&descriptions.sum_to_zero, matches, sum_to_zero)
+        .map_err(|e| format!("operation amounts do not sum to zero: {e}"))?;
     compare_opposite_matches(&descriptions.opposite_amounts, matches, opposite_amounts)
         .map_err(|e| format!("operation amounts are not opposite: {e}"))?;
     compare_opposite_matches(
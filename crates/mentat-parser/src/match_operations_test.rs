@@ -1,6 +1,9 @@
+use std::fs;
+
 use indexmap::indexmap;
+use mentat_keys::address::AddressScheme;
 use num_bigint_dig::BigInt;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::*;
 
@@ -766,6 +769,7 @@ fn test_match_operations() {
                                         decimals: 18,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -782,6 +786,7 @@ fn test_match_operations() {
                                         decimals: 8,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 coin_action: CoinAction::CoinSpent,
                                 ..Default::default()
@@ -890,6 +895,7 @@ fn test_match_operations() {
                                         decimals: 18,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -906,6 +912,7 @@ fn test_match_operations() {
                                         decimals: 8,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 coin_action: CoinAction::CoinSpent,
                                 ..Default::default()
@@ -975,6 +982,7 @@ fn test_match_operations() {
                                         decimals: 18,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -991,6 +999,7 @@ fn test_match_operations() {
                                         decimals: 8,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 coin_action: CoinAction::CoinSpent,
                                 ..Default::default()
@@ -1056,6 +1065,7 @@ fn test_match_operations() {
                                         decimals: 18,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -1072,6 +1082,7 @@ fn test_match_operations() {
                                         decimals: 8,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -1175,6 +1186,7 @@ fn test_match_operations() {
                                         decimals: 18,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -1191,6 +1203,7 @@ fn test_match_operations() {
                                         decimals: 8,
                                         ..Default::default()
                                     }),
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             }),
@@ -2734,3 +2747,586 @@ fn test_match() {
 
     TestCase::run_output_match(tests, |test| Match::first(test.as_ref()).into());
 }
+
+#[test]
+fn test_account_match_address_scheme() {
+    let description = AccountDescription {
+        exists: true,
+        address_scheme: Some(AddressScheme::Ethereum),
+        ..Default::default()
+    };
+    let account = |address: &str| AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "valid address",
+            payload: account("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"),
+            criteria: None,
+        },
+        TestCase {
+            name: "invalid address",
+            payload: account("addr1"),
+            criteria: Some(MatchOperationsError::AccountMatchInvalidAddress.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |account| {
+        account_match(Some(&description), Some(&account))
+    });
+}
+
+fn transfer_descriptions() -> Descriptions {
+    Descriptions {
+        opposite_amounts: vec![vec![0, 1]],
+        operation_descriptions: vec![
+            Some(OperationDescription {
+                account: Some(AccountDescription {
+                    exists: true,
+                    sub_account_metadata_keys: vec![Some(MetadataDescription::new::<String>(
+                        "validator".into(),
+                    ))],
+                    ..Default::default()
+                }),
+                amount: Some(AmountDescription {
+                    exists: true,
+                    sign: AmountSign::Negative,
+                    currency: Some(Currency {
+                        symbol: "BTC".into(),
+                        decimals: 8,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                metadata: vec![Some(MetadataDescription::new::<u64>("nonce".into()))],
+                type_: "transfer".into(),
+                ..Default::default()
+            }),
+            Some(OperationDescription {
+                account: Some(AccountDescription {
+                    exists: true,
+                    address_scheme: Some(AddressScheme::Ethereum),
+                    ..Default::default()
+                }),
+                amount: Some(AmountDescription {
+                    exists: true,
+                    sign: AmountSign::Positive,
+                    ..Default::default()
+                }),
+                type_: "transfer".into(),
+                ..Default::default()
+            }),
+        ],
+        err_unmatched: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_descriptions_from_json() {
+    let descriptions = Descriptions::from_json(
+        r#"{
+            "opposite_amounts": [[0, 1]],
+            "operation_descriptions": [
+                {
+                    "account": {
+                        "exists": true,
+                        "sub_account_metadata_keys": [{ "key": "validator", "value_kind": "string" }]
+                    },
+                    "amount": {
+                        "exists": true,
+                        "sign": "negative",
+                        "currency": { "symbol": "BTC", "decimals": 8 }
+                    },
+                    "metadata": [{ "key": "nonce", "value_kind": "unsigned" }],
+                    "type": "transfer"
+                },
+                {
+                    "account": { "exists": true, "address_scheme": { "type": "ethereum" } },
+                    "amount": { "exists": true, "sign": "positive" },
+                    "type": "transfer"
+                }
+            ],
+            "err_unmatched": true
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(descriptions, transfer_descriptions());
+}
+
+#[test]
+fn test_descriptions_round_trip() {
+    let descriptions = transfer_descriptions();
+
+    let json = descriptions.to_json().unwrap();
+    assert_eq!(Descriptions::from_json(&json).unwrap(), descriptions);
+
+    let toml = descriptions.to_toml().unwrap();
+    assert_eq!(Descriptions::from_toml(&toml).unwrap(), descriptions);
+}
+
+#[test]
+fn test_descriptions_to_toml_missing_entry() {
+    let tests = vec![
+        TestCase {
+            name: "missing operation description",
+            payload: Descriptions {
+                operation_descriptions: vec![Some(Default::default()), None],
+                ..Default::default()
+            },
+            criteria: "operation_descriptions[1] is none".to_string(),
+        },
+        TestCase {
+            name: "missing metadata description",
+            payload: Descriptions {
+                operation_descriptions: vec![Some(OperationDescription {
+                    metadata: vec![None],
+                    ..Default::default()
+                })],
+                ..Default::default()
+            },
+            criteria: "operation_descriptions[0].metadata[0] is none".to_string(),
+        },
+        TestCase {
+            name: "missing sub account metadata description",
+            payload: Descriptions {
+                operation_descriptions: vec![Some(OperationDescription {
+                    account: Some(AccountDescription {
+                        sub_account_metadata_keys: vec![
+                            Some(MetadataDescription::new::<String>("validator".into())),
+                            None,
+                        ],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })],
+                ..Default::default()
+            },
+            criteria: "operation_descriptions[0].account.sub_account_metadata_keys[1] is none"
+                .to_string(),
+        },
+    ];
+
+    TestCase::run_output_match(tests, |descriptions| {
+        // JSON has null for the missing entries, TOML does not.
+        let json = descriptions.to_json().unwrap();
+        assert_eq!(Descriptions::from_json(&json).unwrap(), descriptions);
+
+        let err = descriptions.to_toml().unwrap_err().to_string();
+        assert!(err.contains(&MatchOperationsError::DescriptionsTomlEntryMissing.to_string()));
+        err.split(':').next().unwrap().to_string()
+    });
+}
+
+#[test]
+fn test_descriptions_load() {
+    let dir = std::env::temp_dir().join(format!("mentat-descriptions-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let descriptions = transfer_descriptions();
+
+    let json = dir.join("transfer.json");
+    fs::write(&json, descriptions.to_json().unwrap()).unwrap();
+    let toml = dir.join("transfer.toml");
+    fs::write(&toml, descriptions.to_toml().unwrap()).unwrap();
+    let yaml = dir.join("transfer.yaml");
+    fs::write(&yaml, "").unwrap();
+
+    let tests = vec![
+        TestCase {
+            name: "json",
+            payload: json,
+            criteria: None,
+        },
+        TestCase {
+            name: "toml",
+            payload: toml,
+            criteria: None,
+        },
+        TestCase {
+            name: "unsupported extension",
+            payload: yaml,
+            criteria: Some(MatchOperationsError::DescriptionsFormatUnsupported.into()),
+        },
+        TestCase {
+            name: "missing file",
+            payload: dir.join("missing.json"),
+            criteria: Some(ParserError::String("failed to read file".into())),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |path| {
+        let loaded = Descriptions::load(&path)?;
+        assert_eq!(loaded, descriptions);
+        Ok::<_, ParserError>(())
+    });
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_loaded_descriptions_match_operations() {
+    let descriptions = Descriptions::from_toml(
+        r#"
+        opposite_amounts = [[0, 1]]
+
+        [[operation_descriptions]]
+        type = "transfer"
+        account = { exists = true }
+        amount = { exists = true, sign = "negative" }
+
+        [[operation_descriptions]]
+        type = "transfer"
+        account = { exists = true }
+        amount = { exists = true, sign = "positive" }
+        "#,
+    )
+    .unwrap();
+    let operation = |address: &str, value: &str| {
+        Some(Operation {
+            type_: "transfer".into(),
+            account: Some(AccountIdentifier {
+                address: address.into(),
+                ..Default::default()
+            }),
+            amount: Some(Amount {
+                value: value.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    };
+
+    let matches = match_operations(
+        descriptions,
+        vec![operation("addr2", "100"), operation("addr1", "-100")],
+    )
+    .unwrap();
+    let (operation, amount) = Match::first(matches[0].as_ref());
+    assert_eq!(
+        operation.unwrap().account.as_ref().unwrap().address,
+        "addr1"
+    );
+    assert_eq!(amount.unwrap(), &BigInt::from(-100));
+}
+
+#[test]
+fn test_metadata_description_kind() {
+    assert_eq!(
+        MetadataDescription::from_kind("key".into(), "object").unwrap(),
+        MetadataDescription::new::<IndexMap<String, Value>>("key".into())
+    );
+    assert!(MetadataDescription::from_kind("key".into(), "float").is_err());
+    assert_eq!(MetadataDescription::new::<i32>("key".into()).kind(), None);
+    assert!(serde_json::to_string(&MetadataDescription::new::<i32>("key".into())).is_err());
+}
+
+#[test]
+fn test_amount_match_range() {
+    let description = AmountDescription {
+        exists: true,
+        min: Some(BigInt::from(-10)),
+        max: Some("100000000000000000000000".parse().unwrap()),
+        ..Default::default()
+    };
+    let amount = |value: &str| Amount {
+        value: value.into(),
+        ..Default::default()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "within range",
+            payload: amount("5"),
+            criteria: None,
+        },
+        TestCase {
+            name: "at min",
+            payload: amount("-10"),
+            criteria: None,
+        },
+        TestCase {
+            name: "at max",
+            payload: amount("100000000000000000000000"),
+            criteria: None,
+        },
+        TestCase {
+            name: "below min",
+            payload: amount("-11"),
+            criteria: Some(MatchOperationsError::AmountMatchBelowMin.into()),
+        },
+        TestCase {
+            name: "above max",
+            payload: amount("100000000000000000000001"),
+            criteria: Some(MatchOperationsError::AmountMatchAboveMax.into()),
+        },
+        TestCase {
+            name: "not an integer",
+            payload: amount("1.5"),
+            criteria: Some(ParserError::String("is not an integer".into())),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |amount| {
+        amount_match(Some(&description), Some(&amount))
+    });
+}
+
+#[test]
+fn test_account_match_address_pattern() {
+    let description = AccountDescription {
+        exists: true,
+        address_pattern: Some(Pattern::new("^validator-[0-9]+$").unwrap()),
+        ..Default::default()
+    };
+    let account = |address: &str| AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "matches",
+            payload: account("validator-12"),
+            criteria: None,
+        },
+        TestCase {
+            name: "does not match",
+            payload: account("delegator-12"),
+            criteria: Some(MatchOperationsError::AccountMatchAddressPatternMismatch.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |account| {
+        account_match(Some(&description), Some(&account))
+    });
+}
+
+#[test]
+fn test_pattern() {
+    assert!(Pattern::new("(").is_err());
+    assert_eq!(
+        serde_json::to_string(&Pattern::new("^stake_.*$").unwrap()).unwrap(),
+        r#""^stake_.*$""#
+    );
+    assert!(serde_json::from_str::<Pattern>(r#""[""#).is_err());
+}
+
+fn constrained_operation(
+    type_: &str,
+    status: Option<&str>,
+    value: &str,
+    symbol: &str,
+) -> Option<Operation> {
+    Some(Operation {
+        type_: type_.into(),
+        status: status.map(Into::into),
+        account: Some(AccountIdentifier {
+            address: "addr".into(),
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value: value.into(),
+            currency: Currency {
+                symbol: symbol.into(),
+                decimals: 8,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_match_operations_constraints() {
+    let swap = || Descriptions {
+        operation_descriptions: vec![
+            Some(OperationDescription {
+                type_pattern: Some(Pattern::new("^swap_(in|out)$").unwrap()),
+                status: "success".into(),
+                amount: Some(AmountDescription {
+                    exists: true,
+                    ..Default::default()
+                }),
+                allow_repeats: true,
+                ..Default::default()
+            }),
+            Some(OperationDescription {
+                type_: "fee".into(),
+                amount: Some(AmountDescription {
+                    exists: true,
+                    sign: AmountSign::Negative,
+                    ..Default::default()
+                }),
+                optional: true,
+                ..Default::default()
+            }),
+        ],
+        sum_to_zero: vec![vec![0]],
+        err_unmatched: true,
+        ..Default::default()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "multi asset swap",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_in", Some("success"), "100", "BTC"),
+                    constrained_operation("swap_out", Some("success"), "-5", "ETH"),
+                    constrained_operation("swap_in", Some("success"), "5", "ETH"),
+                ],
+                descriptions: swap(),
+            },
+            criteria: None,
+        },
+        TestCase {
+            name: "currency does not sum to zero",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_in", Some("success"), "100", "ETH"),
+                ],
+                descriptions: swap(),
+            },
+            criteria: Some(MatchOperationsError::SumToZeroNonZero.into()),
+        },
+        TestCase {
+            name: "type does not match pattern",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_inout", Some("success"), "100", "BTC"),
+                ],
+                descriptions: swap(),
+            },
+            criteria: Some(MatchOperationsError::MatchOperationsMatchNotFound.into()),
+        },
+        TestCase {
+            name: "status does not match",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_in", None, "100", "BTC"),
+                ],
+                descriptions: swap(),
+            },
+            criteria: Some(MatchOperationsError::MatchOperationsMatchNotFound.into()),
+        },
+        TestCase {
+            name: "equal currencies",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_in", Some("success"), "100", "BTC"),
+                    constrained_operation("fee", None, "-1", "BTC"),
+                ],
+                descriptions: Descriptions {
+                    equal_currencies: vec![vec![0, 1]],
+                    ..swap()
+                },
+            },
+            criteria: None,
+        },
+        TestCase {
+            name: "currencies are not equal",
+            payload: MatchOperationsTest {
+                operations: vec![
+                    constrained_operation("swap_out", Some("success"), "-100", "BTC"),
+                    constrained_operation("swap_in", Some("success"), "100", "BTC"),
+                    constrained_operation("fee", None, "-1", "ETH"),
+                ],
+                descriptions: Descriptions {
+                    equal_currencies: vec![vec![0, 1]],
+                    ..swap()
+                },
+            },
+            criteria: Some(MatchOperationsError::EqualCurrenciesNotEqual.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |t| {
+        match_operations(t.descriptions, t.operations).map(|_| ())
+    });
+}
+
+#[test]
+fn test_group_constraints_nil() {
+    let operation = constrained_operation("transfer", None, "100", "BTC").unwrap();
+    let no_amount = Operation {
+        amount: None,
+        ..operation.clone()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "equal currencies with nil operation",
+            payload: (
+                equal_currencies as fn(&[Option<&Operation>]) -> _,
+                vec![Some(&operation), None],
+            ),
+            criteria: Some(MatchOperationsError::EqualCurrenciesOperationIsNil.into()),
+        },
+        TestCase {
+            name: "equal currencies with nil amount",
+            payload: (
+                equal_currencies as fn(&[Option<&Operation>]) -> _,
+                vec![Some(&operation), Some(&no_amount)],
+            ),
+            criteria: Some(MatchOperationsError::EqualCurrenciesAmountIsNil.into()),
+        },
+        TestCase {
+            name: "sum to zero with nil operation",
+            payload: (
+                sum_to_zero as fn(&[Option<&Operation>]) -> _,
+                vec![Some(&operation), None],
+            ),
+            criteria: Some(MatchOperationsError::SumToZeroOperationIsNil.into()),
+        },
+        TestCase {
+            name: "sum to zero with nil amount",
+            payload: (
+                sum_to_zero as fn(&[Option<&Operation>]) -> _,
+                vec![Some(&operation), Some(&no_amount)],
+            ),
+            criteria: Some(MatchOperationsError::SumToZeroAmountIsNil.into()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |(check, ops)| check(&ops));
+}
+
+#[test]
+fn test_constraints_round_trip() {
+    let descriptions = Descriptions {
+        operation_descriptions: vec![Some(OperationDescription {
+            account: Some(AccountDescription {
+                exists: true,
+                address_pattern: Some(Pattern::new("^cosmos1").unwrap()),
+                ..Default::default()
+            }),
+            amount: Some(AmountDescription {
+                exists: true,
+                min: Some(BigInt::from(1)),
+                max: Some("1000000000000000000000".parse().unwrap()),
+                ..Default::default()
+            }),
+            type_pattern: Some(Pattern::new("^(delegate|undelegate)$").unwrap()),
+            status: "success".into(),
+            ..Default::default()
+        })],
+        equal_currencies: vec![vec![0]],
+        sum_to_zero: vec![vec![0]],
+        ..Default::default()
+    };
+
+    let json = descriptions.to_json().unwrap();
+    assert!(json.contains(r#""max": "1000000000000000000000""#));
+    assert_eq!(Descriptions::from_json(&json).unwrap(), descriptions);
+    let toml = descriptions.to_toml().unwrap();
+    assert_eq!(Descriptions::from_toml(&toml).unwrap(), descriptions);
+}
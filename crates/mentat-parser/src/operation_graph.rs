@@ -0,0 +1,514 @@
+//! The operation graph file contains code for analysing how the operations
+//! of a transaction relate to each other, and for following related
+//! transactions across blocks.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use async_trait::async_trait;
+use num_bigint_dig::BigInt;
+use num_traits::{Signed, Zero};
+
+use super::*;
+use crate::{OperationGraphError, Parser, ParserResult};
+
+/// `OperationEdge` is a directed edge from an operation to an operation that
+/// lists it in its `related_operations`. Both are identified by their
+/// [`OperationIdentifier`] index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OperationEdge {
+    /// The index of the related operation.
+    pub from: usize,
+    /// The index of the operation listing it.
+    pub to: usize,
+}
+
+/// `OperationGraph` is the directed graph formed by the `related_operations`
+/// of a [`Transaction`].
+#[derive(Clone, Debug)]
+pub struct OperationGraph<'a> {
+    /// The operations of the transaction keyed by their index, in
+    /// transaction order.
+    operations: IndexMap<usize, &'a Operation>,
+    /// The edges of the graph, in transaction order.
+    edges: Vec<OperationEdge>,
+}
+
+impl<'a> OperationGraph<'a> {
+    /// creates a new `OperationGraph` from the operations of a transaction.
+    /// Unlike [`group_operations`], the transaction does not need to be
+    /// asserted first, so the graph may contain cycles.
+    pub fn new(transaction: &'a Transaction) -> ParserResult<Self> {
+        let mut operations = IndexMap::new();
+        for op in &transaction.operations {
+            let index = op.operation_identifier.index;
+            if operations.insert(index, op).is_some() {
+                Err(format!(
+                    "operation {index} is invalid: {}",
+                    OperationGraphError::DuplicateOperation
+                ))?;
+            }
+        }
+
+        let mut edges = Vec::new();
+        for op in &transaction.operations {
+            for related in &op.related_operations {
+                if !operations.contains_key(&related.index) {
+                    Err(format!(
+                        "operation {} is invalid: {} {}",
+                        op.operation_identifier.index,
+                        OperationGraphError::RelatedOperationMissing,
+                        related.index
+                    ))?;
+                }
+                edges.push(OperationEdge {
+                    from: related.index,
+                    to: op.operation_identifier.index,
+                });
+            }
+        }
+
+        Ok(Self { operations, edges })
+    }
+
+    /// `operation` returns the operation with an index, if any.
+    pub fn operation(&self, index: usize) -> Option<&'a Operation> {
+        self.operations.get(&index).copied()
+    }
+
+    /// `edges` returns all edges of the graph.
+    pub fn edges(&self) -> &[OperationEdge] {
+        &self.edges
+    }
+
+    /// `parents` returns the indices of the operations an operation lists in
+    /// its `related_operations`.
+    pub fn parents(&self, index: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.to == index)
+            .map(|edge| edge.from)
+            .collect()
+    }
+
+    /// `children` returns the indices of the operations that list an
+    /// operation in their `related_operations`.
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == index)
+            .map(|edge| edge.to)
+            .collect()
+    }
+
+    /// `child_positions` returns the positions of the children of each
+    /// operation, by the position of the operation.
+    fn child_positions(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![Vec::new(); self.operations.len()];
+        for edge in &self.edges {
+            let from = self.operations.get_index_of(&edge.from).unwrap();
+            children[from].push(self.operations.get_index_of(&edge.to).unwrap());
+        }
+        children
+    }
+
+    /// `find_cycle` returns the indices of the operations of a cycle, in
+    /// edge order, or `None` if the graph is acyclic.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        /// The state of an operation during the depth first search.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            /// The operation has not been reached yet.
+            New,
+            /// The operation is on the current path.
+            OnPath,
+            /// All paths from the operation have been searched.
+            Done,
+        }
+
+        let children = self.child_positions();
+        let mut visits = vec![Visit::New; self.operations.len()];
+        for start in 0..self.operations.len() {
+            if visits[start] != Visit::New {
+                continue;
+            }
+
+            // Each entry is a position in `operations` and the number of its
+            // children searched so far.
+            let mut path = vec![(start, 0)];
+            visits[start] = Visit::OnPath;
+            while let Some((position, searched)) = path.last_mut() {
+                let child = match children[*position].get(*searched) {
+                    Some(child) => *child,
+                    None => {
+                        visits[*position] = Visit::Done;
+                        path.pop();
+                        continue;
+                    }
+                };
+                *searched += 1;
+                match visits[child] {
+                    Visit::New => {
+                        visits[child] = Visit::OnPath;
+                        path.push((child, 0));
+                    }
+                    Visit::OnPath => {
+                        let cycle_start = path.iter().position(|(p, _)| *p == child).unwrap();
+                        return Some(
+                            path[cycle_start..]
+                                .iter()
+                                .map(|(p, _)| *self.operations.get_index(*p).unwrap().0)
+                                .collect(),
+                        );
+                    }
+                    Visit::Done => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// `topological_order` returns the operations ordered so that every
+    /// operation comes after the operations it relates to. Ties are broken by
+    /// transaction order. An error is returned if the graph has a cycle.
+    pub fn topological_order(&self) -> ParserResult<Vec<&'a Operation>> {
+        let children = self.child_positions();
+        let mut in_degrees = vec![0; self.operations.len()];
+        for child in children.iter().flatten() {
+            in_degrees[*child] += 1;
+        }
+
+        let mut ready = in_degrees
+            .iter()
+            .enumerate()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(position, _)| position)
+            .collect::<BTreeSet<_>>();
+        let mut order = Vec::with_capacity(self.operations.len());
+        while let Some(position) = ready.iter().next().copied() {
+            ready.remove(&position);
+            order.push(self.operations[position]);
+            for child in &children[position] {
+                in_degrees[*child] -= 1;
+                if in_degrees[*child] == 0 {
+                    ready.insert(*child);
+                }
+            }
+        }
+
+        if order.len() < self.operations.len() {
+            Err(format!(
+                "{}: {:?}",
+                OperationGraphError::Cycle,
+                self.find_cycle().unwrap_or_default()
+            ))?;
+        }
+
+        Ok(order)
+    }
+
+    /// `components` returns the indices of the operations of each group of
+    /// related operations, like [`group_operations`]. Groups are ordered by
+    /// their lowest operation index and operations within a group are sorted.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        // Union-find over the positions of the operations.
+        let mut roots = (0..self.operations.len()).collect::<Vec<_>>();
+        fn root(roots: &mut [usize], mut position: usize) -> usize {
+            while roots[position] != position {
+                roots[position] = roots[roots[position]];
+                position = roots[position];
+            }
+            position
+        }
+        for edge in &self.edges {
+            let from = root(
+                &mut roots,
+                self.operations.get_index_of(&edge.from).unwrap(),
+            );
+            let to = root(&mut roots, self.operations.get_index_of(&edge.to).unwrap());
+            roots[from.max(to)] = from.min(to);
+        }
+
+        let mut components: IndexMap<usize, Vec<usize>> = IndexMap::new();
+        for position in 0..self.operations.len() {
+            let root = root(&mut roots, position);
+            components
+                .entry(root)
+                .or_default()
+                .push(*self.operations.get_index(position).unwrap().0);
+        }
+
+        let mut components = components
+            .into_values()
+            .map(|mut indices| {
+                indices.sort_unstable();
+                indices
+            })
+            .collect::<Vec<_>>();
+        components.sort_unstable_by_key(|indices| indices[0]);
+        components
+    }
+}
+
+/// `ValueFlow` describes how a [`Currency`] moves within a group of related
+/// operations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValueFlow {
+    /// The indices of the operations of the group.
+    pub operations: Vec<usize>,
+    /// The currency that moves.
+    pub currency: Currency,
+    /// The accounts whose net value in the group is negative.
+    pub sources: Vec<AccountValue>,
+    /// The accounts whose net value in the group is positive.
+    pub sinks: Vec<AccountValue>,
+}
+
+impl Parser {
+    /// `value_flows` returns the source and sink accounts of each
+    /// [`Currency`] moved within each group of related operations of an
+    /// [`OperationGraph`]. Only successful operations and those without a
+    /// status are counted. Accounts whose net value in a group is zero are
+    /// neither sources nor sinks.
+    pub fn value_flows(&self, graph: &OperationGraph<'_>) -> ParserResult<Vec<ValueFlow>> {
+        let asserter = self
+            .asserter
+            .as_ref()
+            .ok_or(AsserterError::NotInitialized)?;
+
+        let mut flows = Vec::new();
+        for component in graph.components() {
+            let mut currencies: IndexMap<String, (Currency, IndexMap<String, AccountValue>)> =
+                IndexMap::new();
+            let mut values: IndexMap<String, BigInt> = IndexMap::new();
+            for index in &component {
+                let op = graph.operation(*index).unwrap();
+                // Operations that are not on-chain yet have no status.
+                let successful = op.status.is_none()
+                    || asserter.operation_successful(op).map_err(|e| {
+                        format!("failed to check the status of operation {op:?}: {e}")
+                    })?;
+                let (account, amount) = match (&op.account, &op.amount) {
+                    (Some(account), Some(amount)) if successful => (account, amount),
+                    _ => continue,
+                };
+                let value = amount_value(Some(amount)).map_err(|e| {
+                    format!("failed to return big int representation of {amount:?}: {e}")
+                })?;
+
+                let currency_key = hash(Some(&amount.currency));
+                let account_key = hash(Some(account));
+                *values
+                    .entry(format!("{currency_key}/{account_key}"))
+                    .or_insert_with(BigInt::zero) += value;
+                currencies
+                    .entry(currency_key)
+                    .or_insert_with(|| (amount.currency.clone(), IndexMap::new()))
+                    .1
+                    .entry(account_key)
+                    .or_insert_with(|| AccountValue {
+                        account: account.clone(),
+                        currency: amount.currency.clone(),
+                        value: String::new(),
+                    });
+            }
+
+            for (currency_key, (currency, accounts)) in currencies {
+                let mut flow = ValueFlow {
+                    operations: component.clone(),
+                    currency,
+                    ..Default::default()
+                };
+                for (account_key, account_value) in accounts {
+                    let value = &values[&format!("{currency_key}/{account_key}")];
+                    let account_value = AccountValue {
+                        value: value.to_string(),
+                        ..account_value
+                    };
+                    if value.is_negative() {
+                        flow.sources.push(account_value);
+                    } else if value.is_positive() {
+                        flow.sinks.push(account_value);
+                    }
+                }
+                flows.push(flow);
+            }
+        }
+
+        Ok(flows)
+    }
+}
+
+/// `TransactionLookup` fetches transactions referenced by
+/// `related_transactions`, i.e. from a Rosetta node.
+#[async_trait]
+pub trait TransactionLookup: Send + Sync {
+    /// `transaction` returns a transaction on a network and the block that
+    /// includes it, or `None` if it cannot be found.
+    async fn transaction(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        transaction_identifier: &TransactionIdentifier,
+    ) -> ParserResult<Option<BlockTransaction>>;
+}
+
+/// `TracedTransaction` is a transaction reached while following
+/// `related_transactions`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TracedTransaction {
+    /// The network of the transaction.
+    pub network_identifier: NetworkIdentifier,
+    /// The block that includes the transaction, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_identifier: Option<BlockIdentifier>,
+    /// The transaction.
+    pub transaction: Transaction,
+    /// The number of relations followed to reach the transaction.
+    pub depth: usize,
+}
+
+/// `TransactionEdge` is a directed edge from a parent transaction to a child
+/// transaction, identified by their position in
+/// [`TransactionTrace::transactions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TransactionEdge {
+    /// The position of the parent transaction.
+    pub from: usize,
+    /// The position of the child transaction.
+    pub to: usize,
+}
+
+/// `UnresolvedTransaction` is a related transaction the
+/// [`TransactionLookup`] could not find.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UnresolvedTransaction {
+    /// The network of the transaction.
+    pub network_identifier: NetworkIdentifier,
+    /// The transaction identifier.
+    pub transaction_identifier: TransactionIdentifier,
+    /// The position of the transaction that references it.
+    pub referenced_by: usize,
+}
+
+/// `TransactionTrace` is the graph of transactions reached while following
+/// `related_transactions`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TransactionTrace {
+    /// The transactions reached, starting with the traced transaction.
+    pub transactions: Vec<TracedTransaction>,
+    /// The relations between the transactions.
+    pub edges: Vec<TransactionEdge>,
+    /// The related transactions that could not be found.
+    pub unresolved: Vec<UnresolvedTransaction>,
+}
+
+/// `trace_transactions` follows the `related_transactions` of a transaction
+/// on a network, breadth first, up to `max_depth` relations away. Related
+/// transactions without a network identifier are on the same network as the
+/// transaction referencing them. If `direction` is set, only relations in
+/// that direction are followed, i.e. [`Direction::Forward`] to trace where
+/// funds go.
+pub async fn trace_transactions(
+    lookup: &dyn TransactionLookup,
+    network_identifier: &NetworkIdentifier,
+    transaction: &Transaction,
+    direction: Option<Direction>,
+    max_depth: usize,
+) -> ParserResult<TransactionTrace> {
+    let mut trace = TransactionTrace {
+        transactions: vec![TracedTransaction {
+            network_identifier: network_identifier.clone(),
+            block_identifier: None,
+            transaction: transaction.clone(),
+            depth: 0,
+        }],
+        ..Default::default()
+    };
+    let mut positions = IndexMap::new();
+    positions.insert(
+        (
+            hash(Some(network_identifier)),
+            transaction.transaction_identifier.hash.clone(),
+        ),
+        0,
+    );
+
+    let mut queue = VecDeque::from([0]);
+    while let Some(position) = queue.pop_front() {
+        let current = &trace.transactions[position];
+        if current.depth >= max_depth {
+            continue;
+        }
+        let (current_network, depth) = (current.network_identifier.clone(), current.depth);
+        let related_transactions = current.transaction.related_transactions.clone();
+
+        for related in related_transactions {
+            // Relations without a direction are backward, like the default
+            // [`Direction`].
+            let related_direction = related.direction.unwrap_or_default();
+            if direction.map_or(false, |direction| direction != related_direction) {
+                continue;
+            }
+
+            let network = related
+                .network_identifier
+                .clone()
+                .unwrap_or_else(|| current_network.clone());
+            let key = (
+                hash(Some(&network)),
+                related.transaction_identifier.hash.clone(),
+            );
+            let related_position = match positions.get(&key) {
+                Some(related_position) => *related_position,
+                None => {
+                    let found = lookup
+                        .transaction(&network, &related.transaction_identifier)
+                        .await
+                        .map_err(|e| {
+                            format!(
+                                "failed to look up transaction {}: {e}",
+                                related.transaction_identifier.hash
+                            )
+                        })?;
+                    let found = match found {
+                        Some(found) => found,
+                        None => {
+                            trace.unresolved.push(UnresolvedTransaction {
+                                network_identifier: network,
+                                transaction_identifier: related.transaction_identifier,
+                                referenced_by: position,
+                            });
+                            continue;
+                        }
+                    };
+
+                    trace.transactions.push(TracedTransaction {
+                        network_identifier: network,
+                        block_identifier: Some(found.block_identifier),
+                        transaction: found.transaction,
+                        depth: depth + 1,
+                    });
+                    positions.insert(key, trace.transactions.len() - 1);
+                    queue.push_back(trace.transactions.len() - 1);
+                    trace.transactions.len() - 1
+                }
+            };
+
+            let edge = match related_direction {
+                Direction::Forward => TransactionEdge {
+                    from: position,
+                    to: related_position,
+                },
+                Direction::Backward => TransactionEdge {
+                    from: related_position,
+                    to: position,
+                },
+            };
+            if !trace.edges.contains(&edge) {
+                trace.edges.push(edge);
+            }
+        }
+    }
+
+    Ok(trace)
+}
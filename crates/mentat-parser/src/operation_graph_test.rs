@@ -0,0 +1,520 @@
+use std::collections::HashMap;
+
+use super::*;
+
+fn btc() -> Currency {
+    Currency {
+        symbol: "BTC".into(),
+        decimals: 8,
+        ..Default::default()
+    }
+}
+
+fn eth() -> Currency {
+    Currency {
+        symbol: "ETH".into(),
+        decimals: 18,
+        ..Default::default()
+    }
+}
+
+fn op(index: usize, related: &[usize]) -> Operation {
+    Operation {
+        operation_identifier: OperationIdentifier {
+            index,
+            network_index: None,
+        },
+        related_operations: related
+            .iter()
+            .map(|index| OperationIdentifier {
+                index: *index,
+                network_index: None,
+            })
+            .collect(),
+        type_: "Transfer".into(),
+        ..Default::default()
+    }
+}
+
+fn transfer(
+    index: usize,
+    related: &[usize],
+    address: &str,
+    value: &str,
+    currency: Currency,
+    status: &str,
+) -> Operation {
+    Operation {
+        status: Some(status.into()),
+        account: Some(AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        }),
+        amount: Some(Amount {
+            value: value.into(),
+            currency,
+            ..Default::default()
+        }),
+        ..op(index, related)
+    }
+}
+
+fn transaction(hash: &str, operations: Vec<Operation>) -> Transaction {
+    Transaction {
+        transaction_identifier: TransactionIdentifier { hash: hash.into() },
+        operations,
+        ..Default::default()
+    }
+}
+
+fn graph_asserter() -> Option<Asserter> {
+    Some(
+        Asserter::new_client_with_options(
+            Some(NetworkIdentifier {
+                blockchain: "bitcoin".to_string(),
+                network: "mainnet".to_string(),
+                ..Default::default()
+            }),
+            Some(UncheckedBlockIdentifier {
+                hash: "block 0".to_string(),
+                index: 0,
+            }),
+            vec!["Transfer".to_string()],
+            vec![
+                Some(OperationStatus {
+                    status: "Success".into(),
+                    successful: true,
+                }),
+                Some(OperationStatus {
+                    status: "Failure".into(),
+                    successful: false,
+                }),
+            ],
+            Vec::new(),
+            None,
+            Validations::default(),
+        )
+        .unwrap(),
+    )
+}
+
+/// 0 -> 1 -> 3, 0 -> 2 -> 3 and 4 on its own.
+fn diamond() -> Transaction {
+    transaction(
+        "diamond",
+        vec![
+            op(0, &[]),
+            op(1, &[0]),
+            op(2, &[0]),
+            op(3, &[1, 2]),
+            op(4, &[]),
+        ],
+    )
+}
+
+#[test]
+fn test_operation_graph_edges() {
+    let tx = diamond();
+    let graph = OperationGraph::new(&tx).unwrap();
+
+    assert_eq!(
+        graph.edges(),
+        &[
+            OperationEdge { from: 0, to: 1 },
+            OperationEdge { from: 0, to: 2 },
+            OperationEdge { from: 1, to: 3 },
+            OperationEdge { from: 2, to: 3 },
+        ]
+    );
+    assert_eq!(graph.parents(3), vec![1, 2]);
+    assert_eq!(graph.children(0), vec![1, 2]);
+    assert!(graph.parents(4).is_empty() && graph.children(4).is_empty());
+    assert_eq!(graph.operation(4), Some(&tx.operations[4]));
+    assert_eq!(graph.operation(5), None);
+}
+
+#[test]
+fn test_operation_graph_invalid() {
+    let tests = vec![
+        TestCase {
+            name: "duplicate operation",
+            payload: transaction("tx", vec![op(0, &[]), op(0, &[])]),
+            criteria: Some(OperationGraphError::DuplicateOperation.into()),
+        },
+        TestCase {
+            name: "related operation missing",
+            payload: transaction("tx", vec![op(0, &[]), op(1, &[2])]),
+            criteria: Some(OperationGraphError::RelatedOperationMissing.into()),
+        },
+        TestCase {
+            name: "valid",
+            payload: diamond(),
+            criteria: None,
+        },
+    ];
+
+    TestCase::run_err_match(tests, |t| OperationGraph::new(&t).map(|_| ()));
+}
+
+#[test]
+fn test_find_cycle() {
+    let tests = vec![
+        TestCase {
+            name: "acyclic",
+            payload: diamond(),
+            criteria: None,
+        },
+        TestCase {
+            name: "cycle",
+            payload: transaction(
+                "tx",
+                vec![op(0, &[2]), op(1, &[0]), op(2, &[1]), op(3, &[])],
+            ),
+            criteria: Some(vec![0, 1, 2]),
+        },
+        TestCase {
+            name: "self reference",
+            payload: transaction("tx", vec![op(0, &[]), op(1, &[1])]),
+            criteria: Some(vec![1]),
+        },
+    ];
+
+    TestCase::run_output_match(tests, |t| OperationGraph::new(&t).unwrap().find_cycle());
+}
+
+#[test]
+fn test_topological_order() {
+    let tests = vec![
+        TestCase {
+            name: "transaction order",
+            payload: diamond(),
+            criteria: Ok(vec![0, 1, 2, 3, 4]),
+        },
+        TestCase {
+            name: "forward reference",
+            payload: transaction("tx", vec![op(0, &[2]), op(1, &[]), op(2, &[])]),
+            criteria: Ok(vec![1, 2, 0]),
+        },
+        TestCase {
+            name: "cycle",
+            payload: transaction("tx", vec![op(0, &[1]), op(1, &[0])]),
+            criteria: Err(format!("{}: [0, 1]", OperationGraphError::Cycle)),
+        },
+    ];
+
+    TestCase::run_output_match(tests, |t| {
+        OperationGraph::new(&t)
+            .unwrap()
+            .topological_order()
+            .map(|ops| {
+                ops.into_iter()
+                    .map(|op| op.operation_identifier.index)
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|e| e.to_string())
+    });
+}
+
+#[test]
+fn test_components() {
+    let tests = vec![
+        TestCase {
+            name: "diamond",
+            payload: diamond(),
+            criteria: vec![vec![0, 1, 2, 3], vec![4]],
+        },
+        TestCase {
+            name: "merged late",
+            payload: transaction(
+                "tx",
+                vec![
+                    op(0, &[]),
+                    op(1, &[]),
+                    op(2, &[]),
+                    op(3, &[2]),
+                    op(4, &[0, 3]),
+                ],
+            ),
+            criteria: vec![vec![0, 2, 3, 4], vec![1]],
+        },
+        TestCase {
+            name: "empty",
+            payload: transaction("tx", Vec::new()),
+            criteria: vec![],
+        },
+    ];
+
+    TestCase::run_output_match(tests, |t| OperationGraph::new(&t).unwrap().components());
+}
+
+#[test]
+fn test_value_flows() {
+    let account_value = |address: &str, currency: Currency, value: &str| AccountValue {
+        account: AccountIdentifier {
+            address: address.into(),
+            ..Default::default()
+        },
+        currency,
+        value: value.into(),
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "split payment and failed transfer",
+            payload: transaction(
+                "tx",
+                vec![
+                    transfer(0, &[], "a", "-100", btc(), "Success"),
+                    transfer(1, &[0], "b", "60", btc(), "Success"),
+                    transfer(2, &[0], "c", "40", btc(), "Success"),
+                    transfer(3, &[], "d", "-5", eth(), "Success"),
+                    transfer(4, &[3], "e", "5", eth(), "Failure"),
+                ],
+            ),
+            criteria: vec![
+                ValueFlow {
+                    operations: vec![0, 1, 2],
+                    currency: btc(),
+                    sources: vec![account_value("a", btc(), "-100")],
+                    sinks: vec![
+                        account_value("b", btc(), "60"),
+                        account_value("c", btc(), "40"),
+                    ],
+                },
+                ValueFlow {
+                    operations: vec![3, 4],
+                    currency: eth(),
+                    sources: vec![account_value("d", eth(), "-5")],
+                    sinks: vec![],
+                },
+            ],
+        },
+        TestCase {
+            name: "net zero account and multiple currencies",
+            payload: transaction(
+                "tx",
+                vec![
+                    transfer(0, &[], "a", "-10", btc(), "Success"),
+                    transfer(1, &[0], "a", "10", btc(), "Success"),
+                    transfer(2, &[0], "a", "-3", eth(), "Success"),
+                    transfer(3, &[2], "b", "3", eth(), "Success"),
+                ],
+            ),
+            criteria: vec![
+                ValueFlow {
+                    operations: vec![0, 1, 2, 3],
+                    currency: btc(),
+                    sources: vec![],
+                    sinks: vec![],
+                },
+                ValueFlow {
+                    operations: vec![0, 1, 2, 3],
+                    currency: eth(),
+                    sources: vec![account_value("a", eth(), "-3")],
+                    sinks: vec![account_value("b", eth(), "3")],
+                },
+            ],
+        },
+    ];
+
+    let parser = Parser::new(graph_asserter(), None, Vec::new());
+    TestCase::run_output_match(tests, |t| {
+        parser
+            .value_flows(&OperationGraph::new(&t).unwrap())
+            .unwrap()
+    });
+}
+
+/// A [`TransactionLookup`] over a fixed set of blocks, keyed by network and
+/// transaction hash.
+struct FixedTransactions(HashMap<(String, String), BlockTransaction>);
+
+#[async_trait::async_trait]
+impl TransactionLookup for FixedTransactions {
+    async fn transaction(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        transaction_identifier: &TransactionIdentifier,
+    ) -> ParserResult<Option<BlockTransaction>> {
+        if transaction_identifier.hash == "broken" {
+            return Err("node unavailable".to_string().into());
+        }
+
+        Ok(self
+            .0
+            .get(&(
+                network_identifier.network.to_lowercase(),
+                transaction_identifier.hash.clone(),
+            ))
+            .cloned())
+    }
+}
+
+fn related(network: Option<&str>, hash: &str, direction: Direction) -> RelatedTransaction {
+    RelatedTransaction {
+        network_identifier: network.map(|network| ("cosmos", network).into()),
+        transaction_identifier: TransactionIdentifier { hash: hash.into() },
+        direction: Some(direction),
+    }
+}
+
+fn with_related(hash: &str, related_transactions: Vec<RelatedTransaction>) -> Transaction {
+    Transaction {
+        related_transactions,
+        ..transaction(hash, Vec::new())
+    }
+}
+
+struct TraceTest {
+    start: Transaction,
+    direction: Option<Direction>,
+    max_depth: usize,
+}
+
+#[test]
+fn test_trace_transactions() {
+    let hub = NetworkIdentifier::from(("cosmos", "hub"));
+    let start = with_related(
+        "a",
+        vec![
+            related(None, "b", Direction::Forward),
+            related(None, "missing", Direction::Backward),
+            related(Some("osmosis"), "x", Direction::Forward),
+        ],
+    );
+    let block = |index| BlockIdentifier {
+        index,
+        hash: format!("block {index}"),
+    };
+    let lookup = FixedTransactions(
+        [
+            (
+                ("hub", "b"),
+                BlockTransaction {
+                    block_identifier: block(2),
+                    transaction: with_related(
+                        "b",
+                        vec![
+                            related(None, "a", Direction::Backward),
+                            related(None, "c", Direction::Forward),
+                        ],
+                    ),
+                },
+            ),
+            (
+                ("hub", "c"),
+                BlockTransaction {
+                    block_identifier: block(3),
+                    transaction: with_related("c", Vec::new()),
+                },
+            ),
+            (
+                ("osmosis", "x"),
+                BlockTransaction {
+                    block_identifier: block(7),
+                    transaction: with_related("x", Vec::new()),
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|((network, hash), tx)| ((network.to_string(), hash.to_string()), tx))
+        .collect(),
+    );
+
+    let traced = |network: &str, index: Option<usize>, tx: Transaction, depth| TracedTransaction {
+        network_identifier: ("cosmos", network).into(),
+        block_identifier: index.map(block),
+        transaction: tx,
+        depth,
+    };
+    let b = lookup.0[&("hub".to_string(), "b".to_string())]
+        .transaction
+        .clone();
+    let c = lookup.0[&("hub".to_string(), "c".to_string())]
+        .transaction
+        .clone();
+    let x = lookup.0[&("osmosis".to_string(), "x".to_string())]
+        .transaction
+        .clone();
+
+    let tests = vec![
+        TestCase {
+            name: "all directions",
+            payload: TraceTest {
+                start: start.clone(),
+                direction: None,
+                max_depth: 5,
+            },
+            criteria: Ok(TransactionTrace {
+                transactions: vec![
+                    traced("hub", None, start.clone(), 0),
+                    traced("hub", Some(2), b.clone(), 1),
+                    traced("osmosis", Some(7), x.clone(), 1),
+                    traced("hub", Some(3), c, 2),
+                ],
+                edges: vec![
+                    TransactionEdge { from: 0, to: 1 },
+                    TransactionEdge { from: 0, to: 2 },
+                    TransactionEdge { from: 1, to: 3 },
+                ],
+                unresolved: vec![UnresolvedTransaction {
+                    network_identifier: hub.clone(),
+                    transaction_identifier: TransactionIdentifier {
+                        hash: "missing".into(),
+                    },
+                    referenced_by: 0,
+                }],
+            }),
+        },
+        TestCase {
+            name: "forward only within one relation",
+            payload: TraceTest {
+                start: start.clone(),
+                direction: Some(Direction::Forward),
+                max_depth: 1,
+            },
+            criteria: Ok(TransactionTrace {
+                transactions: vec![
+                    traced("hub", None, start.clone(), 0),
+                    traced("hub", Some(2), b, 1),
+                    traced("osmosis", Some(7), x, 1),
+                ],
+                edges: vec![
+                    TransactionEdge { from: 0, to: 1 },
+                    TransactionEdge { from: 0, to: 2 },
+                ],
+                unresolved: vec![],
+            }),
+        },
+        TestCase {
+            name: "depth zero",
+            payload: TraceTest {
+                start: start.clone(),
+                direction: None,
+                max_depth: 0,
+            },
+            criteria: Ok(TransactionTrace {
+                transactions: vec![traced("hub", None, start, 0)],
+                ..Default::default()
+            }),
+        },
+        TestCase {
+            name: "lookup fails",
+            payload: TraceTest {
+                start: with_related("a", vec![related(None, "broken", Direction::Forward)]),
+                direction: None,
+                max_depth: 1,
+            },
+            criteria: Err("failed to look up transaction broken: node unavailable".to_string()),
+        },
+    ];
+
+    TestCase::run_async_output_match(tests, |t| {
+        let (lookup, hub) = (&lookup, hub.clone());
+        async move {
+            trace_transactions(lookup, &hub, &t.start, t.direction, t.max_depth)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    });
+}
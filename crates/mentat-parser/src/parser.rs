@@ -3,7 +3,7 @@
 use super::*;
 
 /// a closure that determines if an operation should be skipped
-pub type ExemptionFunc = Box<dyn Fn(&Operation) -> bool>;
+pub type ExemptionFunc = Box<dyn Fn(&Operation) -> bool + Send + Sync>;
 
 /// `Parser` provides support for parsing Rosetta blocks.
 #[allow(clippy::missing_docs_in_private_items)]
@@ -82,12 +82,22 @@ impl<Api: AccountApi> AccountApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.account_balance_request(data.as_ref())?;
+            self.asserter
+                .account_balance_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
+            let request_block = data.as_ref().and_then(|d| d.block_identifier.clone());
             let resp = self
                 .api
                 .account_balance(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/account/balance",
+                &resp,
+                |_, r| mentat_asserter::account_balance_response(request_block.as_ref(), r),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -103,12 +113,21 @@ impl<Api: AccountApi> AccountApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.account_coins_request(data.as_ref())?;
+            self.asserter
+                .account_coins_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp = self
                 .api
                 .account_coins(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/account/coins",
+                &resp,
+                |_, r| mentat_asserter::account_coins(r),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -126,10 +145,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedAccountBalanceRequest>>| async move {
-                    balance
-                        .call_account_balance(Caller { ip }, &conf.mode, req_data)
-                        .await
+                 encoded_req: Encoded<Option<UncheckedAccountBalanceRequest>>| async move {
+                    let resp = balance
+                        .call_account_balance(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -138,9 +158,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedAccountCoinsRequest>>| async move {
-                    self.call_account_coins(Caller { ip }, &conf.mode, req_data)
-                        .await
+                 encoded_req: Encoded<Option<UncheckedAccountCoinsRequest>>| async move {
+                    let resp = self
+                        .call_account_coins(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
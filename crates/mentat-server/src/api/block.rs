@@ -76,12 +76,18 @@ impl<Api: BlockApi> BlockApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.block_request(data.as_ref())?;
+            self.asserter
+                .block_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp: UncheckedBlockResponse = self
                 .api
                 .block(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(self.response_asserter.as_ref(), "/block", &resp, |a, r| {
+                a.block_response(r)
+            })
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -97,12 +103,24 @@ impl<Api: BlockApi> BlockApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.block_transaction_request(data.as_ref())?;
+            self.asserter
+                .block_transaction_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp: UncheckedBlockTransactionResponse = self
                 .api
                 .block_transaction(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/block/transaction",
+                &resp,
+                |a, r| {
+                    a.transaction(r.transaction.as_ref())
+                        .map_err(|e| e.at("transaction"))
+                },
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -120,8 +138,9 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedBlockRequest>>| async move {
-                    block.call_block(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedBlockRequest>>| async move {
+                    let resp = block.call_block(Caller { ip }, &conf.mode, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -130,9 +149,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedBlockTransactionRequest>>| async move {
-                    self.call_block_transaction(Caller { ip }, &conf.mode, req_data)
-                        .await
+                 encoded_req: Encoded<Option<UncheckedBlockTransactionRequest>>| async move {
+                    let resp = self
+                        .call_block_transaction(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -0,0 +1,130 @@
+use mentat_asserter::{BlockError, Validations};
+
+use super::*;
+use crate::conf::AsserterTable;
+
+/// A block api that returns a block from before the minimum timestamp.
+#[derive(Clone, Debug, Default)]
+struct MalformedBlockApi;
+
+fn malformed_block() -> BlockResponse {
+    BlockResponse {
+        block: Some(Block {
+            block_identifier: BlockIdentifier {
+                index: 1,
+                hash: "block 1".into(),
+            },
+            parent_block_identifier: BlockIdentifier {
+                index: 0,
+                hash: "block 0".into(),
+            },
+            timestamp: 0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[axum::async_trait]
+impl BlockApi for MalformedBlockApi {
+    type NodeCaller = TestCaller;
+
+    async fn block(
+        &self,
+        _caller: Caller,
+        _data: BlockRequest,
+        _node_caller: &Self::NodeCaller,
+    ) -> Result<BlockResponse> {
+        Ok(malformed_block())
+    }
+}
+
+fn response_asserter() -> Asserter {
+    Asserter::new_client_with_options(
+        Some(network()),
+        Some(UncheckedBlockIdentifier {
+            index: 0,
+            hash: "block 0".into(),
+        }),
+        vec!["PAYMENT".into()],
+        vec![Some(OperationStatus {
+            status: "SUCCESS".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Validations::default(),
+    )
+    .unwrap()
+}
+
+fn block_request() -> UncheckedBlockRequest {
+    UncheckedBlockRequest {
+        network_identifier: Some(network()),
+        block_identifier: Some(UncheckedPartialBlockIdentifier {
+            index: Some(1),
+            hash: None,
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_validate_block_api_responses() {
+    let router = |table: AsserterTable| -> BlockApiRouter<MalformedBlockApi> {
+        ApiRouter::from(
+            MalformedBlockApi,
+            table.block_api,
+            table.responses.block_api,
+            Arc::new(TestCaller),
+        )
+    };
+
+    // the malformed block is replaced with the failed assertion.
+    let table = AsserterTable::builder()
+        .use_default(asserter())
+        .validate_block_api_responses(response_asserter())
+        .build();
+    let resp = send(
+        router(table),
+        Default::default(),
+        json_request("/", &block_request()),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+    let body = resp.json();
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains(&BlockError::TimestampBeforeMin.to_string()));
+    assert_eq!(
+        body["details"]["assertion"],
+        serde_json::json!({
+            "code": "block.timestamp_before_min",
+            "path": "block.timestamp",
+            "value": 0,
+        })
+    );
+
+    // without response validation the block passes through unchanged.
+    let table = AsserterTable::builder().use_default(asserter()).build();
+    let resp = send(
+        router(table),
+        Default::default(),
+        json_request("/", &block_request()),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::OK);
+    assert_eq!(
+        resp.json(),
+        serde_json::to_value(UncheckedBlockResponse::from(malformed_block())).unwrap()
+    );
+}
+
+#[test]
+#[should_panic(expected = "the block response asserter cannot check responses")]
+fn test_request_asserter_cannot_validate_responses() {
+    AsserterTable::builder()
+        .use_default(asserter())
+        .validate_block_api_responses(asserter())
+        .build();
+}
@@ -35,7 +35,9 @@ impl<Api: CallApi> CallApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.call_request(data.as_ref())?;
+            self.asserter
+                .call_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(
                 self.api
                     .call(caller, data.unwrap().into(), &self.node_caller)
@@ -56,8 +58,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedCallRequest>>| async move {
-                    self.call_call(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedCallRequest>>| async move {
+                    let resp = self
+                        .call_call(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -25,7 +25,9 @@ pub trait ConstructionApi: Clone + Debug + Default + Send + Sync {
 
     /// Derive returns the [`crate::identifiers::AccountIdentifier`] associated
     /// with a public key. Constructionchains that require an on-chain action to
-    /// create an account should not implement this method.
+    /// create an account should not implement this method. When it is not
+    /// implemented and an `address_scheme` is configured, the public key is
+    /// encoded with the configured [`AddressScheme`] instead.
     async fn derive(
         &self,
         _caller: Caller,
@@ -143,12 +145,21 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedConstructionCombineRequest>,
     ) -> MentatResponse<UncheckedConstructionCombineResponse> {
-        self.asserter.construction_combine_request(data.as_ref())?;
+        self.asserter
+            .construction_combine_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .combine(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/combine",
+            &resp,
+            |_, r| mentat_asserter::construction_combine_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -157,14 +168,38 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
     async fn call_derive(
         &self,
         caller: Caller,
+        address_scheme: Option<&AddressScheme>,
         data: Option<UncheckedConstructionDeriveRequest>,
     ) -> MentatResponse<UncheckedConstructionDeriveResponse> {
-        self.asserter.construction_derive_request(data.as_ref())?;
-        let resp = self
-            .api
-            .derive(caller, data.unwrap().into(), &self.node_caller)
-            .await?
-            .into();
+        self.asserter
+            .construction_derive_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
+        let data: ConstructionDeriveRequest = data.unwrap().into();
+        let public_key = data.public_key.clone();
+        let resp = match self.api.derive(caller, data, &self.node_caller).await {
+            Err(e) if e.is_not_implemented() => match address_scheme {
+                Some(address_scheme) => match address_scheme.derive(&public_key) {
+                    Ok(address) => ConstructionDeriveResponse {
+                        account_identifier: Some(AccountIdentifier {
+                            address,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                    Err(e) => return MentatError::unable_to_derive_address(Some(e)),
+                },
+                None => return Err(e),
+            },
+            resp => resp?,
+        }
+        .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/derive",
+            &resp,
+            |_, r| mentat_asserter::construction_derive_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -175,12 +210,21 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedConstructionHashRequest>,
     ) -> MentatResponse<UncheckedTransactionIdentifierResponse> {
-        self.asserter.construction_hash_request(data.as_ref())?;
+        self.asserter
+            .construction_hash_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .hash(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/hash",
+            &resp,
+            |_, r| mentat_asserter::transaction_identifier_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -195,12 +239,21 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.construction_metadata_request(data.as_ref())?;
+            self.asserter
+                .construction_metadata_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp = self
                 .api
                 .metadata(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/construction/metadata",
+                &resp,
+                |_, r| mentat_asserter::construction_metadata_response(Some(r)),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -212,13 +265,23 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedConstructionParseRequest>,
     ) -> MentatResponse<UncheckedConstructionParseResponse> {
-        self.asserter.construction_parse_request(data.as_ref())?;
+        self.asserter
+            .construction_parse_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let data: ConstructionParseRequest = data.unwrap().into();
+        let signed = data.signed;
         let resp = self
             .api
             .parse(caller, data, &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/parse",
+            &resp,
+            |a, r| a.construction_parse_response(Some(r), signed),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -229,12 +292,21 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedConstructionPayloadsRequest>,
     ) -> MentatResponse<UncheckedConstructionPayloadsResponse> {
-        self.asserter.construction_payload_request(data.as_ref())?;
+        self.asserter
+            .construction_payload_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .payloads(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/payloads",
+            &resp,
+            |_, r| mentat_asserter::construction_payloads_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -246,12 +318,20 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         data: Option<UncheckedConstructionPreprocessRequest>,
     ) -> MentatResponse<UncheckedConstructionPreprocessResponse> {
         self.asserter
-            .construction_preprocess_request(data.as_ref())?;
+            .construction_preprocess_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .preprocess(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/construction/preprocess",
+            &resp,
+            |_, r| mentat_asserter::construction_preprocess_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -266,12 +346,21 @@ impl<Api: ConstructionApi> ConstructionApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.construction_submit_request(data.as_ref())?;
+            self.asserter
+                .construction_submit_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp = self
                 .api
                 .submit(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/construction/submit",
+                &resp,
+                |_, r| mentat_asserter::transaction_identifier_response(Some(r)),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -294,8 +383,9 @@ where
             "/combine",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionCombineRequest>>| async move {
-                    combine.call_combine(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionCombineRequest>>| async move {
+                    let resp = combine.call_combine(Caller { ip }, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -303,8 +393,12 @@ where
             "/derive",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionDeriveRequest>>| async move {
-                    derive.call_derive(Caller { ip }, req_data).await
+                 State(conf): State<Configuration<CustomConfig>>,
+                 encoded_req: Encoded<Option<UncheckedConstructionDeriveRequest>>| async move {
+                    let resp = derive
+                        .call_derive(Caller { ip }, conf.address_scheme.as_ref(), encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -312,8 +406,9 @@ where
             "/hash",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionHashRequest>>| async move {
-                    hash.call_hash(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionHashRequest>>| async move {
+                    let resp = hash.call_hash(Caller { ip }, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -322,8 +417,10 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedConstructionMetadataRequest>>| async move {
-                    metadata.call_metadata(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionMetadataRequest>>| async move {
+                    let resp = metadata.call_metadata(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -331,8 +428,9 @@ where
             "/parse",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionParseRequest>>| async move {
-                    parse.call_parse(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionParseRequest>>| async move {
+                    let resp = parse.call_parse(Caller { ip }, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -340,8 +438,9 @@ where
             "/payloads",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionPayloadsRequest>>| async move {
-                    payloads.call_payloads(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionPayloadsRequest>>| async move {
+                    let resp = payloads.call_payloads(Caller { ip }, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -349,8 +448,9 @@ where
             "/preprocess",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedConstructionPreprocessRequest>>| async move {
-                    preprocess.call_preprocess(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionPreprocessRequest>>| async move {
+                    let resp = preprocess.call_preprocess(Caller { ip }, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -359,8 +459,9 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedConstructionSubmitRequest>>| async move {
-                    self.call_submit(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedConstructionSubmitRequest>>| async move {
+                    let resp = self.call_submit(Caller { ip }, &conf.mode, encoded_req.data).await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
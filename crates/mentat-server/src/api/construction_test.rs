@@ -0,0 +1,238 @@
+use mentat_asserter::ConstructionError;
+use mentat_keys::{address::AddressScheme, types::KeyPair, SignerInterface};
+
+use super::*;
+use crate::conf::AsserterTable;
+
+/// A construction api that combines any signatures.
+#[derive(Clone, Debug, Default)]
+struct TestConstructionApi;
+
+#[axum::async_trait]
+impl ConstructionApi for TestConstructionApi {
+    type NodeCaller = TestCaller;
+
+    async fn combine(
+        &self,
+        _caller: Caller,
+        data: ConstructionCombineRequest,
+        _node_caller: &Self::NodeCaller,
+    ) -> Result<ConstructionCombineResponse> {
+        Ok(ConstructionCombineResponse {
+            signed_transaction: format!("signed {}", data.unsigned_transaction),
+        })
+    }
+}
+
+/// A construction api that derives every public key to the same account.
+#[derive(Clone, Debug, Default)]
+struct DeriveConstructionApi;
+
+#[axum::async_trait]
+impl ConstructionApi for DeriveConstructionApi {
+    type NodeCaller = TestCaller;
+
+    async fn derive(
+        &self,
+        _caller: Caller,
+        _data: ConstructionDeriveRequest,
+        _node_caller: &Self::NodeCaller,
+    ) -> Result<ConstructionDeriveResponse> {
+        Ok(ConstructionDeriveResponse {
+            account_identifier: Some(AccountIdentifier {
+                address: "derived".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+/// A construction api whose derive fails.
+#[derive(Clone, Debug, Default)]
+struct FailingDeriveConstructionApi;
+
+#[axum::async_trait]
+impl ConstructionApi for FailingDeriveConstructionApi {
+    type NodeCaller = TestCaller;
+
+    async fn derive(
+        &self,
+        _caller: Caller,
+        _data: ConstructionDeriveRequest,
+        _node_caller: &Self::NodeCaller,
+    ) -> Result<ConstructionDeriveResponse> {
+        Err(MentatError::from("boom"))
+    }
+}
+
+fn router(asserter: Asserter) -> ConstructionApiRouter<TestConstructionApi> {
+    ApiRouter::from(TestConstructionApi, asserter, None, Arc::new(TestCaller))
+}
+
+fn sign(key_pair: &KeyPair) -> Signature {
+    key_pair
+        .clone()
+        .signer()
+        .unwrap()
+        .sign(
+            SigningPayload {
+                account_identifier: Some(AccountIdentifier {
+                    address: "alice".into(),
+                    ..Default::default()
+                }),
+                bytes: b"unsigned transaction".to_vec(),
+                signature_type: SignatureType::Ed25519,
+                ..Default::default()
+            },
+            SignatureType::Ed25519,
+        )
+        .unwrap()
+}
+
+fn combine_request(signature: Signature) -> UncheckedConstructionCombineRequest {
+    UncheckedConstructionCombineRequest {
+        network_identifier: Some(network()),
+        unsigned_transaction: "unsigned transaction".into(),
+        signatures: vec![Some(signature.into())],
+    }
+}
+
+#[tokio::test]
+async fn test_combine_verifies_signatures() {
+    let key_pair = KeyPair::generate(CurveType::Edwards25519).unwrap();
+    let valid = sign(&key_pair);
+    let mut forged = valid.clone();
+    forged.public_key.bytes = KeyPair::generate(CurveType::Edwards25519)
+        .unwrap()
+        .public_key
+        .bytes;
+
+    let table = AsserterTable::from(asserter());
+    let resp = send(
+        router(table.construction_api.clone()),
+        Default::default(),
+        json_request("/combine", &combine_request(valid)),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::OK);
+    assert_eq!(
+        resp.json()["signed_transaction"],
+        "signed unsigned transaction"
+    );
+
+    let resp = send(
+        router(table.construction_api),
+        Default::default(),
+        json_request("/combine", &combine_request(forged.clone())),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(resp.json()["message"]
+        .as_str()
+        .unwrap()
+        .contains(&ConstructionError::SignatureVerificationFailed.to_string()));
+
+    // the default table built from a builder verifies signatures too, while an
+    // asserter without a verifier lets the forged signature through.
+    let table = AsserterTable::builder().use_default(asserter()).build();
+    let resp = send(
+        router(table.construction_api),
+        Default::default(),
+        json_request("/combine", &combine_request(forged.clone())),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let resp = send(
+        router(asserter()),
+        Default::default(),
+        json_request("/combine", &combine_request(forged)),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::OK);
+}
+
+fn derive_request(public_key: &PublicKey) -> UncheckedConstructionDeriveRequest {
+    UncheckedConstructionDeriveRequest {
+        network_identifier: Some(network()),
+        public_key: Some(public_key.clone().into()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_derive_with_address_scheme() {
+    let public_key = KeyPair::generate(CurveType::Secp256k1).unwrap().public_key;
+    let request = derive_request(&public_key);
+    let config = Configuration {
+        address_scheme: Some(AddressScheme::Ethereum),
+        ..Default::default()
+    };
+
+    // without an address scheme an unimplemented derive stays unimplemented.
+    let resp = send(
+        router(asserter()),
+        Default::default(),
+        json_request("/derive", &request),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        resp.json()["message"],
+        MentatError::not_implemented::<()>().unwrap_err().message
+    );
+
+    // the configured scheme answers for an unimplemented derive.
+    let resp = send(
+        router(asserter()),
+        config.clone(),
+        json_request("/derive", &request),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::OK);
+    assert_eq!(
+        resp.json()["account_identifier"]["address"],
+        AddressScheme::Ethereum.derive(&public_key).unwrap()
+    );
+
+    // a scheme that does not support the curve is an error.
+    let ed25519 = KeyPair::generate(CurveType::Edwards25519)
+        .unwrap()
+        .public_key;
+    let resp = send(
+        router(asserter()),
+        config.clone(),
+        json_request("/derive", &derive_request(&ed25519)),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        resp.json()["message"],
+        MentatError::unable_to_derive_address::<&str, ()>(None)
+            .unwrap_err()
+            .message
+    );
+
+    // an implemented derive takes precedence over the configured scheme.
+    let derive: ConstructionApiRouter<DeriveConstructionApi> = ApiRouter::from(
+        DeriveConstructionApi,
+        asserter(),
+        None,
+        Arc::new(TestCaller),
+    );
+    let resp = send(derive, config.clone(), json_request("/derive", &request)).await;
+    assert_eq!(resp.status, StatusCode::OK);
+    assert_eq!(resp.json()["account_identifier"]["address"], "derived");
+
+    // an error of an implemented derive is not replaced by the scheme.
+    let failing: ConstructionApiRouter<FailingDeriveConstructionApi> = ApiRouter::from(
+        FailingDeriveConstructionApi,
+        asserter(),
+        None,
+        Arc::new(TestCaller),
+    );
+    let resp = send(failing, config, json_request("/derive", &request)).await;
+    assert_eq!(resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(resp.json()["message"], "boom");
+}
@@ -0,0 +1,180 @@
+use axum::body::Body;
+
+use super::*;
+
+/// A network api that lists the test network.
+#[derive(Clone, Debug, Default)]
+struct ListNetworkApi;
+
+#[axum::async_trait]
+impl NetworkApi for ListNetworkApi {
+    type NodeCaller = TestCaller;
+
+    async fn network_list(
+        &self,
+        _caller: Caller,
+        _data: MetadataRequest,
+        _node_caller: &Self::NodeCaller,
+    ) -> Result<NetworkListResponse> {
+        Ok(NetworkListResponse {
+            network_identifiers: vec![network()],
+        })
+    }
+}
+
+fn router() -> NetworkApiRouter<ListNetworkApi> {
+    ApiRouter::from(ListNetworkApi, asserter(), None, Arc::new(TestCaller))
+}
+
+fn network_list() -> serde_json::Value {
+    serde_json::to_value(UncheckedNetworkListResponse::from(NetworkListResponse {
+        network_identifiers: vec![network()],
+    }))
+    .unwrap()
+}
+
+fn decode_network_list(encoding: Encoding, body: &[u8]) -> serde_json::Value {
+    serde_json::to_value(
+        encoding
+            .decode::<UncheckedNetworkListResponse>(body)
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+fn list_request(encoding: Encoding, accept: Option<&str>) -> Request<Body> {
+    request(
+        "/list",
+        Some(encoding.mime_type()),
+        accept,
+        encoding
+            .encode(&UncheckedMetadataRequest::default())
+            .unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_encoded_request() {
+    for encoding in [Encoding::Json, Encoding::Cbor, Encoding::MessagePack] {
+        let resp = send(
+            router(),
+            Default::default(),
+            list_request(encoding, Some(encoding.mime_type())),
+        )
+        .await;
+        assert_eq!(resp.status, StatusCode::OK, "{encoding}");
+        assert_eq!(
+            Encoding::from_content_type(resp.content_type.as_deref()),
+            Some(encoding)
+        );
+        assert_eq!(
+            decode_network_list(encoding, &resp.body),
+            network_list(),
+            "{encoding}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_encoded_accept_quality() {
+    let tests = [
+        (None, Encoding::Json),
+        (Some("application/cbor"), Encoding::Cbor),
+        (
+            Some("application/json;q=0.5, application/msgpack;q=0.9, application/cbor;q=0.1"),
+            Encoding::MessagePack,
+        ),
+        (
+            Some("application/msgpack;q=0, application/cbor;q=0.2"),
+            Encoding::Cbor,
+        ),
+        (Some("text/html, */*;q=0.8"), Encoding::Json),
+    ];
+
+    for (accept, expected) in tests {
+        let resp = send(
+            router(),
+            Default::default(),
+            list_request(Encoding::Json, accept),
+        )
+        .await;
+        assert_eq!(resp.status, StatusCode::OK, "{accept:?}");
+        assert_eq!(
+            Encoding::from_content_type(resp.content_type.as_deref()),
+            Some(expected),
+            "{accept:?}"
+        );
+        assert_eq!(
+            decode_network_list(expected, &resp.body),
+            network_list(),
+            "{accept:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_encoded_unsupported_content_type() {
+    let resp = send(
+        router(),
+        Default::default(),
+        request("/list", Some("text/plain"), None, b"{}".to_vec()),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert!(resp.json()["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("unsupported content type"));
+}
+
+#[tokio::test]
+async fn test_encoded_invalid_body() {
+    let resp = send(
+        router(),
+        Default::default(),
+        request("/list", Some("application/cbor"), None, vec![0xff, 0x00]),
+    )
+    .await;
+    assert_eq!(resp.status, StatusCode::BAD_REQUEST);
+    assert!(resp.json()["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid application/cbor request"));
+}
+
+#[tokio::test]
+async fn test_encoded_error_body() {
+    // an error is encoded like a response, including one rejecting the request.
+    let requests = [
+        (
+            request(
+                "/list",
+                Some("text/plain"),
+                Some("application/cbor"),
+                b"{}".to_vec(),
+            ),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported content type",
+        ),
+        (
+            request(
+                "/list",
+                Some("application/json"),
+                Some("application/cbor"),
+                b"{\"metadata\": 1}".to_vec(),
+            ),
+            StatusCode::BAD_REQUEST,
+            "invalid application/json request",
+        ),
+    ];
+
+    for (request, status, message) in requests {
+        let resp = send(router(), Default::default(), request).await;
+        assert_eq!(resp.status, status);
+        assert_eq!(resp.content_type.as_deref(), Some("application/cbor"));
+        let err = Encoding::Cbor
+            .decode::<UncheckedMentatError>(&resp.body)
+            .unwrap();
+        assert!(err.message.starts_with(message), "{}", err.message);
+    }
+}
@@ -39,15 +39,24 @@ impl<Api: EventsApi> EventsApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedEventsBlocksRequest>,
     ) -> MentatResponse<UncheckedEventsBlocksResponse> {
-        self.asserter.events_block_request(data.as_ref())?;
+        self.asserter
+            .events_block_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .events_blocks(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
-        // if assert_resp {
-        //     events_blocks_response(Some(&resp)).unwrap();
-        // }
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/events/blocks",
+            &resp,
+            |a, r| {
+                a.require_feature(mentat_asserter::SpecFeature::Events)?;
+                mentat_asserter::events_blocks_response(Some(r))
+            },
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 }
@@ -61,8 +70,11 @@ where
             "/blocks",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedEventsBlocksRequest>>| async move {
-                    self.call_events_blocks(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedEventsBlocksRequest>>| async move {
+                    let resp = self
+                        .call_events_blocks(Caller { ip }, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
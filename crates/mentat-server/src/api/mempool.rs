@@ -54,12 +54,21 @@ impl<Api: MempoolApi> MempoolApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.network_request(data.as_ref())?;
+            self.asserter
+                .network_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp: UncheckedMempoolResponse = self
                 .api
                 .mempool(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/mempool",
+                &resp,
+                |_, r| mentat_asserter::mempool_transactions(&r.transaction_identifiers),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -75,12 +84,24 @@ impl<Api: MempoolApi> MempoolApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.mempool_transaction_request(data.as_ref())?;
+            self.asserter
+                .mempool_transaction_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp: UncheckedMempoolTransactionResponse = self
                 .api
                 .mempool_transaction(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/mempool/transaction",
+                &resp,
+                |a, r| {
+                    a.transaction(r.transaction.as_ref())
+                        .map_err(|e| e.at("transaction"))
+                },
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -98,8 +119,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedNetworkRequest>>| async move {
-                    mempool.call_mempool(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedNetworkRequest>>| async move {
+                    let resp = mempool
+                        .call_mempool(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -108,8 +132,11 @@ where
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                  State(conf): State<Configuration<CustomConfig>>,
-                 Json(req_data): Json<Option<UncheckedMempoolTransactionRequest>>| async move {
-                    self.call_mempool_transaction(Caller { ip }, &conf.mode, req_data).await
+                 encoded_req: Encoded<Option<UncheckedMempoolTransactionRequest>>| async move {
+                    let resp = self
+                        .call_mempool_transaction(Caller { ip }, &conf.mode, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
@@ -21,12 +21,17 @@ mod search;
 use std::{fmt::Debug, sync::Arc};
 
 use axum::{
-    extract::{ConnectInfo, State},
+    body::Bytes,
+    extract::{ConnectInfo, FromRequest, State},
+    http::{header, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
-use mentat_asserter::Asserter;
+use mentat_asserter::{Asserter, AsserterError};
+use mentat_keys::address::AddressScheme;
 use mentat_types::*;
 pub use search::*;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     conf::{Configuration, Mode, NodeConf},
@@ -44,6 +49,8 @@ pub struct ApiRouter<Api, NodeCaller> {
     pub api: Api,
     /// An `Asserter` instance for the API.
     pub asserter: Asserter,
+    /// The `Asserter` that validates the responses of the API, if enabled.
+    pub response_asserter: Option<Asserter>,
     /// The `NodeCaller` instance for the router.
     pub node_caller: Arc<NodeCaller>,
 }
@@ -53,17 +60,37 @@ impl<Api, NodeCaller> ApiRouter<Api, NodeCaller> {
     pub fn from<R: From<ApiRouter<Api, NodeCaller>>>(
         api: Api,
         asserter: Asserter,
+        response_asserter: Option<Asserter>,
         node_caller: Arc<NodeCaller>,
     ) -> R {
         Self {
             api,
             asserter,
+            response_asserter,
             node_caller,
         }
         .into()
     }
 }
 
+/// Validates a response with the response asserter of a route group, if it
+/// has one. Invalid responses are logged, and the handler replaces them with
+/// a 500 [`MentatError`] holding the details of the failed assertion.
+pub(crate) fn assert_response<R>(
+    response_asserter: Option<&Asserter>,
+    route: &str,
+    response: &R,
+    assert: impl FnOnce(&Asserter, &R) -> Result<(), AsserterError>,
+) -> Result<(), AsserterError> {
+    match response_asserter {
+        Some(asserter) => assert(asserter, response).map_err(|e| {
+            tracing::error!("{route} returned an invalid response: {e}");
+            e
+        }),
+        None => Ok(()),
+    }
+}
+
 #[macro_export]
 /// Creates a Router Specific type and implements From<ApiRouter> for it.
 macro_rules! router {
@@ -74,6 +101,9 @@ macro_rules! router {
             pub api: Api,
             /// An `Asserter` instance for the API.
             pub asserter: Asserter,
+            /// The `Asserter` that validates the responses of the API, if
+            /// enabled.
+            pub response_asserter: Option<Asserter>,
             /// The `NodeCaller` instance for the router.
             pub node_caller: ::std::sync::Arc<Api::NodeCaller>,
         }
@@ -84,6 +114,7 @@ macro_rules! router {
                 Self {
                     api: Default::default(),
                     asserter: Default::default(),
+                    response_asserter: None,
                     node_caller,
                 }
             }
@@ -96,6 +127,7 @@ macro_rules! router {
                 Self {
                     api: router.api,
                     asserter: router.asserter,
+                    response_asserter: router.response_asserter,
                     node_caller: router.node_caller,
                 }
             }
@@ -103,8 +135,101 @@ macro_rules! router {
     };
 }
 
+/// A request body decoded with the [`Encoding`] of its `Content-Type` header,
+/// along with the [`Encoding`] the response should use according to its
+/// `Accept` header. Requests without these headers use JSON.
+pub struct Encoded<T> {
+    /// The decoded request body.
+    pub data: T,
+    /// The encoding of the response.
+    pub accept: Encoding,
+}
+
+#[axum::async_trait]
+impl<S, B, T> FromRequest<S, B> for Encoded<T>
+where
+    Bytes: FromRequest<S, B>,
+    B: Send + 'static,
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let header = |req: &Request<B>, name| {
+            req.headers()
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let accept = Encoding::negotiate(header(&req, header::ACCEPT).as_deref());
+        let content_type = header(&req, header::CONTENT_TYPE);
+        let encoding = match Encoding::from_content_type(content_type.as_deref()) {
+            Some(encoding) => encoding,
+            None => {
+                let err = MentatError {
+                    status_code: StatusCode::UNSUPPORTED_MEDIA_TYPE.as_u16(),
+                    message: format!("unsupported content type {content_type:?}"),
+                    ..MentatError::default_error()
+                };
+                return Err(encoded_response::<()>(accept, Err(err)));
+            }
+        };
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        match encoding.decode(&body) {
+            Ok(data) => Ok(Self { data, accept }),
+            Err(e) => {
+                let err = MentatError {
+                    status_code: StatusCode::BAD_REQUEST.as_u16(),
+                    message: format!("invalid {encoding} request: {e}"),
+                    ..MentatError::default_error()
+                };
+                Err(encoded_response::<()>(accept, Err(err)))
+            }
+        }
+    }
+}
+
+/// Encodes a response, or the error in its place, with the given
+/// [`Encoding`].
+pub fn encoded_response<R: Serialize>(encoding: Encoding, response: MentatResponse<R>) -> Response {
+    if encoding == Encoding::Json {
+        return response.into_response();
+    }
+
+    let (status, body) = match response {
+        Ok(Json(resp)) => (StatusCode::OK, encoding.encode(&resp)),
+        Err(e) => (
+            StatusCode::from_u16(e.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            encoding.encode(&e),
+        ),
+    };
+    match body {
+        Ok(body) => (status, [(header::CONTENT_TYPE, encoding.mime_type())], body).into_response(),
+        Err(e) => {
+            MentatError::from(format!("failed to encode {encoding} response: {e}")).into_response()
+        }
+    }
+}
+
 /// A trait to define how to convert structs into an `axum::Router`.
 pub trait ToRouter {
     /// For converting the type to the router.
     fn to_router<CustomConfig: NodeConf>(self) -> axum::Router<Arc<AppState<CustomConfig>>>;
 }
+
+#[cfg(test)]
+#[path = ""]
+mod tests {
+    use super::*;
+
+    mod block_test;
+    mod construction_test;
+    mod encoded_test;
+
+    mod test_utils;
+    use test_utils::*;
+}
@@ -61,12 +61,21 @@ impl<Api: NetworkApi> NetworkApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedMetadataRequest>,
     ) -> MentatResponse<UncheckedNetworkListResponse> {
-        self.asserter.metadata_request(data.as_ref())?;
+        self.asserter
+            .metadata_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .network_list(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/network/list",
+            &resp,
+            |_, r| mentat_asserter::network_list_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -77,12 +86,21 @@ impl<Api: NetworkApi> NetworkApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedNetworkRequest>,
     ) -> MentatResponse<UncheckedNetworkOptionsResponse> {
-        self.asserter.network_request(data.as_ref())?;
+        self.asserter
+            .network_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .network_options(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/network/options",
+            &resp,
+            |_, r| mentat_asserter::network_options_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 
@@ -97,12 +115,21 @@ impl<Api: NetworkApi> NetworkApiRouter<Api> {
         if mode.is_offline() {
             MentatError::unavailable_offline(Some(mode))
         } else {
-            self.asserter.network_request(data.as_ref())?;
+            self.asserter
+                .network_request(data.as_ref())
+                .map_err(AsserterError::into_mentat_error)?;
             let resp = self
                 .api
                 .network_status(caller, data.unwrap().into(), &self.node_caller)
                 .await?
                 .into();
+            assert_response(
+                self.response_asserter.as_ref(),
+                "/network/status",
+                &resp,
+                |_, r| mentat_asserter::network_status_response(Some(r)),
+            )
+            .map_err(AsserterError::into_mentat_error)?;
             Ok(Json(resp))
         }
     }
@@ -120,8 +147,11 @@ where
                 "/list",
                 axum::routing::post(
                     |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                     Json(req_data): Json<Option<UncheckedMetadataRequest>>| async move {
-                        list.call_network_list(Caller { ip }, req_data).await
+                     encoded_req: Encoded<Option<UncheckedMetadataRequest>>| async move {
+                        let resp = list
+                            .call_network_list(Caller { ip }, encoded_req.data)
+                            .await;
+                        encoded_response(encoded_req.accept, resp)
                     },
                 ),
             )
@@ -129,8 +159,11 @@ where
                 "/options",
                 axum::routing::post(
                     |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                     Json(req_data): Json<Option<UncheckedNetworkRequest>>| async move {
-                        options.call_network_options(Caller { ip }, req_data).await
+                     encoded_req: Encoded<Option<UncheckedNetworkRequest>>| async move {
+                        let resp = options
+                            .call_network_options(Caller { ip }, encoded_req.data)
+                            .await;
+                        encoded_response(encoded_req.accept, resp)
                     },
                 ),
             )
@@ -139,9 +172,11 @@ where
                 axum::routing::post(
                     |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
                      State(conf): State<Configuration<CustomConfig>>,
-                     Json(req_data): Json<Option<UncheckedNetworkRequest>>| async move {
-                        self.call_network_status(Caller { ip }, &conf.mode, req_data)
-                            .await
+                     encoded_req: Encoded<Option<UncheckedNetworkRequest>>| async move {
+                        let resp = self
+                            .call_network_status(Caller { ip }, &conf.mode, encoded_req.data)
+                            .await;
+                        encoded_response(encoded_req.accept, resp)
                     },
                 ),
             )
@@ -39,12 +39,21 @@ impl<Api: SearchApi> SearchApiRouter<Api> {
         caller: Caller,
         data: Option<UncheckedSearchTransactionsRequest>,
     ) -> MentatResponse<UncheckedSearchTransactionsResponse> {
-        self.asserter.search_transactions_request(data.as_ref())?;
+        self.asserter
+            .search_transactions_request(data.as_ref())
+            .map_err(AsserterError::into_mentat_error)?;
         let resp = self
             .api
             .search_transactions(caller, data.unwrap().into(), &self.node_caller)
             .await?
             .into();
+        assert_response(
+            self.response_asserter.as_ref(),
+            "/search/transactions",
+            &resp,
+            |a, r| a.search_transaction_response(Some(r)),
+        )
+        .map_err(AsserterError::into_mentat_error)?;
         Ok(Json(resp))
     }
 }
@@ -58,8 +67,11 @@ where
             "/transactions",
             axum::routing::post(
                 |ConnectInfo(ip): ConnectInfo<::std::net::SocketAddr>,
-                 Json(req_data): Json<Option<UncheckedSearchTransactionsRequest>>| async move {
-                    self.call_search_transactions(Caller { ip }, req_data).await
+                 encoded_req: Encoded<Option<UncheckedSearchTransactionsRequest>>| async move {
+                    let resp = self
+                        .call_search_transactions(Caller { ip }, encoded_req.data)
+                        .await;
+                    encoded_response(encoded_req.accept, resp)
                 },
             ),
         )
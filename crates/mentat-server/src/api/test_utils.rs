@@ -0,0 +1,126 @@
+use std::{net::SocketAddr, process::Command};
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde::Deserialize;
+use sysinfo::{Pid, PidExt};
+use tower::ServiceExt;
+
+use super::*;
+use crate::conf::{NodePid, ServerPid};
+
+/// A node configuration without any custom fields.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct TestConf;
+
+impl NodeConf for TestConf {
+    const BLOCKCHAIN: &'static str = "test";
+
+    fn node_command(_config: &Configuration<Self>) -> Command {
+        Command::new("true")
+    }
+}
+
+/// A node caller that is never called.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TestCaller;
+
+/// The network the test asserters support.
+pub(crate) fn network() -> NetworkIdentifier {
+    NetworkIdentifier {
+        blockchain: "test".into(),
+        network: "testnet".into(),
+        sub_network_identifier: None,
+    }
+}
+
+/// A request asserter for [`network`] with a `PAYMENT` operation type.
+pub(crate) fn asserter() -> Asserter {
+    Asserter::new_server(
+        vec!["PAYMENT".into()],
+        false,
+        vec![network()],
+        Vec::new(),
+        false,
+        None,
+    )
+    .unwrap()
+}
+
+/// The response to a request sent through a router.
+pub(crate) struct TestResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) content_type: Option<String>,
+    pub(crate) body: Bytes,
+}
+
+impl TestResponse {
+    /// Decodes a JSON body.
+    pub(crate) fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+/// Builds a POST request to `path` with the given headers.
+pub(crate) fn request(
+    path: &str,
+    content_type: Option<&str>,
+    accept: Option<&str>,
+    body: Vec<u8>,
+) -> Request<Body> {
+    let mut builder = Request::post(path);
+    if let Some(content_type) = content_type {
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(accept) = accept {
+        builder = builder.header(header::ACCEPT, accept);
+    }
+    let mut request = builder.body(Body::from(body)).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+    request
+}
+
+/// Builds a JSON POST request to `path`.
+pub(crate) fn json_request<T: Serialize>(path: &str, body: &T) -> Request<Body> {
+    request(
+        path,
+        Some("application/json"),
+        None,
+        serde_json::to_vec(body).unwrap(),
+    )
+}
+
+/// Serves a single request with the router of an api group.
+pub(crate) async fn send<R: ToRouter>(
+    router: R,
+    config: Configuration<TestConf>,
+    request: Request<Body>,
+) -> TestResponse {
+    let state = Arc::new(AppState {
+        config,
+        node_pid: NodePid(Pid::from_u32(0)),
+        server_pid: ServerPid(Pid::from_u32(std::process::id())),
+    });
+    let response = router
+        .to_router::<TestConf>()
+        .with_state(state)
+        .oneshot(request)
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    TestResponse {
+        status,
+        content_type,
+        body,
+    }
+}
@@ -1,5 +1,6 @@
 //! contains tools to supply custom asserters to each route group
 use mentat_asserter::Asserter;
+use mentat_keys::Verifier;
 
 /// helper fn to construct builder methods
 macro_rules! builder_fn {
@@ -11,10 +12,22 @@ macro_rules! builder_fn {
     };
 }
 
+/// helper fn to construct builder methods that enable response validation
+macro_rules! response_builder_fn {
+    ($fn_name:ident, $name:ident) => {
+        pub fn $fn_name(mut self, v: Asserter) -> Self {
+            self.responses.$name = Some(v);
+            self
+        }
+    };
+}
+
 /// a builder struct that allows you to define custom asserters for different
 /// route groups and a default asserter to fall back on if needed
 #[derive(Default)]
 pub struct AsserterTableBuilder {
+    /// the asserters that validate the responses of each route group
+    responses: ResponseAsserterTable,
     /// the default asserter to use for a route group if one wasn't set
     use_default: Option<Asserter>,
     /// the asserter for the routes in AccountApi
@@ -54,10 +67,28 @@ impl AsserterTableBuilder {
 
     builder_fn!(search_api);
 
+    response_builder_fn!(validate_account_api_responses, account_api);
+
+    response_builder_fn!(validate_block_api_responses, block_api);
+
+    response_builder_fn!(validate_construction_api_responses, construction_api);
+
+    response_builder_fn!(validate_events_api_responses, events_api);
+
+    response_builder_fn!(validate_mempool_api_responses, mempool_api);
+
+    response_builder_fn!(validate_network_api_responses, network_api);
+
+    response_builder_fn!(validate_search_api_responses, search_api);
+
     /// constructs an AsserterTable from the builder. if any routes were left
     /// out then it will fall back to the default route. if no default route was
-    /// provided then it will panic
+    /// provided then it will panic. when the construction routes fall back to
+    /// the default route, the signatures of /construction/combine requests
+    /// are verified with [`Verifier`]. it also panics if a response asserter
+    /// cannot check responses, since it would reject every response
     pub fn build(self) -> AsserterTable {
+        self.responses.check();
         AsserterTable {
             account_api: self.account_api.unwrap_or_else(|| {
                 self.use_default
@@ -76,6 +107,7 @@ impl AsserterTableBuilder {
                 self.use_default
                     .clone()
                     .expect("no construction asserter provided")
+                    .with_signature_verifier(Verifier)
             }),
             events_api: self.events_api.unwrap_or_else(|| {
                 self.use_default
@@ -98,6 +130,59 @@ impl AsserterTableBuilder {
                     .expect("no search asserter provided")
             }),
             optional_api: (),
+            responses: self.responses,
+        }
+    }
+}
+
+/// contains the asserters that validate the responses of each api group
+/// before they are returned. they need the response side of an asserter, i.e.
+/// one built with `Asserter::new_client_with_options`. the responses of a
+/// group without one are not validated.
+///
+/// there is no entry for the CallApi: the result of a `/call` is whatever the
+/// node returns for the method, so the Rosetta spec defines nothing to check
+/// and its responses are never validated.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseAsserterTable {
+    /// the response asserter for the routes in AccountApi
+    pub account_api: Option<Asserter>,
+    /// the response asserter for the routes in BlockApi
+    pub block_api: Option<Asserter>,
+    /// the response asserter for the routes in ConstructionApi
+    pub construction_api: Option<Asserter>,
+    /// the response asserter for the routes in EventsApi
+    pub events_api: Option<Asserter>,
+    /// the response asserter for the routes in MempoolApi
+    pub mempool_api: Option<Asserter>,
+    /// the response asserter for the routes in NetworkApi
+    pub network_api: Option<Asserter>,
+    /// the response asserter for the routes in SearchApi
+    pub search_api: Option<Asserter>,
+}
+
+impl ResponseAsserterTable {
+    /// panics if an asserter cannot check responses.
+    fn check(&self) {
+        let asserters = [
+            ("account", &self.account_api),
+            ("block", &self.block_api),
+            ("construction", &self.construction_api),
+            ("events", &self.events_api),
+            ("mempool", &self.mempool_api),
+            ("network", &self.network_api),
+            ("search", &self.search_api),
+        ];
+        for (group, asserter) in asserters {
+            if asserter
+                .as_ref()
+                .map_or(false, |a| !a.validates_responses())
+            {
+                panic!(
+                    "the {group} response asserter cannot check responses, build it with \
+                     `Asserter::new_client_with_options`"
+                );
+            }
         }
     }
 }
@@ -124,6 +209,8 @@ pub struct AsserterTable {
     // TODO
     /// exists due to an edge case in our proc macro
     pub optional_api: (),
+    /// the asserters that validate the responses of each api group
+    pub responses: ResponseAsserterTable,
 }
 
 impl AsserterTable {
@@ -133,18 +220,21 @@ impl AsserterTable {
     }
 }
 
+/// uses the asserter for every route group. the signatures of
+/// /construction/combine requests are verified with [`Verifier`]
 impl From<Asserter> for AsserterTable {
     fn from(v: Asserter) -> Self {
         Self {
             account_api: v.clone(),
             block_api: v.clone(),
             call_api: v.clone(),
-            construction_api: v.clone(),
+            construction_api: v.clone().with_signature_verifier(Verifier),
             events_api: v.clone(),
             mempool_api: v.clone(),
             network_api: v.clone(),
             search_api: v,
             optional_api: (),
+            responses: Default::default(),
         }
     }
 }
@@ -11,6 +11,7 @@ use std::{
 };
 
 use axum::async_trait;
+use mentat_keys::address::AddressScheme;
 use serde::de::DeserializeOwned;
 use sysinfo::{Pid, PidExt};
 
@@ -118,6 +119,10 @@ pub struct Configuration<Custom: NodeConf> {
     pub node_address: Ipv4Addr,
     /// The port that the node will bind to.
     pub node_rpc_port: u16,
+    /// The scheme used to answer /construction/derive when the
+    /// implementation does not derive addresses itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_scheme: Option<AddressScheme>,
     /// Configuration settings specific to the rosetta implementation
     #[serde(
         default,
@@ -211,6 +216,7 @@ impl<Custom: NodeConf> Default for Configuration<Custom> {
             node_address: Ipv4Addr::new(0, 0, 0, 0),
             node_path: PathBuf::from("/app/rosetta-mentat-service"),
             node_rpc_port: 4032,
+            address_scheme: None,
             port: 8080,
             secure_http: true,
             custom,
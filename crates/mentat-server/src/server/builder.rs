@@ -60,31 +60,73 @@ impl<Types: ServerType> ServerBuilder<Types> {
         Server {
             account_api: self
                 .account_api
-                .map(|api| ApiRouter::from(api, asserters.account_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.account_api,
+                        asserters.responses.account_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
             block_api: self
                 .block_api
-                .map(|api| ApiRouter::from(api, asserters.block_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.block_api,
+                        asserters.responses.block_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
             call_api: self
                 .call_api
-                .map(|api| ApiRouter::from(api, asserters.call_api, node_caller.clone()))
+                .map(|api| ApiRouter::from(api, asserters.call_api, None, node_caller.clone()))
                 .expect("You did not set the call api."),
             construction_api: self
                 .construction_api
-                .map(|api| ApiRouter::from(api, asserters.construction_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.construction_api,
+                        asserters.responses.construction_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the construction api."),
             events_api: self
                 .events_api
-                .map(|api| ApiRouter::from(api, asserters.events_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.events_api,
+                        asserters.responses.events_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
             mempool_api: self
                 .mempool_api
-                .map(|api| ApiRouter::from(api, asserters.mempool_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.mempool_api,
+                        asserters.responses.mempool_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
             network_api: self
                 .network_api
-                .map(|api| ApiRouter::from(api, asserters.network_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.network_api,
+                        asserters.responses.network_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
             optional_api: self
                 .optional_api
@@ -96,7 +138,14 @@ impl<Types: ServerType> ServerBuilder<Types> {
                 .expect("You did not set the additional api."),
             search_api: self
                 .search_api
-                .map(|api| ApiRouter::from(api, asserters.search_api, node_caller.clone()))
+                .map(|api| {
+                    ApiRouter::from(
+                        api,
+                        asserters.search_api,
+                        asserters.responses.search_api.clone(),
+                        node_caller.clone(),
+                    )
+                })
                 .expect("You did not set the call api."),
 
             configuration,
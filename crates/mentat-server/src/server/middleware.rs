@@ -2,22 +2,46 @@
 
 use axum::{middleware::Next, response::IntoResponse};
 use hyper::{Body, HeaderMap, Method, Request, StatusCode};
-use mentat_types::Result;
+use mentat_types::{Encoding, Result};
+use opentelemetry_http::HeaderExtractor;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 /// sets the `Content-Type` field in the response header to `application/json;
-/// charset=UTF-8`
+/// charset=UTF-8`, unless the response was encoded in a binary encoding
 pub(crate) async fn content_type_middleware(
     req: Request<Body>,
     next: Next<Body>,
 ) -> Result<impl IntoResponse> {
     let mut resp = next.run(req).await;
-    resp.headers_mut().insert(
-        "Content-Type",
-        "application/json; charset=UTF-8".parse().unwrap(),
-    );
+    let content_type = resp
+        .headers()
+        .get("Content-Type")
+        .and_then(|v| v.to_str().ok());
+    if Encoding::from_content_type(content_type).map_or(true, |e| e == Encoding::Json) {
+        resp.headers_mut().insert(
+            "Content-Type",
+            "application/json; charset=UTF-8".parse().unwrap(),
+        );
+    }
     Ok(resp)
 }
 
+/// continues any OpenTelemetry trace propagated by the caller through the
+/// request headers, i.e. by a `mentat_client::TracingInterceptor`, so the
+/// spans of the request are attached to the caller's trace.
+pub(crate) async fn trace_context_middleware(
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Result<impl IntoResponse> {
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(req.headers()))
+    });
+    let span = tracing::info_span!("request", path = %req.uri().path());
+    span.set_parent(parent);
+    Ok(next.run(req).instrument(span).await)
+}
+
 /// cors_middleware handles CORS and ensures OPTIONS requests are
 /// handled properly.
 ///
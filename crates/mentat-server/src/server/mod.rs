@@ -102,6 +102,7 @@ impl<Types: ServerType> Server<Types> {
             .nest("/search", self.search_api.to_router())
             .layer(
                 tower::ServiceBuilder::new()
+                    .layer(axum::middleware::from_fn(trace_context_middleware))
                     .layer(axum::middleware::from_fn(content_type_middleware)),
             )
             .fallback(MentatError::not_found);
@@ -0,0 +1,131 @@
+//! The configuration of the `mentat-signer` binary.
+
+use std::{
+    env,
+    fs,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::{Path, PathBuf},
+};
+
+use mentat_asserter::Asserter;
+use mentat_client::Client;
+use mentat_keys::keystore::Keystore;
+use mentat_parser::Parser;
+use mentat_types::{AccountIdentifier, Currency, NetworkIdentifier, UncheckedNetworkRequest};
+use serde::{Deserialize, Serialize};
+
+use crate::{AllowedAccounts, MaxAmount, SigningService};
+
+/// The configuration of a signing service, loaded from a toml file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Configuration {
+    /// The address the service listens on.
+    pub address: SocketAddr,
+    /// The directory of the keystore holding the keys.
+    pub keystore: PathBuf,
+    /// The environment variable containing the keystore password.
+    pub password_env: String,
+    /// The origin of a Rosetta implementation used to parse unsigned
+    /// transactions, i.e. `http://localhost:8080/`. Requires `network`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parser: Option<String>,
+    /// The network of the transactions. Its /network/status and
+    /// /network/options are used to check the responses of `parser`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkIdentifier>,
+    /// The only accounts allowed to sign. Every account in the keystore may
+    /// sign if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_accounts: Option<Vec<AccountIdentifier>>,
+    /// The maximum amounts an account may spend in a single transaction.
+    /// Requires `parser`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub max_amounts: Vec<MaxAmountConfiguration>,
+}
+
+/// The maximum amount of a currency an account may spend in a single
+/// transaction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MaxAmountConfiguration {
+    /// The currency that is limited.
+    pub currency: Currency,
+    /// The largest amount that may be spent, in atomic units.
+    pub value: String,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            address: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8090).into(),
+            keystore: PathBuf::from("keystore"),
+            password_env: "MENTAT_SIGNER_PASSWORD".to_string(),
+            parser: None,
+            network: None,
+            allowed_accounts: None,
+            max_amounts: Vec::new(),
+        }
+    }
+}
+
+impl Configuration {
+    /// Loads a configuration file from the supplied path.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {e}", path.display()))?;
+        toml::from_str(&content)
+            .map_err(|e| format!("failed to parse config file {}: {e}", path.display()))
+    }
+
+    /// Builds the [`SigningService`] described by the configuration. The
+    /// keystore password is read from the `password_env` environment
+    /// variable.
+    pub async fn service(&self) -> Result<SigningService, String> {
+        let password = env::var(&self.password_env).map_err(|e| {
+            format!(
+                "failed to read environment variable {}: {e}",
+                self.password_env
+            )
+        })?;
+        let keystore = Keystore::open(&self.keystore).map_err(|e| e.to_string())?;
+        let mut service = SigningService::new()
+            .with_keystore(&keystore, &password)
+            .map_err(|e| e.to_string())?;
+
+        if let Some(parser) = &self.parser {
+            let network = self
+                .network
+                .clone()
+                .ok_or("a network is required to parse transactions")?;
+            let client = Client::new(parser).map_err(|e| e.to_string())?;
+            let request = UncheckedNetworkRequest::from(network.clone());
+            let status = client
+                .network_status(request.clone())
+                .await
+                .map_err(|e| format!("failed to fetch the network status: {e}"))?;
+            let options = client
+                .network_options(request)
+                .await
+                .map_err(|e| format!("failed to fetch the network options: {e}"))?;
+            let asserter = Asserter::new_client_with_responses(
+                Some(network),
+                Some(status),
+                Some(options),
+                None,
+            )
+            .map_err(|e| format!("failed to create the asserter: {e}"))?;
+            service = service.with_parser(client, Parser::new(Some(asserter), None, Vec::new()));
+        }
+        if let Some(accounts) = &self.allowed_accounts {
+            service = service.with_policy(AllowedAccounts::new(accounts.iter().cloned()));
+        }
+        for max_amount in &self.max_amounts {
+            service = service.with_policy(MaxAmount::new(
+                max_amount.currency.clone(),
+                &max_amount.value,
+            )?);
+        }
+
+        Ok(service)
+    }
+}
@@ -0,0 +1,89 @@
+//! The errors returned by the signing service.
+
+use mentat_client::ClientError;
+use mentat_types::MentatError;
+use thiserror::Error;
+
+/// The reasons the signing service refuses or fails to sign. They are
+/// returned to clients as a [`MentatError`] with a stable code.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignerError {
+    /// The request is malformed.
+    #[error("invalid signing request: {0}")]
+    InvalidRequest(String),
+    /// The service holds no key for the account.
+    #[error("no key for account: {0}")]
+    UnknownAccount(String),
+    /// A policy refused to sign the payload.
+    #[error("signing refused by policy: {0}")]
+    PolicyRefused(String),
+    /// The transaction could not be parsed.
+    #[error("failed to parse transaction: {0}")]
+    ParseFailed(String),
+    /// The signer failed to produce a signature.
+    #[error("failed to sign payload: {0}")]
+    SigningFailed(String),
+}
+
+impl SignerError {
+    /// The http status code returned for the error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::InvalidRequest(_) => 400,
+            Self::UnknownAccount(_) => 404,
+            Self::PolicyRefused(_) => 403,
+            Self::ParseFailed(_) | Self::SigningFailed(_) => 500,
+        }
+    }
+
+    /// The stable error code returned for the error.
+    pub fn code(&self) -> usize {
+        match self {
+            Self::InvalidRequest(_) => 100,
+            Self::UnknownAccount(_) => 101,
+            Self::PolicyRefused(_) => 102,
+            Self::ParseFailed(_) => 103,
+            Self::SigningFailed(_) => 104,
+        }
+    }
+
+    /// The message returned for the error, without its details.
+    fn message(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest(_) => "Invalid signing request",
+            Self::UnknownAccount(_) => "No key for account",
+            Self::PolicyRefused(_) => "Signing refused by policy",
+            Self::ParseFailed(_) => "Failed to parse transaction",
+            Self::SigningFailed(_) => "Failed to sign payload",
+        }
+    }
+
+    /// The details of the error.
+    fn details(&self) -> &str {
+        match self {
+            Self::InvalidRequest(d)
+            | Self::UnknownAccount(d)
+            | Self::PolicyRefused(d)
+            | Self::ParseFailed(d)
+            | Self::SigningFailed(d) => d,
+        }
+    }
+
+    /// Converts the error into the [`MentatError`] sent to clients.
+    pub fn to_mentat_error(&self) -> MentatError {
+        MentatError {
+            status_code: self.status_code(),
+            code: self.code(),
+            message: self.message().to_string(),
+            description: None,
+            retriable: matches!(self, Self::ParseFailed(_)),
+            details: [("context".to_string(), self.details().into())].into(),
+        }
+    }
+}
+
+impl From<SignerError> for ClientError {
+    fn from(err: SignerError) -> Self {
+        ClientError::ServerError(err.to_mentat_error())
+    }
+}
@@ -0,0 +1,29 @@
+//! A reference signing service that keeps private keys out of the process
+//! building transactions. It signs [`SignRequest`]s received over http with
+//! the existing `mentat-keys` signers, after checking them against a set of
+//! [`Policy`]s, and is used from the client side through an
+//! [`HttpSigner`](mentat_client::HttpSigner).
+
+#![deny(clippy::all, clippy::missing_docs_in_private_items)]
+#![warn(clippy::todo)]
+
+pub mod conf;
+
+mod errors;
+pub use errors::*;
+
+mod policy;
+pub use policy::*;
+
+mod service;
+pub use mentat_client::{PublicKeyRequest, RemoteSigner, SignRequest};
+pub use service::*;
+
+#[cfg(test)]
+#[path = ""]
+mod tests {
+    use super::*;
+
+    mod policy_test;
+    mod service_test;
+}
@@ -0,0 +1,38 @@
+//! The reference signing service binary. It takes the path of a toml
+//! [`Configuration`] as its only argument and serves the keys of the
+//! configured keystore over http.
+
+#![deny(clippy::all, clippy::missing_docs_in_private_items)]
+#![warn(clippy::todo)]
+
+use std::{net::TcpListener, path::PathBuf, process::exit, sync::Arc};
+
+use mentat_signer::{conf::Configuration, serve};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("signer.toml"));
+    let config = Configuration::load(&path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let service = config.service().await.unwrap_or_else(|e| {
+        eprintln!("failed to start signing service: {e}");
+        exit(1)
+    });
+
+    let listener = TcpListener::bind(config.address).unwrap_or_else(|e| {
+        eprintln!("failed to bind {}: {e}", config.address);
+        exit(1)
+    });
+    tracing::info!("signing service listening on {}", config.address);
+    if let Err(e) = serve(listener, Arc::new(service)).await {
+        eprintln!("signing service failed: {e}");
+        exit(1)
+    }
+}
@@ -0,0 +1,100 @@
+//! The policies a signing service checks before signing a payload.
+
+use std::collections::HashSet;
+
+use mentat_types::{amount_value, big_int, hash, AccountIdentifier, Currency, Operation};
+use num_bigint_dig::BigInt;
+
+use crate::SignRequest;
+
+/// A `Policy` decides whether the signing service may sign a request.
+pub trait Policy: Send + Sync {
+    /// Returns an error describing why `request` must not be signed.
+    /// `operations` are the operations parsed from the unsigned transaction,
+    /// or `None` if the service has no parser configured. Policies that
+    /// depend on the operations must refuse when they are missing.
+    fn check(&self, request: &SignRequest, operations: Option<&[Operation]>) -> Result<(), String>;
+}
+
+/// Only signs payloads for a fixed set of accounts.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedAccounts {
+    /// The hashes of the allowed accounts.
+    accounts: HashSet<String>,
+}
+
+impl AllowedAccounts {
+    /// Allows signing for `accounts` only.
+    pub fn new(accounts: impl IntoIterator<Item = AccountIdentifier>) -> Self {
+        Self {
+            accounts: accounts.into_iter().map(|a| hash(Some(&a))).collect(),
+        }
+    }
+}
+
+impl Policy for AllowedAccounts {
+    fn check(&self, request: &SignRequest, _: Option<&[Operation]>) -> Result<(), String> {
+        let account = request.payload.account_identifier.as_ref();
+        if !self.accounts.contains(&hash(account)) {
+            Err(format!("account {account:?} is not allowed to sign"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Limits how much of a currency the signing account may spend in a single
+/// transaction. The amount spent is the sum of the negative amounts of the
+/// signing account in the parsed operations.
+#[derive(Debug, Clone)]
+pub struct MaxAmount {
+    /// The currency that is limited.
+    currency: Currency,
+    /// The largest amount that may be spent, in atomic units.
+    max: BigInt,
+}
+
+impl MaxAmount {
+    /// Limits spending of `currency` to `max` atomic units.
+    pub fn new(currency: Currency, max: &str) -> Result<Self, String> {
+        Ok(Self {
+            currency,
+            max: big_int(max)?,
+        })
+    }
+}
+
+impl Policy for MaxAmount {
+    fn check(&self, request: &SignRequest, operations: Option<&[Operation]>) -> Result<(), String> {
+        let operations = operations.ok_or_else(|| {
+            format!(
+                "the transaction must be parsed to check the amount of {} spent",
+                self.currency.symbol
+            )
+        })?;
+
+        let account = hash(request.payload.account_identifier.as_ref());
+        let mut spent = BigInt::default();
+        for operation in operations {
+            let amount = match &operation.amount {
+                Some(amount) if amount.currency == self.currency => amount,
+                _ => continue,
+            };
+            if hash(operation.account.as_ref()) != account {
+                continue;
+            }
+
+            let value = amount_value(Some(amount))?;
+            if value < BigInt::default() {
+                spent -= value;
+            }
+        }
+
+        if spent > self.max {
+            Err(format!(
+                "account {:?} spends {spent} {} which exceeds the maximum of {}",
+                request.payload.account_identifier, self.currency.symbol, self.max
+            ))?;
+        }
+        Ok(())
+    }
+}
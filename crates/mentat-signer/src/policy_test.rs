@@ -0,0 +1,124 @@
+use mentat_test_utils::TestCase;
+use mentat_types::*;
+
+use super::*;
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+fn btc() -> Currency {
+    Currency {
+        symbol: "BTC".into(),
+        decimals: 8,
+        ..Default::default()
+    }
+}
+
+fn request(address: &str) -> SignRequest {
+    SignRequest {
+        payload: SigningPayload {
+            account_identifier: Some(account(address)),
+            bytes: vec![1; 32],
+            signature_type: SignatureType::EmptyString,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn operation(address: &str, value: &str, currency: Currency) -> Operation {
+    Operation {
+        type_: "transfer".into(),
+        account: Some(account(address)),
+        amount: Some(Amount {
+            value: value.into(),
+            currency,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_allowed_accounts() {
+    let policy = AllowedAccounts::new([account("alice")]);
+
+    let tests = vec![
+        TestCase {
+            name: "allowed",
+            payload: request("alice"),
+            criteria: None,
+        },
+        TestCase {
+            name: "not allowed",
+            payload: request("mallory"),
+            criteria: Some("is not allowed to sign".to_string()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |r| policy.check(&r, None));
+}
+
+#[test]
+fn test_max_amount() {
+    let policy = MaxAmount::new(btc(), "100").unwrap();
+    let eth = Currency {
+        symbol: "ETH".into(),
+        decimals: 18,
+        ..Default::default()
+    };
+
+    let tests = vec![
+        TestCase {
+            name: "below the maximum",
+            payload: Some(vec![
+                operation("alice", "-60", btc()),
+                operation("bob", "60", btc()),
+            ]),
+            criteria: None,
+        },
+        TestCase {
+            name: "exactly the maximum",
+            payload: Some(vec![
+                operation("alice", "-60", btc()),
+                operation("alice", "-40", btc()),
+                operation("alice", "1000", btc()),
+            ]),
+            criteria: None,
+        },
+        TestCase {
+            name: "other accounts and currencies are ignored",
+            payload: Some(vec![
+                operation("bob", "-1000", btc()),
+                operation("alice", "-1000", eth),
+            ]),
+            criteria: None,
+        },
+        TestCase {
+            name: "above the maximum",
+            payload: Some(vec![
+                operation("alice", "-60", btc()),
+                operation("alice", "-41", btc()),
+            ]),
+            criteria: Some("spends 101 BTC which exceeds the maximum of 100".to_string()),
+        },
+        TestCase {
+            name: "invalid amount",
+            payload: Some(vec![operation("alice", "-1.5", btc())]),
+            criteria: Some("-1.5 is not an integer".to_string()),
+        },
+        TestCase {
+            name: "transaction not parsed",
+            payload: None,
+            criteria: Some("the transaction must be parsed".to_string()),
+        },
+    ];
+
+    TestCase::run_err_match(tests, |ops: Option<Vec<Operation>>| {
+        policy.check(&request("alice"), ops.as_deref())
+    });
+}
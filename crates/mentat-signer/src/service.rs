@@ -0,0 +1,255 @@
+//! The [`SigningService`] and the http routes that expose it.
+
+use std::{net::TcpListener, sync::Arc};
+
+use async_trait::async_trait;
+use axum::{extract::State, routing::post, Json, Router};
+use mentat_asserter::{construction_payloads_response, AsserterError};
+use mentat_client::{Client, ClientError, PublicKeyRequest, RemoteSigner, SignRequest};
+use mentat_keys::{keystore::Keystore, KeysResult, Signer, SignerInterface};
+use mentat_parser::Parser;
+use mentat_types::*;
+
+use crate::{Policy, SignerError};
+
+/// A [`RemoteSigner`] that signs with in-process [`Signer`]s once every
+/// configured [`Policy`] accepts the request. It is the signer behind the
+/// http routes built by [`router`].
+#[derive(Default)]
+pub struct SigningService {
+    /// The signers, keyed by their account.
+    signers: Vec<(AccountIdentifier, Signer)>,
+    /// The policies every request is checked against.
+    policies: Vec<Box<dyn Policy>>,
+    /// The client used to parse unsigned transactions through
+    /// /construction/parse and rebuild their payloads through
+    /// /construction/payloads, and the [`Parser`] checking its responses.
+    parser: Option<(Client, Parser)>,
+}
+
+impl SigningService {
+    /// Creates a service without keys or policies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the signer for an account.
+    pub fn with_signer(mut self, account: AccountIdentifier, signer: Signer) -> Self {
+        self.signers.push((account, signer));
+        self
+    }
+
+    /// Adds the signer of every key in `keystore`, decrypting them with
+    /// `password`.
+    pub fn with_keystore(mut self, keystore: &Keystore, password: &str) -> KeysResult<Self> {
+        for key in keystore.list()? {
+            let signer = key.decrypt(password)?.signer()?;
+            self.signers.push((key.account_identifier, signer));
+        }
+        Ok(self)
+    }
+
+    /// Adds a policy every request must pass.
+    pub fn with_policy<P: Policy + 'static>(mut self, policy: P) -> Self {
+        self.policies.push(Box::new(policy));
+        self
+    }
+
+    /// Parses the unsigned transaction of every request through the
+    /// /construction/parse endpoint of the Rosetta implementation `client`
+    /// is connected to, so that policies can inspect its operations.
+    ///
+    /// The payloads of the transaction are then rebuilt from the parsed
+    /// operations and the metadata of the request through
+    /// /construction/payloads. A request is refused unless this rebuilds
+    /// its unsigned transaction and its payload is one of the rebuilt
+    /// payloads, so the operations the policies see are the ones that get
+    /// signed. Both responses are checked with the asserter of `parser`.
+    pub fn with_parser(mut self, client: Client, parser: Parser) -> Self {
+        self.parser = Some((client, parser));
+        self
+    }
+
+    /// Finds the signer registered for `account`.
+    fn signer(&self, account: Option<&AccountIdentifier>) -> Result<&Signer, SignerError> {
+        let account_hash = hash(account);
+        self.signers
+            .iter()
+            .find(|(a, _)| hash(Some(a)) == account_hash)
+            .map(|(_, signer)| signer)
+            .ok_or_else(|| SignerError::UnknownAccount(format!("{account:?}")))
+    }
+
+    /// Parses the operations of the unsigned transaction of `request` and
+    /// checks that its payload belongs to the transaction, if a parser is
+    /// configured.
+    async fn operations(
+        &self,
+        request: &SignRequest,
+    ) -> Result<Option<Vec<Operation>>, SignerError> {
+        let (client, parser) = match &self.parser {
+            Some(parser) => parser,
+            None => return Ok(None),
+        };
+        let asserter = parser
+            .asserter
+            .as_ref()
+            .ok_or_else(|| SignerError::ParseFailed(AsserterError::NotInitialized.to_string()))?;
+
+        let parsed = client
+            .construction_parse(UncheckedConstructionParseRequest {
+                network_identifier: Some(request.network_identifier.clone()),
+                signed: false,
+                transaction: request.unsigned_transaction.clone(),
+            })
+            .await
+            .map_err(|e| SignerError::ParseFailed(e.to_string()))?;
+        asserter
+            .construction_parse_response(Some(&parsed), false)
+            .map_err(|e| SignerError::ParseFailed(format!("parse response is invalid: {e}")))?;
+
+        let rebuilt = client
+            .construction_payloads(UncheckedConstructionPayloadsRequest {
+                network_identifier: Some(request.network_identifier.clone()),
+                operations: parsed.operations.clone(),
+                metadata: request.metadata.clone(),
+                public_keys: request
+                    .public_keys
+                    .iter()
+                    .cloned()
+                    .map(|k| Some(k.into()))
+                    .collect(),
+            })
+            .await
+            .map_err(|e| SignerError::ParseFailed(e.to_string()))?;
+        construction_payloads_response(Some(&rebuilt))
+            .map_err(|e| SignerError::ParseFailed(format!("payloads response is invalid: {e}")))?;
+        if rebuilt.unsigned_transaction != request.unsigned_transaction {
+            Err(SignerError::InvalidRequest(
+                "the operations of the unsigned transaction build a different transaction"
+                    .to_string(),
+            ))?;
+        }
+        if !rebuilt
+            .payloads
+            .into_iter()
+            .flatten()
+            .map(SigningPayload::from)
+            .any(|payload| same_payload(&payload, &request.payload))
+        {
+            Err(SignerError::InvalidRequest(
+                "the signing payload is not a payload of the unsigned transaction".to_string(),
+            ))?;
+        }
+
+        Ok(Some(
+            parsed
+                .operations
+                .into_iter()
+                .flatten()
+                .map(Operation::from)
+                .collect(),
+        ))
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for SigningService {
+    async fn public_key(&self, account: &AccountIdentifier) -> Result<PublicKey, ClientError> {
+        Ok(self.signer(Some(account))?.public_key())
+    }
+
+    async fn sign(&self, request: SignRequest) -> Result<Signature, ClientError> {
+        if request.payload.account_identifier.is_none() {
+            Err(SignerError::InvalidRequest(
+                "signing payload has no account".to_string(),
+            ))?;
+        }
+        // The payload type is the one checked against the transaction, so a
+        // request cannot ask for another one.
+        if request.signature_type != SignatureType::EmptyString
+            && request.payload.signature_type != SignatureType::EmptyString
+            && request.signature_type != request.payload.signature_type
+        {
+            Err(SignerError::InvalidRequest(format!(
+                "signature type {} does not match the payload signature type {}",
+                request.signature_type, request.payload.signature_type
+            )))?;
+        }
+        let signer = self.signer(request.payload.account_identifier.as_ref())?;
+
+        let operations = self.operations(&request).await?;
+        for policy in &self.policies {
+            policy
+                .check(&request, operations.as_deref())
+                .map_err(SignerError::PolicyRefused)?;
+        }
+
+        let signature_type = match (request.signature_type, request.payload.signature_type) {
+            (SignatureType::EmptyString, SignatureType::EmptyString) => signer
+                .default_signature_type()
+                .map_err(|e| SignerError::SigningFailed(e.to_string()))?,
+            (SignatureType::EmptyString, signature_type) | (signature_type, _) => signature_type,
+        };
+        let mut signature = signer
+            .sign(request.payload, signature_type)
+            .map_err(|e| SignerError::SigningFailed(e.to_string()))?;
+        signature.signature_type = signature_type;
+        Ok(signature)
+    }
+}
+
+/// Whether a payload to sign is the `rebuilt` one. The deprecated address is
+/// not compared, it is derived from the account.
+fn same_payload(rebuilt: &SigningPayload, payload: &SigningPayload) -> bool {
+    hash(rebuilt.account_identifier.as_ref()) == hash(payload.account_identifier.as_ref())
+        && rebuilt.bytes == payload.bytes
+        && rebuilt.signature_type == payload.signature_type
+}
+
+/// Maps an error returned by a [`RemoteSigner`] to the error sent to
+/// clients.
+fn to_mentat_error(err: ClientError) -> MentatError {
+    match err {
+        ClientError::ServerError(e) => e,
+        e => SignerError::SigningFailed(e.to_string()).to_mentat_error(),
+    }
+}
+
+/// Handles the `/public_key` route.
+async fn public_key(
+    State(signer): State<Arc<dyn RemoteSigner>>,
+    Json(request): Json<PublicKeyRequest>,
+) -> Result<Json<UncheckedPublicKey>, MentatError> {
+    let public_key = signer
+        .public_key(&request.account_identifier)
+        .await
+        .map_err(to_mentat_error)?;
+    Ok(Json(public_key.into()))
+}
+
+/// Handles the `/sign` route.
+async fn sign(
+    State(signer): State<Arc<dyn RemoteSigner>>,
+    Json(request): Json<SignRequest>,
+) -> Result<Json<UncheckedSignature>, MentatError> {
+    let signature = signer.sign(request).await.map_err(to_mentat_error)?;
+    Ok(Json(signature.into()))
+}
+
+/// Builds the http routes of a signing service: `POST /public_key` with a
+/// [`PublicKeyRequest`] and `POST /sign` with a [`SignRequest`]. Errors are
+/// returned as a [`MentatError`].
+pub fn router(signer: Arc<dyn RemoteSigner>) -> Router {
+    Router::new()
+        .route("/public_key", post(public_key))
+        .route("/sign", post(sign))
+        .with_state(signer)
+}
+
+/// Serves the routes of [`router`] on `listener` until the server fails.
+pub async fn serve(listener: TcpListener, signer: Arc<dyn RemoteSigner>) -> hyper::Result<()> {
+    axum::Server::from_tcp(listener)?
+        .serve(router(signer).into_make_service())
+        .await
+}
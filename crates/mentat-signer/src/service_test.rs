@@ -0,0 +1,358 @@
+use std::{net::TcpListener, sync::Arc};
+
+use mentat_asserter::Asserter;
+use mentat_client::{Client, ClientError, HttpSigner, RecordedExchange, ReplayInterceptor};
+use mentat_keys::{keystore::Keystore, types::KeyPair, Signer, SignerInterface, Verifier};
+use mentat_parser::Parser;
+use mentat_types::*;
+use serde_json::json;
+
+use super::*;
+
+const PRIVATE_KEY: &str = "aeb121b4c545f0f850e1480492508c65a250e9965b0d90176fab4d7506398ebb";
+
+fn account(address: &str) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.into(),
+        ..Default::default()
+    }
+}
+
+fn network() -> NetworkIdentifier {
+    ("bitcoin", "mainnet").into()
+}
+
+fn btc() -> Currency {
+    Currency {
+        symbol: "BTC".into(),
+        decimals: 8,
+        ..Default::default()
+    }
+}
+
+fn signer() -> Signer {
+    KeyPair::import_private_key(PRIVATE_KEY.into(), CurveType::Edwards25519)
+        .unwrap()
+        .signer()
+        .unwrap()
+}
+
+fn payload(address: &str) -> SigningPayload {
+    SigningPayload {
+        account_identifier: Some(account(address)),
+        bytes: vec![1; 32],
+        signature_type: SignatureType::EmptyString,
+        ..Default::default()
+    }
+}
+
+fn request(address: &str, transaction: &str) -> SignRequest {
+    SignRequest {
+        network_identifier: network(),
+        unsigned_transaction: transaction.into(),
+        payload: payload(address),
+        signature_type: SignatureType::EmptyString,
+        ..Default::default()
+    }
+}
+
+/// The operations of a transaction spending `value` BTC from alice.
+fn operations(value: &str) -> Vec<Option<UncheckedOperation>> {
+    serde_json::from_value(json!([{
+        "operation_identifier": {"index": 0},
+        "type": "transfer",
+        "account": {"address": "alice"},
+        "amount": {"value": value, "currency": {"symbol": "BTC", "decimals": 8}},
+    }]))
+    .unwrap()
+}
+
+fn asserter() -> Asserter {
+    Asserter::new_client_with_options(
+        Some(network()),
+        Some(UncheckedBlockIdentifier {
+            index: 0,
+            hash: "block 0".into(),
+        }),
+        vec!["transfer".into()],
+        vec![Some(OperationStatus {
+            status: "SUCCESS".into(),
+            successful: true,
+        })],
+        Vec::new(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+/// A client answering /construction/parse and /construction/payloads for
+/// the `small` and `large` transactions, which spend 10 and 1000 BTC from
+/// alice and are signed by alice and bob. The `forged` transaction parses
+/// like `small`, but its operations build `small` instead.
+fn client() -> Client {
+    let parse = |transaction: &str, value: &str| RecordedExchange {
+        path: "construction/parse".into(),
+        request: serde_json::to_value(UncheckedConstructionParseRequest {
+            network_identifier: Some(network()),
+            signed: false,
+            transaction: transaction.into(),
+        })
+        .unwrap(),
+        status: 200,
+        response: json!({ "operations": operations(value) }),
+        latency_ms: 0,
+    };
+    let payloads = |transaction: &str, value: &str| RecordedExchange {
+        path: "construction/payloads".into(),
+        request: serde_json::to_value(UncheckedConstructionPayloadsRequest {
+            network_identifier: Some(network()),
+            operations: operations(value),
+            ..Default::default()
+        })
+        .unwrap(),
+        status: 200,
+        response: json!({
+            "unsigned_transaction": transaction,
+            "payloads": [
+                UncheckedSigningPayload::from(payload("alice")),
+                UncheckedSigningPayload::from(payload("bob")),
+            ],
+        }),
+        latency_ms: 0,
+    };
+
+    Client::new("http://localhost:1/")
+        .unwrap()
+        .with_interceptor(ReplayInterceptor::new(vec![
+            parse("small", "-10"),
+            parse("large", "-1000"),
+            parse("forged", "-10"),
+            payloads("small", "-10"),
+            payloads("large", "-1000"),
+        ]))
+}
+
+fn parser() -> Parser {
+    Parser::new(Some(asserter()), None, Vec::new())
+}
+
+/// Serves `service` on an ephemeral local port and returns a client for it.
+fn spawn(service: SigningService) -> HttpSigner {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let origin = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(serve(listener, Arc::new(service)));
+    HttpSigner::new(Client::new(&origin).unwrap())
+}
+
+fn service() -> SigningService {
+    SigningService::new()
+        .with_signer(account("alice"), signer())
+        .with_signer(account("bob"), signer())
+        .with_policy(AllowedAccounts::new([account("alice")]))
+        .with_policy(MaxAmount::new(btc(), "100").unwrap())
+        .with_parser(client(), parser())
+}
+
+/// Returns the code of the [`MentatError`] returned by the service.
+async fn error_code(remote: &HttpSigner, request: SignRequest) -> usize {
+    match remote.sign(request).await {
+        Err(ClientError::ServerError(e)) => e.code,
+        other => panic!("expected a server error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_sign_over_http() {
+    let remote = spawn(service());
+
+    assert_eq!(
+        remote.public_key(&account("alice")).await.unwrap(),
+        signer().public_key()
+    );
+
+    let signature = remote.sign(request("alice", "small")).await.unwrap();
+    assert_eq!(signature.signature_type, SignatureType::Ed25519);
+    Verifier::verify(&signature.into()).unwrap();
+}
+
+#[tokio::test]
+async fn test_sign_refused() {
+    let remote = spawn(service());
+
+    let tests = [
+        (
+            request("alice", "large"),
+            SignerError::PolicyRefused(String::new()),
+        ),
+        (
+            request("bob", "small"),
+            SignerError::PolicyRefused(String::new()),
+        ),
+        (
+            request("carol", "small"),
+            SignerError::UnknownAccount(String::new()),
+        ),
+        (
+            request("alice", "unknown"),
+            SignerError::ParseFailed(String::new()),
+        ),
+    ];
+    for (request, err) in tests {
+        assert_eq!(error_code(&remote, request).await, err.code());
+    }
+
+    let mut request = request("alice", "small");
+    request.payload.account_identifier = None;
+    assert_eq!(
+        error_code(&remote, request).await,
+        SignerError::InvalidRequest(String::new()).code()
+    );
+
+    match remote.public_key(&account("carol")).await {
+        Err(ClientError::ServerError(e)) => {
+            assert_eq!(e.code, SignerError::UnknownAccount(String::new()).code())
+        }
+        other => panic!("expected a server error but got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_sign_mismatching_payload() {
+    let remote = spawn(service());
+
+    // the payload is not one of the payloads of the transaction.
+    let mut mismatching = request("alice", "small");
+    mismatching.payload.bytes = vec![2; 32];
+    assert_eq!(
+        error_code(&remote, mismatching).await,
+        SignerError::InvalidRequest(String::new()).code()
+    );
+
+    // the parsed operations build a different transaction.
+    assert_eq!(
+        error_code(&remote, request("alice", "forged")).await,
+        SignerError::InvalidRequest(String::new()).code()
+    );
+
+    // responses cannot be checked without an asserter.
+    let remote = spawn(
+        SigningService::new()
+            .with_signer(account("alice"), signer())
+            .with_parser(client(), Parser::new(None, None, Vec::new())),
+    );
+    assert_eq!(
+        error_code(&remote, request("alice", "small")).await,
+        SignerError::ParseFailed(String::new()).code()
+    );
+}
+
+#[tokio::test]
+async fn test_sign_mismatching_signature_type() {
+    let remote = spawn(SigningService::new().with_signer(account("alice"), signer()));
+    let request = |signature_type, payload_signature_type| {
+        let mut request = request("alice", "small");
+        request.signature_type = signature_type;
+        request.payload.signature_type = payload_signature_type;
+        request
+    };
+
+    // the requested type cannot override the type of the payload.
+    assert_eq!(
+        error_code(
+            &remote,
+            request(SignatureType::EcdsaRecovery, SignatureType::Ed25519)
+        )
+        .await,
+        SignerError::InvalidRequest(String::new()).code()
+    );
+
+    for (signature_type, payload_signature_type) in [
+        (SignatureType::Ed25519, SignatureType::Ed25519),
+        (SignatureType::EmptyString, SignatureType::Ed25519),
+        (SignatureType::Ed25519, SignatureType::EmptyString),
+    ] {
+        let signature = remote
+            .sign(request(signature_type, payload_signature_type))
+            .await
+            .unwrap();
+        assert_eq!(signature.signature_type, SignatureType::Ed25519);
+    }
+}
+
+#[tokio::test]
+async fn test_max_amount_requires_parser() {
+    let remote = spawn(
+        SigningService::new()
+            .with_signer(account("alice"), signer())
+            .with_policy(MaxAmount::new(btc(), "100").unwrap()),
+    );
+
+    assert_eq!(
+        error_code(&remote, request("alice", "small")).await,
+        SignerError::PolicyRefused(String::new()).code()
+    );
+}
+
+#[tokio::test]
+async fn test_configuration() {
+    let dir = std::env::temp_dir().join(format!("mentat-signer-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let key_pair =
+        KeyPair::import_private_key(PRIVATE_KEY.into(), CurveType::Edwards25519).unwrap();
+    Keystore::open(dir.join("keystore"))
+        .unwrap()
+        .with_params(mentat_keys::keystore::ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        })
+        .import(&account("alice"), &key_pair, "password")
+        .unwrap();
+
+    let path = dir.join("signer.toml");
+    std::fs::write(
+        &path,
+        format!(
+            r#"
+address = "127.0.0.1:0"
+keystore = "{}"
+password_env = "MENTAT_SIGNER_TEST_PASSWORD"
+allowed_accounts = [{{ address = "alice" }}]
+
+[[max_amounts]]
+currency = {{ symbol = "BTC", decimals = 8 }}
+value = "100"
+"#,
+            dir.join("keystore").display()
+        ),
+    )
+    .unwrap();
+
+    let config = conf::Configuration::load(&path).unwrap();
+    assert!(config.service().await.is_err());
+
+    std::env::set_var("MENTAT_SIGNER_TEST_PASSWORD", "password");
+    let remote = spawn(config.service().await.unwrap());
+    assert_eq!(
+        remote.public_key(&account("alice")).await.unwrap(),
+        signer().public_key()
+    );
+    // No parser is configured so the max amount policy refuses.
+    assert_eq!(
+        error_code(&remote, request("alice", "small")).await,
+        SignerError::PolicyRefused(String::new()).code()
+    );
+
+    // a parser needs the network to check its responses.
+    let config = conf::Configuration {
+        parser: Some("http://localhost:1/".into()),
+        ..config
+    };
+    match config.service().await {
+        Err(e) => assert!(e.contains("a network is required"), "{e}"),
+        Ok(_) => panic!("expected the service to require a network"),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
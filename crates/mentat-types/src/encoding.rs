@@ -0,0 +1,110 @@
+//! The wire encodings Rosetta types can be sent in.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+/// Encoding is a format the Rosetta types can be serialized to. JSON is the
+/// default and the only one the Rosetta spec defines. The binary encodings
+/// keep bytes raw and store canonical amount values as integers, see
+/// [`amount_value_to_canonical`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// JSON, sent as `application/json`.
+    #[default]
+    Json,
+    /// CBOR, sent as `application/cbor`.
+    Cbor,
+    /// MessagePack, sent as `application/msgpack`. Structs are encoded as
+    /// maps, so fields can be added or omitted like in JSON.
+    MessagePack,
+}
+
+impl Encoding {
+    /// `mime_type` returns the media type of the encoding.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Cbor => "application/cbor",
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// `from_content_type` returns the encoding of a `Content-Type` header,
+    /// ignoring its parameters. A missing header means JSON.
+    pub fn from_content_type(content_type: Option<&str>) -> Option<Self> {
+        let content_type = match content_type {
+            Some(content_type) => content_type,
+            None => return Some(Self::Json),
+        };
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match mime.as_str() {
+            "application/json" => Some(Self::Json),
+            "application/cbor" => Some(Self::Cbor),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MessagePack)
+            }
+            _ => None,
+        }
+    }
+
+    /// `negotiate` picks the encoding of a response from an `Accept` header.
+    /// The supported media type with the highest quality wins, with ties
+    /// going to the one listed first. Without a match it falls back to JSON.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let mut best: Option<(Self, f32)> = None;
+        for range in accept.unwrap_or_default().split(',') {
+            let mut params = range.split(';');
+            let mime = params.next().unwrap_or_default().trim();
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let encoding = match mime {
+                "*/*" | "application/*" => Some(Self::Json),
+                mime => Self::from_content_type(Some(mime)),
+            };
+            if let Some(encoding) = encoding {
+                if quality > 0.0 && best.map_or(true, |(_, q)| quality > q) {
+                    best = Some((encoding, quality));
+                }
+            }
+        }
+        best.map(|(encoding, _)| encoding).unwrap_or_default()
+    }
+
+    /// `encode` serializes a value with the encoding.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::ser::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// `decode` deserializes a value from the encoding.
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Self::Cbor => ciborium::de::from_reader(bytes).map_err(|e| e.to_string()),
+            Self::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.mime_type())
+    }
+}
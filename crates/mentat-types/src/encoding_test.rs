@@ -0,0 +1,204 @@
+use indexmap::indexmap;
+use mentat_test_utils::*;
+use serde_json::json;
+
+use super::*;
+
+fn block() -> UncheckedBlockResponse {
+    let amount = |value: &str| UncheckedAmount {
+        value: value.into(),
+        currency: Some(UncheckedCurrency {
+            symbol: "BTC".into(),
+            decimals: 8,
+            metadata: Default::default(),
+        }),
+        metadata: Default::default(),
+    };
+    UncheckedBlockResponse {
+        block: Some(UncheckedBlock {
+            block_identifier: Some(UncheckedBlockIdentifier {
+                index: 1,
+                hash: "block 1".into(),
+            }),
+            parent_block_identifier: Some(UncheckedBlockIdentifier {
+                index: 0,
+                hash: "block 0".into(),
+            }),
+            timestamp: 1_600_000_000_000,
+            transactions: vec![Some(UncheckedTransaction {
+                transaction_identifier: Some(TransactionIdentifier {
+                    hash: "tx 1".into(),
+                }),
+                operations: vec![
+                    Some(UncheckedOperation {
+                        type_: "PAYMENT".into(),
+                        amount: Some(amount("-123456789012345678901234567890")),
+                        ..Default::default()
+                    }),
+                    Some(UncheckedOperation {
+                        type_: "PAYMENT".into(),
+                        amount: Some(amount("1.5")),
+                        metadata: indexmap!("nested".to_string() => json!({ "a": [1, null] })),
+                        ..Default::default()
+                    }),
+                ],
+                ..Default::default()
+            })],
+            metadata: indexmap!("size".to_string() => json!(2)),
+        }),
+        other_transactions: vec![Some(TransactionIdentifier {
+            hash: "tx 2".into(),
+        })],
+    }
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    let signature = UncheckedSignature {
+        signing_payload: Some(UncheckedSigningPayload {
+            bytes: vec![0, 1, 2, 255],
+            ..Default::default()
+        }),
+        public_key: Some(UncheckedPublicKey {
+            bytes: vec![3; 33],
+            curve_type: UncheckedCurveType::SECP256K1.into(),
+        }),
+        signature_type: UncheckedSignatureType::ECDSA.into(),
+        bytes: vec![4; 64],
+    };
+
+    for encoding in [Encoding::Json, Encoding::Cbor, Encoding::MessagePack] {
+        let bytes = encoding.encode(&block()).unwrap();
+        let decoded: UncheckedBlockResponse = encoding.decode(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(decoded).unwrap(),
+            serde_json::to_value(block()).unwrap(),
+            "{encoding}"
+        );
+
+        let bytes = encoding.encode(&signature).unwrap();
+        let decoded: UncheckedSignature = encoding.decode(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(decoded).unwrap(),
+            serde_json::to_value(&signature).unwrap(),
+            "{encoding}"
+        );
+    }
+
+    // the binary encodings keep the bytes raw instead of hex encoding them.
+    for encoding in [Encoding::Cbor, Encoding::MessagePack] {
+        let bytes = encoding.encode(&signature).unwrap();
+        assert!(bytes.windows(64).any(|w| w == [4; 64]), "{encoding}");
+        assert!(bytes.len() < Encoding::Json.encode(&signature).unwrap().len());
+    }
+    Encoding::Cbor
+        .decode::<UncheckedBlockResponse>(b"{}")
+        .unwrap_err();
+}
+
+#[test]
+fn test_canonical_amount_value() {
+    let tests = vec![
+        TestCase {
+            name: "canonical",
+            payload: "1000",
+            criteria: true,
+        },
+        TestCase {
+            name: "negative",
+            payload: "-1000",
+            criteria: true,
+        },
+        TestCase {
+            name: "leading zeros",
+            payload: "0001000",
+            criteria: false,
+        },
+        TestCase {
+            name: "plus sign",
+            payload: "+1000",
+            criteria: false,
+        },
+        TestCase {
+            name: "negative zero",
+            payload: "-0",
+            criteria: false,
+        },
+        TestCase {
+            name: "not an integer",
+            payload: "1.5",
+            criteria: false,
+        },
+    ];
+
+    // every encoding decodes the value as it was sent, only canonical
+    // integers are stored as bytes.
+    TestCase::run_output_match(tests, |value| {
+        let amount = UncheckedAmount {
+            value: value.into(),
+            ..Default::default()
+        };
+        let json: UncheckedAmount = serde_json::from_value(json!({ "value": value })).unwrap();
+        assert_eq!(json.value, value);
+
+        let mut as_bytes = Vec::new();
+        for encoding in [Encoding::Cbor, Encoding::MessagePack] {
+            let bytes = encoding.encode(&amount).unwrap();
+            let decoded: UncheckedAmount = encoding.decode(&bytes).unwrap();
+            assert_eq!(decoded.value, value, "{encoding}");
+            as_bytes.push(!bytes.windows(value.len()).any(|w| w == value.as_bytes()));
+        }
+        assert_eq!(as_bytes[0], as_bytes[1]);
+        as_bytes[0]
+    });
+}
+
+#[test]
+fn test_negotiate_encoding() {
+    let tests = vec![
+        TestCase {
+            name: "missing",
+            payload: None,
+            criteria: Encoding::Json,
+        },
+        TestCase {
+            name: "cbor",
+            payload: Some("application/cbor"),
+            criteria: Encoding::Cbor,
+        },
+        TestCase {
+            name: "first of equal quality",
+            payload: Some("application/msgpack, application/cbor"),
+            criteria: Encoding::MessagePack,
+        },
+        TestCase {
+            name: "highest quality",
+            payload: Some("application/json;q=0.5, application/cbor;q=0.9"),
+            criteria: Encoding::Cbor,
+        },
+        TestCase {
+            name: "wildcard",
+            payload: Some("application/cbor;q=0.5, */*"),
+            criteria: Encoding::Json,
+        },
+        TestCase {
+            name: "unsupported",
+            payload: Some("text/html, application/xml;q=0.9"),
+            criteria: Encoding::Json,
+        },
+        TestCase {
+            name: "refused",
+            payload: Some("application/cbor;q=0, application/msgpack;q=0.1"),
+            criteria: Encoding::MessagePack,
+        },
+    ];
+
+    TestCase::run_output_match(tests, Encoding::negotiate);
+
+    assert_eq!(
+        Encoding::from_content_type(Some("application/CBOR; charset=binary")),
+        Some(Encoding::Cbor)
+    );
+    assert_eq!(Encoding::from_content_type(None), Some(Encoding::Json));
+    assert_eq!(Encoding::from_content_type(Some("text/plain")), None);
+}
@@ -73,6 +73,7 @@ impl MentatError {
             MentatError::transaction_not_found::<&str, ()>(None).unwrap_err(),
             MentatError::couldnt_get_fee_rate::<&str, ()>(None).unwrap_err(),
             MentatError::couldnt_get_balance::<&str, ()>(None).unwrap_err(),
+            MentatError::assertion_failed::<&str, ()>(None).unwrap_err(),
         ]
     }
 
@@ -100,6 +101,13 @@ impl MentatError {
         }
     }
 
+    /// Whether this is the error returned by [`MentatError::not_implemented`].
+    /// Other errors can share its code, i.e. the ones converted from any
+    /// [`Display`] type, so the whole error is compared.
+    pub fn is_not_implemented(&self) -> bool {
+        Self::not_implemented::<()>().unwrap_err() == *self
+    }
+
     /// Endpoint not implemented
     pub fn not_implemented<R>() -> Result<R> {
         Err(MentatError {
@@ -328,6 +336,19 @@ impl MentatError {
             details: Self::context(details, |n| n.to_string()),
         })
     }
+
+    /// Assertion failed, for a request or response rejected by the asserter.
+    /// The stable code of the failed assertion is in the details.
+    pub fn assertion_failed<D: Display, R>(details: Option<D>) -> Result<R> {
+        Err(MentatError {
+            status_code: 500,
+            code: 19,
+            message: "Assertion failed".to_string(),
+            description: None,
+            retriable: false,
+            details: Self::context(details, |n| n.to_string()),
+        })
+    }
 }
 
 impl<T: Display> From<T> for MentatError {
@@ -9,6 +9,8 @@ use mentat_macros::Unchecked;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+mod encoding;
+pub use encoding::*;
 mod errors;
 pub use errors::*;
 mod identifiers;
@@ -41,6 +43,7 @@ const ROSETTA_API_VERSION: &str = "1.4.12";
 mod tests {
     use super::*;
 
+    mod encoding_test;
     mod serialize_test;
     mod utils_test;
 }
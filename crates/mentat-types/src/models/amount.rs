@@ -10,6 +10,10 @@ pub struct UncheckedAmount {
     /// Value of the transaction in atomic units represented as an
     /// arbitrary-sized signed integer. For example, 1 BTC would be represented
     /// by a value of 100000000.
+    #[serde(
+        serialize_with = "amount_value_to_canonical",
+        deserialize_with = "canonical_to_amount_value"
+    )]
     pub value: String,
     /// `Currency` is composed of a canonical Symbol and Decimals. This
     /// Decimals value is used to convert an Amount.Value from atomic units
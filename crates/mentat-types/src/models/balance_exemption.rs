@@ -14,7 +14,7 @@ use super::*;
 /// balance changes. If your implementation relies on any `[BalanceExemption]`s,
 /// you MUST implement historical balance lookup (the ability to query an
 /// account balance at any [`BlockIdentifier`]).
-#[derive(Clone, Debug, Default, Deserialize, Serialize, Unchecked)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Unchecked)]
 #[serde(default)]
 pub struct UncheckedBalanceExemption {
     /// SubAccountAddress is the [`SubAccountIdentifier`]. Address that the
@@ -7,7 +7,7 @@ use super::*;
 /// `ExemptionType` is used to indicate if the live balance for an account
 /// subject to a `BalanceExemption` could increase above, decrease below, or
 /// equal the computed balance.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(transparent)]
 pub struct UncheckedExemptionType(String);
 
@@ -7,7 +7,7 @@ use super::*;
 /// the SignatureType. [`PublicKey`] is often times not known during
 /// construction of the signing payloads but may be needed to combine signatures
 /// properly.
-#[derive(Debug, Deserialize, Serialize, Default, Unchecked)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default, Unchecked)]
 #[serde(default)]
 pub struct UncheckedSignature {
     /// [`SigningPayload`] is signed by the client with the keypair associated
@@ -16,8 +16,11 @@ impl UncheckedSignatureType {
     pub const ECDSA_RECOVERY: &'static str = "ecdsa_recovery";
     /// R (32-bytes) + s (32-bytes)
     pub const ED25519: &'static str = "ed25519";
-    /// r (32-bytes) + s (32-bytes)
+    /// r (32-bytes) + s (32-bytes) where r = SHA256(Q + pk + message) as used
+    /// by Zilliqa
     pub const SCHNORR_1: &'static str = "schnorr_1";
+    /// R.x (32-bytes) + s (32-bytes) as defined by BIP-340
+    pub const SCHNORR_BIP340: &'static str = "schnorr_bip340";
     /// r (32-bytes) + s (32-bytes) where s = Hash(1st pk + 2nd pk + r)
     pub const SCHNORR_POSEIDON: &'static str = "schnorr_poseidon";
 
@@ -29,6 +32,7 @@ impl UncheckedSignatureType {
                 | Self::ECDSA_RECOVERY
                 | Self::ED25519
                 | Self::SCHNORR_1
+                | Self::SCHNORR_BIP340
                 | Self::SCHNORR_POSEIDON
         )
     }
@@ -68,9 +72,13 @@ pub enum SignatureType {
     EcdsaRecovery,
     /// R (32-bytes) + s (32-bytes)
     Ed25519,
-    /// r (32-bytes) + s (32-bytes)
+    /// r (32-bytes) + s (32-bytes) where r = SHA256(Q + pk + message) as used
+    /// by Zilliqa
     #[serde(rename = "schnorr_1")]
     Schnorr1,
+    /// R.x (32-bytes) + s (32-bytes) as defined by BIP-340
+    #[serde(rename = "schnorr_bip340")]
+    SchnorrBip340,
     /// r (32-bytes) + s (32-bytes) where s = Hash(1st pk + 2nd pk + r)
     SchnorrPoseidon,
     /// Necessary to match rosetta logic.
@@ -84,6 +92,7 @@ impl From<UncheckedSignatureType> for SignatureType {
             UncheckedSignatureType::ECDSA_RECOVERY => Self::EcdsaRecovery,
             UncheckedSignatureType::ED25519 => Self::Ed25519,
             UncheckedSignatureType::SCHNORR_1 => Self::Schnorr1,
+            UncheckedSignatureType::SCHNORR_BIP340 => Self::SchnorrBip340,
             UncheckedSignatureType::SCHNORR_POSEIDON => Self::SchnorrPoseidon,
             empty if empty.is_empty() => Self::EmptyString,
             i => panic!("unsupported ExemptionType: {i}"),
@@ -98,6 +107,7 @@ impl From<SignatureType> for UncheckedSignatureType {
             SignatureType::EcdsaRecovery => Self::ECDSA_RECOVERY.into(),
             SignatureType::Ed25519 => Self::ED25519.into(),
             SignatureType::Schnorr1 => Self::SCHNORR_1.into(),
+            SignatureType::SchnorrBip340 => Self::SCHNORR_BIP340.into(),
             SignatureType::SchnorrPoseidon => Self::SCHNORR_POSEIDON.into(),
             SignatureType::EmptyString => "".into(),
         }
@@ -111,6 +121,7 @@ impl fmt::Display for SignatureType {
             SignatureType::EcdsaRecovery => write!(f, "ecdsa_recovery"),
             SignatureType::Ed25519 => write!(f, "ed25519"),
             SignatureType::Schnorr1 => write!(f, "schnorr_1"),
+            SignatureType::SchnorrBip340 => write!(f, "schnorr_bip340"),
             SignatureType::SchnorrPoseidon => write!(f, "schnorr_poseidon"),
             SignatureType::EmptyString => write!(f, "unspecified_signature_type"),
         }
@@ -40,8 +40,7 @@ impl Serialize for UncheckedSigningPayload {
         }
         let mut state = serializer.serialize_struct("SigningPayload", field_count)?;
 
-        let hex = encode_to_hex_string(&self.bytes);
-        state.serialize_field("hex_bytes", &hex)?;
+        state.serialize_field("hex_bytes", &HexBytes(&self.bytes))?;
         state.serialize_field("signature_type", &self.signature_type)?;
         if let Some(ai) = self.account_identifier.as_ref() {
             state.serialize_field("account_identifier", &ai)?;
@@ -271,13 +271,28 @@ pub fn decode_from_hex_string(hex: String) -> Result<Vec<u8>, u8> {
 }
 
 // TODO maybe move this and null_default_bytes_to_hex to utils?
-/// For serializing a slice of bytes to a hex string.
+/// For serializing a slice of bytes to a hex string. Binary encodings keep
+/// the raw bytes.
 pub fn bytes_to_hex_str<S>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let hex = encode_to_hex_string(bytes);
-    s.serialize_str(&hex)
+    if s.is_human_readable() {
+        let hex = encode_to_hex_string(bytes);
+        s.serialize_str(&hex)
+    } else {
+        s.serialize_bytes(bytes)
+    }
+}
+
+/// Serializes bytes with [`bytes_to_hex_str`], for hand written
+/// [`Serialize`] impls.
+pub(crate) struct HexBytes<'a>(pub(crate) &'a [u8]);
+
+impl Serialize for HexBytes<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        bytes_to_hex_str(self.0, s)
+    }
 }
 
 /// custom deserializer that replaces `null` with an empty vec of bytes
@@ -285,6 +300,10 @@ pub fn null_default_bytes_to_hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::
 where
     D: Deserializer<'de>,
 {
+    if !deserializer.is_human_readable() {
+        return deserializer.deserialize_any(RawVisitor);
+    }
+
     let opt: Option<String> = Option::deserialize(deserializer)?;
     if let Some(hex_str) = opt {
         decode_from_hex_string(hex_str).map_err(serde::de::Error::custom)
@@ -292,3 +311,87 @@ where
         Ok(Vec::new())
     }
 }
+
+/// For serializing an amount value. Binary encodings store canonical integers
+/// as their minimal big-endian two's complement bytes, so every integer has
+/// exactly one encoding. Any other value, including integers written with a
+/// sign or leading zeros, is kept as the string it was, so every encoding
+/// decodes to the same value.
+pub fn amount_value_to_canonical<S>(value: &str, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match BigInt::from_str(value) {
+        Ok(int) if !s.is_human_readable() && int.to_string() == value => {
+            s.serialize_bytes(&int.to_signed_bytes_be())
+        }
+        _ => s.serialize_str(value),
+    }
+}
+
+/// custom deserializer for amount values written by
+/// [`amount_value_to_canonical`].
+pub fn canonical_to_amount_value<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        String::deserialize(deserializer)
+    } else {
+        deserializer.deserialize_any(AmountValueVisitor)
+    }
+}
+
+/// visits the raw bytes of a binary encoding.
+struct RawVisitor;
+
+impl<'de> serde::de::Visitor<'de> for RawVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+}
+
+/// visits an amount value of a binary encoding.
+struct AmountValueVisitor;
+
+impl<'de> serde::de::Visitor<'de> for AmountValueVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an integer as bytes or a string")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(BigInt::from_signed_bytes_be(v).to_string())
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.to_string())
+    }
+}